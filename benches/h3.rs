@@ -0,0 +1,185 @@
+// Benchmarks of representative workloads, run with
+// `cargo bench --features bench`. Criterion is not a dependency, so each
+// workload is timed with a simple loop: it is repeated until at least
+// MIN_TIME has passed and the mean time per call is printed. Pass a substring
+// to only run matching benchmarks, such as
+// `cargo bench --features bench -- gridDisk`.
+
+#![allow(non_snake_case)]
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use h3_rs::algos::gridDisk;
+use h3_rs::base_cells::getPentagons;
+use h3_rs::bench_utils::{closestFaceByScan, closestFaceByTable, randomCells, randomLatLngs};
+use h3_rs::h3_index::{cellToBoundary, cellToChildren, cellToLatLng, latLngToCell};
+use h3_rs::lat_lng::LatLng;
+use h3_rs::polygon::{bboxToCells, polygonToCells, GeoLoop, GeoPolygon};
+
+const MIN_TIME: Duration = Duration::from_millis(500);
+const SEED: u64 = 0x4833;
+
+/** Times f, which processes `per` items per call, if name matches. */
+fn bench(filter: &Option<String>, name: &str, per: usize, mut f: impl FnMut()) {
+    if let Some(filter) = filter {
+        if !name.contains(filter.as_str()) {
+            return;
+        }
+    }
+    // warm up
+    f();
+    let mut calls: u32 = 0;
+    let start = Instant::now();
+    while start.elapsed() < MIN_TIME {
+        f();
+        calls += 1;
+    }
+    let perItem = start.elapsed() / (calls * per as u32);
+    println!("{:<40} {:>12?} per item", name, perItem);
+}
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+
+    // at res 0 the time is mostly finding the icosahedron face; res 0 and 1
+    // use the coarse cell table, res 2 is the general path for comparison
+    let points = randomLatLngs(SEED, 10_000);
+    for res in [0, 1, 2, 9, 15] {
+        bench(
+            &filter,
+            &format!("latLngToCell/res{}", res),
+            points.len(),
+            || {
+                for g in &points {
+                    black_box(latLngToCell(black_box(g), res).unwrap());
+                }
+            },
+        );
+    }
+
+    // the icosahedron face lookup behind every latLngToCell: the band table
+    // against checking all 20 faces
+    bench(&filter, "closestFace/table", points.len(), || {
+        for g in &points {
+            black_box(closestFaceByTable(black_box(g)));
+        }
+    });
+    bench(&filter, "closestFace/scan", points.len(), || {
+        for g in &points {
+            black_box(closestFaceByScan(black_box(g)));
+        }
+    });
+
+    let cells = randomCells(SEED, 10_000, 9).unwrap();
+    bench(&filter, "cellToLatLng/res9", cells.len(), || {
+        for &h in &cells {
+            black_box(cellToLatLng(black_box(h)).unwrap());
+        }
+    });
+    bench(&filter, "cellToBoundary/res9", cells.len(), || {
+        for &h in &cells {
+            black_box(cellToBoundary(black_box(h)).unwrap());
+        }
+    });
+
+    let some = &cells[..100];
+    for k in [1, 10] {
+        bench(&filter, &format!("gridDisk/k{}", k), some.len(), || {
+            for &h in some {
+                black_box(gridDisk(black_box(h), k).unwrap());
+            }
+        });
+    }
+    // the safe fallback
+    let pentagons = getPentagons(9).unwrap();
+    bench(&filter, "gridDisk/pentagon/k10", pentagons.len(), || {
+        for &h in &pentagons {
+            black_box(gridDisk(black_box(h), 10).unwrap());
+        }
+    });
+
+    bench(&filter, "cellToChildren/res9to12", some.len(), || {
+        for &h in some {
+            black_box(cellToChildren(black_box(h), 12).unwrap());
+        }
+    });
+
+    let sf = GeoPolygon {
+        geoloop: GeoLoop {
+            verts: vec![
+                LatLng {
+                    lat: 0.659966917655,
+                    lng: -2.1364398519396,
+                },
+                LatLng {
+                    lat: 0.6595011102219,
+                    lng: -2.1359434279405,
+                },
+                LatLng {
+                    lat: 0.6583348114025,
+                    lng: -2.1354884206045,
+                },
+                LatLng {
+                    lat: 0.6581220034068,
+                    lng: -2.1382437718946,
+                },
+                LatLng {
+                    lat: 0.6594479998527,
+                    lng: -2.1384597563896,
+                },
+                LatLng {
+                    lat: 0.6599990002976,
+                    lng: -2.1376771158464,
+                },
+            ],
+        },
+        holes: vec![],
+    };
+    bench(&filter, "polygonToCells/sf/res9", 1, || {
+        black_box(polygonToCells(black_box(&sf), 9).unwrap());
+    });
+
+    // the bounding box of the polygon above, scanned directly
+    let min = LatLng {
+        lat: 0.6581220034068,
+        lng: -2.1384597563896,
+    };
+    let max = LatLng {
+        lat: 0.6599990002976,
+        lng: -2.1354884206045,
+    };
+    for res in [9, 11] {
+        bench(&filter, &format!("bboxToCells/sf/res{}", res), 1, || {
+            black_box(bboxToCells(black_box(&min), black_box(&max), res).unwrap());
+        });
+    }
+    let rect = GeoPolygon {
+        geoloop: GeoLoop {
+            verts: vec![
+                min,
+                LatLng {
+                    lat: min.lat,
+                    lng: max.lng,
+                },
+                max,
+                LatLng {
+                    lat: max.lat,
+                    lng: min.lng,
+                },
+            ],
+        },
+        holes: vec![],
+    };
+    // the same box through the general polygon path, for comparison
+    for res in [9, 11] {
+        bench(
+            &filter,
+            &format!("polygonToCells/sfBbox/res{}", res),
+            1,
+            || {
+                black_box(polygonToCells(black_box(&rect), res).unwrap());
+            },
+        );
+    }
+}
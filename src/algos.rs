@@ -1,18 +1,25 @@
+use alloc::collections::BTreeMap;
+
 use enum_primitive::FromPrimitive;
 
 use crate::{
     base_cells::{
         _baseCellIsCwOffset, _isBaseCellPentagon, _isBaseCellPolarPentagon, baseCellData,
-        baseCellNeighbor60CCWRots, baseCellNeighbors, INVALID_BASE_CELL,
+        baseCellNeighbor60CCWRots, baseCellNeighbors, getNumCells, INVALID_BASE_CELL,
     },
+    cell_set::CellSet,
     constants::NUM_BASE_CELLS,
     coord_ijk::{Direction, _rotate60ccw},
     error::Error,
     h3_index::{
         H3Index, _h3LeadingNonZeroDigit, _h3Rotate60ccw, _h3Rotate60cw, _h3RotatePent60ccw,
-        isPentagon, isResolutionClassIII, H3_GET_BASE_CELL, H3_GET_INDEX_DIGIT, H3_GET_RESOLUTION,
-        H3_SET_BASE_CELL, H3_SET_INDEX_DIGIT,
+        cellToBoundary, isPentagon, isResolutionClassIII, isValidCell, H3_GET_BASE_CELL,
+        H3_GET_INDEX_DIGIT, H3_GET_RESOLUTION, H3_SET_BASE_CELL, H3_SET_INDEX_DIGIT,
     },
+    polygon::{normalizeMultiPolygon, GeoLoop, GeoMultiPolygon},
+    prelude::*,
+    scratch::{withCellScratch, ScratchSet},
+    vertex_graph::VertexGraph,
 };
 
 /**
@@ -335,7 +342,130 @@ const NEW_ADJUSTMENT_III: [[Direction; 7]; 7] = [
  * @param out   size in indexes
  */
 pub fn maxGridDiskSize(k: u32) -> usize {
-    return (3 * k as i64 * (k as i64 + 1) + 1) as usize;
+    (3 * k as i64 * (k as i64 + 1) + 1) as usize
+}
+
+/**
+ * Number of cells to reserve for a disk of radius k around origin. This is
+ * maxGridDiskSize(k), but never more than there are cells at the resolution
+ * of origin, which a large k at a coarse resolution would exceed by far.
+ *
+ * @param   origin  origin cell
+ * @param   k       k value, k >= 0.
+ * @return          size in indexes
+ */
+fn _gridDiskCapacity(origin: H3Index, k: u32) -> usize {
+    let numCells = getNumCells(H3_GET_RESOLUTION(origin)).unwrap_or(0) as u64;
+    let k = k as u64;
+    let maxSize = (3 * k)
+        .checked_mul(k + 1)
+        .map_or(u64::MAX, |n| n.saturating_add(1));
+    maxSize.min(numCells) as usize
+}
+
+/**
+ * Number of cells that result from the gridRingUnsafe algorithm with the
+ * given k.
+ *
+ * @param   k   k value, k >= 0.
+ * @return      size in indexes
+ */
+pub fn maxGridRingSize(k: u32) -> usize {
+    if k == 0 {
+        return 1;
+    }
+    6 * k as usize
+}
+
+/**
+ * Algorithm used to produce a grid disk.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DiskAlgorithm {
+    /** The fast ring traversal, which fails when it encounters a pentagon
+     * or pentagonal distortion. */
+    Unsafe,
+    /** The slower neighbor traversal, which handles pentagons. */
+    Safe,
+    /** Try the unsafe algorithm first and fall back to the safe one if it
+     * fails. */
+    #[default]
+    Auto,
+}
+
+/**
+ * Cells of a grid disk along with the algorithm that produced them.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridDisk {
+    /** Cells and their distances from the origin, in no particular order. */
+    pub cells: Vec<(H3Index, u32)>,
+    /** The algorithm that produced the cells, Unsafe or Safe. */
+    pub algorithm: DiskAlgorithm,
+    /** Whether Auto tried the unsafe algorithm and had to fall back. */
+    pub fellBack: bool,
+}
+
+/**
+ * Produce cells within grid distance k of the origin cell.
+ *
+ * k-ring 0 is defined as the origin cell, k-ring 1 is defined as k-ring 0 and
+ * all neighboring cells, and so on.
+ *
+ * Output is in no particular order and contains no gaps, even when crossing a
+ * pentagon.
+ *
+ * @param  origin      origin cell
+ * @param  k           k >= 0
+ * @return             the cells, at most maxGridDiskSize(k) of them
+ */
+pub fn gridDisk(origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error> {
+    Ok(gridDiskDistances(origin, k)?
+        .into_iter()
+        .map(|(h3index, _distance)| h3index)
+        .collect())
+}
+
+/**
+ * Produce cells within grid distance k of the origin cell into a
+ * caller-provided buffer, in no particular order.
+ *
+ * The buffer is written from the start; the remainder is left as it was.
+ * Scratch space for the pentagon-safe fallback is reused between calls on the
+ * same thread, so repeated calls don't allocate.
+ *
+ * @param  origin      origin cell
+ * @param  k           k >= 0
+ * @param  out         buffer of at least maxGridDiskSize(k) cells
+ * @return             the number of cells written, or MemoryBounds if out is
+ *                     too short
+ */
+pub fn gridDiskInto(origin: H3Index, k: u32, out: &mut [H3Index]) -> Result<usize, Error> {
+    let maxIdx = maxGridDiskSize(k);
+    if out.len() < maxIdx {
+        return Err(Error::MemoryBounds);
+    }
+
+    // Optimistically try the faster gridDiskUnsafe algorithm first
+    let mut n: usize = 0;
+    if _gridDiskUnsafeEach(origin, k, |h3index, _distance| {
+        out[n] = h3index;
+        n += 1;
+    })
+    .is_ok()
+    {
+        return Ok(n);
+    }
+
+    // Fast algo failed, use the slower, correct algo
+    let mut n: usize = 0;
+    withCellScratch(|seen| {
+        _gridDiskDistancesInternal(origin, k, seen, |h3index, _distance| {
+            out[n] = h3index;
+            n += 1;
+        })
+    })?;
+    Ok(n)
 }
 
 /**
@@ -345,96 +475,146 @@ pub fn maxGridDiskSize(k: u32) -> usize {
  * k-ring 0 is defined as the origin cell, k-ring 1 is defined as k-ring 0 and
  * all neighboring cells, and so on.
  *
- * Output is placed in the provided array in no particular order. Elements of
- * the output array may be left zero, as can happen when crossing a pentagon.
+ * Output is in no particular order. Each cell appears once, and unlike the C
+ * output there are no zero entries for the cells missing around a pentagon.
  *
  * @param  origin      origin cell
  * @param  k           k >= 0
- * @param  out         zero-filled array which must be of size
- * maxGridDiskSize(k)
- * @param  distances   NULL or a zero-filled array which must be of size
- *                     maxGridDiskSize(k)
+ * @return the cells and their distances from the origin
  */
 pub fn gridDiskDistances(origin: H3Index, k: u32) -> Result<Vec<(H3Index, u32)>, Error> {
-    // Optimistically try the faster gridDiskUnsafe algorithm first
-    match gridDiskDistancesUnsafe(origin, k) {
-        Ok(out) => return Ok(out),
-        Err(e) => e,
-    };
+    Ok(gridDiskDistancesWith(origin, k, DiskAlgorithm::Auto)?.cells)
+}
 
-    let maxIdx = maxGridDiskSize(k);
+/**
+ * Produce cells within grid distance k of the origin cell, keyed by cell for
+ * looking up their distances.
+ *
+ * @param  origin      origin cell
+ * @param  k           k >= 0
+ * @return the distance of each cell in the disk, with exactly the disk's
+ *         cells as keys
+ */
+pub fn gridDiskDistancesMap(origin: H3Index, k: u32) -> Result<BTreeMap<H3Index, u32>, Error> {
+    Ok(gridDiskDistances(origin, k)?.into_iter().collect())
+}
+
+/**
+ * Produce cells and their distances from the given origin cell, up to
+ * distance k, sorted by distance and then by index.
+ *
+ * Unlike gridDiskDistances the order does not depend on the algorithm used,
+ * so the output is reproducible for snapshot tests.
+ *
+ * @param  origin      origin cell
+ * @param  k           k >= 0
+ * @return the cells and their distances from the origin, in order
+ */
+pub fn gridDiskDistancesSorted(origin: H3Index, k: u32) -> Result<Vec<(H3Index, u32)>, Error> {
+    let mut cells = gridDiskDistances(origin, k)?;
+    cells.sort_unstable_by_key(|&(h, distance)| (distance, h));
+    Ok(cells)
+}
 
-    // Fast algo failed, fall back to slower, correct algo
-    let mut out: Vec<(H3Index, u32)> = Vec::new();
-    out.resize(maxIdx, (0, 0));
+/**
+ * Produce cells and their distances from the given origin cell, up to
+ * distance k, with an explicit choice of algorithm.
+ *
+ * Unsafe returns an error if a pentagon is encountered, Safe always uses the
+ * slower algorithm, and Auto behaves as gridDiskDistances. The result records
+ * which algorithm produced the cells, so callers can see when Auto fell back.
+ *
+ * @param  origin      origin cell
+ * @param  k           k >= 0
+ * @param  algorithm   the algorithm to use
+ * @return the cells and the algorithm that produced them
+ */
+pub fn gridDiskDistancesWith(
+    origin: H3Index,
+    k: u32,
+    algorithm: DiskAlgorithm,
+) -> Result<GridDisk, Error> {
+    let mut fellBack = false;
+    if algorithm != DiskAlgorithm::Safe {
+        // Optimistically try the faster gridDiskUnsafe algorithm first
+        match gridDiskDistancesUnsafe(origin, k) {
+            Ok(cells) => {
+                return Ok(GridDisk {
+                    cells,
+                    algorithm: DiskAlgorithm::Unsafe,
+                    fellBack,
+                })
+            }
+            Err(e) if algorithm == DiskAlgorithm::Unsafe => return Err(e),
+            Err(_) => fellBack = true,
+        };
+    }
 
-    _gridDiskDistancesInternal(origin, k, &mut out, maxIdx, 0)?;
-    Ok(out
-        .into_iter()
-        .filter(|(h3index, _distance)| *h3index != 0)
-        .collect())
+    // Fast algo failed or was not requested, use the slower, correct algo
+    let mut cells: Vec<(H3Index, u32)> = Vec::with_capacity(_gridDiskCapacity(origin, k));
+    withCellScratch(|seen| {
+        _gridDiskDistancesInternal(origin, k, seen, |h3index, distance| {
+            cells.push((h3index, distance));
+        })
+    })?;
+    Ok(GridDisk {
+        cells,
+        algorithm: DiskAlgorithm::Safe,
+        fellBack,
+    })
 }
 
 /**
  * Internal algorithm for the safe but slow version of gridDiskDistances
  *
- * Adds the origin cell to the output set (treating it as a hash set)
- * and recurses to its neighbors, if needed.
+ * Searches breadth first from the origin, one ring of distance at a time,
+ * so each cell is reached first by a shortest path and is only expanded
+ * once. The search uses explicit frontiers rather than recursion, so large
+ * k cannot overflow the stack.
  *
  * @param  origin      Origin cell
  * @param  k           Maximum distance to move from the origin
- * @param  out         Array treated as a hash set, elements being either
- *                     H3Index or 0.
- * @param  distances   Scratch area, with elements paralleling the out array.
- *                     Elements indicate ijk distance from the origin cell to
- *                     the output cell
- * @param  maxIdx      Size of out and scratch arrays (must be
- * maxGridDiskSize(k))
- * @param  curK        Current distance from the origin
+ * @param  seen        Empty set, used for the cells already reached
+ * @param  f           Called once for each cell in the disk, with its
+ *                     distance, in order of increasing distance
  */
 fn _gridDiskDistancesInternal(
-    mut origin: H3Index,
+    origin: H3Index,
     k: u32,
-    out: &mut Vec<(H3Index, u32)>,
-    maxIdx: usize,
-    curK: u32,
+    seen: &mut ScratchSet,
+    mut f: impl FnMut(H3Index, u32),
 ) -> Result<(), Error> {
-    // Put origin in the output array. out is used as a hash set.
-    let mut off: usize = (origin % maxIdx as u64) as usize;
-    while out[off].0 != 0 && out[off].0 != origin {
-        off = ((off + 1) % maxIdx) as usize;
-    }
-
-    // We either got a free slot in the hash set or hit a duplicate
-    // We might need to process the duplicate anyways because we got
-    // here on a longer path before.
-    if out[off].0 == origin && out[off].1 <= curK {
-        return Ok(());
-    }
-
-    out[off].0 = origin;
-    out[off].1 = curK;
-
-    // Base case: reached an index k away from the origin.
-    if curK >= k {
-        return Ok(());
-    }
-
-    // Recurse to all neighbors in no particular order.
-    for i in 0..6 {
-        let mut rotations: i32 = 0;
-        match h3NeighborRotations(origin, DIRECTIONS[i], &mut rotations) {
-            Ok(result) => {
-                _gridDiskDistancesInternal(result, k, out, maxIdx, curK + 1)?;
-            }
-            Err(e) => {
-                if e != Error::Pentagon {
-                    return Err(e);
-                }
+    seen.insert(origin);
+    f(origin, 0);
+    let mut frontier: Vec<H3Index> = vec![origin];
+    let mut next: Vec<H3Index> = Vec::new();
+    for curK in 0..k {
+        for &h in &frontier {
+            for dir in DIRECTIONS {
+                let mut rotations: i32 = 0;
+                match h3NeighborRotations(h, dir, &mut rotations) {
+                    Ok(neighbor) => {
+                        if seen.insert(neighbor) {
+                            f(neighbor, curK + 1);
+                            next.push(neighbor);
+                        }
+                    }
+                    Err(e) => {
+                        // the deleted k subsequence of a pentagon
+                        if e != Error::Pentagon {
+                            return Err(e);
+                        }
+                    }
+                };
             }
-        };
+        }
+        if next.is_empty() {
+            break;
+        }
+        core::mem::swap(&mut frontier, &mut next);
+        next.clear();
     }
-    return Ok(());
+    Ok(())
 }
 
 /**
@@ -468,7 +648,7 @@ pub fn h3NeighborRotations(
 
     let mut newRotations: i32 = 0;
     let oldBaseCell: i32 = H3_GET_BASE_CELL(current);
-    if oldBaseCell < 0 || oldBaseCell >= NUM_BASE_CELLS {
+    if !(0..NUM_BASE_CELLS).contains(&oldBaseCell) {
         // LCOV_EXCL_BR_LINE
         // Base cells less than zero can not be represented in an index
         return Err(Error::CellInvalid);
@@ -498,7 +678,7 @@ pub fn h3NeighborRotations(
                 // perform the adjustment for the k-subsequence we're skipping
                 // over.
                 current = _h3Rotate60ccw(current);
-                *rotations = *rotations + 1;
+                *rotations += 1;
             }
             break;
         } else {
@@ -569,13 +749,13 @@ pub fn h3NeighborRotations(
                     // We also need an additional change to the direction we're
                     // moving in
                     current = _h3Rotate60ccw(current);
-                    *rotations = *rotations + 1;
+                    *rotations += 1;
                 } else if oldLeadingDigit == Direction::IKAxesDigit {
                     // Rotate current of the deleted k subsequence
                     // We also need an additional change to the direction we're
                     // moving in
                     current = _h3Rotate60cw(current);
-                    *rotations = *rotations + 5;
+                    *rotations += 5;
                 } else {
                     // Should never occur
                     return Err(Error::Failed); // LCOV_EXCL_LINE
@@ -597,14 +777,14 @@ pub fn h3NeighborRotations(
                     && oldBaseCell != 8
                     && _h3LeadingNonZeroDigit(current) != Direction::JKAxesDigit
                 {
-                    *rotations = *rotations + 1;
+                    *rotations += 1;
                 }
             } else if _h3LeadingNonZeroDigit(current) == Direction::IKAxesDigit
                 && alreadyAdjustedKSubsequence == 0
             {
                 // account for distortion introduced to the 5 neighbor by the
                 // deleted k subsequence.
-                *rotations = *rotations + 1;
+                *rotations += 1;
             }
         }
     } else {
@@ -615,7 +795,7 @@ pub fn h3NeighborRotations(
 
     *rotations = (*rotations + newRotations) % 6;
 
-    return Ok(current);
+    Ok(current)
 }
 
 /**
@@ -648,10 +828,56 @@ pub fn directionForNeighbor(origin: H3Index, destination: H3Index) -> Direction
                     return Direction::from_usize(direction).unwrap();
                 }
             }
-            Err(err) => {}
+            Err(_err) => {}
         }
     }
-    return Direction::InvalidDigit;
+    Direction::InvalidDigit
+}
+
+/**
+ * Returns the cell neighboring a cell in a direction, taking one step across
+ * the grid. Base cell and face crossings are handled as in gridDisk.
+ *
+ * @param cell The origin cell.
+ * @param dir The direction to step in; CenterDigit returns the cell.
+ * @return The neighbor, CellInvalid if cell is not a valid cell, Domain for
+ *         InvalidDigit, or Pentagon for the k direction of a pentagon, which
+ *         is deleted.
+ */
+pub fn neighbor(cell: H3Index, dir: Direction) -> Result<H3Index, Error> {
+    if !isValidCell(cell) {
+        return Err(Error::CellInvalid);
+    }
+    if dir == Direction::InvalidDigit {
+        return Err(Error::Domain);
+    }
+    if dir == Direction::KAxesDigit && isPentagon(cell) {
+        return Err(Error::Pentagon);
+    }
+    let mut rotations: i32 = 0;
+    h3NeighborRotations(cell, dir, &mut rotations)
+}
+
+/**
+ * gridDiskUnsafe produces indexes within k distance of the origin index.
+ * Output behavior is undefined when one of the indexes returned by this
+ * function is a pentagon or is in the pentagon distortion area.
+ *
+ * k-ring 0 is defined as the origin index, k-ring 1 is defined as k-ring 0 and
+ * all neighboring indexes, and so on.
+ *
+ * Output is placed in order of increasing distance from the origin.
+ *
+ * @param origin Origin location.
+ * @param k k >= 0
+ * @return The indexes, or Pentagon if a pentagon or pentagonal distortion
+ *         area was encountered.
+ */
+pub fn gridDiskUnsafe(origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error> {
+    Ok(gridDiskDistancesUnsafe(origin, k)?
+        .into_iter()
+        .map(|(h3index, _distance)| h3index)
+        .collect())
 }
 
 /**
@@ -672,26 +898,36 @@ pub fn directionForNeighbor(origin: H3Index, destination: H3Index) -> Direction
  * @param distances Null or array which must be of size maxGridDiskSize(k).
  * @return 0 if no pentagon or pentagonal distortion area was encountered.
  */
-pub fn gridDiskDistancesUnsafe(
+pub fn gridDiskDistancesUnsafe(origin: H3Index, k: u32) -> Result<Vec<(H3Index, u32)>, Error> {
+    let mut out: Vec<(H3Index, u32)> = Vec::with_capacity(_gridDiskCapacity(origin, k));
+    _gridDiskUnsafeEach(origin, k, |h3index, distance| out.push((h3index, distance)))?;
+    Ok(out)
+}
+
+/**
+ * Walks the rings of gridDiskDistancesUnsafe, passing each cell and its
+ * distance to emit in order of increasing distance. Cells may already have
+ * been emitted when a pentagon is encountered.
+ *
+ * @param origin Origin location.
+ * @param k k >= 0
+ * @param emit Called with each cell and its distance.
+ * @return Ok if no pentagon or pentagonal distortion area was encountered.
+ */
+fn _gridDiskUnsafeEach(
     mut origin: H3Index,
     k: u32,
-    //out: &mut Vec<H3Index>,
-    //distances: &mut Vec<i32>,
-) -> Result<Vec<(H3Index, u32)>, Error> {
+    mut emit: impl FnMut(H3Index, u32),
+) -> Result<(), Error> {
     // Return codes:
     // 1 Pentagon was encountered
     // 2 Pentagon distortion (deleted k subsequence) was encountered
     // Pentagon being encountered is not itself a problem; really the deleted
     // k-subsequence is the problem, but for compatibility reasons we fail on
     // the pentagon.
-    if k < 0 {
-        return Err(Error::Domain);
-    }
 
-    let mut out: Vec<(H3Index, u32)> = Vec::new();
-
-    // k must be >= 0, so origin is always needed
-    out.push((origin, 0));
+    // origin is always needed
+    emit(origin, 0);
 
     if isPentagon(origin) {
         // Pentagon was encountered; bail out as user doesn't want this.
@@ -721,9 +957,7 @@ pub fn gridDiskDistancesUnsafe(
         }
 
         origin = h3NeighborRotations(origin, DIRECTIONS[direction as usize], &mut rotations)?;
-        out.push((origin, ring));
-        //distances.push(ring);
-        //idx += 1;
+        emit(origin, ring);
 
         i += 1;
         // Check if end of this side of the k-ring
@@ -742,7 +976,7 @@ pub fn gridDiskDistancesUnsafe(
             return Err(Error::Pentagon);
         }
     }
-    return Ok(out);
+    Ok(())
 }
 
 /**
@@ -755,12 +989,11 @@ pub fn gridDiskDistancesUnsafe(
  *
  * @param origin Origin location.
  * @param k k >= 0
- * @param out Array which must be of size 6 * k (or 1 if k == 0)
+ * @param out Array which must be of size maxGridRingSize(k)
  * @return 0 if successful; nonzero otherwise.
  */
 pub fn gridRingUnsafe(mut origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error> {
-    let mut out = Vec::<H3Index>::new();
-    out.reserve_exact(6 * k as usize);
+    let mut out = Vec::<H3Index>::with_capacity(maxGridRingSize(k));
 
     // Short-circuit on 'identity' ring
     if k == 0 {
@@ -777,7 +1010,7 @@ pub fn gridRingUnsafe(mut origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error
         return Err(Error::Pentagon);
     }
 
-    for ring in 0..k {
+    for _ring in 0..k {
         origin = h3NeighborRotations(origin, NEXT_RING_DIRECTION, &mut rotations)?;
 
         if isPentagon(origin) {
@@ -789,9 +1022,9 @@ pub fn gridRingUnsafe(mut origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error
 
     out.push(origin);
 
-    for direction in 0..6 {
+    for (direction, &dir) in DIRECTIONS.iter().enumerate() {
         for pos in 0..k {
-            origin = h3NeighborRotations(origin, DIRECTIONS[direction], &mut rotations)?;
+            origin = h3NeighborRotations(origin, dir, &mut rotations)?;
 
             // Skip the very last index, it was already added. We do
             // however need to traverse to it because of the pentagonal
@@ -810,15 +1043,99 @@ pub fn gridRingUnsafe(mut origin: H3Index, k: u32) -> Result<Vec<H3Index>, Error
     // it indicates pentagonal distortion occurred and we should report
     // failure.
     if lastIndex != origin {
-        return Err(Error::Pentagon);
+        Err(Error::Pentagon)
     } else {
-        return Ok(out);
+        Ok(out)
+    }
+}
+
+/**
+ * Produces the union of the k-disks around each of a set of origin cells.
+ *
+ * All origins are searched together breadth first, so cells shared between
+ * overlapping disks are only visited once. This is much cheaper than
+ * computing each disk and deduplicating when the origins are dense.
+ *
+ * @param origins The origin cells
+ * @param k k >= 0
+ * @return The cells within grid distance k of any origin
+ */
+pub fn coverageFromSeeds(origins: &[H3Index], k: u32) -> Result<CellSet, Error> {
+    let mut out = CellSet::new();
+    let mut frontier: Vec<H3Index> = Vec::with_capacity(origins.len());
+    for &origin in origins {
+        if !isValidCell(origin) {
+            return Err(Error::CellInvalid);
+        }
+        if out.insert(origin) {
+            frontier.push(origin);
+        }
+    }
+
+    let mut next: Vec<H3Index> = Vec::new();
+    for _ring in 0..k {
+        for &h in &frontier {
+            for dir in DIRECTIONS {
+                let mut rotations: i32 = 0;
+                match h3NeighborRotations(h, dir, &mut rotations) {
+                    Ok(neighbor) => {
+                        if out.insert(neighbor) {
+                            next.push(neighbor);
+                        }
+                    }
+                    Err(e) => {
+                        // the deleted k subsequence of a pentagon
+                        if e != Error::Pentagon {
+                            return Err(e);
+                        }
+                    }
+                };
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        core::mem::swap(&mut frontier, &mut next);
+        next.clear();
     }
+    Ok(out)
+}
+
+/**
+ * Creates a multipolygon describing the outline(s) of a set of cells.
+ * Polygon outlines follow GeoJSON MultiPolygon order: each polygon has one
+ * counterclockwise outer loop, followed by any clockwise holes.
+ *
+ * @param h3Set Set of cells, all at the same resolution and without
+ *              duplicates
+ * @return The outlines, or ResMismatch if the resolutions differ and
+ *         DuplicateInput if a cell appears twice
+ */
+pub fn cellsToMultiPolygon(h3Set: &[H3Index]) -> Result<GeoMultiPolygon, Error> {
+    let mut seen = CellSet::new();
+    let mut graph = VertexGraph::new();
+    for &h in h3Set {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        if H3_GET_RESOLUTION(h) != H3_GET_RESOLUTION(h3Set[0]) {
+            return Err(Error::ResMismatch);
+        }
+        if !seen.insert(h) {
+            return Err(Error::DuplicateInput);
+        }
+        graph.addLoop(&cellToBoundary(h)?.to_vec());
+    }
+    let loops = graph
+        .takeLoops()
+        .into_iter()
+        .map(|verts| GeoLoop { verts })
+        .collect();
+    normalizeMultiPolygon(loops)
 }
 
 #[cfg(test)]
 mod tests {
-    use num::Float;
 
     use crate::{
         h3_index::{latLngToCell, setH3Index},
@@ -828,6 +1145,11 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(
+        clippy::needless_range_loop,
+        clippy::approx_constant,
+        reason = "ported from the C test suite"
+    )]
     fn gridDisk0() {
         let sf = LatLng {
             lat: 0.659966917655,
@@ -863,6 +1185,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop, reason = "ported from the C test suite")]
     fn gridDisk0_PolarPentagon() {
         let mut polar: H3Index = 0;
         setH3Index(&mut polar, 0, 4, 0);
@@ -881,7 +1204,7 @@ mod tests {
         k2.resize(7, (0u64, 0));
         let mut k2present = 0;
         for i in 0..7 {
-            if (k2[i].0 != 0) {
+            if k2[i].0 != 0 {
                 k2present += 1;
                 let mut in_list = 0;
                 for j in 0..7 {
@@ -898,4 +1221,418 @@ mod tests {
         }
         assert!(k2present == 6, "pentagon has 5 neighbors");
     }
+
+    #[test]
+    fn gridDiskDistances_ScratchReuse() {
+        let mut polar: H3Index = 0;
+        setH3Index(&mut polar, 2, 4, 0);
+
+        // the safe algorithm reuses its scratch buffer between calls, which
+        // must not leak cells from a larger disk into a smaller one
+        let k3 = gridDiskDistances(polar, 3).unwrap();
+        let k1 = gridDiskDistances(polar, 1).unwrap();
+        let k3Again = gridDiskDistances(polar, 3).unwrap();
+        assert_eq!(k1.len(), 6, "pentagon has 5 neighbors");
+        assert!(k1.iter().all(|(_, d)| *d <= 1), "distances within k");
+        assert_eq!(k3, k3Again, "repeated calls are identical");
+    }
+
+    #[test]
+    fn coverageFromSeeds_MatchesDisks() {
+        let mut polar: H3Index = 0;
+        setH3Index(&mut polar, 3, 4, 0);
+        let sf = latLngToCell(
+            &LatLng {
+                lat: 0.659966917655,
+                lng: -2.1364398519396,
+            },
+            3,
+        )
+        .unwrap();
+        let mut origins: Vec<H3Index> = gridDiskDistances(sf, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(h, _)| h)
+            .step_by(3)
+            .collect();
+        origins.push(polar);
+
+        for k in 0..4 {
+            let coverage = coverageFromSeeds(&origins, k).unwrap();
+            let mut expected = CellSet::new();
+            for origin in &origins {
+                expected.extend(
+                    gridDiskDistances(*origin, k)
+                        .unwrap()
+                        .into_iter()
+                        .map(|(h, _)| h),
+                );
+            }
+            assert_eq!(coverage, expected, "union of disks at k {}", k);
+        }
+
+        assert!(coverageFromSeeds(&[], 3).unwrap().is_empty(), "no seeds");
+        assert_eq!(
+            coverageFromSeeds(&[0], 1),
+            Err(Error::CellInvalid),
+            "invalid seed"
+        );
+    }
+
+    #[test]
+    fn gridDiskDistancesWithAlgorithm() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        let auto = gridDiskDistancesWith(hex, 2, DiskAlgorithm::Auto).unwrap();
+        assert_eq!(
+            auto.algorithm,
+            DiskAlgorithm::Unsafe,
+            "auto uses unsafe away from pentagons"
+        );
+        assert!(!auto.fellBack, "auto did not fall back");
+
+        let mut safe = gridDiskDistancesWith(hex, 2, DiskAlgorithm::Safe).unwrap();
+        assert_eq!(safe.algorithm, DiskAlgorithm::Safe, "safe was used");
+        assert!(!safe.fellBack, "safe does not fall back");
+        let mut unsafeCells = auto.cells.clone();
+        unsafeCells.sort();
+        safe.cells.sort();
+        assert_eq!(
+            unsafeCells, safe.cells,
+            "both algorithms give the same disk"
+        );
+
+        let pentagon: H3Index = 0x821c07fffffffff;
+        assert!(
+            gridDiskDistancesWith(pentagon, 1, DiskAlgorithm::Unsafe).is_err(),
+            "unsafe fails on a pentagon"
+        );
+        let auto = gridDiskDistancesWith(pentagon, 1, DiskAlgorithm::Auto).unwrap();
+        assert_eq!(
+            auto.algorithm,
+            DiskAlgorithm::Safe,
+            "auto falls back near pentagons"
+        );
+        assert!(auto.fellBack, "the fallback is reported");
+        assert_eq!(auto.cells.len(), 6, "pentagon disk has six cells");
+    }
+
+    #[test]
+    fn gridDisk_matchesDistances() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        let mut disk = gridDisk(hex, 2).unwrap();
+        assert_eq!(
+            disk.len(),
+            maxGridDiskSize(2),
+            "full disk away from pentagons"
+        );
+        let mut expected: Vec<H3Index> = gridDiskDistances(hex, 2)
+            .unwrap()
+            .iter()
+            .map(|(h, _)| *h)
+            .collect();
+        disk.sort();
+        expected.sort();
+        assert_eq!(disk, expected, "gridDisk drops only the distances");
+
+        let mut polar: H3Index = 0;
+        setH3Index(&mut polar, 0, 4, 0);
+        let disk = gridDisk(polar, 1).unwrap();
+        assert_eq!(disk.len(), 6, "pentagon disk has no gaps");
+        assert!(!disk.contains(&0), "no null indexes in the output");
+    }
+
+    #[test]
+    fn gridDiskDistancesMap_exact() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 5, 4, 0);
+        for origin in [pentagon, 0x85283473fffffff] {
+            let disk = gridDiskDistances(origin, 3).unwrap();
+            let map = gridDiskDistancesMap(origin, 3).unwrap();
+            assert_eq!(map.len(), disk.len(), "one entry per cell");
+            assert!(!map.contains_key(&0), "no null keys");
+            assert_eq!(map[&origin], 0, "origin at distance 0");
+            for (h, distance) in disk {
+                assert_eq!(map[&h], distance, "same distances");
+            }
+        }
+        assert_eq!(
+            gridDiskDistancesMap(pentagon, 3).unwrap().len(),
+            maxGridDiskSize(3) - 6,
+            "pentagon disk is missing one sector"
+        );
+    }
+
+    #[test]
+    fn gridDiskUnsafe_orderedByDistance() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        let disk = gridDiskUnsafe(hex, 2).unwrap();
+        assert_eq!(disk.len(), maxGridDiskSize(2), "full disk");
+        assert_eq!(disk[0], hex, "origin comes first");
+        let ring1 = gridRingUnsafe(hex, 1).unwrap();
+        for h in &disk[1..7] {
+            assert!(ring1.contains(h), "ring 1 comes next");
+        }
+
+        let mut polar: H3Index = 0;
+        setH3Index(&mut polar, 0, 4, 0);
+        assert_eq!(
+            gridDiskUnsafe(polar, 1),
+            Err(Error::Pentagon),
+            "unsafe fails on a pentagon"
+        );
+    }
+
+    #[test]
+    fn gridDiskInto() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        let mut out = [0 as H3Index; 19];
+        let n = super::gridDiskInto(hex, 2, &mut out).unwrap();
+        assert_eq!(n, 19, "full disk");
+        let mut expected = gridDisk(hex, 2).unwrap();
+        let mut got = out.to_vec();
+        expected.sort();
+        got.sort();
+        assert_eq!(got, expected, "same cells as gridDisk");
+
+        assert_eq!(
+            super::gridDiskInto(hex, 2, &mut out[..18]),
+            Err(Error::MemoryBounds),
+            "short buffer fails"
+        );
+
+        let mut polar: H3Index = 0;
+        setH3Index(&mut polar, 0, 4, 0);
+        let mut out = [0 as H3Index; 7];
+        let n = super::gridDiskInto(polar, 1, &mut out).unwrap();
+        assert_eq!(n, 6, "pentagon disk falls back to the safe algorithm");
+        let mut expected = gridDisk(polar, 1).unwrap();
+        let mut got = out[..n].to_vec();
+        expected.sort();
+        got.sort();
+        assert_eq!(got, expected, "same cells as gridDisk");
+    }
+
+    #[test]
+    fn gridDiskDistancesSorted_order() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 9, 4, 0);
+        let hex = neighbor(pentagon, Direction::JAxesDigit).unwrap();
+        for origin in [pentagon, hex] {
+            let sorted = gridDiskDistancesSorted(origin, 3).unwrap();
+            assert_eq!(sorted[0], (origin, 0), "origin first");
+            assert!(
+                sorted
+                    .windows(2)
+                    .all(|w| (w[0].1, w[0].0) < (w[1].1, w[1].0)),
+                "sorted by distance, then index"
+            );
+            let mut expected = gridDiskDistancesWith(origin, 3, DiskAlgorithm::Safe)
+                .unwrap()
+                .cells;
+            expected.sort_by_key(|&(h, distance)| (distance, h));
+            assert_eq!(sorted, expected, "same order from either algorithm");
+        }
+    }
+
+    #[test]
+    fn gridDiskDistancesSafe_exact() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 9, 4, 0);
+        for k in 0..5 {
+            let disk = gridDiskDistancesWith(pentagon, k, DiskAlgorithm::Safe)
+                .unwrap()
+                .cells;
+            let expected = maxGridDiskSize(k) - (k * (k + 1) / 2) as usize;
+            assert_eq!(disk.len(), expected, "exactly the reachable cells");
+            assert!(
+                disk.iter().all(|&(h, _)| h != crate::H3_NULL),
+                "no placeholders"
+            );
+            let distinct: BTreeMap<H3Index, u32> = disk.iter().copied().collect();
+            assert_eq!(distinct.len(), disk.len(), "no duplicates");
+            assert!(
+                disk.windows(2).all(|w| w[0].1 <= w[1].1),
+                "in order of distance"
+            );
+        }
+    }
+
+    #[test]
+    fn neighborSteps() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        assert_eq!(neighbor(hex, Direction::CenterDigit), Ok(hex), "center");
+        let mut ring: Vec<H3Index> = Vec::new();
+        for d in 1..7 {
+            let dir = Direction::from_usize(d).unwrap();
+            let n = neighbor(hex, dir).unwrap();
+            assert_eq!(directionForNeighbor(hex, n), dir, "direction round trips");
+            ring.push(n);
+        }
+        let mut expected = gridDisk(hex, 1).unwrap();
+        expected.retain(|&h| h != hex);
+        ring.sort();
+        expected.sort();
+        assert_eq!(ring, expected, "steps reach the ring");
+
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 9, 4, 0);
+        assert_eq!(
+            neighbor(pentagon, Direction::KAxesDigit),
+            Err(Error::Pentagon),
+            "deleted k direction"
+        );
+        assert!(neighbor(pentagon, Direction::JAxesDigit).is_ok());
+        assert_eq!(
+            neighbor(hex, Direction::InvalidDigit),
+            Err(Error::Domain),
+            "invalid direction"
+        );
+        assert_eq!(
+            neighbor(0, Direction::JAxesDigit),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+    }
+
+    #[test]
+    fn cellsToMultiPolygon_ring() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let hex = latLngToCell(&sf, 9).unwrap();
+        let single = cellsToMultiPolygon(&[hex]).unwrap();
+        assert_eq!(single.polygons.len(), 1, "one polygon");
+        assert_eq!(single.polygons[0].geoloop.verts.len(), 6, "hexagon outline");
+        assert!(single.polygons[0].holes.is_empty(), "no holes");
+
+        let disk = cellsToMultiPolygon(&gridDisk(hex, 1).unwrap()).unwrap();
+        assert_eq!(disk.polygons.len(), 1, "disk is one polygon");
+        assert_eq!(disk.polygons[0].geoloop.verts.len(), 18, "disk outline");
+
+        let ring: Vec<H3Index> = gridDiskDistances(hex, 2)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, d)| *d > 0)
+            .map(|(h, _)| h)
+            .collect();
+        let donut = cellsToMultiPolygon(&ring).unwrap();
+        assert_eq!(donut.polygons.len(), 1, "ring is one polygon");
+        assert_eq!(donut.polygons[0].holes.len(), 1, "with one hole");
+        assert_eq!(donut.polygons[0].holes[0].verts.len(), 6, "hexagon hole");
+
+        let far = latLngToCell(&LatLng { lat: 0.0, lng: 0.0 }, 9).unwrap();
+        let two = cellsToMultiPolygon(&[hex, far]).unwrap();
+        assert_eq!(two.polygons.len(), 2, "separate cells");
+    }
+
+    #[test]
+    fn cellsToMultiPolygon_pentagon() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 1, 4, 0);
+        let outline = cellsToMultiPolygon(&[pentagon]).unwrap();
+        assert_eq!(outline.polygons.len(), 1, "one polygon");
+        assert_eq!(
+            outline.polygons[0].geoloop.verts.len(),
+            cellToBoundary(pentagon).unwrap().numVerts,
+            "outline follows the boundary, distortion vertices included"
+        );
+        let disk = cellsToMultiPolygon(&gridDisk(pentagon, 1).unwrap()).unwrap();
+        assert_eq!(disk.polygons.len(), 1, "pentagon disk is one polygon");
+        assert!(disk.polygons[0].holes.is_empty(), "no holes");
+    }
+
+    #[test]
+    fn cellsToMultiPolygon_invalid() {
+        let hex: H3Index = 0x89283082837ffff;
+        assert_eq!(
+            cellsToMultiPolygon(&[]),
+            Ok(GeoMultiPolygon::default()),
+            "empty set"
+        );
+        assert_eq!(
+            cellsToMultiPolygon(&[hex, hex]),
+            Err(Error::DuplicateInput),
+            "duplicates fail"
+        );
+        assert_eq!(
+            cellsToMultiPolygon(&[hex, 0x85283473fffffff]),
+            Err(Error::ResMismatch),
+            "mixed resolutions fail"
+        );
+        assert_eq!(cellsToMultiPolygon(&[0]), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn gridDiskDistances_largeKCoarse() {
+        // the disk covers the globe long before reaching k
+        for res in 0..=1 {
+            let origin = latLngToCell(&LatLng { lat: 0.5, lng: 0.5 }, res).unwrap();
+            let numCells = getNumCells(res).unwrap() as usize;
+            for k in [1_000_000, u32::MAX] {
+                let disk = gridDiskDistancesWith(origin, k, DiskAlgorithm::Safe).unwrap();
+                assert_eq!(disk.cells.len(), numCells, "all cells at res {}", res);
+                let auto = gridDiskDistances(origin, k).unwrap();
+                assert_eq!(auto.len(), numCells, "auto finds all cells at res {}", res);
+            }
+        }
+    }
+
+    #[test]
+    fn gridDiskDistancesSafe_largeK() {
+        // Runs on a small stack, which the recursive search overflowed
+        let worker = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(|| {
+                let mut pentagon: H3Index = 0;
+                setH3Index(&mut pentagon, 9, 4, 0);
+                let k = 400;
+                let disk = gridDiskDistancesWith(pentagon, k, DiskAlgorithm::Safe).unwrap();
+                // a pentagon's disk is missing one of the six sextants
+                let expected = maxGridDiskSize(k) - (k * (k + 1) / 2) as usize;
+                assert_eq!(disk.cells.len(), expected, "pentagon disk size");
+                assert!(
+                    disk.cells.iter().any(|&(_, d)| d == k),
+                    "reaches distance k"
+                );
+
+                let hex = latLngToCell(
+                    &LatLng {
+                        lat: 0.659966917655,
+                        lng: -2.1364398519396,
+                    },
+                    9,
+                )
+                .unwrap();
+                let mut safe = gridDiskDistancesWith(hex, 50, DiskAlgorithm::Safe)
+                    .unwrap()
+                    .cells;
+                let mut fast = gridDiskDistancesUnsafe(hex, 50).unwrap();
+                safe.sort();
+                fast.sort();
+                assert_eq!(safe, fast, "same cells and distances as unsafe");
+            })
+            .unwrap();
+        worker.join().unwrap();
+    }
 }
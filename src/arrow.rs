@@ -0,0 +1,145 @@
+use arrow_array::{Array, Float64Array, UInt64Array};
+use arrow_buffer::NullBuffer;
+
+use crate::bulk::{cellsToLatLngColumns, latLngColumnsToCells};
+use crate::constants::{M_180_PI, M_PI_180};
+use crate::error::Error;
+use crate::h3_index::{cellToLatLng, latLngToCell};
+use crate::lat_lng::LatLng;
+use crate::H3_NULL;
+
+/**
+ * Indexes the points in a pair of Arrow latitude and longitude arrays. The
+ * value buffers are read in place, and the cells are returned in a buffer
+ * the output array takes ownership of, so neither side is copied.
+ *
+ * A row that is null in either input is null in the output.
+ *
+ * @param lat The latitudes, in degrees.
+ * @param lng The longitudes, in degrees.
+ * @param res The resolution.
+ * @return The cell of each row, Domain if the arrays differ in length, or
+ *         the first error encountered for a non-null row.
+ */
+pub fn latlng_to_cell(
+    lat: &Float64Array,
+    lng: &Float64Array,
+    res: i32,
+) -> Result<UInt64Array, Error> {
+    if lat.len() != lng.len() {
+        return Err(Error::Domain);
+    }
+    let nulls = NullBuffer::union(lat.nulls(), lng.nulls());
+    let cells = match &nulls {
+        None => latLngColumnsToCells(lat.values(), lng.values(), res)?,
+        Some(nulls) => {
+            // the values under null slots are unspecified, so skip them
+            let mut cells = Vec::with_capacity(lat.len());
+            for (i, (&lat, &lng)) in lat.values().iter().zip(lng.values().iter()).enumerate() {
+                if nulls.is_null(i) {
+                    cells.push(H3_NULL);
+                    continue;
+                }
+                let g = LatLng {
+                    lat: lat * M_PI_180,
+                    lng: lng * M_PI_180,
+                };
+                cells.push(latLngToCell(&g, res)?);
+            }
+            cells
+        }
+    };
+    Ok(UInt64Array::new(cells.into(), nulls))
+}
+
+/**
+ * Finds the centers of the cells in an Arrow array, the inverse of
+ * latlng_to_cell.
+ *
+ * A null cell has a null center.
+ *
+ * @param cells The cells.
+ * @return The latitudes and longitudes of the centers, in degrees, or the
+ *         first error encountered for a non-null row.
+ */
+pub fn cell_to_latlng(cells: &UInt64Array) -> Result<(Float64Array, Float64Array), Error> {
+    let nulls = cells.nulls().cloned();
+    let (lats, lngs) = match &nulls {
+        None => cellsToLatLngColumns(cells.values())?,
+        Some(nulls) => {
+            let mut lats = Vec::with_capacity(cells.len());
+            let mut lngs = Vec::with_capacity(cells.len());
+            for (i, &h) in cells.values().iter().enumerate() {
+                let g = if nulls.is_null(i) {
+                    LatLng { lat: 0.0, lng: 0.0 }
+                } else {
+                    cellToLatLng(h)?
+                };
+                lats.push(g.lat * M_180_PI);
+                lngs.push(g.lng * M_180_PI);
+            }
+            (lats, lngs)
+        }
+    };
+    Ok((
+        Float64Array::new(lats.into(), nulls.clone()),
+        Float64Array::new(lngs.into(), nulls),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundTrip() {
+        let lat = Float64Array::from(vec![37.775938728915946, -33.8688, 0.0]);
+        let lng = Float64Array::from(vec![-122.41795063018799, 151.2093, 0.0]);
+        let cells = latlng_to_cell(&lat, &lng, 9).unwrap();
+        assert_eq!(cells.null_count(), 0, "no null cells");
+        for i in 0..lat.len() {
+            let g = LatLng::from_degrees(lat.value(i), lng.value(i));
+            assert_eq!(cells.value(i), latLngToCell(&g, 9).unwrap(), "row {}", i);
+        }
+
+        let (centerLat, centerLng) = cell_to_latlng(&cells).unwrap();
+        assert_eq!(
+            latlng_to_cell(&centerLat, &centerLng, 9).unwrap(),
+            cells,
+            "centers index back to the cells"
+        );
+
+        assert_eq!(
+            latlng_to_cell(&lat, &lng.slice(0, 2), 9),
+            Err(Error::Domain),
+            "arrays must have the same length"
+        );
+        assert_eq!(
+            latlng_to_cell(&lat, &lng, 16),
+            Err(Error::ResDomain),
+            "invalid resolution"
+        );
+        assert_eq!(
+            cell_to_latlng(&UInt64Array::from(vec![0x7fffffffffffffff])),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+    }
+
+    #[test]
+    fn nulls() {
+        let lat = Float64Array::from(vec![Some(37.775938728915946), None, Some(f64::NAN)]);
+        let lng = Float64Array::from(vec![Some(-122.41795063018799), Some(0.0), None]);
+        let cells = latlng_to_cell(&lat, &lng, 9).unwrap();
+        assert!(cells.is_valid(0), "row with both coordinates");
+        assert!(cells.is_null(1), "null latitude");
+        assert!(cells.is_null(2), "null longitude");
+
+        let (centerLat, centerLng) = cell_to_latlng(&cells).unwrap();
+        assert!(
+            centerLat.is_valid(0) && centerLng.is_valid(0),
+            "center of a cell"
+        );
+        assert!(centerLat.is_null(1) && centerLng.is_null(1), "null cell");
+    }
+}
@@ -1,9 +1,12 @@
+use enum_primitive::FromPrimitive;
+
 use crate::{
-    constants::H3_CELL_MODE,
-    coord_ijk::CoordIJK,
+    constants::{H3_CELL_MODE, MAX_H3_RES, NUM_PENTAGONS},
+    coord_ijk::{CoordIJK, Direction},
     error::Error,
     face_ijk::FaceIJK,
-    h3_index::{H3Index, H3_INIT, H3_SET_BASE_CELL, H3_SET_MODE},
+    h3_index::{setH3Index, H3Index, H3_INIT, H3_SET_BASE_CELL, H3_SET_MODE},
+    prelude::*,
 };
 
 /** @struct BaseCellData
@@ -4057,17 +4060,17 @@ pub const baseCellData: [BaseCellData; NUM_BASE_CELLS as usize] = [
 
 /** @brief Return whether or not the indicated base cell is a pentagon. */
 pub fn _isBaseCellPentagon(baseCell: i32) -> bool {
-    if baseCell < 0 || baseCell >= NUM_BASE_CELLS {
+    if !(0..NUM_BASE_CELLS).contains(&baseCell) {
         // Base cells less than zero can not be represented in an index
         return false;
     }
-    return baseCellData[baseCell as usize].isPentagon != 0;
+    baseCellData[baseCell as usize].isPentagon != 0
 }
 
 /** @brief Return whether the indicated base cell is a pentagon where all
  * neighbors are oriented towards it. */
 pub fn _isBaseCellPolarPentagon(baseCell: i32) -> bool {
-    return baseCell == 4 || baseCell == 117;
+    baseCell == 4 || baseCell == 117
 }
 
 /** @brief Find base cell given FaceIJK.
@@ -4079,9 +4082,8 @@ pub fn _isBaseCellPolarPentagon(baseCell: i32) -> bool {
  * Valid ijk+ lookup coordinates are from (0, 0, 0) to (2, 2, 2).
  */
 pub fn _faceIjkToBaseCell(h: &FaceIJK) -> i32 {
-    return faceIjkBaseCells[h.face as usize][h.coord.i as usize][h.coord.j as usize]
-        [h.coord.k as usize]
-        .baseCell;
+    faceIjkBaseCells[h.face as usize][h.coord.i as usize][h.coord.j as usize][h.coord.k as usize]
+        .baseCell
 }
 
 /** @brief Find base cell given FaceIJK.
@@ -4093,16 +4095,57 @@ pub fn _faceIjkToBaseCell(h: &FaceIJK) -> i32 {
  * Valid ijk+ lookup coordinates are from (0, 0, 0) to (2, 2, 2).
  */
 pub fn _faceIjkToBaseCellCCWrot60(h: &FaceIJK) -> i32 {
-    return faceIjkBaseCells[h.face as usize][h.coord.i as usize][h.coord.j as usize]
-        [h.coord.k as usize]
-        .ccwRot60;
+    faceIjkBaseCells[h.face as usize][h.coord.i as usize][h.coord.j as usize][h.coord.k as usize]
+        .ccwRot60
+}
+
+/** Invalid number of rotations */
+pub const INVALID_ROTATIONS: i32 = -1;
+
+/** @brief Given a base cell and the face it appears on, return
+ * the number of 60' ccw rotations for that base cell's
+ * coordinate system.
+ * @returns The number of rotations, or INVALID_ROTATIONS if the base
+ *          cell is not found on the given face
+ */
+pub fn _baseCellToCCWrot60(baseCell: i32, face: i32) -> i32 {
+    if !(0..NUM_ICOSA_FACES).contains(&face) {
+        return INVALID_ROTATIONS;
+    }
+    for orient in faceIjkBaseCells[face as usize].iter().flatten().flatten() {
+        if orient.baseCell == baseCell {
+            return orient.ccwRot60;
+        }
+    }
+    INVALID_ROTATIONS
 }
 
 /** @brief Return whether or not the tested face is a cw offset face.
  */
 pub fn _baseCellIsCwOffset(baseCell: i32, testFace: i32) -> bool {
-    return baseCellData[baseCell as usize].cwOffsetPent[0] == testFace
-        || baseCellData[baseCell as usize].cwOffsetPent[1] == testFace;
+    baseCellData[baseCell as usize].cwOffsetPent[0] == testFace
+        || baseCellData[baseCell as usize].cwOffsetPent[1] == testFace
+}
+
+/** @brief Return the direction from the origin base cell to the neighbor.
+ * Returns INVALID_DIGIT if the base cells are not neighbors.
+ */
+pub fn _getBaseCellDirection(originBaseCell: i32, neighboringBaseCell: i32) -> Direction {
+    for (dir, &testBaseCell) in baseCellNeighbors[originBaseCell as usize]
+        .iter()
+        .enumerate()
+    {
+        if testBaseCell == neighboringBaseCell {
+            return Direction::from_usize(dir).unwrap();
+        }
+    }
+    Direction::InvalidDigit
+}
+
+/** @brief Return the neighboring base cell in the given direction.
+ */
+pub fn _getBaseCellNeighbor(baseCell: i32, dir: Direction) -> i32 {
+    baseCellNeighbors[baseCell as usize][dir as usize]
 }
 
 /**
@@ -4111,7 +4154,21 @@ pub fn _baseCellIsCwOffset(baseCell: i32, testFace: i32) -> bool {
  * @return int count of resolution 0 cells
  */
 pub fn res0CellCount() -> i32 {
-    return NUM_BASE_CELLS;
+    NUM_BASE_CELLS
+}
+
+/**
+ * getNumCells returns the number of unique cells at a resolution
+ *
+ * @param res the resolution
+ * @return count of cells, or ResDomain if res is not a valid resolution
+ */
+pub fn getNumCells(res: i32) -> Result<i64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    // 2 + 120 * 7^r
+    Ok(2 + 120 * 7i64.pow(res as u32))
 }
 
 /**
@@ -4122,27 +4179,273 @@ pub fn res0CellCount() -> i32 {
  * @returns E_SUCCESS.
  */
 pub fn getRes0Cells() -> Result<Vec<H3Index>, Error> {
-    let mut out = Vec::<H3Index>::new();
-    out.resize(NUM_BASE_CELLS as usize, 0);
-    for bc in 0..(NUM_BASE_CELLS as usize) {
-        //(int bc = 0; bc < NUM_BASE_CELLS; bc++) {
-        let mut baseCell: H3Index = H3_INIT;
-        H3_SET_MODE(&mut baseCell, H3_CELL_MODE);
-        H3_SET_BASE_CELL(&mut baseCell, bc as i32);
-        out[bc] = baseCell;
+    Ok(res0_cells().collect())
+}
+
+/**
+ * Iterates over all base cells in base cell order, without allocating.
+ *
+ * @return An iterator over the resolution 0 cells.
+ */
+pub fn res0_cells() -> impl Iterator<Item = H3Index> {
+    (0..NUM_BASE_CELLS).map(_baseCellToCell)
+}
+
+/** The resolution 0 cell of a base cell number known to be valid. */
+fn _baseCellToCell(bc: i32) -> H3Index {
+    let mut baseCell: H3Index = H3_INIT;
+    H3_SET_MODE(&mut baseCell, H3_CELL_MODE);
+    H3_SET_BASE_CELL(&mut baseCell, bc);
+    baseCell
+}
+
+/**
+ * Returns the resolution 0 cell of a base cell number, the inverse of
+ * getBaseCellNumber on resolution 0 cells.
+ *
+ * @param bc The base cell number.
+ * @return The resolution 0 cell, or Domain if bc is not a base cell number.
+ */
+pub fn baseCellNumberToCell(bc: i32) -> Result<H3Index, Error> {
+    if !(0..NUM_BASE_CELLS).contains(&bc) {
+        return Err(Error::Domain);
     }
-    return Ok(out);
+    Ok(_baseCellToCell(bc))
+}
+
+/**
+ * pentagonCount returns the number of pentagons (same at any resolution)
+ *
+ * @return int count of pentagon indexes
+ */
+pub fn pentagonCount() -> i32 {
+    NUM_PENTAGONS
+}
+
+/**
+ * Generates all pentagons at the specified resolution
+ *
+ * The output always has pentagonCount() cells, which serves as its size
+ * companion.
+ *
+ * @param res The resolution to produce pentagons at.
+ * @returns The 12 pentagon indexes, in base cell order, or ResDomain if the
+ *          resolution is invalid.
+ */
+pub fn getPentagons(res: i32) -> Result<Vec<H3Index>, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let mut out = Vec::<H3Index>::with_capacity(NUM_PENTAGONS as usize);
+    for bc in 0..NUM_BASE_CELLS {
+        if _isBaseCellPentagon(bc) {
+            let mut pentagon: H3Index = 0;
+            setH3Index(&mut pentagon, res, bc, 0);
+            out.push(pentagon);
+        }
+    }
+    Ok(out)
+}
+
+/**
+ * selfCheck verifies the invariants of the base cell lookup tables: that base
+ * cell neighbors are symmetric, that faceIjkBaseCells agrees with the home
+ * coordinates in baseCellData, and that the pentagons are the expected base
+ * cells. This is cheap enough to run at startup, to detect corrupted builds or
+ * mistakes made when editing the tables.
+ *
+ * @return Ok, or a description of every violated invariant.
+ */
+pub fn selfCheck() -> Result<(), Vec<String>> {
+    let mut problems: Vec<String> = Vec::new();
+
+    for bc in 0..NUM_BASE_CELLS {
+        let neighbors = &baseCellNeighbors[bc as usize];
+        if neighbors[Direction::CenterDigit as usize] != bc {
+            problems.push(format!("base cell {} is not its own center neighbor", bc));
+        }
+        for dir in (Direction::KAxesDigit as usize)..(Direction::NUM_DIGITS as usize) {
+            let neighbor = neighbors[dir];
+            let rot = baseCellNeighbor60CCWRots[bc as usize][dir];
+            if neighbor == INVALID_BASE_CELL {
+                if !_isBaseCellPentagon(bc) || dir != Direction::PENTAGON_SKIPPED_DIGIT as usize {
+                    problems.push(format!(
+                        "base cell {} has no neighbor in direction {}",
+                        bc, dir
+                    ));
+                }
+                continue;
+            }
+            if !(0..NUM_BASE_CELLS).contains(&neighbor) {
+                problems.push(format!(
+                    "base cell {} has invalid neighbor {} in direction {}",
+                    bc, neighbor, dir
+                ));
+                continue;
+            }
+            if !(0..=5).contains(&rot) {
+                problems.push(format!(
+                    "base cell {} has invalid rotation {} in direction {}",
+                    bc, rot, dir
+                ));
+            }
+            if _getBaseCellDirection(neighbor, bc) == Direction::InvalidDigit {
+                problems.push(format!(
+                    "base cell {} is a neighbor of {} but not the reverse",
+                    neighbor, bc
+                ));
+            }
+        }
+    }
+
+    let mut seen = [false; NUM_BASE_CELLS as usize];
+    for (face, faceCells) in faceIjkBaseCells.iter().enumerate() {
+        for (i, iCells) in faceCells.iter().enumerate() {
+            for (j, jCells) in iCells.iter().enumerate() {
+                for (k, orient) in jCells.iter().enumerate() {
+                    if orient.baseCell < 0 || orient.baseCell >= NUM_BASE_CELLS {
+                        problems.push(format!(
+                            "face {} ijk ({}, {}, {}) has invalid base cell {}",
+                            face, i, j, k, orient.baseCell
+                        ));
+                        continue;
+                    }
+                    if orient.ccwRot60 < 0 || orient.ccwRot60 > 5 {
+                        problems.push(format!(
+                            "face {} ijk ({}, {}, {}) has invalid rotation {}",
+                            face, i, j, k, orient.ccwRot60
+                        ));
+                    }
+                    seen[orient.baseCell as usize] = true;
+                }
+            }
+        }
+    }
+
+    let mut pentagons: Vec<i32> = Vec::new();
+    for bc in 0..NUM_BASE_CELLS {
+        if !seen[bc as usize] {
+            problems.push(format!("base cell {} is not on any face", bc));
+        }
+
+        let data = &baseCellData[bc as usize];
+        let home = &data.homeFijk;
+        if home.face < 0
+            || home.face >= NUM_ICOSA_FACES
+            || home.coord.i < 0
+            || home.coord.i > MAX_FACE_COORD
+            || home.coord.j < 0
+            || home.coord.j > MAX_FACE_COORD
+            || home.coord.k < 0
+            || home.coord.k > MAX_FACE_COORD
+        {
+            problems.push(format!("base cell {} has an invalid home", bc));
+        } else if _faceIjkToBaseCell(home) != bc || _faceIjkToBaseCellCCWrot60(home) != 0 {
+            problems.push(format!(
+                "base cell {} home does not map back to it without rotation",
+                bc
+            ));
+        }
+
+        if data.isPentagon != 0 {
+            pentagons.push(bc);
+            let polar = data.cwOffsetPent == [-1, -1];
+            if polar != _isBaseCellPolarPentagon(bc) {
+                problems.push(format!("pentagon {} has unexpected offset faces", bc));
+            }
+            if !polar
+                && data
+                    .cwOffsetPent
+                    .iter()
+                    .any(|f| *f < 0 || *f >= NUM_ICOSA_FACES)
+            {
+                problems.push(format!("pentagon {} has invalid offset faces", bc));
+            }
+        }
+    }
+    if pentagons != [4, 14, 24, 38, 49, 58, 63, 72, 83, 97, 107, 117] {
+        problems.push(format!("unexpected pentagon base cells {:?}", pentagons));
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(problems)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::h3_index::{getBaseCellNumber, getResolution, isPentagon, isValidCell};
+
     use super::*;
 
+    #[test]
+    fn getNumCells() {
+        assert_eq!(super::getNumCells(0), Ok(122), "base cells");
+        assert_eq!(super::getNumCells(15), Ok(569707381193162), "res 15 cells");
+        assert_eq!(super::getNumCells(16), Err(Error::ResDomain), "invalid res");
+    }
+
     #[test]
     fn getRes0Cells() {
-        let count = res0CellCount();
+        let _count = res0CellCount();
         let indexes = super::getRes0Cells().unwrap();
         assert_eq!(indexes[0], 0x8001fffffffffff, "correct first basecell");
         assert_eq!(indexes[121], 0x80f3fffffffffff, "correct last basecell");
     }
+
+    #[test]
+    fn baseCellNumberRoundTrip() {
+        let cells: Vec<H3Index> = res0_cells().collect();
+        assert_eq!(
+            cells,
+            super::getRes0Cells().unwrap(),
+            "iterator matches getRes0Cells"
+        );
+        for (bc, &h) in cells.iter().enumerate() {
+            assert_eq!(getBaseCellNumber(h), bc as i32, "base cell number");
+            assert_eq!(baseCellNumberToCell(bc as i32), Ok(h), "round trips");
+        }
+        assert_eq!(
+            getBaseCellNumber(0x85283473fffffff),
+            20,
+            "base cell of a finer cell"
+        );
+        assert_eq!(baseCellNumberToCell(-1), Err(Error::Domain));
+        assert_eq!(baseCellNumberToCell(NUM_BASE_CELLS), Err(Error::Domain));
+    }
+
+    #[test]
+    fn getPentagons() {
+        for res in 0..=MAX_H3_RES {
+            let pentagons = super::getPentagons(res).unwrap();
+            assert_eq!(
+                pentagons.len(),
+                pentagonCount() as usize,
+                "got the expected number of pentagons"
+            );
+            for (i, &h) in pentagons.iter().enumerate() {
+                assert!(isValidCell(h), "pentagon is valid");
+                assert!(isPentagon(h), "pentagon is a pentagon");
+                assert_eq!(getResolution(h), res, "pentagon has correct resolution");
+                assert!(!pentagons[..i].contains(&h), "pentagon is unique");
+            }
+        }
+        assert_eq!(
+            super::getPentagons(0).unwrap()[0],
+            0x8009fffffffffff,
+            "first pentagon is base cell 4"
+        );
+    }
+
+    #[test]
+    fn getPentagons_invalid() {
+        assert_eq!(super::getPentagons(-1), Err(Error::ResDomain));
+        assert_eq!(super::getPentagons(MAX_H3_RES + 1), Err(Error::ResDomain));
+    }
+
+    #[test]
+    fn selfCheck() {
+        assert_eq!(super::selfCheck(), Ok(()), "tables are consistent");
+    }
 }
@@ -0,0 +1,279 @@
+use core::f64::consts::PI;
+
+use crate::constants::*;
+use crate::error::Error;
+use crate::h3_index::{cellBounds, H3Index};
+use crate::lat_lng::{constrainLng, LatLng};
+use crate::prelude::*;
+
+/** @struct BBox
+ *  @brief  Geographic bounding box with coordinates defined in radians
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BBox {
+    /** north latitude */
+    pub north: f64,
+    /** south latitude */
+    pub south: f64,
+    /** east longitude */
+    pub east: f64,
+    /** west longitude */
+    pub west: f64,
+}
+
+/**
+ * Whether the given bounding box crosses the antimeridian
+ * @param  bbox Bounding box to inspect
+ * @return      is transmeridian
+ */
+pub fn bboxIsTransmeridian(bbox: &BBox) -> bool {
+    bbox.east < bbox.west
+}
+
+/**
+ * Normalize a longitude into the frame of a bounding box or loop, shifting
+ * negative longitudes by 360 degrees if the geometry crosses the
+ * antimeridian, so longitudes within it increase eastward without a jump.
+ * Longitudes outside [-180, 180] degrees, as used by some GeoJSON to draw
+ * loops across the antimeridian, are first wrapped into that range.
+ * @param  lng             Longitude in radians
+ * @param  isTransmeridian Whether the geometry crosses the antimeridian
+ * @return                 The normalized longitude
+ */
+pub fn bboxNormalizeLng(lng: f64, isTransmeridian: bool) -> f64 {
+    let lng = constrainLng(lng);
+    if isTransmeridian && lng < 0.0 {
+        return lng + M_2PI;
+    }
+    lng
+}
+
+/**
+ * How far east of the west edge of the bounding box a longitude is,
+ * wrapping around the antimeridian. The longitude is within the box's
+ * longitudes if this is at most bboxWidthRads.
+ * @param  bbox Bounding box
+ * @param  lng  Longitude in radians
+ * @return      The offset in radians, in [0, 2pi)
+ */
+pub fn bboxLngOffset(bbox: &BBox, lng: f64) -> f64 {
+    let d = constrainLng(lng - bbox.west);
+    if d < 0.0 {
+        d + M_2PI
+    } else {
+        d
+    }
+}
+
+/**
+ * The width of the bounding box in radians, accounting for boxes crossing the
+ * antimeridian.
+ * @param  bbox Bounding box to inspect
+ * @return      width in radians
+ */
+pub fn bboxWidthRads(bbox: &BBox) -> f64 {
+    if bboxIsTransmeridian(bbox) {
+        return bbox.east - bbox.west + M_2PI;
+    }
+    bbox.east - bbox.west
+}
+
+/**
+ * The height of the bounding box in radians.
+ * @param  bbox Bounding box to inspect
+ * @return      height in radians
+ */
+pub fn bboxHeightRads(bbox: &BBox) -> f64 {
+    bbox.north - bbox.south
+}
+
+/**
+ * Whether the bounding box contains a given point
+ * @param  bbox  Bounding box
+ * @param  point Point to test
+ * @return       Whether the point is contained
+ */
+pub fn bboxContains(bbox: &BBox, point: &LatLng) -> bool {
+    point.lat >= bbox.south
+        && point.lat <= bbox.north
+        && (if bboxIsTransmeridian(bbox) {
+            // transmeridian case
+            point.lng >= bbox.west || point.lng <= bbox.east
+        } else {
+            // standard case
+            point.lng >= bbox.west && point.lng <= bbox.east
+        })
+}
+
+/**
+ * Whether two bounding boxes overlap, including touching at an edge
+ * @param  a First bounding box
+ * @param  b Second bounding box
+ * @return   Whether the boxes overlap
+ */
+pub fn bboxOverlapsBBox(a: &BBox, b: &BBox) -> bool {
+    if a.north < b.south || a.south > b.north {
+        return false;
+    }
+    // each box covers the longitudes up to its width east of its west edge,
+    // so they overlap if either west edge is within the other box
+    bboxLngOffset(a, b.west) <= bboxWidthRads(a) || bboxLngOffset(b, a.west) <= bboxWidthRads(b)
+}
+
+/**
+ * Create a bounding box covering a set of cells, such as for fitting a map
+ * viewport to them. Longitudes are covered by the narrowest band containing
+ * every cell, so cells on both sides of the antimeridian give a
+ * transmeridian box rather than one spanning the globe.
+ * @param  cells The cells
+ * @return       The bounding box, all zeros if there are no cells, or
+ *               CellInvalid if a cell is invalid
+ */
+pub fn bboxFromCells(cells: &[H3Index]) -> Result<BBox, Error> {
+    if cells.is_empty() {
+        return Ok(BBox {
+            north: 0.0,
+            south: 0.0,
+            east: 0.0,
+            west: 0.0,
+        });
+    }
+
+    let mut north = -f64::MAX;
+    let mut south = f64::MAX;
+    // longitude ranges as (west, east), with east unwrapped to be >= west
+    let mut ranges: Vec<(f64, f64)> = Vec::with_capacity(cells.len());
+    for &h in cells {
+        let (sw, ne) = cellBounds(h)?;
+        north = north.max(ne.lat);
+        south = south.min(sw.lat);
+        let east = if ne.lng < sw.lng {
+            ne.lng + M_2PI
+        } else {
+            ne.lng
+        };
+        ranges.push((sw.lng, east));
+    }
+
+    // merge the overlapping ranges, in order of west longitude
+    ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (west, east) in ranges {
+        match merged.last_mut() {
+            Some(last) if west <= last.1 => last.1 = last.1.max(east),
+            _ => merged.push((west, east)),
+        }
+    }
+
+    // the box is the complement of the widest longitude gap between ranges.
+    // Only the last range can extend past the antimeridian, covering the
+    // start of the gaps after the first ranges.
+    let last = merged[merged.len() - 1];
+    let wrappedEast = last.1 - M_2PI;
+    let mut widestGap = merged[0].0 + M_2PI - last.1;
+    let mut west = merged[0].0;
+    let mut east = last.1;
+    for pair in merged.windows(2) {
+        let gapStart = pair[0].1.max(wrappedEast);
+        if pair[1].0 - gapStart > widestGap {
+            widestGap = pair[1].0 - gapStart;
+            west = pair[1].0;
+            east = gapStart;
+        }
+    }
+    if widestGap <= 0.0 {
+        // every longitude is covered
+        west = -PI;
+        east = PI;
+    }
+    Ok(BBox {
+        north,
+        south,
+        east: constrainLng(east),
+        west: constrainLng(west),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_PI_2;
+
+    use crate::algos::gridDisk;
+    use crate::h3_index::{cellToBoundary, latLngToCell};
+
+    use super::*;
+
+    fn cellAt(lat: f64, lng: f64, res: i32) -> H3Index {
+        let g = LatLng::from_degrees(lat, lng);
+        latLngToCell(&g, res).unwrap()
+    }
+
+    #[test]
+    fn bboxFromCells_coversCells() {
+        let cells = gridDisk(cellAt(37.77, -122.42, 7), 3).unwrap();
+        let bbox = bboxFromCells(&cells).unwrap();
+        assert!(!bboxIsTransmeridian(&bbox), "not transmeridian");
+        for &h in &cells {
+            for v in cellToBoundary(h).unwrap().to_vec() {
+                assert!(bboxContains(&bbox, &v), "contains the vertexes");
+            }
+        }
+
+        // cells on both sides of the antimeridian
+        let across = [cellAt(10.0, 179.5, 5), cellAt(10.0, -179.5, 5)];
+        let bbox = bboxFromCells(&across).unwrap();
+        assert!(bboxIsTransmeridian(&bbox), "transmeridian");
+        assert!(bboxWidthRads(&bbox) < 0.05, "narrow, not the whole globe");
+
+        // cells spread around the globe leave the widest gap out
+        let spread = [
+            cellAt(0.0, 0.0, 3),
+            cellAt(0.0, 90.0, 3),
+            cellAt(0.0, -150.0, 3),
+        ];
+        let bbox = bboxFromCells(&spread).unwrap();
+        assert!(bboxIsTransmeridian(&bbox), "gap is around 75W");
+        assert!(bboxWidthRads(&bbox) < 4.0, "the gap is excluded");
+
+        // a cell containing a pole covers every longitude
+        let polar = bboxFromCells(&[cellAt(90.0, 0.0, 2), cellAt(80.0, 50.0, 2)]).unwrap();
+        assert_eq!(bboxWidthRads(&polar), M_2PI, "all longitudes");
+        assert_eq!(polar.north, FRAC_PI_2, "reaches the pole");
+
+        assert_eq!(bboxFromCells(&[]).unwrap().north, 0.0, "empty");
+        assert_eq!(bboxFromCells(&[0]), Err(Error::CellInvalid), "invalid");
+    }
+
+    #[test]
+    fn normalizeLngs() {
+        let bbox = BBox {
+            north: 0.1,
+            south: -0.1,
+            east: -3.0,
+            west: 3.0,
+        };
+        assert!(bboxIsTransmeridian(&bbox), "transmeridian");
+        assert_eq!(bboxNormalizeLng(-3.0, true), -3.0 + M_2PI, "shifted east");
+        assert_eq!(bboxNormalizeLng(3.0, true), 3.0, "positive unchanged");
+        assert_eq!(bboxNormalizeLng(-3.0, false), -3.0, "not transmeridian");
+        assert!(
+            (bboxNormalizeLng(-3.0 - M_2PI, false) + 3.0).abs() < 1e-12,
+            "wrapped first"
+        );
+        assert!(
+            bboxNormalizeLng(bbox.east, true) - bboxNormalizeLng(bbox.west, true) > 0.0,
+            "east of west in the frame"
+        );
+
+        assert_eq!(bboxLngOffset(&bbox, 3.0), 0.0, "at the west edge");
+        assert!(
+            (bboxLngOffset(&bbox, -3.0) - bboxWidthRads(&bbox)).abs() < 1e-12,
+            "east edge is the width away"
+        );
+        assert!(
+            bboxLngOffset(&bbox, PI) < bboxWidthRads(&bbox),
+            "antimeridian inside"
+        );
+        assert!(bboxLngOffset(&bbox, 0.0) > bboxWidthRads(&bbox), "outside");
+    }
+}
@@ -0,0 +1,138 @@
+use core::f64::consts::PI;
+
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+use crate::error::Error;
+use crate::face_ijk::{_geoVec3dToClosestFace, _vec3dToClosestFace};
+use crate::h3_index::{latLngToCell, H3Index};
+use crate::lat_lng::LatLng;
+use crate::prelude::*;
+use crate::vec3d::{_geoToVec3d, Vec3d};
+
+/**
+ * A small deterministic random number generator (SplitMix64), so benchmarks
+ * and tests see the same inputs on every run without a rand dependency.
+ */
+#[derive(Debug, Clone)]
+pub struct BenchRng {
+    state: u64,
+}
+
+impl BenchRng {
+    /** Creates a generator from a seed. */
+    pub fn new(seed: u64) -> BenchRng {
+        BenchRng { state: seed }
+    }
+
+    /** Returns the next 64 random bits. */
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /** Returns a random number in [0, 1). */
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/**
+ * Generates points uniformly distributed over the sphere.
+ *
+ * @param seed The seed; the same seed always gives the same points.
+ * @param n The number of points.
+ * @return The points, in radians.
+ */
+pub fn randomLatLngs(seed: u64, n: usize) -> Vec<LatLng> {
+    let mut rng = BenchRng::new(seed);
+    let mut points = Vec::with_capacity(n);
+    for _ in 0..n {
+        let lat = (2.0 * rng.next_f64() - 1.0).asin();
+        let lng = (2.0 * rng.next_f64() - 1.0) * PI;
+        points.push(LatLng { lat, lng });
+    }
+    points
+}
+
+/**
+ * Generates cells containing points uniformly distributed over the sphere,
+ * so larger cells (near the icosahedron face centers) are more likely.
+ *
+ * @param seed The seed; the same seed always gives the same cells.
+ * @param n The number of cells, which may include duplicates.
+ * @param res The resolution.
+ * @return The cells.
+ */
+pub fn randomCells(seed: u64, n: usize, res: i32) -> Result<Vec<H3Index>, Error> {
+    randomLatLngs(seed, n)
+        .iter()
+        .map(|g| latLngToCell(g, res))
+        .collect()
+}
+
+/** Returns the closest icosahedron face using the band table, as
+ * latLngToCell does. */
+pub fn closestFaceByTable(g: &LatLng) -> i32 {
+    let mut v3d = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    _geoToVec3d(g, &mut v3d);
+    let (mut face, mut sqd) = (0, 0.0);
+    _geoVec3dToClosestFace(g, &v3d, &mut face, &mut sqd);
+    face
+}
+
+/** Returns the closest icosahedron face by checking all 20 faces, for
+ * comparison with closestFaceByTable. */
+pub fn closestFaceByScan(g: &LatLng) -> i32 {
+    let mut v3d = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    _geoToVec3d(g, &mut v3d);
+    let (mut face, mut sqd) = (0, 0.0);
+    _vec3dToClosestFace(&v3d, &mut face, &mut sqd);
+    face
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::h3_index::{getResolution, isValidCell};
+
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(
+            randomLatLngs(42, 100),
+            randomLatLngs(42, 100),
+            "same seed, same points"
+        );
+        assert_ne!(
+            randomLatLngs(42, 100),
+            randomLatLngs(43, 100),
+            "different seed, different points"
+        );
+        let cells = randomCells(7, 1000, 9).unwrap();
+        assert_eq!(cells.len(), 1000);
+        assert!(
+            cells
+                .iter()
+                .all(|&h| isValidCell(h) && getResolution(h) == 9),
+            "valid cells at the resolution"
+        );
+
+        let mut rng = BenchRng::new(0);
+        for _ in 0..1000 {
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f), "in [0, 1)");
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// Command line filters, like the ones shipped with the C library: each
+// subcommand reads one input per line from stdin and writes the results to
+// stdout, so they can be chained in shell pipelines.
+//
+//     echo "37.7749 -122.4194" | h3 latLngToCell -r 9 | h3 gridDisk -k 1
+
+#![allow(non_snake_case)]
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+
+use h3_rs::algos::gridDiskDistances;
+use h3_rs::h3_index::{cellToBoundary, cellToLatLng, h3ToString, latLngToCell, stringToH3};
+use h3_rs::lat_lng::LatLng;
+
+const USAGE: &str = "usage: h3 <command> [options] < input
+
+commands:
+  latLngToCell -r <res>   lat lng (degrees) per line to cells
+  cellToLatLng            cells to center lat lng (degrees)
+  cellToBoundary          cells to boundary vertexes (degrees)
+  gridDisk -k <k>         cells to the cells within k of each
+                          (--print-distances adds each distance)";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    LatLngToCell { res: i32 },
+    CellToLatLng,
+    CellToBoundary,
+    GridDisk { k: u32, printDistances: bool },
+}
+
+/** Parses the command line, excluding the program name. */
+fn parseArgs(args: &[String]) -> Result<Command, String> {
+    let (name, rest) = args.split_first().ok_or("missing command")?;
+    let mut res: Option<i32> = None;
+    let mut k: Option<u32> = None;
+    let mut printDistances = false;
+
+    let mut i = 0;
+    while i < rest.len() {
+        let flag = rest[i].as_str();
+        match flag {
+            "-r" | "--resolution" | "-k" => {
+                let value = rest.get(i + 1).ok_or(format!("{} needs a value", flag))?;
+                if flag == "-k" {
+                    k = Some(value.parse().map_err(|_| format!("invalid k: {}", value))?);
+                } else {
+                    res = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid resolution: {}", value))?,
+                    );
+                }
+                i += 2;
+            }
+            "--print-distances" => {
+                printDistances = true;
+                i += 1;
+            }
+            _ => return Err(format!("unknown option: {}", flag)),
+        }
+    }
+
+    match name.as_str() {
+        "latLngToCell" => Ok(Command::LatLngToCell {
+            res: res.ok_or("latLngToCell needs -r")?,
+        }),
+        "cellToLatLng" => Ok(Command::CellToLatLng),
+        "cellToBoundary" => Ok(Command::CellToBoundary),
+        "gridDisk" => Ok(Command::GridDisk {
+            k: k.ok_or("gridDisk needs -k")?,
+            printDistances,
+        }),
+        _ => Err(format!("unknown command: {}", name)),
+    }
+}
+
+fn parseCell(line: &str) -> Result<u64, String> {
+    stringToH3(line).map_err(|e| format!("{}: {}", e, line))
+}
+
+/** Runs the command on one line of input, writing its output lines. */
+fn filterLine(command: Command, line: &str, out: &mut impl Write) -> Result<(), String> {
+    let io = |e: io::Error| e.to_string();
+    match command {
+        Command::LatLngToCell { res } => {
+            let mut parts = line.split_whitespace().map(str::parse::<f64>);
+            let (Some(Ok(lat)), Some(Ok(lng)), None) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!("expected \"lat lng\": {}", line));
+            };
+            let g = LatLng::from_degrees(lat, lng);
+            let h = latLngToCell(&g, res).map_err(|e| e.to_string())?;
+            writeln!(out, "{}", h3ToString(h)).map_err(io)?;
+        }
+        Command::CellToLatLng => {
+            let g = cellToLatLng(parseCell(line)?).map_err(|e| e.to_string())?;
+            writeln!(out, "{:.9} {:.9}", g.lat_degrees(), g.lng_degrees()).map_err(io)?;
+        }
+        Command::CellToBoundary => {
+            let h = parseCell(line)?;
+            let boundary = cellToBoundary(h).map_err(|e| e.to_string())?;
+            writeln!(out, "{}\n{{", h3ToString(h)).map_err(io)?;
+            for g in boundary.to_vec() {
+                writeln!(out, "   {:.9} {:.9}", g.lat_degrees(), g.lng_degrees()).map_err(io)?;
+            }
+            writeln!(out, "}}").map_err(io)?;
+        }
+        Command::GridDisk { k, printDistances } => {
+            let disk = gridDiskDistances(parseCell(line)?, k).map_err(|e| e.to_string())?;
+            for (h, distance) in disk {
+                if printDistances {
+                    writeln!(out, "{} {}", h3ToString(h), distance).map_err(io)?;
+                } else {
+                    writeln!(out, "{}", h3ToString(h)).map_err(io)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parseArgs(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("h3: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    for (i, line) in io::stdin().lock().lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("h3: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Err(e) = filterLine(command, line, &mut out) {
+            let _ = out.flush();
+            eprintln!("h3: line {}: {}", i + 1, e);
+            return ExitCode::FAILURE;
+        }
+    }
+    if out.flush().is_err() {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn run(command: &str, input: &str) -> Result<String, String> {
+        let command = parseArgs(&args(command))?;
+        let mut out: Vec<u8> = Vec::new();
+        filterLine(command, input, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn parsesArgs() {
+        assert_eq!(
+            parseArgs(&args("latLngToCell -r 9")),
+            Ok(Command::LatLngToCell { res: 9 })
+        );
+        assert_eq!(
+            parseArgs(&args("gridDisk --print-distances -k 2")),
+            Ok(Command::GridDisk {
+                k: 2,
+                printDistances: true
+            })
+        );
+        assert!(parseArgs(&args("latLngToCell")).is_err(), "needs res");
+        assert!(parseArgs(&args("gridDisk -k -1")).is_err(), "bad k");
+        assert!(parseArgs(&args("nope")).is_err(), "unknown command");
+        assert!(parseArgs(&[]).is_err(), "no command");
+    }
+
+    #[test]
+    fn filters() {
+        let cell = run("latLngToCell -r 5", "37.3615593 -122.0553238").unwrap();
+        assert_eq!(cell, "85283473fffffff\n");
+        let center = run("cellToLatLng", cell.trim()).unwrap();
+        assert_eq!(
+            run("latLngToCell -r 5", center.trim()).unwrap(),
+            cell,
+            "center indexes back to the cell"
+        );
+
+        let boundary = run("cellToBoundary", "85283473fffffff").unwrap();
+        assert!(boundary.starts_with("85283473fffffff\n{\n"), "header");
+        assert_eq!(boundary.lines().count(), 9, "cell, braces and 6 vertexes");
+
+        let disk = run("gridDisk -k 1 --print-distances", "85283473fffffff").unwrap();
+        assert_eq!(disk.lines().count(), 7, "disk of 7 cells");
+        assert!(disk.contains("85283473fffffff 0\n"), "origin at 0");
+
+        assert!(run("latLngToCell -r 5", "37.3").is_err(), "missing lng");
+        assert!(run("cellToLatLng", "zzz").is_err(), "invalid cell");
+    }
+}
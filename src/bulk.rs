@@ -0,0 +1,553 @@
+use core::ops::Range;
+
+use crate::constants::{M_180_PI, M_PI_180, NUM_BASE_CELLS};
+use crate::error::Error;
+#[cfg(feature = "parallel")]
+use crate::h3_index::uncompactCellsSize;
+use crate::h3_index::{
+    cellToLatLng, compactCells, latLngToCell, uncompactCells, H3Index, H3_GET_BASE_CELL,
+    H3_GET_RESOLUTION,
+};
+use crate::lat_lng::LatLng;
+use crate::prelude::*;
+use crate::H3_NULL;
+
+/** Inputs shorter than this are not worth splitting across threads. */
+#[cfg(feature = "parallel")]
+const MIN_CHUNK_LEN: usize = 4096;
+
+/** Number of threads to split work across. */
+#[cfg(feature = "parallel")]
+fn _numThreads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/**
+ * Applies f to each chunk on its own scoped thread and concatenates the
+ * results in order.
+ *
+ * @param chunks The input, already split into one chunk per thread.
+ * @param capacity The expected length of the output.
+ * @param f Maps a chunk of the input to its output.
+ * @return The concatenated output, or the error for the earliest failing
+ *         chunk.
+ */
+#[cfg(feature = "parallel")]
+fn _mapSlices<'a, T: Sync + 'a, U: Send>(
+    chunks: impl Iterator<Item = &'a [T]>,
+    capacity: usize,
+    f: impl Fn(&[T]) -> Result<Vec<U>, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    // reserve the output before the workers allocate their parts of it
+    let mut out = Vec::<U>::new();
+    out.try_reserve_exact(capacity)
+        .map_err(|_| Error::MemoryBounds)?;
+    let f = &f;
+    let results: Vec<Result<Vec<U>, Error>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks.map(|chunk| scope.spawn(move || f(chunk))).collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("bulk worker panicked"))
+            .collect()
+    });
+
+    for result in results {
+        out.extend(result?);
+    }
+    Ok(out)
+}
+
+/**
+ * Applies f to consecutive chunks of items and concatenates the results in
+ * order. With the parallel feature the chunks are processed on scoped
+ * threads, one per available core; otherwise f is applied to all of the items
+ * at once.
+ *
+ * @param items The input.
+ * @param f Maps a chunk of the input to its output.
+ * @return The concatenated output, or the error for the earliest failing
+ *         chunk.
+ */
+#[cfg(feature = "parallel")]
+fn _mapChunks<T: Sync, U: Send>(
+    items: &[T],
+    f: impl Fn(&[T]) -> Result<Vec<U>, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    let threads = _numThreads();
+    if threads <= 1 || items.len() < 2 * MIN_CHUNK_LEN {
+        return f(items);
+    }
+    let chunkLen = items.len().div_ceil(threads).max(MIN_CHUNK_LEN);
+    _mapSlices(items.chunks(chunkLen), items.len(), f)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn _mapChunks<T: Sync, U: Send>(
+    items: &[T],
+    f: impl Fn(&[T]) -> Result<Vec<U>, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    f(items)
+}
+
+/**
+ * Applies f to consecutive ranges of rows and concatenates the results in
+ * order, like _mapChunks for inputs held in separate columns.
+ *
+ * @param len The number of rows.
+ * @param f Maps a range of rows to its output.
+ * @return The concatenated output, or the error for the earliest failing
+ *         range.
+ */
+#[cfg(feature = "parallel")]
+fn _mapRows<U: Send>(
+    len: usize,
+    f: impl Fn(Range<usize>) -> Result<Vec<U>, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    let threads = _numThreads();
+    if threads <= 1 || len < 2 * MIN_CHUNK_LEN {
+        return f(0..len);
+    }
+    let chunkLen = len.div_ceil(threads).max(MIN_CHUNK_LEN);
+    let starts: Vec<usize> = (0..len).step_by(chunkLen).collect();
+    _mapSlices(starts.chunks(1), len, |start| {
+        f(start[0]..(start[0] + chunkLen).min(len))
+    })
+}
+
+#[cfg(not(feature = "parallel"))]
+fn _mapRows<U: Send>(
+    len: usize,
+    f: impl Fn(Range<usize>) -> Result<Vec<U>, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    f(0..len)
+}
+
+/**
+ * Applies f to consecutive chunks of items, each with the matching chunk of
+ * the output. With the parallel feature the chunks are processed on scoped
+ * threads, one per available core; otherwise f is applied to all of the items
+ * at once.
+ *
+ * @param items The input.
+ * @param out The output, as long as the input.
+ * @param f Writes the output for a chunk of the input.
+ * @return The error for the earliest failing chunk, if any.
+ */
+#[cfg(feature = "parallel")]
+fn _forChunksMut<T: Sync, U: Send>(
+    items: &[T],
+    out: &mut [U],
+    f: impl Fn(&[T], &mut [U]) -> Result<(), Error> + Sync,
+) -> Result<(), Error> {
+    let threads = _numThreads();
+    if threads <= 1 || items.len() < 2 * MIN_CHUNK_LEN {
+        return f(items, out);
+    }
+    let chunkLen = items.len().div_ceil(threads).max(MIN_CHUNK_LEN);
+    let f = &f;
+    let results: Vec<Result<(), Error>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunkLen)
+            .zip(out.chunks_mut(chunkLen))
+            .map(|(chunk, outChunk)| scope.spawn(move || f(chunk, outChunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("bulk worker panicked"))
+            .collect()
+    });
+    results.into_iter().collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn _forChunksMut<T: Sync, U: Send>(
+    items: &[T],
+    out: &mut [U],
+    f: impl Fn(&[T], &mut [U]) -> Result<(), Error> + Sync,
+) -> Result<(), Error> {
+    f(items, out)
+}
+
+/**
+ * Indexes many points at the same resolution. With the parallel feature the
+ * points are split across threads.
+ *
+ * @param points The points, in radians.
+ * @param res The resolution.
+ * @return The cell of each point, in the same order, or the first error
+ *         encountered.
+ */
+pub fn latLngToCellBulk(points: &[LatLng], res: i32) -> Result<Vec<H3Index>, Error> {
+    _mapChunks(points, |chunk| chunk.iter().map(|g| latLngToCell(g, res)).collect())
+}
+
+/**
+ * Finds the centers of many cells. With the parallel feature the cells are
+ * split across threads.
+ *
+ * @param cells The cells.
+ * @return The center of each cell, in the same order, or the first error
+ *         encountered.
+ */
+pub fn cellToLatLngBulk(cells: &[H3Index]) -> Result<Vec<LatLng>, Error> {
+    let mut out = vec![LatLng { lat: 0.0, lng: 0.0 }; cells.len()];
+    cellToLatLngBulkInto(cells, &mut out)?;
+    Ok(out)
+}
+
+/**
+ * Finds the centers of many cells into a caller provided buffer, such as one
+ * reused across batches of stored indexes. With the parallel feature the
+ * cells are split across threads.
+ *
+ * @param cells The cells.
+ * @param out The center of each cell, in the same order. Must be the same
+ *            length as cells. Its contents are unspecified on error.
+ * @return The first error encountered, or MemoryBounds if out is not the
+ *         same length as cells.
+ */
+pub fn cellToLatLngBulkInto(cells: &[H3Index], out: &mut [LatLng]) -> Result<(), Error> {
+    if out.len() != cells.len() {
+        return Err(Error::MemoryBounds);
+    }
+    _forChunksMut(cells, out, |chunk, outChunk| {
+        for (&h, g) in chunk.iter().zip(outChunk) {
+            *g = cellToLatLng(h)?;
+        }
+        Ok(())
+    })
+}
+
+/**
+ * Indexes points given as separate latitude and longitude columns, such as
+ * the value buffers of two Float64 Arrow arrays. The output can be wrapped
+ * as a UInt64 array without copying.
+ *
+ * @param lats The latitudes, in degrees.
+ * @param lngs The longitudes, in degrees.
+ * @param res The resolution.
+ * @return The cell of each row, or Domain if the columns differ in length.
+ */
+pub fn latLngColumnsToCells(lats: &[f64], lngs: &[f64], res: i32) -> Result<Vec<H3Index>, Error> {
+    if lats.len() != lngs.len() {
+        return Err(Error::Domain);
+    }
+    _mapRows(lats.len(), |rows| {
+        lats[rows.clone()]
+            .iter()
+            .zip(&lngs[rows])
+            .map(|(&lat, &lng)| {
+                let g = LatLng {
+                    lat: lat * M_PI_180,
+                    lng: lng * M_PI_180,
+                };
+                latLngToCell(&g, res)
+            })
+            .collect()
+    })
+}
+
+/**
+ * Finds the centers of many cells as separate latitude and longitude
+ * columns, the inverse of latLngColumnsToCells.
+ *
+ * @param cells The cells.
+ * @return The latitudes and longitudes of the centers, in degrees.
+ */
+pub fn cellsToLatLngColumns(cells: &[H3Index]) -> Result<(Vec<f64>, Vec<f64>), Error> {
+    let centers = cellToLatLngBulk(cells)?;
+    Ok(centers
+        .iter()
+        .map(|g| (g.lat * M_180_PI, g.lng * M_180_PI))
+        .unzip())
+}
+
+/**
+ * Compacts a set of cells, like compactCells. Cells in different base cells
+ * never share a parent, so the set is split by base cell and, with the
+ * parallel feature, the groups are compacted on separate threads.
+ *
+ * @param h3Set Set of cells, all at the same resolution.
+ * @return The compacted set, grouped by base cell, or ResMismatch if the
+ *         cells have different resolutions.
+ */
+pub fn compactCellsBulk(h3Set: &[H3Index]) -> Result<Vec<H3Index>, Error> {
+    let mut groups: Vec<Vec<H3Index>> = vec![Vec::new(); NUM_BASE_CELLS as usize];
+    let mut res: Option<i32> = None;
+    for &h in h3Set {
+        if h == H3_NULL {
+            continue;
+        }
+        let cellRes = H3_GET_RESOLUTION(h);
+        if *res.get_or_insert(cellRes) != cellRes {
+            return Err(Error::ResMismatch);
+        }
+        let baseCell = H3_GET_BASE_CELL(h);
+        if baseCell >= NUM_BASE_CELLS {
+            return Err(Error::CellInvalid);
+        }
+        groups[baseCell as usize].push(h);
+    }
+    groups.retain(|group| !group.is_empty());
+
+    // one group per task, since groups can be very uneven in size
+    let compacted = _mapEach(&groups, |group| compactCells(group))?;
+    Ok(compacted.into_iter().flatten().collect())
+}
+
+/**
+ * Uncompacts a set of cells, like uncompactCells. With the parallel feature
+ * the set is split across threads so that each expands to about the same
+ * number of cells, since a single coarse cell can have far more children
+ * than many fine ones.
+ *
+ * @param compactedSet Set of compacted cells.
+ * @param res The resolution to decompress to.
+ * @return The uncompacted cells, or ResMismatch if a cell is finer than res.
+ */
+#[cfg(feature = "parallel")]
+pub fn uncompactCellsBulk(compactedSet: &[H3Index], res: i32) -> Result<Vec<H3Index>, Error> {
+    let total = uncompactCellsSize(compactedSet, res)? as usize;
+    let threads = _numThreads();
+    if threads <= 1 || total < 2 * MIN_CHUNK_LEN {
+        return uncompactCells(compactedSet, res);
+    }
+    let chunkSize = total.div_ceil(threads).max(MIN_CHUNK_LEN);
+
+    // cut the input wherever the output of the chunk reaches chunkSize
+    let mut chunks: Vec<&[H3Index]> = Vec::new();
+    let mut start: usize = 0;
+    let mut size: usize = 0;
+    for (i, &h) in compactedSet.iter().enumerate() {
+        size += uncompactCellsSize(&[h], res)? as usize;
+        if size >= chunkSize {
+            chunks.push(&compactedSet[start..=i]);
+            start = i + 1;
+            size = 0;
+        }
+    }
+    chunks.push(&compactedSet[start..]);
+
+    _mapSlices(chunks.into_iter(), total, |chunk| {
+        uncompactCells(chunk, res)
+    })
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn uncompactCellsBulk(compactedSet: &[H3Index], res: i32) -> Result<Vec<H3Index>, Error> {
+    uncompactCells(compactedSet, res)
+}
+
+/**
+ * Applies f to each item. With the parallel feature the items are shared out
+ * to one scoped thread per available core, each taking the next unclaimed
+ * item when it finishes one, so uneven items still balance.
+ *
+ * @param items The input.
+ * @param f Maps an item to its output.
+ * @return The output for each item, in order, or the error for the earliest
+ *         failing item.
+ */
+#[cfg(feature = "parallel")]
+fn _mapEach<T: Sync, U: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> Result<U, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    let threads = _numThreads().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let (f, next) = (&f, &next);
+    let done: Vec<Vec<(usize, Result<U, Error>)>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            break;
+                        };
+                        done.push((i, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("bulk worker panicked"))
+            .collect()
+    });
+
+    let mut results: Vec<Option<Result<U, Error>>> = (0..items.len()).map(|_| None).collect();
+    for (i, result) in done.into_iter().flatten() {
+        results[i] = Some(result);
+    }
+    results.into_iter().map(|result| result.unwrap()).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn _mapEach<T: Sync, U: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> Result<U, Error> + Sync,
+) -> Result<Vec<U>, Error> {
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::h3_index::cellToChildren;
+
+    use super::*;
+
+    fn samplePoints(n: usize) -> Vec<LatLng> {
+        let mut points = Vec::with_capacity(n);
+        for i in 0..n {
+            let f = i as f64 / n as f64;
+            points.push(LatLng::from_degrees(
+                -80.0 + 160.0 * f,
+                -179.0 + 358.0 * ((f * 97.0) % 1.0),
+            ));
+        }
+        points
+    }
+
+    #[test]
+    fn latLngToCellBulk_matchesSerial() {
+        // long enough to be split with the parallel feature
+        let points = samplePoints(10000);
+        let cells = latLngToCellBulk(&points, 7).unwrap();
+        assert_eq!(cells.len(), points.len(), "one cell per point");
+        for (g, &h) in points.iter().zip(cells.iter()) {
+            assert_eq!(latLngToCell(g, 7).unwrap(), h, "matches latLngToCell");
+        }
+
+        let centers = cellToLatLngBulk(&cells).unwrap();
+        for (&h, center) in cells.iter().zip(centers.iter()) {
+            assert_eq!(cellToLatLng(h).unwrap(), *center, "matches cellToLatLng");
+        }
+    }
+
+    #[test]
+    fn cellToLatLngBulkInto_reusesBuffer() {
+        let cells = latLngToCellBulk(&samplePoints(10_000), 9).unwrap();
+        let mut out = vec![LatLng { lat: 0.0, lng: 0.0 }; cells.len()];
+        cellToLatLngBulkInto(&cells, &mut out).unwrap();
+        for (h, g) in cells.iter().zip(&out) {
+            assert_eq!(*g, cellToLatLng(*h).unwrap(), "center of {:x}", h);
+        }
+        cellToLatLngBulkInto(&cells[..10], &mut out[..10]).unwrap();
+
+        assert_eq!(
+            cellToLatLngBulkInto(&cells, &mut out[1..]),
+            Err(Error::MemoryBounds),
+            "short output"
+        );
+        let mut bad = cells.clone();
+        bad[9_000] |= 0x7f << 45; // base cell 127
+        assert_eq!(
+            cellToLatLngBulkInto(&bad, &mut out),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+        assert_eq!(cellToLatLngBulkInto(&[], &mut []), Ok(()), "empty input");
+    }
+
+    #[test]
+    fn latLngToCellBulk_errors() {
+        let mut points = samplePoints(10000);
+        assert_eq!(
+            latLngToCellBulk(&points, 16),
+            Err(Error::ResDomain),
+            "invalid res fails"
+        );
+        points[9000].lat = f64::NAN;
+        assert_eq!(
+            latLngToCellBulk(&points, 5),
+            Err(Error::LatLngDomain),
+            "invalid point fails"
+        );
+    }
+
+    #[test]
+    fn latLngColumnsToCells_roundTrip() {
+        let lats = [37.775938728915946, -33.8688, 0.0];
+        let lngs = [-122.41795063018799, 151.2093, 0.0];
+        let cells = latLngColumnsToCells(&lats, &lngs, 9).unwrap();
+        for i in 0..lats.len() {
+            let g = LatLng::from_degrees(lats[i], lngs[i]);
+            assert_eq!(cells[i], latLngToCell(&g, 9).unwrap(), "row {}", i);
+        }
+
+        let (centerLats, centerLngs) = cellsToLatLngColumns(&cells).unwrap();
+        assert_eq!(
+            latLngColumnsToCells(&centerLats, &centerLngs, 9).unwrap(),
+            cells,
+            "centers index back to the cells"
+        );
+        assert_eq!(
+            latLngColumnsToCells(&lats, &lngs[..2], 9),
+            Err(Error::Domain),
+            "columns must have the same length"
+        );
+
+        // long enough to be split with the parallel feature
+        let points = samplePoints(10000);
+        let (lats, lngs): (Vec<f64>, Vec<f64>) = points
+            .iter()
+            .map(|g| (g.lat * M_180_PI, g.lng * M_180_PI))
+            .unzip();
+        let cells = latLngColumnsToCells(&lats, &lngs, 7).unwrap();
+        for (g, &h) in points.iter().zip(cells.iter()) {
+            assert_eq!(latLngToCell(g, 7).unwrap(), h, "matches latLngToCell");
+        }
+    }
+
+    #[test]
+    fn compactCellsBulk_roundTrip() {
+        let mut cells = cellToChildren(0x8029fffffffffff, 3).unwrap();
+        cells.extend(cellToChildren(0x8009fffffffffff, 3).unwrap());
+        cells.pop();
+        let compacted = compactCellsBulk(&cells).unwrap();
+        let mut expected = compactCells(&cells).unwrap();
+        let mut got = compacted.clone();
+        expected.sort();
+        got.sort();
+        assert_eq!(got, expected, "same cells as compactCells");
+
+        let mut uncompacted = uncompactCellsBulk(&compacted, 3).unwrap();
+        uncompacted.sort();
+        cells.sort();
+        assert_eq!(uncompacted, cells, "uncompacts back to the input");
+
+        // one coarse cell expands to more than all of the others
+        let mut uneven = cellToChildren(0x8009fffffffffff, 3).unwrap();
+        uneven.push(0x8029fffffffffff);
+        assert_eq!(
+            uncompactCellsBulk(&uneven, 6),
+            uncompactCells(&uneven, 6),
+            "uneven input keeps its order"
+        );
+        assert_eq!(
+            uncompactCellsBulk(&uneven, 2),
+            Err(Error::ResMismatch),
+            "finer cells fail"
+        );
+        assert_eq!(
+            uncompactCellsBulk(&crate::base_cells::getRes0Cells().unwrap(), 15),
+            Err(Error::MemoryBounds),
+            "huge expansions fail"
+        );
+
+        assert_eq!(
+            compactCellsBulk(&[0x8029fffffffffff, 0x85283473fffffff]),
+            Err(Error::ResMismatch),
+            "mixed resolutions fail"
+        );
+        assert_eq!(compactCellsBulk(&[]), Ok(Vec::new()), "empty input");
+    }
+}
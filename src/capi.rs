@@ -0,0 +1,1344 @@
+// C ABI matching h3api.h from the C library, so the crate can stand in for
+// libh3 where existing bindings link against it. Build the shared library
+// with:
+//
+//     cargo rustc --release --features capi --crate-type cdylib
+//
+// As in C, output pointers must point to enough memory for the output (use
+// the matching *Size functions) and no pointer is checked for null.
+#![allow(
+    clippy::missing_safety_doc,
+    reason = "every function shares the pointer contract above, as in h3api.h"
+)]
+
+use alloc::boxed::Box;
+use core::ffi::{c_char, c_int, CStr};
+use core::{ptr, slice};
+
+use crate::constants::{EARTH_RADIUS_KM, M_180_PI, M_PI_180, NUM_BASE_CELLS, NUM_PENTAGONS};
+use crate::coord_ijk::CoordIJ;
+use crate::error::{Error, H3Error, E_DOMAIN, E_MEMORY_BOUNDS, E_OPTION_INVALID, E_SUCCESS};
+use crate::face_ijk::INVALID_FACE;
+use crate::h3_index::H3Index;
+use crate::lat_lng::{LatLng, MAX_CELL_BNDRY_VERTS};
+use crate::prelude::*;
+use crate::{
+    algos, base_cells, directed_edge, error, h3_index, lat_lng, local_ij, measurement, polygon,
+    vertex,
+};
+
+/** @struct CellBoundary
+ *  @brief cell boundary in latitude/longitude, laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CellBoundary {
+    /** number of vertices */
+    pub numVerts: c_int,
+    /** vertices in ccw order */
+    pub verts: [LatLng; MAX_CELL_BNDRY_VERTS],
+}
+
+/** @struct GeoLoop
+ *  @brief similar to GeoJSON linear ring, laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GeoLoop {
+    /** number of vertices */
+    pub numVerts: c_int,
+    /** vertices, in radians */
+    pub verts: *mut LatLng,
+}
+
+/** @struct GeoPolygon
+ *  @brief Simplified core of GeoJSON Polygon coordinates, laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GeoPolygon {
+    /** exterior boundary of the polygon */
+    pub geoloop: GeoLoop,
+    /** number of elements in the array pointed to by holes */
+    pub numHoles: c_int,
+    /** interior boundaries (holes) in the polygon */
+    pub holes: *mut GeoLoop,
+}
+
+/** @struct LinkedLatLng
+ *  @brief A coordinate node in a linked geo structure, laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug)]
+pub struct LinkedLatLng {
+    pub vertex: LatLng,
+    pub next: *mut LinkedLatLng,
+}
+
+/** @struct LinkedGeoLoop
+ *  @brief A loop node in a linked geo structure, part of a linked polygon,
+ *  laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug)]
+pub struct LinkedGeoLoop {
+    pub first: *mut LinkedLatLng,
+    pub last: *mut LinkedLatLng,
+    pub next: *mut LinkedGeoLoop,
+}
+
+/** @struct LinkedGeoPolygon
+ *  @brief A polygon node in a linked geo structure, part of a linked
+ *  multi-polygon, laid out as in C
+ */
+#[repr(C)]
+#[derive(Debug)]
+pub struct LinkedGeoPolygon {
+    pub first: *mut LinkedGeoLoop,
+    pub last: *mut LinkedGeoLoop,
+    pub next: *mut LinkedGeoPolygon,
+}
+
+impl LinkedGeoPolygon {
+    const EMPTY: LinkedGeoPolygon = LinkedGeoPolygon {
+        first: ptr::null_mut(),
+        last: ptr::null_mut(),
+        next: ptr::null_mut(),
+    };
+}
+
+/** Stores a result through an output pointer, returning its error code. */
+unsafe fn _store<T>(out: *mut T, result: Result<T, Error>) -> H3Error {
+    match result {
+        Ok(value) => {
+            *out = value;
+            E_SUCCESS
+        }
+        Err(e) => e.code(),
+    }
+}
+
+/**
+ * Copies cells to an output array of the given length, zeroing the slots
+ * past the end as the C functions leave them.
+ */
+unsafe fn _storeCells(
+    out: *mut H3Index,
+    len: usize,
+    result: Result<Vec<H3Index>, Error>,
+) -> H3Error {
+    let cells = match result {
+        Ok(cells) => cells,
+        Err(e) => return e.code(),
+    };
+    if cells.len() > len {
+        return E_MEMORY_BOUNDS;
+    }
+    let out = slice::from_raw_parts_mut(out, len);
+    out[..cells.len()].copy_from_slice(&cells);
+    out[cells.len()..].fill(0);
+    E_SUCCESS
+}
+
+/** Views a C array as a slice, treating a non-positive length as empty. */
+unsafe fn _slice<'a, T>(ptr: *const T, len: i64) -> &'a [T] {
+    if len <= 0 || ptr.is_null() {
+        return &[];
+    }
+    slice::from_raw_parts(ptr, len as usize)
+}
+
+fn _cellBoundary(boundary: lat_lng::CellBoundary) -> CellBoundary {
+    CellBoundary {
+        numVerts: boundary.numVerts as c_int,
+        verts: boundary.verts,
+    }
+}
+
+fn _verts(verts: &[LatLng]) -> CellBoundary {
+    let mut boundary = CellBoundary {
+        numVerts: verts.len() as c_int,
+        verts: [LatLng { lat: 0.0, lng: 0.0 }; MAX_CELL_BNDRY_VERTS],
+    };
+    boundary.verts[..verts.len()].copy_from_slice(verts);
+    boundary
+}
+
+unsafe fn _geoLoop(geoloop: &GeoLoop) -> polygon::GeoLoop {
+    polygon::GeoLoop {
+        verts: _slice(geoloop.verts, geoloop.numVerts as i64).to_vec(),
+    }
+}
+
+unsafe fn _geoPolygon(geoPolygon: &GeoPolygon) -> polygon::GeoPolygon {
+    polygon::GeoPolygon {
+        geoloop: _geoLoop(&geoPolygon.geoloop),
+        holes: _slice(geoPolygon.holes, geoPolygon.numHoles as i64)
+            .iter()
+            .map(|hole| _geoLoop(hole))
+            .collect(),
+    }
+}
+
+/** Returns the description of an error code as a C string. */
+#[no_mangle]
+pub extern "C" fn describeH3Error(err: H3Error) -> *const c_char {
+    let description: &'static CStr = match err {
+        error::E_SUCCESS => c"Success",
+        error::E_FAILED => c"The operation failed but a more specific error is not available",
+        error::E_DOMAIN => c"Argument was outside of acceptable range",
+        error::E_LATLNG_DOMAIN => {
+            c"Latitude or longitude arguments were outside of acceptable range"
+        }
+        error::E_RES_DOMAIN => c"Resolution argument was outside of acceptable range",
+        error::E_CELL_INVALID => c"Cell argument was not valid",
+        error::E_DIR_EDGE_INVALID => c"Directed edge argument was not valid",
+        error::E_UNDIR_EDGE_INVALID => c"Undirected edge argument was not valid",
+        error::E_VERTEX_INVALID => c"Vertex argument was not valid",
+        error::E_PENTAGON => c"Pentagon distortion was encountered",
+        error::E_DUPLICATE_INPUT => c"Duplicate input",
+        error::E_NOT_NEIGHBORS => c"Cell arguments were not neighbors",
+        error::E_RES_MISMATCH => c"Cell arguments had incompatible resolutions",
+        error::E_MEMORY_ALLOC => c"Memory allocation failed",
+        error::E_MEMORY_BOUNDS => c"Bounds of provided memory were insufficient",
+        error::E_OPTION_INVALID => c"Mode or flags argument was not valid",
+        _ => c"Invalid error code",
+    };
+    description.as_ptr()
+}
+
+/** Indexes the location at the specified resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn latLngToCell(g: *const LatLng, res: c_int, out: *mut H3Index) -> H3Error {
+    _store(out, h3_index::latLngToCell(&*g, res))
+}
+
+/** Finds the center of the cell in grid space. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToLatLng(h3: H3Index, g: *mut LatLng) -> H3Error {
+    _store(g, h3_index::cellToLatLng(h3))
+}
+
+/** Gives the cell boundary in lat/lng coordinates for the cell h3. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToBoundary(h3: H3Index, gp: *mut CellBoundary) -> H3Error {
+    _store(gp, h3_index::cellToBoundary(h3).map(_cellBoundary))
+}
+
+/** Maximum number of cells that result from the gridDisk algorithm. */
+#[no_mangle]
+pub unsafe extern "C" fn maxGridDiskSize(k: c_int, out: *mut i64) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    _store(out, Ok(algos::maxGridDiskSize(k as u32) as i64))
+}
+
+/**
+ * Produces the cells within k distance of the origin. The output must have
+ * room for maxGridDiskSize(k) cells; unused slots are set to 0.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDisk(origin: H3Index, k: c_int, out: *mut H3Index) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    let len = algos::maxGridDiskSize(k as u32);
+    _storeCells(out, len, algos::gridDisk(origin, k as u32))
+}
+
+/**
+ * Produces the cells and their distances from the origin, within k. Both
+ * outputs must have room for maxGridDiskSize(k) entries; unused slots are
+ * set to 0.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDiskDistances(
+    origin: H3Index,
+    k: c_int,
+    out: *mut H3Index,
+    distances: *mut c_int,
+) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    _storeDisk(
+        out,
+        distances,
+        k,
+        algos::gridDiskDistances(origin, k as u32),
+    )
+}
+
+/**
+ * Copies cells and their distances to output arrays with room for
+ * maxGridDiskSize(k) entries, zeroing the slots past the end.
+ */
+unsafe fn _storeDisk(
+    out: *mut H3Index,
+    distances: *mut c_int,
+    k: c_int,
+    result: Result<Vec<(H3Index, u32)>, Error>,
+) -> H3Error {
+    let disk = match result {
+        Ok(disk) => disk,
+        Err(e) => return e.code(),
+    };
+    let len = algos::maxGridDiskSize(k as u32);
+    let out = slice::from_raw_parts_mut(out, len);
+    let distances = slice::from_raw_parts_mut(distances, len);
+    out.fill(0);
+    distances.fill(0);
+    for (i, (h, distance)) in disk.into_iter().enumerate() {
+        out[i] = h;
+        distances[i] = distance as c_int;
+    }
+    E_SUCCESS
+}
+
+/**
+ * Produces the cells within k distance of the origin, in order of increasing
+ * distance. Fails with Pentagon if pentagonal distortion is encountered.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDiskUnsafe(origin: H3Index, k: c_int, out: *mut H3Index) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    let len = algos::maxGridDiskSize(k as u32);
+    _storeCells(out, len, algos::gridDiskUnsafe(origin, k as u32))
+}
+
+/**
+ * Produces the cells and their distances from the origin, within k, in order
+ * of increasing distance. Fails with Pentagon if pentagonal distortion is
+ * encountered.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDiskDistancesUnsafe(
+    origin: H3Index,
+    k: c_int,
+    out: *mut H3Index,
+    distances: *mut c_int,
+) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    _storeDisk(
+        out,
+        distances,
+        k,
+        algos::gridDiskDistancesUnsafe(origin, k as u32),
+    )
+}
+
+/**
+ * Produces the cells and their distances from the origin, within k, always
+ * with the slower algorithm that handles pentagons.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDiskDistancesSafe(
+    origin: H3Index,
+    k: c_int,
+    out: *mut H3Index,
+    distances: *mut c_int,
+) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    let disk = algos::gridDiskDistancesWith(origin, k as u32, algos::DiskAlgorithm::Safe);
+    _storeDisk(out, distances, k, disk.map(|disk| disk.cells))
+}
+
+/**
+ * Produces the disks of radius k around each of the cells, one after the
+ * other. The output must have room for length * maxGridDiskSize(k) cells.
+ * Fails with Pentagon if pentagonal distortion is encountered.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridDisksUnsafe(
+    h3Set: *const H3Index,
+    length: c_int,
+    k: c_int,
+    out: *mut H3Index,
+) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    let len = algos::maxGridDiskSize(k as u32);
+    for (i, &origin) in _slice(h3Set, length as i64).iter().enumerate() {
+        let err = _storeCells(
+            out.add(i * len),
+            len,
+            algos::gridDiskUnsafe(origin, k as u32),
+        );
+        if err != E_SUCCESS {
+            return err;
+        }
+    }
+    E_SUCCESS
+}
+
+/**
+ * Produces the hollow ring of cells at exactly grid distance k from the
+ * origin. Fails with Pentagon if pentagonal distortion is encountered.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridRingUnsafe(origin: H3Index, k: c_int, out: *mut H3Index) -> H3Error {
+    if k < 0 {
+        return E_DOMAIN;
+    }
+    let len = algos::maxGridRingSize(k as u32);
+    _storeCells(out, len, algos::gridRingUnsafe(origin, k as u32))
+}
+
+/**
+ * Maximum number of cells that result from polygonToCells with the
+ * containment mode given by the flags.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn maxPolygonToCellsSize(
+    geoPolygon: *const GeoPolygon,
+    res: c_int,
+    flags: u32,
+    out: *mut i64,
+) -> H3Error {
+    let mode = match polygon::ContainmentMode::from_flags(flags) {
+        Ok(mode) => mode,
+        Err(e) => return e.code(),
+    };
+    let geoPolygon = _geoPolygon(&*geoPolygon);
+    _store(
+        out,
+        polygon::maxPolygonToCellsSizeWithMode(&geoPolygon, res, mode),
+    )
+}
+
+/**
+ * Finds the cells in the polygon, with the containment mode given by the
+ * flags. The output must have room for maxPolygonToCellsSize cells; unused
+ * slots are set to 0.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn polygonToCells(
+    geoPolygon: *const GeoPolygon,
+    res: c_int,
+    flags: u32,
+    out: *mut H3Index,
+) -> H3Error {
+    let mode = match polygon::ContainmentMode::from_flags(flags) {
+        Ok(mode) => mode,
+        Err(e) => return e.code(),
+    };
+    let geoPolygon = _geoPolygon(&*geoPolygon);
+    let len = match polygon::maxPolygonToCellsSizeWithMode(&geoPolygon, res, mode) {
+        Ok(len) => len as usize,
+        Err(e) => return e.code(),
+    };
+    _storeCells(
+        out,
+        len,
+        polygon::polygonToCellsWithMode(&geoPolygon, res, mode),
+    )
+}
+
+/** Appends a loop with the given vertices to a linked polygon. */
+unsafe fn _addLinkedLoop(polygon: *mut LinkedGeoPolygon, verts: &[LatLng]) {
+    let geoloop = Box::into_raw(Box::new(LinkedGeoLoop {
+        first: ptr::null_mut(),
+        last: ptr::null_mut(),
+        next: ptr::null_mut(),
+    }));
+    for &vertex in verts {
+        let coord = Box::into_raw(Box::new(LinkedLatLng {
+            vertex,
+            next: ptr::null_mut(),
+        }));
+        match (*geoloop).last.as_mut() {
+            Some(last) => last.next = coord,
+            None => (*geoloop).first = coord,
+        }
+        (*geoloop).last = coord;
+    }
+    match (*polygon).last.as_mut() {
+        Some(last) => last.next = geoloop,
+        None => (*polygon).first = geoloop,
+    }
+    (*polygon).last = geoloop;
+}
+
+/**
+ * Creates the outlines of a set of cells as a linked multipolygon, with the
+ * first polygon in out and the rest allocated after it. Each polygon has its
+ * outer loop first, followed by any holes. Free it with
+ * destroyLinkedMultiPolygon.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn cellsToLinkedMultiPolygon(
+    h3Set: *const H3Index,
+    numHexes: c_int,
+    out: *mut LinkedGeoPolygon,
+) -> H3Error {
+    let multiPolygon = match algos::cellsToMultiPolygon(_slice(h3Set, numHexes as i64)) {
+        Ok(multiPolygon) => multiPolygon,
+        Err(e) => return e.code(),
+    };
+    *out = LinkedGeoPolygon::EMPTY;
+    let mut polygon = out;
+    for (i, geoPolygon) in multiPolygon.polygons.iter().enumerate() {
+        if i > 0 {
+            let next = Box::into_raw(Box::new(LinkedGeoPolygon::EMPTY));
+            (*polygon).next = next;
+            polygon = next;
+        }
+        _addLinkedLoop(polygon, &geoPolygon.geoloop.verts);
+        for hole in &geoPolygon.holes {
+            _addLinkedLoop(polygon, &hole.verts);
+        }
+    }
+    E_SUCCESS
+}
+
+/**
+ * Frees the memory allocated by cellsToLinkedMultiPolygon. The first polygon
+ * itself is owned by the caller and is only reset.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn destroyLinkedMultiPolygon(polygon: *mut LinkedGeoPolygon) {
+    let mut current = polygon;
+    while !current.is_null() {
+        let mut geoloop = (*current).first;
+        while !geoloop.is_null() {
+            let mut coord = (*geoloop).first;
+            while !coord.is_null() {
+                let next = (*coord).next;
+                drop(Box::from_raw(coord));
+                coord = next;
+            }
+            let next = (*geoloop).next;
+            drop(Box::from_raw(geoloop));
+            geoloop = next;
+        }
+        let next = (*current).next;
+        if current == polygon {
+            *current = LinkedGeoPolygon::EMPTY;
+        } else {
+            drop(Box::from_raw(current));
+        }
+        current = next;
+    }
+}
+
+/** Converts degrees to radians. */
+#[no_mangle]
+pub extern "C" fn degsToRads(degrees: f64) -> f64 {
+    degrees * M_PI_180
+}
+
+/** Converts radians to degrees. */
+#[no_mangle]
+pub extern "C" fn radsToDegs(radians: f64) -> f64 {
+    radians * M_180_PI
+}
+
+/** The great circle distance in radians between two points. */
+#[no_mangle]
+pub unsafe extern "C" fn greatCircleDistanceRads(a: *const LatLng, b: *const LatLng) -> f64 {
+    lat_lng::greatCircleDistanceRads(&*a, &*b)
+}
+
+/** The great circle distance in kilometers between two points. */
+#[no_mangle]
+pub unsafe extern "C" fn greatCircleDistanceKm(a: *const LatLng, b: *const LatLng) -> f64 {
+    lat_lng::greatCircleDistanceKm(&*a, &*b)
+}
+
+/** The great circle distance in meters between two points. */
+#[no_mangle]
+pub unsafe extern "C" fn greatCircleDistanceM(a: *const LatLng, b: *const LatLng) -> f64 {
+    lat_lng::greatCircleDistanceRads(&*a, &*b) * EARTH_RADIUS_KM * 1000.0
+}
+
+/** Average hexagon area in square kilometers at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn getHexagonAreaAvgKm2(res: c_int, out: *mut f64) -> H3Error {
+    _store(out, measurement::getHexagonAreaAvgKm2(res))
+}
+
+/** Average hexagon area in square meters at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn getHexagonAreaAvgM2(res: c_int, out: *mut f64) -> H3Error {
+    _store(out, measurement::getHexagonAreaAvgM2(res))
+}
+
+/** Exact area of a cell in square radians. */
+#[no_mangle]
+pub unsafe extern "C" fn cellAreaRads2(h: H3Index, out: *mut f64) -> H3Error {
+    _store(out, measurement::cellAreaRads2(h))
+}
+
+/** Exact area of a cell in square kilometers. */
+#[no_mangle]
+pub unsafe extern "C" fn cellAreaKm2(h: H3Index, out: *mut f64) -> H3Error {
+    _store(out, measurement::cellAreaKm2(h))
+}
+
+/** Exact area of a cell in square meters. */
+#[no_mangle]
+pub unsafe extern "C" fn cellAreaM2(h: H3Index, out: *mut f64) -> H3Error {
+    _store(out, measurement::cellAreaM2(h))
+}
+
+/** Average hexagon edge length in kilometers at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn getHexagonEdgeLengthAvgKm(res: c_int, out: *mut f64) -> H3Error {
+    _store(out, measurement::getHexagonEdgeLengthAvgKm(res))
+}
+
+/** Average hexagon edge length in meters at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn getHexagonEdgeLengthAvgM(res: c_int, out: *mut f64) -> H3Error {
+    _store(out, measurement::getHexagonEdgeLengthAvgM(res))
+}
+
+/** Exact length of a directed edge in radians. */
+#[no_mangle]
+pub unsafe extern "C" fn edgeLengthRads(edge: H3Index, length: *mut f64) -> H3Error {
+    _store(length, measurement::edgeLengthRads(edge))
+}
+
+/** Exact length of a directed edge in kilometers. */
+#[no_mangle]
+pub unsafe extern "C" fn edgeLengthKm(edge: H3Index, length: *mut f64) -> H3Error {
+    _store(length, measurement::edgeLengthKm(edge))
+}
+
+/** Exact length of a directed edge in meters. */
+#[no_mangle]
+pub unsafe extern "C" fn edgeLengthM(edge: H3Index, length: *mut f64) -> H3Error {
+    _store(length, measurement::edgeLengthM(edge))
+}
+
+/** Number of unique cells at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn getNumCells(res: c_int, out: *mut i64) -> H3Error {
+    _store(out, base_cells::getNumCells(res))
+}
+
+/** Number of resolution 0 cells. */
+#[no_mangle]
+pub extern "C" fn res0CellCount() -> c_int {
+    base_cells::res0CellCount()
+}
+
+/** Provides all resolution 0 cells. The output must have room for 122 cells. */
+#[no_mangle]
+pub unsafe extern "C" fn getRes0Cells(out: *mut H3Index) -> H3Error {
+    _storeCells(out, NUM_BASE_CELLS as usize, base_cells::getRes0Cells())
+}
+
+/** Number of pentagons at each resolution. */
+#[no_mangle]
+pub extern "C" fn pentagonCount() -> c_int {
+    base_cells::pentagonCount()
+}
+
+/** Provides all pentagons at a resolution. The output must have room for 12 cells. */
+#[no_mangle]
+pub unsafe extern "C" fn getPentagons(res: c_int, out: *mut H3Index) -> H3Error {
+    _storeCells(out, NUM_PENTAGONS as usize, base_cells::getPentagons(res))
+}
+
+/** Returns the resolution of the index. */
+#[no_mangle]
+pub extern "C" fn getResolution(h: H3Index) -> c_int {
+    h3_index::getResolution(h)
+}
+
+/** Returns the base cell number of the index. */
+#[no_mangle]
+pub extern "C" fn getBaseCellNumber(h: H3Index) -> c_int {
+    h3_index::getBaseCellNumber(h)
+}
+
+/** Parses a hexadecimal string into an index. */
+#[no_mangle]
+pub unsafe extern "C" fn stringToH3(str: *const c_char, out: *mut H3Index) -> H3Error {
+    let result = match CStr::from_ptr(str).to_str() {
+        Ok(s) => h3_index::stringToH3(s),
+        Err(_) => Err(Error::Failed),
+    };
+    _store(out, result)
+}
+
+/**
+ * Writes the index as a nul-terminated hexadecimal string. Fails with
+ * MemoryBounds if sz bytes are not enough, 17 always are.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn h3ToString(h: H3Index, str: *mut c_char, sz: usize) -> H3Error {
+    let s = h3_index::h3ToString(h);
+    if sz < s.len() + 1 {
+        return E_MEMORY_BOUNDS;
+    }
+    let out = slice::from_raw_parts_mut(str as *mut u8, s.len() + 1);
+    out[..s.len()].copy_from_slice(s.as_bytes());
+    out[s.len()] = 0;
+    E_SUCCESS
+}
+
+/** Returns 1 if the index is a valid cell, 0 otherwise. */
+#[no_mangle]
+pub extern "C" fn isValidCell(h: H3Index) -> c_int {
+    h3_index::isValidCell(h) as c_int
+}
+
+/** Returns 1 if the index has a class III resolution, 0 otherwise. */
+#[no_mangle]
+pub extern "C" fn isResClassIII(h: H3Index) -> c_int {
+    h3_index::isResClassIII(h) as c_int
+}
+
+/** Returns 1 if the index is a pentagon, 0 otherwise. */
+#[no_mangle]
+pub extern "C" fn isPentagon(h: H3Index) -> c_int {
+    h3_index::isPentagon(h) as c_int
+}
+
+/** Maximum number of icosahedron faces the cell may intersect. */
+#[no_mangle]
+pub unsafe extern "C" fn maxFaceCount(h3: H3Index, out: *mut c_int) -> H3Error {
+    _store(out, h3_index::maxFaceCount(h3))
+}
+
+/**
+ * Finds the icosahedron faces the cell intersects. The output must have room
+ * for maxFaceCount entries; unused slots are set to -1.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn getIcosahedronFaces(h3: H3Index, out: *mut c_int) -> H3Error {
+    let faces = match h3_index::getIcosahedronFaces(h3) {
+        Ok(faces) => faces,
+        Err(e) => return e.code(),
+    };
+    let len = h3_index::maxFaceCount(h3).unwrap_or(0) as usize;
+    let out = slice::from_raw_parts_mut(out, len);
+    out[..faces.len()].copy_from_slice(&faces);
+    out[faces.len()..].fill(INVALID_FACE);
+    E_SUCCESS
+}
+
+/** Finds the parent of the cell at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToParent(
+    h: H3Index,
+    parentRes: c_int,
+    parent: *mut H3Index,
+) -> H3Error {
+    _store(parent, h3_index::cellToParent(h, parentRes))
+}
+
+/** Number of children of the cell at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToChildrenSize(h: H3Index, childRes: c_int, out: *mut i64) -> H3Error {
+    _store(out, h3_index::cellToChildrenSize(h, childRes))
+}
+
+/**
+ * Provides the children of the cell at the given resolution. The output
+ * must have room for cellToChildrenSize cells.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn cellToChildren(
+    h: H3Index,
+    childRes: c_int,
+    children: *mut H3Index,
+) -> H3Error {
+    let len = match h3_index::cellToChildrenSize(h, childRes) {
+        Ok(len) => len as usize,
+        Err(e) => return e.code(),
+    };
+    let children = slice::from_raw_parts_mut(children, len);
+    match h3_index::cellToChildrenInto(h, childRes, children) {
+        Ok(_) => E_SUCCESS,
+        Err(e) => e.code(),
+    }
+}
+
+/** Finds the center child of the cell at the given resolution. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToCenterChild(
+    h: H3Index,
+    childRes: c_int,
+    child: *mut H3Index,
+) -> H3Error {
+    _store(child, h3_index::childPosToCell(0, h, childRes))
+}
+
+/** Finds the position of the cell among the children of its parent. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToChildPos(
+    child: H3Index,
+    parentRes: c_int,
+    out: *mut i64,
+) -> H3Error {
+    _store(out, h3_index::cellToChildPos(child, parentRes))
+}
+
+/** Finds the child of the cell at a position among its children. */
+#[no_mangle]
+pub unsafe extern "C" fn childPosToCell(
+    childPos: i64,
+    parent: H3Index,
+    childRes: c_int,
+    child: *mut H3Index,
+) -> H3Error {
+    _store(child, h3_index::childPosToCell(childPos, parent, childRes))
+}
+
+/**
+ * Compacts a set of cells of the same resolution. The output must have room
+ * for numHexes cells; unused slots are set to 0.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn compactCells(
+    h3Set: *const H3Index,
+    compactedSet: *mut H3Index,
+    numHexes: i64,
+) -> H3Error {
+    let h3Set = _slice(h3Set, numHexes);
+    _storeCells(compactedSet, h3Set.len(), h3_index::compactCells(h3Set))
+}
+
+/** Number of cells that uncompactCells will produce. */
+#[no_mangle]
+pub unsafe extern "C" fn uncompactCellsSize(
+    compactedSet: *const H3Index,
+    numCompacted: i64,
+    res: c_int,
+    out: *mut i64,
+) -> H3Error {
+    _store(
+        out,
+        h3_index::uncompactCellsSize(_slice(compactedSet, numCompacted), res),
+    )
+}
+
+/**
+ * Uncompacts a set of cells to the given resolution. Fails with
+ * MemoryBounds if numOut is smaller than uncompactCellsSize.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn uncompactCells(
+    compactedSet: *const H3Index,
+    numCompacted: i64,
+    outSet: *mut H3Index,
+    numOut: i64,
+    res: c_int,
+) -> H3Error {
+    let outSet: &mut [H3Index] = if numOut <= 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(outSet, numOut as usize)
+    };
+    match h3_index::uncompactCellsInto(_slice(compactedSet, numCompacted), res, outSet) {
+        Ok(_) => E_SUCCESS,
+        Err(e) => e.code(),
+    }
+}
+
+/** Sets out to 1 if the cells are neighbors, 0 otherwise. */
+#[no_mangle]
+pub unsafe extern "C" fn areNeighborCells(
+    origin: H3Index,
+    destination: H3Index,
+    out: *mut c_int,
+) -> H3Error {
+    if !h3_index::isValidCell(origin) || !h3_index::isValidCell(destination) {
+        return error::E_CELL_INVALID;
+    }
+    if h3_index::getResolution(origin) != h3_index::getResolution(destination) {
+        return error::E_RES_MISMATCH;
+    }
+    let neighbors = directed_edge::cellsToDirectedEdge(origin, destination).is_ok();
+    _store(out, Ok(neighbors as c_int))
+}
+
+/** Finds the directed edge from origin to destination. */
+#[no_mangle]
+pub unsafe extern "C" fn cellsToDirectedEdge(
+    origin: H3Index,
+    destination: H3Index,
+    out: *mut H3Index,
+) -> H3Error {
+    _store(out, directed_edge::cellsToDirectedEdge(origin, destination))
+}
+
+/** Returns 1 if the index is a valid directed edge, 0 otherwise. */
+#[no_mangle]
+pub extern "C" fn isValidDirectedEdge(edge: H3Index) -> c_int {
+    directed_edge::isValidDirectedEdge(edge) as c_int
+}
+
+/** Finds the origin cell of a directed edge. */
+#[no_mangle]
+pub unsafe extern "C" fn getDirectedEdgeOrigin(edge: H3Index, out: *mut H3Index) -> H3Error {
+    _store(out, directed_edge::getDirectedEdgeOrigin(edge))
+}
+
+/** Finds the destination cell of a directed edge. */
+#[no_mangle]
+pub unsafe extern "C" fn getDirectedEdgeDestination(edge: H3Index, out: *mut H3Index) -> H3Error {
+    _store(out, directed_edge::getDirectedEdgeDestination(edge))
+}
+
+/** Finds the origin and destination cells of a directed edge, in that order. */
+#[no_mangle]
+pub unsafe extern "C" fn directedEdgeToCells(
+    edge: H3Index,
+    originDestination: *mut H3Index,
+) -> H3Error {
+    match directed_edge::directedEdgeToCells(edge) {
+        Ok((origin, destination)) => {
+            *originDestination = origin;
+            *originDestination.add(1) = destination;
+            E_SUCCESS
+        }
+        Err(e) => e.code(),
+    }
+}
+
+/** Provides the 6 directed edges from a cell; for a pentagon one is 0. */
+#[no_mangle]
+pub unsafe extern "C" fn originToDirectedEdges(origin: H3Index, edges: *mut H3Index) -> H3Error {
+    if !h3_index::isValidCell(origin) {
+        return error::E_CELL_INVALID;
+    }
+    *(edges as *mut [H3Index; 6]) = directed_edge::originToDirectedEdges(origin);
+    E_SUCCESS
+}
+
+/** Gives the boundary of a directed edge in lat/lng coordinates. */
+#[no_mangle]
+pub unsafe extern "C" fn directedEdgeToBoundary(edge: H3Index, gb: *mut CellBoundary) -> H3Error {
+    _store(
+        gb,
+        directed_edge::directedEdgeToBoundary(edge).map(|verts| _verts(&verts)),
+    )
+}
+
+/** Finds the vertex of the cell with the given number. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToVertex(
+    origin: H3Index,
+    vertexNum: c_int,
+    out: *mut H3Index,
+) -> H3Error {
+    _store(out, vertex::cellToVertex(origin, vertexNum))
+}
+
+/** Provides the 6 vertexes of a cell; for a pentagon the last is 0. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToVertexes(origin: H3Index, vertexes: *mut H3Index) -> H3Error {
+    _store(
+        vertexes as *mut [H3Index; 6],
+        vertex::cellToVertexes(origin),
+    )
+}
+
+/** Finds the location of a vertex. */
+#[no_mangle]
+pub unsafe extern "C" fn vertexToLatLng(vertex: H3Index, point: *mut LatLng) -> H3Error {
+    _store(point, vertex::vertexToLatLng(vertex))
+}
+
+/** Returns 1 if the index is a valid vertex, 0 otherwise. */
+#[no_mangle]
+pub extern "C" fn isValidVertex(vertex: H3Index) -> c_int {
+    vertex::isValidVertex(vertex) as c_int
+}
+
+/** Grid distance between two cells. */
+#[no_mangle]
+pub unsafe extern "C" fn gridDistance(origin: H3Index, h3: H3Index, distance: *mut i64) -> H3Error {
+    _store(distance, local_ij::gridDistance(origin, h3))
+}
+
+/** Number of cells in the line from start to end. */
+#[no_mangle]
+pub unsafe extern "C" fn gridPathCellsSize(
+    start: H3Index,
+    end: H3Index,
+    size: *mut i64,
+) -> H3Error {
+    _store(size, local_ij::gridPathCellsSize(start, end))
+}
+
+/**
+ * Provides the line of cells from start to end. The output must have room
+ * for gridPathCellsSize cells.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn gridPathCells(start: H3Index, end: H3Index, out: *mut H3Index) -> H3Error {
+    let len = match local_ij::gridPathCellsSize(start, end) {
+        Ok(len) => len as usize,
+        Err(e) => return e.code(),
+    };
+    _storeCells(out, len, local_ij::gridPathCells(start, end))
+}
+
+/** Produces local IJ coordinates for a cell anchored by an origin. */
+#[no_mangle]
+pub unsafe extern "C" fn cellToLocalIj(
+    origin: H3Index,
+    h3: H3Index,
+    mode: u32,
+    out: *mut CoordIJ,
+) -> H3Error {
+    if mode != 0 {
+        return E_OPTION_INVALID;
+    }
+    _store(out, local_ij::cellToLocalIj(origin, h3))
+}
+
+/** Produces a cell from local IJ coordinates anchored by an origin. */
+#[no_mangle]
+pub unsafe extern "C" fn localIjToCell(
+    origin: H3Index,
+    ij: *const CoordIJ,
+    mode: u32,
+    out: *mut H3Index,
+) -> H3Error {
+    if mode != 0 {
+        return E_OPTION_INVALID;
+    }
+    _store(out, local_ij::localIjToCell(origin, &*ij))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+
+    use super::*;
+
+    #[test]
+    fn latLngToCellRoundTrip() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let mut h: H3Index = 0;
+        let mut center = LatLng { lat: 0.0, lng: 0.0 };
+        let mut boundary = _verts(&[]);
+        unsafe {
+            assert_eq!(latLngToCell(&sf, 9, &mut h), E_SUCCESS);
+            assert_eq!(
+                h,
+                h3_index::latLngToCell(&sf, 9).unwrap(),
+                "same cell as latLngToCell"
+            );
+            assert_eq!(cellToLatLng(h, &mut center), E_SUCCESS);
+            assert_eq!(cellToBoundary(h, &mut boundary), E_SUCCESS);
+            assert_eq!(
+                latLngToCell(&sf, 16, &mut h),
+                error::E_RES_DOMAIN,
+                "error code is returned"
+            );
+        }
+        assert_eq!(boundary.numVerts, 6, "hexagon boundary");
+        assert_eq!(isValidCell(h), 1);
+        assert_eq!(getResolution(h), 9);
+        assert_eq!(isResClassIII(h), 1);
+        assert_eq!(getBaseCellNumber(h), 20);
+    }
+
+    #[test]
+    fn gridDiskFillsSentinels() {
+        let mut polar: H3Index = 0;
+        h3_index::setH3Index(&mut polar, 0, 4, 0);
+        let mut size: i64 = 0;
+        unsafe {
+            assert_eq!(maxGridDiskSize(1, &mut size), E_SUCCESS);
+            let mut out = vec![0xffff; size as usize];
+            let mut distances = vec![-1; size as usize];
+            assert_eq!(gridDisk(polar, 1, out.as_mut_ptr()), E_SUCCESS);
+            assert_eq!(out.iter().filter(|&&h| h != 0).count(), 6, "pentagon disk");
+            assert_eq!(out[6], 0, "unused slot is zeroed");
+            assert_eq!(
+                gridDiskDistances(polar, 1, out.as_mut_ptr(), distances.as_mut_ptr()),
+                E_SUCCESS
+            );
+            let origin = out.iter().position(|&h| h == polar).unwrap();
+            assert_eq!(distances[origin], 0, "origin is at distance 0");
+            assert_eq!(gridDisk(polar, -1, out.as_mut_ptr()), E_DOMAIN);
+
+            assert_eq!(
+                gridDiskDistancesSafe(polar, 1, out.as_mut_ptr(), distances.as_mut_ptr()),
+                E_SUCCESS
+            );
+            assert_eq!(
+                out.iter().filter(|&&h| h != 0).count(),
+                6,
+                "safe pentagon disk"
+            );
+            assert_eq!(
+                gridDiskUnsafe(polar, 1, out.as_mut_ptr()),
+                error::E_PENTAGON,
+                "unsafe fails at a pentagon"
+            );
+            assert_eq!(
+                gridDiskDistancesUnsafe(polar, 1, out.as_mut_ptr(), distances.as_mut_ptr()),
+                error::E_PENTAGON
+            );
+
+            let hexes: [H3Index; 2] = [0x85283473fffffff, 0x8928308280fffff];
+            let mut disks = vec![0; 2 * size as usize];
+            assert_eq!(
+                gridDisksUnsafe(hexes.as_ptr(), 2, 1, disks.as_mut_ptr()),
+                E_SUCCESS
+            );
+            for (i, &h) in hexes.iter().enumerate() {
+                assert_eq!(
+                    disks[i * size as usize..(i + 1) * size as usize],
+                    algos::gridDiskUnsafe(h, 1).unwrap(),
+                    "one disk after the other"
+                );
+            }
+            assert_eq!(
+                gridDiskDistancesUnsafe(hexes[0], 1, out.as_mut_ptr(), distances.as_mut_ptr()),
+                E_SUCCESS
+            );
+            assert_eq!(out[0], hexes[0], "origin first");
+            assert_eq!(distances[1..], [1; 6], "then the ring");
+        }
+    }
+
+    #[test]
+    fn icosahedronFaces() {
+        let mut pentagon: H3Index = 0;
+        h3_index::setH3Index(&mut pentagon, 1, 4, 0);
+        let mut count: c_int = 0;
+        unsafe {
+            assert_eq!(maxFaceCount(0x85283473fffffff, &mut count), E_SUCCESS);
+            let mut faces = vec![0; count as usize];
+            assert_eq!(
+                getIcosahedronFaces(0x85283473fffffff, faces.as_mut_ptr()),
+                E_SUCCESS
+            );
+            assert_eq!(faces[1], INVALID_FACE, "unused slot is -1");
+
+            assert_eq!(maxFaceCount(pentagon, &mut count), E_SUCCESS);
+            assert_eq!(count, 5, "pentagons have five faces");
+            let mut faces = vec![0; count as usize];
+            assert_eq!(getIcosahedronFaces(pentagon, faces.as_mut_ptr()), E_SUCCESS);
+            assert!(faces.iter().all(|&f| f != INVALID_FACE), "all slots used");
+        }
+    }
+
+    #[test]
+    fn linkedMultiPolygon() {
+        let disk = algos::gridDisk(0x85283473fffffff, 1).unwrap();
+        let expected = algos::cellsToMultiPolygon(&disk).unwrap();
+        let mut polygon = LinkedGeoPolygon::EMPTY;
+        unsafe {
+            assert_eq!(
+                cellsToLinkedMultiPolygon(disk.as_ptr(), disk.len() as c_int, &mut polygon),
+                E_SUCCESS
+            );
+            assert!(polygon.next.is_null(), "one polygon");
+            let geoloop = &*polygon.first;
+            assert!(geoloop.next.is_null(), "no holes");
+            let mut verts = Vec::new();
+            let mut coord = geoloop.first;
+            while !coord.is_null() {
+                verts.push((*coord).vertex);
+                coord = (*coord).next;
+            }
+            assert_eq!(verts, expected.polygons[0].geoloop.verts, "same outline");
+            assert_eq!((*geoloop.last).vertex, verts[verts.len() - 1]);
+
+            destroyLinkedMultiPolygon(&mut polygon);
+            assert!(polygon.first.is_null(), "reset after destroy");
+
+            let mixed = [0x85283473fffffffu64, 0x8928308280fffff];
+            assert_eq!(
+                cellsToLinkedMultiPolygon(mixed.as_ptr(), 2, &mut polygon),
+                error::E_RES_MISMATCH,
+                "mixed resolutions fail"
+            );
+
+            // two separate cells make two polygons
+            let apart = [disk[0], algos::gridRingUnsafe(disk[0], 3).unwrap()[0]];
+            assert_eq!(
+                cellsToLinkedMultiPolygon(apart.as_ptr(), 2, &mut polygon),
+                E_SUCCESS
+            );
+            assert!(!polygon.next.is_null(), "second polygon");
+            assert!((*polygon.next).next.is_null(), "only two");
+            destroyLinkedMultiPolygon(&mut polygon);
+        }
+    }
+
+    #[test]
+    fn strings() {
+        let mut h: H3Index = 0;
+        let mut buf = [1 as c_char; 17];
+        unsafe {
+            assert_eq!(stringToH3(c"85283473fffffff".as_ptr(), &mut h), E_SUCCESS);
+            assert_eq!(h, 0x85283473fffffff);
+            assert_eq!(h3ToString(h, buf.as_mut_ptr(), buf.len()), E_SUCCESS);
+            assert_eq!(
+                CStr::from_ptr(buf.as_ptr()).to_str(),
+                Ok("85283473fffffff"),
+                "nul terminated"
+            );
+            assert_eq!(h3ToString(h, buf.as_mut_ptr(), 15), E_MEMORY_BOUNDS);
+            assert_eq!(
+                CStr::from_ptr(describeH3Error(error::E_CELL_INVALID)).to_str(),
+                Ok(error::describeH3Error(error::E_CELL_INVALID)),
+                "same description"
+            );
+        }
+    }
+
+    #[test]
+    fn hierarchy() {
+        let h: H3Index = 0x85283473fffffff;
+        let mut size: i64 = 0;
+        unsafe {
+            assert_eq!(cellToChildrenSize(h, 7, &mut size), E_SUCCESS);
+            let mut children = vec![0; size as usize];
+            assert_eq!(cellToChildren(h, 7, children.as_mut_ptr()), E_SUCCESS);
+            assert_eq!(
+                children,
+                h3_index::cellToChildren(h, 7).unwrap(),
+                "same children"
+            );
+
+            let mut compacted = vec![0; children.len()];
+            assert_eq!(
+                compactCells(children.as_ptr(), compacted.as_mut_ptr(), size),
+                E_SUCCESS
+            );
+            assert_eq!(compacted[0], h, "compacts to the parent");
+            assert!(compacted[1..].iter().all(|&c| c == 0), "rest zeroed");
+
+            let mut uncompacted = vec![0; size as usize];
+            assert_eq!(
+                uncompactCells(compacted.as_ptr(), 1, uncompacted.as_mut_ptr(), size, 7),
+                E_SUCCESS
+            );
+            assert_eq!(uncompacted, children, "uncompacts to the children");
+            assert_eq!(
+                uncompactCells(compacted.as_ptr(), 1, uncompacted.as_mut_ptr(), 3, 7),
+                E_MEMORY_BOUNDS,
+                "short output fails"
+            );
+
+            let mut pos: i64 = 0;
+            let mut child: H3Index = 0;
+            assert_eq!(cellToChildPos(children[5], 5, &mut pos), E_SUCCESS);
+            assert_eq!(pos, 5, "position among the children");
+            assert_eq!(childPosToCell(pos, h, 7, &mut child), E_SUCCESS);
+            assert_eq!(child, children[5], "child at the position");
+            assert_eq!(childPosToCell(size, h, 7, &mut child), E_DOMAIN);
+        }
+    }
+
+    #[test]
+    fn polygonToCellsMatches() {
+        let mut verts = [
+            LatLng {
+                lat: 0.659966917655,
+                lng: -2.1364398519396,
+            },
+            LatLng {
+                lat: 0.6595011102219,
+                lng: -2.1359434279405,
+            },
+            LatLng {
+                lat: 0.6583348114025,
+                lng: -2.1354884206045,
+            },
+            LatLng {
+                lat: 0.6581220034068,
+                lng: -2.1382437718946,
+            },
+        ];
+        let geoPolygon = GeoPolygon {
+            geoloop: GeoLoop {
+                numVerts: verts.len() as c_int,
+                verts: verts.as_mut_ptr(),
+            },
+            numHoles: 0,
+            holes: ptr::null_mut(),
+        };
+        let mut size: i64 = 0;
+        unsafe {
+            assert_eq!(
+                maxPolygonToCellsSize(&geoPolygon, 9, 0, &mut size),
+                E_SUCCESS
+            );
+            let mut out = vec![0; size as usize];
+            assert_eq!(
+                polygonToCells(&geoPolygon, 9, 0, out.as_mut_ptr()),
+                E_SUCCESS
+            );
+            let cells: Vec<H3Index> = out.into_iter().filter(|&h| h != 0).collect();
+            assert_eq!(
+                cells,
+                polygon::polygonToCells(&_geoPolygon(&geoPolygon), 9).unwrap(),
+                "same cells"
+            );
+            assert_eq!(
+                maxPolygonToCellsSize(&geoPolygon, 9, 2, &mut size),
+                E_SUCCESS
+            );
+            let mut out = vec![0; size as usize];
+            assert_eq!(
+                polygonToCells(&geoPolygon, 9, 2, out.as_mut_ptr()),
+                E_SUCCESS
+            );
+            let cells: Vec<H3Index> = out.into_iter().filter(|&h| h != 0).collect();
+            assert_eq!(
+                cells,
+                polygon::polygonToCellsWithMode(
+                    &_geoPolygon(&geoPolygon),
+                    9,
+                    polygon::ContainmentMode::Overlapping
+                )
+                .unwrap(),
+                "overlapping mode flag"
+            );
+            assert_eq!(
+                polygonToCells(&geoPolygon, 9, 4, ptr::null_mut()),
+                E_OPTION_INVALID,
+                "flags are checked"
+            );
+        }
+    }
+
+    #[test]
+    fn edgesAndVertexes() {
+        let origin: H3Index = 0x85283473fffffff;
+        let mut edges = [0; 6];
+        let mut cells = [0; 2];
+        let mut vertexes = [0; 6];
+        let mut neighbors: c_int = -1;
+        unsafe {
+            assert_eq!(originToDirectedEdges(origin, edges.as_mut_ptr()), E_SUCCESS);
+            assert_eq!(isValidDirectedEdge(edges[0]), 1);
+            assert_eq!(directedEdgeToCells(edges[0], cells.as_mut_ptr()), E_SUCCESS);
+            assert_eq!(cells[0], origin, "origin first");
+            assert_eq!(
+                areNeighborCells(origin, cells[1], &mut neighbors),
+                E_SUCCESS
+            );
+            assert_eq!(neighbors, 1, "destination is a neighbor");
+            assert_eq!(areNeighborCells(origin, origin, &mut neighbors), E_SUCCESS);
+            assert_eq!(neighbors, 0, "a cell is not its own neighbor");
+            assert_eq!(cellToVertexes(origin, vertexes.as_mut_ptr()), E_SUCCESS);
+            assert!(vertexes.iter().all(|&v| isValidVertex(v) == 1), "vertexes");
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::error::Error;
+use crate::h3_index::{
+    _cellToParentInRange, _latLngToCellInRange, cellToBoundary, cellToChildren, cellToLatLng,
+    cellToParent, getResolution, isPentagon, isValidCell, latLngToCell, stringToH3, H3Index,
+    H3_GET_BASE_CELL,
+};
+use crate::iterators::IterCellsChildren;
+use crate::lat_lng::{CellBoundary, LatLng};
+use crate::prelude::*;
+use crate::resolution::Resolution;
+
+/**
+ * An H3 index that is known to be a valid cell.
+ *
+ * H3Index is a bare integer, so cells, edges, vertexes and arbitrary numbers
+ * can be mixed up. A CellIndex can only be made from a valid cell, so its
+ * methods don't need to revalidate it. The raw functions taking H3Index remain
+ * available, and CellIndex converts to and from H3Index.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct CellIndex(H3Index);
+
+impl CellIndex {
+    /**
+     * Wraps an H3 index.
+     *
+     * @param h The H3 index.
+     * @return The cell, or CellInvalid if h is not a valid cell.
+     */
+    pub fn new(h: H3Index) -> Result<CellIndex, Error> {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        Ok(CellIndex(h))
+    }
+
+    /**
+     * Indexes the location at the specified resolution.
+     *
+     * @param g The location in radians.
+     * @param res The resolution.
+     * @return The cell containing the location, or LatLngDomain if the
+     *         location is not finite.
+     */
+    pub fn from_latlng(g: &LatLng, res: Resolution) -> Result<CellIndex, Error> {
+        Ok(CellIndex(latLngToCell(g, res.into())?))
+    }
+
+    /**
+     * Indexes the location at a resolution fixed when compiling, like
+     * from_latlng with Resolution::new_const::<RES>(). RES is checked when
+     * compiling, so there is no range check at run time.
+     *
+     * @param g The location in radians.
+     * @return The cell containing the location, or LatLngDomain if the
+     *         location is not finite.
+     */
+    pub fn from_latlng_const<const RES: u8>(g: &LatLng) -> Result<CellIndex, Error> {
+        let res = Resolution::new_const::<RES>();
+        Ok(CellIndex(_latLngToCellInRange(g, res.into())?))
+    }
+
+    /** Returns the raw H3 index. */
+    pub fn index(self) -> H3Index {
+        self.0
+    }
+
+    /** Returns the H3 index as 8 big endian (network order) bytes. */
+    pub fn to_be_bytes(self) -> [u8; 8] {
+        self.0.to_be_bytes()
+    }
+
+    /** Returns the H3 index as 8 little endian bytes. */
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+
+    /**
+     * Reads a cell from 8 big endian bytes, as written by to_be_bytes.
+     *
+     * @param bytes The bytes.
+     * @return The cell, or CellInvalid if the bytes are not a valid cell.
+     */
+    pub fn from_be_bytes(bytes: [u8; 8]) -> Result<CellIndex, Error> {
+        CellIndex::new(H3Index::from_be_bytes(bytes))
+    }
+
+    /**
+     * Reads a cell from 8 little endian bytes, as written by to_le_bytes.
+     *
+     * @param bytes The bytes.
+     * @return The cell, or CellInvalid if the bytes are not a valid cell.
+     */
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Result<CellIndex, Error> {
+        CellIndex::new(H3Index::from_le_bytes(bytes))
+    }
+
+    /** Returns the resolution of the cell. */
+    pub fn resolution(self) -> Resolution {
+        Resolution::try_from(getResolution(self.0)).expect("valid cell has a resolution")
+    }
+
+    /** Returns the base cell number of the cell. */
+    pub fn base_cell(self) -> i32 {
+        H3_GET_BASE_CELL(self.0)
+    }
+
+    /** Returns whether the cell is a pentagon. */
+    pub fn is_pentagon(self) -> bool {
+        isPentagon(self.0)
+    }
+
+    /**
+     * Returns the parent (coarser) cell at the given resolution.
+     *
+     * @param res The parent resolution, no finer than the cell's own.
+     * @return The parent, or ResMismatch if res is finer than the cell.
+     */
+    pub fn parent(self, res: Resolution) -> Result<CellIndex, Error> {
+        Ok(CellIndex(cellToParent(self.0, res.into())?))
+    }
+
+    /**
+     * Returns the children (finer cells) at the given resolution.
+     *
+     * @param res The child resolution, no coarser than the cell's own.
+     * @return The children, or an error if res is coarser than the cell.
+     */
+    pub fn children(self, res: Resolution) -> Result<Vec<CellIndex>, Error> {
+        Ok(cellToChildren(self.0, res.into())?
+            .into_iter()
+            .map(CellIndex)
+            .collect())
+    }
+
+    /**
+     * Iterates over the children at the given resolution without allocating.
+     *
+     * @param res The child resolution.
+     * @return The children in ascending order; empty if res is coarser than
+     *         the cell.
+     */
+    pub fn children_iter(
+        self,
+        res: Resolution,
+    ) -> impl ExactSizeIterator<Item = CellIndex> + DoubleEndedIterator {
+        IterCellsChildren::from_parent(self.0, res.into()).map(CellIndex)
+    }
+
+    /**
+     * Returns the parent cell at a resolution fixed when compiling, like
+     * parent with Resolution::new_const::<RES>(). RES is checked when
+     * compiling, so only whether it is finer than the cell is checked at run
+     * time.
+     *
+     * @return The parent, or ResMismatch if RES is finer than the cell.
+     */
+    pub fn parent_const<const RES: u8>(self) -> Result<CellIndex, Error> {
+        let res = Resolution::new_const::<RES>();
+        Ok(CellIndex(_cellToParentInRange(self.0, res.into())?))
+    }
+
+    /**
+     * Iterates over the children at a resolution fixed when compiling, like
+     * children_iter with Resolution::new_const::<RES>(). RES is checked when
+     * compiling, so only whether it is coarser than the cell is checked at
+     * run time.
+     *
+     * @return The children in ascending order; empty if RES is coarser than
+     *         the cell.
+     */
+    pub fn children_const<const RES: u8>(
+        self,
+    ) -> impl ExactSizeIterator<Item = CellIndex> + DoubleEndedIterator {
+        let res = Resolution::new_const::<RES>();
+        IterCellsChildren::_from_parent_in_range(self.0, res.into()).map(CellIndex)
+    }
+
+    /** Returns the center of the cell. */
+    pub fn center(self) -> LatLng {
+        cellToLatLng(self.0).expect("valid cell has a center")
+    }
+
+    /** Returns the boundary of the cell. */
+    pub fn boundary(self) -> CellBoundary {
+        cellToBoundary(self.0).expect("valid cell has a boundary")
+    }
+}
+
+impl From<CellIndex> for H3Index {
+    fn from(cell: CellIndex) -> H3Index {
+        cell.0
+    }
+}
+
+impl TryFrom<H3Index> for CellIndex {
+    type Error = Error;
+
+    fn try_from(h: H3Index) -> Result<CellIndex, Error> {
+        CellIndex::new(h)
+    }
+}
+
+/**
+ * Converts to a signed 64-bit integer, such as for a BIGINT database column.
+ * The high bit of a cell is always 0, so the value is never negative.
+ */
+impl From<CellIndex> for i64 {
+    fn from(cell: CellIndex) -> i64 {
+        cell.0 as i64
+    }
+}
+
+/** Converts from a signed 64-bit integer, such as a BIGINT database column. */
+impl TryFrom<i64> for CellIndex {
+    type Error = Error;
+
+    fn try_from(h: i64) -> Result<CellIndex, Error> {
+        if h < 0 {
+            return Err(Error::CellInvalid);
+        }
+        CellIndex::new(h as H3Index)
+    }
+}
+
+impl fmt::Display for CellIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl FromStr for CellIndex {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CellIndex, Error> {
+        CellIndex::new(stringToH3(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directed_edge::originToDirectedEdges;
+    use crate::h3_index::{H3_SET_MODE, H3_SET_RESERVED_BITS};
+
+    use super::*;
+
+    #[test]
+    fn newValidates() {
+        let h: H3Index = 0x85283473fffffff;
+        assert_eq!(CellIndex::new(h).unwrap().index(), h, "valid cell wraps");
+        assert_eq!(
+            CellIndex::new(0),
+            Err(Error::CellInvalid),
+            "null is invalid"
+        );
+        let edge = originToDirectedEdges(h)[0];
+        assert_eq!(
+            CellIndex::try_from(edge),
+            Err(Error::CellInvalid),
+            "edge is not a cell"
+        );
+        assert_eq!(H3Index::from(CellIndex::new(h).unwrap()), h, "unwraps");
+    }
+
+    #[test]
+    fn i64RoundTrip() {
+        let cell = CellIndex::new(0x8f2830828052d25).unwrap();
+        let value = i64::from(cell);
+        assert_eq!(value, 0x8f2830828052d25, "same bits");
+        assert_eq!(CellIndex::try_from(value), Ok(cell), "round trip");
+        assert_eq!(
+            CellIndex::try_from(-1i64),
+            Err(Error::CellInvalid),
+            "negative"
+        );
+        assert_eq!(
+            CellIndex::try_from(i64::MIN | 0x8f2830828052d25),
+            Err(Error::CellInvalid),
+            "high bit set"
+        );
+        // and TEXT columns use the canonical string form
+        assert_eq!(cell.to_string().parse::<CellIndex>(), Ok(cell), "text");
+    }
+
+    #[test]
+    fn bytesRoundTrip() {
+        let cell = CellIndex::new(0x85283473fffffff).unwrap();
+        assert_eq!(CellIndex::from_be_bytes(cell.to_be_bytes()), Ok(cell));
+        assert_eq!(CellIndex::from_le_bytes(cell.to_le_bytes()), Ok(cell));
+        assert_eq!(cell.to_be_bytes()[0], 0x08, "most significant byte first");
+        assert_eq!(
+            CellIndex::from_le_bytes(cell.to_be_bytes()),
+            Err(Error::CellInvalid),
+            "wrong byte order"
+        );
+    }
+
+    #[test]
+    fn constResolutions() {
+        let g = LatLng::from_degrees(37.7749, -122.4194);
+        let cell = CellIndex::from_latlng_const::<9>(&g).unwrap();
+        assert_eq!(cell, CellIndex::from_latlng(&g, Resolution::Res9).unwrap());
+        assert_eq!(
+            CellIndex::from_latlng_const::<9>(&LatLng {
+                lat: f64::NAN,
+                lng: 0.0
+            }),
+            Err(Error::LatLngDomain),
+            "location still checked"
+        );
+        assert_eq!(
+            cell.parent_const::<5>(),
+            cell.parent(Resolution::Res5),
+            "parent"
+        );
+        assert_eq!(
+            cell.parent_const::<10>(),
+            Err(Error::ResMismatch),
+            "finer parent"
+        );
+        assert_eq!(
+            cell.children_const::<11>().collect::<Vec<CellIndex>>(),
+            cell.children(Resolution::Res11).unwrap(),
+            "children"
+        );
+        assert_eq!(cell.children_const::<8>().len(), 0, "coarser children");
+    }
+
+    #[test]
+    fn accessors() {
+        let cell = CellIndex::new(0x85283473fffffff).unwrap();
+        assert_eq!(cell.resolution(), Resolution::Res5, "resolution");
+        assert_eq!(cell.base_cell(), 20, "base cell");
+        assert!(!cell.is_pentagon(), "hexagon");
+        assert!(
+            CellIndex::new(0x8009fffffffffff).unwrap().is_pentagon(),
+            "pentagon"
+        );
+
+        let parent = cell.parent(Resolution::Res3).unwrap();
+        assert_eq!(parent.resolution(), Resolution::Res3, "parent resolution");
+        assert!(
+            parent.children(Resolution::Res5).unwrap().contains(&cell),
+            "cell is among its parent's children"
+        );
+        assert_eq!(
+            cell.children(Resolution::Res6).unwrap().len(),
+            7,
+            "seven children"
+        );
+        assert_eq!(
+            cell.children_iter(Resolution::Res7)
+                .collect::<Vec<CellIndex>>(),
+            cell.children(Resolution::Res7).unwrap(),
+            "iterator matches the vector"
+        );
+        assert_eq!(cell.children_iter(Resolution::Res7).len(), 49, "exact size");
+        assert_eq!(
+            cell.children_iter(Resolution::Res4).next(),
+            None,
+            "no children at a coarser res"
+        );
+        assert_eq!(
+            cell.parent(Resolution::Res6),
+            Err(Error::ResMismatch),
+            "finer parent fails"
+        );
+
+        let center = cell.center();
+        assert_eq!(
+            CellIndex::from_latlng(&center, Resolution::Res5).unwrap(),
+            cell,
+            "center indexes back to the cell"
+        );
+        assert_eq!(cell.boundary().numVerts, 6, "hexagon boundary");
+    }
+
+    #[test]
+    fn strings() {
+        let cell: CellIndex = "85283473fffffff".parse().unwrap();
+        assert_eq!(cell.to_string(), "85283473fffffff", "round trips");
+        assert!("0".parse::<CellIndex>().is_err(), "null is not a cell");
+        assert!("xyz".parse::<CellIndex>().is_err(), "garbage fails");
+
+        let mut edge: H3Index = 0x85283473fffffff;
+        H3_SET_MODE(&mut edge, 2);
+        H3_SET_RESERVED_BITS(&mut edge, 1);
+        assert_eq!(
+            format!("{:x}", edge).parse::<CellIndex>(),
+            Err(Error::CellInvalid),
+            "edge string is not a cell"
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use core::cmp::Reverse;
+use alloc::collections::btree_set;
+use alloc::collections::BTreeSet;
+use alloc::collections::BinaryHeap;
+
+use crate::constants::MAX_H3_RES;
+use crate::error::Error;
+use crate::h3_index::{
+    cellToChildrenSize, cellToParent, isValidCell, H3Index, H3_GET_RESOLUTION, H3_SET_INDEX_DIGIT,
+    H3_SET_RESOLUTION,
+};
+use crate::iterators::IterCellsChildren;
+use crate::prelude::*;
+use crate::H3_NULL;
+
+/**
+ * A set of H3 indexes. Cells are kept in ascending index order so iteration
+ * is deterministic.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellSet {
+    cells: BTreeSet<H3Index>,
+}
+
+impl CellSet {
+    /** Creates an empty set. */
+    pub fn new() -> CellSet {
+        CellSet {
+            cells: BTreeSet::new(),
+        }
+    }
+
+    /** Adds a cell to the set. Returns whether the cell was newly added. */
+    pub fn insert(&mut self, h: H3Index) -> bool {
+        self.cells.insert(h)
+    }
+
+    /** Removes a cell from the set. Returns whether the cell was present. */
+    pub fn remove(&mut self, h: H3Index) -> bool {
+        self.cells.remove(&h)
+    }
+
+    /** Returns whether the cell is in the set. */
+    pub fn contains(&self, h: H3Index) -> bool {
+        self.cells.contains(&h)
+    }
+
+    /** Returns the number of cells in the set. */
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /** Returns whether the set contains no cells. */
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /** Iterates over the cells in ascending index order. */
+    pub fn iter(&self) -> impl Iterator<Item = H3Index> + '_ {
+        self.cells.iter().copied()
+    }
+}
+
+impl FromIterator<H3Index> for CellSet {
+    fn from_iter<I: IntoIterator<Item = H3Index>>(iter: I) -> CellSet {
+        CellSet {
+            cells: BTreeSet::from_iter(iter),
+        }
+    }
+}
+
+impl Extend<H3Index> for CellSet {
+    fn extend<I: IntoIterator<Item = H3Index>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+impl IntoIterator for CellSet {
+    type Item = H3Index;
+    type IntoIter = btree_set::IntoIter<H3Index>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+/**
+ * Unions the cells produced by several traversals, such as the disks around
+ * many origins or the coverage of many polygons.
+ *
+ * Each input is sorted and deduplicated on its own, and the sorted chunks
+ * are then merged, so memory use is proportional to the inputs after
+ * deduplication rather than to everything produced by them.
+ *
+ * @param iterators The traversal outputs to merge. H3_NULL entries, as can
+ *                  appear in gridDisk style outputs, are skipped.
+ * @return The union of the inputs.
+ */
+pub fn mergeCells<I, J>(iterators: I) -> CellSet
+where
+    I: IntoIterator<Item = J>,
+    J: IntoIterator<Item = H3Index>,
+{
+    let mut chunks: Vec<Vec<H3Index>> = Vec::new();
+    for iter in iterators {
+        let mut chunk: Vec<H3Index> = iter.into_iter().filter(|h| *h != H3_NULL).collect();
+        chunk.sort_unstable();
+        chunk.dedup();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+    }
+
+    // k-way merge of the sorted chunks, keyed on the next cell of each
+    let mut positions: Vec<usize> = vec![0; chunks.len()];
+    let mut heap: BinaryHeap<Reverse<(H3Index, usize)>> = BinaryHeap::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        heap.push(Reverse((chunk[0], i)));
+    }
+
+    let mut merged: Vec<H3Index> = Vec::new();
+    while let Some(Reverse((h, i))) = heap.pop() {
+        if merged.last() != Some(&h) {
+            merged.push(h);
+        }
+        positions[i] += 1;
+        if positions[i] < chunks[i].len() {
+            heap.push(Reverse((chunks[i][positions[i]], i)));
+        } else {
+            // release each chunk as soon as it is consumed
+            chunks[i] = Vec::new();
+        }
+    }
+    merged.into_iter().collect()
+}
+
+/**
+ * A set of cells kept in canonical compacted form: no cell is stored along
+ * with one of its ancestors, and a parent is stored instead of its children
+ * whenever all of them are present. A region covered by millions of fine
+ * cells is then held as a few coarse cells.
+ *
+ * Because the form is canonical, two sets covering the same area are equal
+ * regardless of the order or the resolutions their cells were added at.
+ * Cells are kept in ascending index order, which sorts them by resolution
+ * first.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompactCellSet {
+    cells: BTreeSet<H3Index>,
+}
+
+impl CompactCellSet {
+    /** Creates an empty set. */
+    pub fn new() -> CompactCellSet {
+        CompactCellSet {
+            cells: BTreeSet::new(),
+        }
+    }
+
+    /**
+     * Creates a set covering the given cells, which may overlap and be of
+     * mixed resolutions.
+     *
+     * @param cells The cells.
+     * @return The set, or CellInvalid if any cell is invalid.
+     */
+    pub fn from_cells(cells: &[H3Index]) -> Result<CompactCellSet, Error> {
+        let mut set = CompactCellSet::new();
+        for &h in cells {
+            set.insert(h)?;
+        }
+        Ok(set)
+    }
+
+    /**
+     * Adds the area of a cell to the set, compacting as needed.
+     *
+     * @param h The cell.
+     * @return Whether the set changed, or CellInvalid if h is invalid.
+     */
+    pub fn insert(&mut self, h: H3Index) -> Result<bool, Error> {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        if self.contains_cell(h) {
+            return Ok(false);
+        }
+        self._removeDescendants(h);
+
+        // replace complete sets of siblings by their parent
+        let mut h = h;
+        let mut res = H3_GET_RESOLUTION(h);
+        while res > 0 {
+            let parent = cellToParent(h, res - 1)?;
+            let siblings: Vec<H3Index> = IterCellsChildren::from_parent(parent, res).collect();
+            if !siblings.iter().all(|c| *c == h || self.cells.contains(c)) {
+                break;
+            }
+            for c in &siblings {
+                self.cells.remove(c);
+            }
+            h = parent;
+            res -= 1;
+        }
+        self.cells.insert(h);
+        Ok(true)
+    }
+
+    /**
+     * Returns whether the area of a cell is covered by the set, which is the
+     * case if it or one of its ancestors is stored.
+     */
+    pub fn contains_cell(&self, h: H3Index) -> bool {
+        if !isValidCell(h) {
+            return false;
+        }
+        for res in (0..=H3_GET_RESOLUTION(h)).rev() {
+            if let Ok(ancestor) = cellToParent(h, res) {
+                if self.cells.contains(&ancestor) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /** Returns the union of two sets. */
+    pub fn union(&self, other: &CompactCellSet) -> CompactCellSet {
+        let (mut out, smaller) = if self.cells.len() >= other.cells.len() {
+            (self.clone(), other)
+        } else {
+            (other.clone(), self)
+        };
+        for &h in &smaller.cells {
+            let _ = out.insert(h);
+        }
+        out
+    }
+
+    /** Returns the area covered by both sets. */
+    pub fn intersection(&self, other: &CompactCellSet) -> CompactCellSet {
+        let mut out = CompactCellSet::new();
+        for &h in &self.cells {
+            if other.contains_cell(h) {
+                out.cells.insert(h);
+            } else {
+                // the parts of h in other are its stored descendants, which
+                // are already compacted and disjoint from the rest
+                out.cells.extend(other._descendants(h));
+            }
+        }
+        out
+    }
+
+    /** Returns the area covered by this set but not by other. */
+    pub fn difference(&self, other: &CompactCellSet) -> CompactCellSet {
+        let mut out = CompactCellSet::new();
+        for &h in &self.cells {
+            other._subtractFrom(h, &mut out.cells);
+        }
+        out
+    }
+
+    /** Returns the number of cells stored, in compacted form. */
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /** Returns whether the set covers no area. */
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /** Iterates over the stored compacted cells in ascending index order. */
+    pub fn iter(&self) -> impl Iterator<Item = H3Index> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /**
+     * Iterates over the cells of the set uncompacted to a resolution.
+     *
+     * @param res The resolution, which must not be coarser than any stored
+     *            cell.
+     * @return The iterator, or ResDomain if res is invalid and ResMismatch if
+     *         a stored cell is finer than res.
+     */
+    pub fn iter_at_res(&self, res: i32) -> Result<impl Iterator<Item = H3Index> + '_, Error> {
+        if !(0..=MAX_H3_RES).contains(&res) {
+            return Err(Error::ResDomain);
+        }
+        // the finest cells sort last
+        if let Some(&finest) = self.cells.last() {
+            if H3_GET_RESOLUTION(finest) > res {
+                return Err(Error::ResMismatch);
+            }
+        }
+        Ok(self
+            .cells
+            .iter()
+            .flat_map(move |&h| IterCellsChildren::from_parent(h, res)))
+    }
+
+    /**
+     * Returns the number of cells in the set at a resolution, without
+     * uncompacting it.
+     *
+     * @param res The resolution, which must not be coarser than any stored
+     *            cell.
+     * @return The number of cells, or the errors of iter_at_res.
+     */
+    pub fn len_at_res(&self, res: i32) -> Result<i64, Error> {
+        if !(0..=MAX_H3_RES).contains(&res) {
+            return Err(Error::ResDomain);
+        }
+        let mut total: i64 = 0;
+        for &h in &self.cells {
+            total += cellToChildrenSize(h, res).map_err(|_| Error::ResMismatch)?;
+        }
+        Ok(total)
+    }
+
+    /**
+     * Returns the stored cells strictly inside a cell. At each finer
+     * resolution these share the digits of h, so they are a contiguous
+     * range of the sorted set.
+     */
+    fn _descendants(&self, h: H3Index) -> impl Iterator<Item = H3Index> + '_ {
+        let parentRes = H3_GET_RESOLUTION(h);
+        (parentRes + 1..=MAX_H3_RES).flat_map(move |res| {
+            let mut first = h;
+            H3_SET_RESOLUTION(&mut first, res);
+            let mut last = first;
+            for r in parentRes + 1..=res {
+                H3_SET_INDEX_DIGIT(&mut first, r, 0);
+                H3_SET_INDEX_DIGIT(&mut last, r, 6);
+            }
+            self.cells.range(first..=last).copied()
+        })
+    }
+
+    fn _removeDescendants(&mut self, h: H3Index) {
+        let descendants: Vec<H3Index> = self._descendants(h).collect();
+        for c in descendants {
+            self.cells.remove(&c);
+        }
+    }
+
+    /** Adds the parts of h not covered by this set to out. */
+    fn _subtractFrom(&self, h: H3Index, out: &mut BTreeSet<H3Index>) {
+        if self.contains_cell(h) {
+            return;
+        }
+        if self._descendants(h).next().is_none() {
+            out.insert(h);
+            return;
+        }
+        // partly covered, so split it; h has descendants, so it is coarser
+        // than res 15
+        let res = H3_GET_RESOLUTION(h) + 1;
+        for c in IterCellsChildren::from_parent(h, res) {
+            self._subtractFrom(c, out);
+        }
+    }
+}
+
+impl IntoIterator for CompactCellSet {
+    type Item = H3Index;
+    type IntoIter = btree_set::IntoIter<H3Index>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::algos::{gridDisk, gridDiskDistances};
+    use crate::h3_index::{cellToChildren, latLngToCell};
+    use crate::lat_lng::LatLng;
+
+    use super::*;
+
+    #[test]
+    fn mergeCellsOfDisks() {
+        let origin = latLngToCell(
+            &LatLng {
+                lat: 0.659966917655,
+                lng: -2.1364398519396,
+            },
+            9,
+        )
+        .unwrap();
+        let disks: Vec<Vec<H3Index>> = gridDiskDistances(origin, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(h, _)| {
+                gridDiskDistances(h, 1)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(n, _)| n)
+                    .collect()
+            })
+            .collect();
+
+        let expected: HashSet<H3Index> = disks.iter().flatten().copied().collect();
+        let merged = mergeCells(disks.iter().map(|d| d.iter().copied()));
+        assert_eq!(merged.len(), expected.len(), "union has no duplicates");
+        assert!(
+            merged.iter().all(|h| expected.contains(&h)),
+            "union matches"
+        );
+        assert_eq!(merged.len(), 37, "disks of disks make a bigger disk");
+
+        assert!(
+            mergeCells(vec![vec![H3_NULL], vec![]]).is_empty(),
+            "null cells are skipped"
+        );
+    }
+
+    #[test]
+    fn compactCellSet_canonical() {
+        let parent: H3Index = 0x85283473fffffff;
+        let mut children = cellToChildren(parent, 7).unwrap();
+        children.reverse();
+        let set = CompactCellSet::from_cells(&children).unwrap();
+        assert_eq!(
+            set.iter().collect::<Vec<H3Index>>(),
+            vec![parent],
+            "compacts to the parent"
+        );
+        assert!(set.contains_cell(children[0]), "contains a child");
+        assert!(set.contains_cell(parent), "contains the parent");
+        assert!(
+            !set.contains_cell(cellToParent(parent, 4).unwrap()),
+            "does not contain the grandparent"
+        );
+        assert_eq!(set.len_at_res(7), Ok(49), "49 cells at res 7");
+        assert_eq!(set.iter_at_res(7).unwrap().count(), 49, "iterates 49 cells");
+        assert_eq!(
+            set.iter_at_res(4).err(),
+            Some(Error::ResMismatch),
+            "coarser res"
+        );
+
+        let pentagon: H3Index = 0x8009fffffffffff;
+        let set = CompactCellSet::from_cells(&cellToChildren(pentagon, 2).unwrap()).unwrap();
+        assert_eq!(
+            set.iter().collect::<Vec<H3Index>>(),
+            vec![pentagon],
+            "pentagon compacts"
+        );
+
+        let mut set = CompactCellSet::new();
+        assert_eq!(
+            set.insert(0x7fffffffffffffff),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+        assert_eq!(set.insert(parent), Ok(true), "newly added");
+        assert_eq!(set.insert(children[3]), Ok(false), "already covered");
+    }
+
+    #[test]
+    fn compactCellSet_algebra() {
+        let origin = latLngToCell(
+            &LatLng {
+                lat: 0.659966917655,
+                lng: -2.1364398519396,
+            },
+            6,
+        )
+        .unwrap();
+        let neighbor = gridDisk(origin, 1).unwrap()[1];
+        // mix a coarse disk with a fine one so both compaction and splitting
+        // are exercised
+        let a = CompactCellSet::from_cells(&gridDisk(origin, 1).unwrap()).unwrap();
+        let fine = cellToChildren(neighbor, 8).unwrap();
+        let b = CompactCellSet::from_cells(&gridDisk(fine[0], 4).unwrap()).unwrap();
+
+        let cells =
+            |set: &CompactCellSet| -> HashSet<H3Index> { set.iter_at_res(8).unwrap().collect() };
+        let (cellsA, cellsB) = (cells(&a), cells(&b));
+        assert_eq!(
+            cells(&a.union(&b)),
+            cellsA.union(&cellsB).copied().collect(),
+            "union"
+        );
+        assert_eq!(
+            cells(&a.intersection(&b)),
+            cellsA.intersection(&cellsB).copied().collect(),
+            "intersection"
+        );
+        assert_eq!(
+            cells(&a.difference(&b)),
+            cellsA.difference(&cellsB).copied().collect(),
+            "difference"
+        );
+        assert_eq!(
+            cells(&b.difference(&a)),
+            cellsB.difference(&cellsA).copied().collect(),
+            "reverse difference"
+        );
+
+        let union = a.union(&b);
+        let expected: Vec<H3Index> = cells(&union).into_iter().collect();
+        assert_eq!(
+            union,
+            CompactCellSet::from_cells(&expected).unwrap(),
+            "results are canonical"
+        );
+        assert!(a.difference(&a).is_empty(), "a - a is empty");
+        assert_eq!(a.intersection(&a), a, "a & a is a");
+    }
+}
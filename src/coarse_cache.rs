@@ -0,0 +1,141 @@
+use std::sync::OnceLock;
+
+use crate::constants::{H3_CELL_MODE, NUM_BASE_CELLS};
+use crate::h3_index::{
+    H3Index, H3_GET_BASE_CELL, H3_GET_INDEX_DIGIT, H3_GET_RESOLUTION, H3_INIT, H3_SET_BASE_CELL,
+    H3_SET_MODE, H3_SET_RESOLUTION, _cellToBoundaryUncached, _cellToLatLngUncached, cellToChildren,
+    isValidCell,
+};
+use crate::lat_lng::{CellBoundary, LatLng};
+use crate::prelude::*;
+
+/** Finest resolution held in the cache. */
+pub const MAX_CACHED_RES: i32 = 2;
+
+/** Cell centers by resolution, indexed by _cacheSlot. */
+static CENTERS: [OnceLock<Vec<LatLng>>; (MAX_CACHED_RES + 1) as usize] =
+    [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+/** Cell boundaries by resolution, indexed by _cacheSlot. */
+static BOUNDARIES: [OnceLock<Vec<CellBoundary>>; (MAX_CACHED_RES + 1) as usize] =
+    [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+
+/**
+ * Returns the number of table slots at a resolution: every base cell followed
+ * by every digit sequence, including the unused pentagon sub-sequences.
+ */
+fn _numSlots(res: i32) -> usize {
+    NUM_BASE_CELLS as usize * 7usize.pow(res as u32)
+}
+
+/**
+ * Returns the table slot of a valid cell: its base cell and digits read as a
+ * base 7 number.
+ */
+fn _cacheSlot(h: H3Index) -> usize {
+    let mut slot = H3_GET_BASE_CELL(h) as usize;
+    for r in 1..=H3_GET_RESOLUTION(h) {
+        slot = slot * 7 + H3_GET_INDEX_DIGIT(h, r) as usize;
+    }
+    slot
+}
+
+/**
+ * Builds a table with the value for every cell at a resolution, calling the
+ * given function for each cell.
+ */
+fn _buildTable<T: Copy>(res: i32, empty: T, f: impl Fn(H3Index) -> T) -> Vec<T> {
+    let mut table = vec![empty; _numSlots(res)];
+    for baseCell in 0..NUM_BASE_CELLS {
+        let mut bc: H3Index = H3_INIT;
+        H3_SET_MODE(&mut bc, H3_CELL_MODE);
+        H3_SET_RESOLUTION(&mut bc, 0);
+        H3_SET_BASE_CELL(&mut bc, baseCell);
+        // base cells are valid, so their children always are
+        for h in cellToChildren(bc, res).unwrap() {
+            table[_cacheSlot(h)] = f(h);
+        }
+    }
+    table
+}
+
+/**
+ * Returns whether a cell is answered from the cache.
+ */
+fn _isCached(h: H3Index) -> bool {
+    H3_GET_RESOLUTION(h) <= MAX_CACHED_RES && isValidCell(h)
+}
+
+/**
+ * Looks up the center of a coarse cell, building the table for its
+ * resolution on first use.
+ *
+ * @param h The H3 cell.
+ * @return The cell center, or None if the cell is finer than MAX_CACHED_RES
+ *         or invalid.
+ */
+pub fn cachedCellToLatLng(h: H3Index) -> Option<LatLng> {
+    if !_isCached(h) {
+        return None;
+    }
+    let res = H3_GET_RESOLUTION(h);
+    let table = CENTERS[res as usize].get_or_init(|| {
+        _buildTable(res, LatLng { lat: 0.0, lng: 0.0 }, |h| {
+            _cellToLatLngUncached(h).unwrap()
+        })
+    });
+    Some(table[_cacheSlot(h)])
+}
+
+/**
+ * Looks up the boundary of a coarse cell, building the table for its
+ * resolution on first use.
+ *
+ * @param h The H3 cell.
+ * @return The cell boundary, or None if the cell is finer than
+ *         MAX_CACHED_RES or invalid.
+ */
+pub fn cachedCellToBoundary(h: H3Index) -> Option<CellBoundary> {
+    if !_isCached(h) {
+        return None;
+    }
+    let res = H3_GET_RESOLUTION(h);
+    let table = BOUNDARIES[res as usize].get_or_init(|| {
+        _buildTable(res, CellBoundary::new(), |h| {
+            _cellToBoundaryUncached(h).unwrap()
+        })
+    });
+    Some(table[_cacheSlot(h)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cacheMatchesUncached() {
+        for baseCell in 0..NUM_BASE_CELLS {
+            let mut bc: H3Index = H3_INIT;
+            H3_SET_MODE(&mut bc, H3_CELL_MODE);
+            H3_SET_RESOLUTION(&mut bc, 0);
+            H3_SET_BASE_CELL(&mut bc, baseCell);
+            for res in 0..=MAX_CACHED_RES {
+                for h in cellToChildren(bc, res).unwrap() {
+                    assert_eq!(
+                        cachedCellToLatLng(h),
+                        Some(_cellToLatLngUncached(h).unwrap()),
+                        "cached center matches"
+                    );
+                    assert_eq!(
+                        cachedCellToBoundary(h),
+                        Some(_cellToBoundaryUncached(h).unwrap()),
+                        "cached boundary matches"
+                    );
+                }
+            }
+        }
+
+        let fine = cellToChildren(0x8001fffffffffff, MAX_CACHED_RES + 1).unwrap()[0];
+        assert_eq!(cachedCellToLatLng(fine), None, "finer cells are not cached");
+        assert_eq!(cachedCellToLatLng(0), None, "invalid cells are not cached");
+    }
+}
@@ -1,5 +1,5 @@
 /** 2.0 * PI */
-pub const M_2PI: f64 = 6.28318530717958647692528676655900576839433f64;
+pub const M_2PI: f64 = core::f64::consts::TAU;
 
 /** pi / 180 */
 pub const M_PI_180: f64 = 0.0174532925199432957692369076848861271111f64;
@@ -18,18 +18,16 @@ pub const M_SIN60: f64 = M_SQRT3_2;
  * (asin(sqrt(3.0 / 28.0))) */
 pub const M_AP7_ROT_RADS: f64 = 0.333473172251832115336090755351601070065900389f64;
 
-/** sin(M_AP7_ROT_RADS) */
-pub const M_SIN_AP7_ROT: f64 = 0.3273268353539885718950318f64;
-
-/** cos(M_AP7_ROT_RADS) */
-pub const M_COS_AP7_ROT: f64 = 0.9449111825230680680167902f64;
-
 /** earth radius in kilometers using WGS84 authalic radius */
 pub const EARTH_RADIUS_KM: f64 = 6371.007180918475f64;
 
 /** scaling factor from hex2d resolution 0 unit length
  * (or distance between adjacent cell center points
  * on the plane) to gnomonic unit length. */
+#[allow(
+    clippy::excessive_precision,
+    reason = "value as published in the C library's constants.h"
+)]
 pub const RES0_U_GNOMONIC: f64 = 0.38196601125010500003f64;
 
 /** max H3 resolution; H3 version 1 has 16 resolutions, numbered 0 through 15 */
@@ -1,15 +1,33 @@
 use num::FromPrimitive;
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
 
 use crate::constants::*;
+use crate::error::Error;
 use crate::vec2d::Vec2d;
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct CoordIJK {
     pub i: i32,
     pub j: i32,
     pub k: i32,
 }
 
+/** @struct CoordIJ
+ * @brief IJ hexagon coordinates
+ *
+ * Each axis is spaced 120 degrees apart.
+ */
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CoordIJ {
+    ///< i component
+    pub i: i32,
+    ///< j component
+    pub j: i32,
+}
+
 /** @brief CoordIJK unit vectors corresponding to the 7 H3 digits.
  */
 pub const UNIT_VECS: [CoordIJK; 7] = [
@@ -22,11 +40,11 @@ pub const UNIT_VECS: [CoordIJK; 7] = [
     CoordIJK { i: 1, j: 1, k: 0 }, // direction 6
 ];
 
-/** @brief H3 digit representing ijk+ axes direction.
- * Values will be within the lowest 3 bits of an integer.
- */
 enum_from_primitive! {
-    #[derive(PartialEq, PartialOrd, Copy, Clone)]
+    /** @brief H3 digit representing ijk+ axes direction.
+     * Values will be within the lowest 3 bits of an integer.
+     */
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Copy, Clone)]
     pub enum Direction {
         /** H3 digit in center */
         CenterDigit = 0,
@@ -77,32 +95,23 @@ pub fn _setIJK(ijk: &mut CoordIJK, i: i32, j: i32, k: i32) {
  * @param h The ijk+ coordinates of the containing hex.
  */
 pub fn _hex2dToCoordIJK(v: Vec2d, h: &mut CoordIJK) {
-    let a1: f64;
-    let a2: f64;
-    let x1: f64;
-    let x2: f64;
-    let m1: i32;
-    let m2: i32;
-    let r1: f64;
-    let r2: f64;
-
     // quantize into the ij system and then normalize
     h.k = 0;
 
-    a1 = v.x.abs();
-    a2 = v.y.abs();
+    let a1: f64 = v.x.abs();
+    let a2: f64 = v.y.abs();
 
     // first do a reverse conversion
-    x2 = a2 / M_SIN60;
-    x1 = a1 + x2 / 2.0f64;
+    let x2: f64 = a2 / M_SIN60;
+    let x1: f64 = a1 + x2 / 2.0f64;
 
     // check if we have the center of a hex
-    m1 = x1 as i32;
-    m2 = x2 as i32;
+    let m1: i32 = x1 as i32;
+    let m2: i32 = x2 as i32;
 
     // otherwise round correctly
-    r1 = x1 - (m1 as f64);
-    r2 = x2 - (m2 as f64);
+    let r1: f64 = x1 - (m1 as f64);
+    let r2: f64 = x2 - (m2 as f64);
 
     if r1 < 0.5f64 {
         if r1 < 1.0f64 / 3.0f64 {
@@ -158,17 +167,17 @@ pub fn _hex2dToCoordIJK(v: Vec2d, h: &mut CoordIJK) {
         {
             let axisi: i32 = h.j / 2;
             let diff: i32 = h.i - axisi;
-            h.i = h.i - 2 * diff;
+            h.i -= 2 * diff;
         } else {
             let axisi: i32 = (h.j + 1) / 2;
             let diff: i32 = h.i - axisi;
-            h.i = h.i - (2 * diff + 1);
+            h.i -= 2 * diff + 1;
         }
     }
 
     if v.y < 0.0f64 {
-        h.i = h.i - (2 * h.j + 1) / 2;
-        h.j = -1 * h.j;
+        h.i -= (2 * h.j + 1) / 2;
+        h.j = -h.j;
     }
 
     _ijkNormalize(h);
@@ -188,7 +197,7 @@ pub fn _ijkToHex2d(h: &CoordIJK) -> Vec2d {
         x: (i as f64) - 0.5f64 * (j as f64),
         y: (j as f64) * M_SQRT3_2,
     };
-    return v;
+    v
 }
 
 /**
@@ -200,7 +209,7 @@ pub fn _ijkToHex2d(h: &CoordIJK) -> Vec2d {
  * @return 1 if the two addresses match, 0 if they do not.
  */
 pub fn _ijkMatches(c1: CoordIJK, c2: CoordIJK) -> bool {
-    return c1.i == c2.i && c1.j == c2.j && c1.k == c2.k;
+    c1.i == c2.i && c1.j == c2.j && c1.k == c2.k
 }
 
 /**
@@ -294,15 +303,14 @@ pub fn _unitIjkToDigit(ijk: CoordIJK) -> Direction {
     _ijkNormalize(&mut c);
 
     let mut digit: Direction = Direction::InvalidDigit;
-    for i in (Direction::CenterDigit as usize)..(Direction::InvalidDigit as usize) {
-        // Direction::iterator() {
-        if _ijkMatches(c, UNIT_VECS[i]) {
+    for (i, &unitVec) in UNIT_VECS.iter().enumerate() {
+        if _ijkMatches(c, unitVec) {
             digit = Direction::from_usize(i).unwrap_or(Direction::InvalidDigit);
             break;
         }
     }
 
-    return digit;
+    digit
 }
 
 /**
@@ -384,6 +392,51 @@ pub fn _downAp7r(ijk: &mut CoordIJK) {
     _ijkNormalize(ijk);
 }
 
+/**
+ * Find the normalized ijk coordinates of the hex centered on the indicated
+ * hex at the next finer aperture 3 counter-clockwise resolution. Works in
+ * place.
+ *
+ * @param ijk The ijk coordinates.
+ */
+pub fn _downAp3(ijk: &mut CoordIJK) {
+    // res r unit vectors in res r+1
+    let mut iVec: CoordIJK = CoordIJK { i: 2, j: 0, k: 1 };
+    let mut jVec: CoordIJK = CoordIJK { i: 1, j: 2, k: 0 };
+    let mut kVec: CoordIJK = CoordIJK { i: 0, j: 1, k: 2 };
+
+    _ijkScale(&mut iVec, ijk.i);
+    _ijkScale(&mut jVec, ijk.j);
+    _ijkScale(&mut kVec, ijk.k);
+
+    _ijkAdd(iVec, jVec, ijk);
+    _ijkAdd(*ijk, kVec, ijk);
+
+    _ijkNormalize(ijk);
+}
+
+/**
+ * Find the normalized ijk coordinates of the hex centered on the indicated
+ * hex at the next finer aperture 3 clockwise resolution. Works in place.
+ *
+ * @param ijk The ijk coordinates.
+ */
+pub fn _downAp3r(ijk: &mut CoordIJK) {
+    // res r unit vectors in res r+1
+    let mut iVec: CoordIJK = CoordIJK { i: 2, j: 1, k: 0 };
+    let mut jVec: CoordIJK = CoordIJK { i: 0, j: 2, k: 1 };
+    let mut kVec: CoordIJK = CoordIJK { i: 1, j: 0, k: 2 };
+
+    _ijkScale(&mut iVec, ijk.i);
+    _ijkScale(&mut jVec, ijk.j);
+    _ijkScale(&mut kVec, ijk.k);
+
+    _ijkAdd(iVec, jVec, ijk);
+    _ijkAdd(*ijk, kVec, ijk);
+
+    _ijkNormalize(ijk);
+}
+
 /**
  * Find the normalized ijk coordinates of the hex in the specified digit
  * direction from the specified ijk coordinates. Works in place.
@@ -446,7 +499,7 @@ pub fn _ijkRotate60cw(ijk: &mut CoordIJK) {
  * @param digit Indexing digit (between 1 and 6 inclusive)
  */
 pub fn _rotate60ccw(digit: Direction) -> Direction {
-    return match digit {
+    match digit {
         Direction::KAxesDigit => Direction::IKAxesDigit,
         Direction::IKAxesDigit => Direction::IAxesDigit,
         Direction::IAxesDigit => Direction::IJAxesDigit,
@@ -455,7 +508,7 @@ pub fn _rotate60ccw(digit: Direction) -> Direction {
         Direction::JKAxesDigit => Direction::KAxesDigit,
         Direction::CenterDigit => digit,
         Direction::InvalidDigit => digit,
-    };
+    }
 }
 
 /**
@@ -464,7 +517,7 @@ pub fn _rotate60ccw(digit: Direction) -> Direction {
  * @param digit Indexing digit (between 1 and 6 inclusive)
  */
 pub fn _rotate60cw(digit: Direction) -> Direction {
-    return match digit {
+    match digit {
         Direction::KAxesDigit => Direction::JKAxesDigit,
         Direction::JKAxesDigit => Direction::JAxesDigit,
         Direction::JAxesDigit => Direction::IJAxesDigit,
@@ -473,5 +526,113 @@ pub fn _rotate60cw(digit: Direction) -> Direction {
         Direction::IKAxesDigit => Direction::KAxesDigit,
         Direction::CenterDigit => digit,
         Direction::InvalidDigit => digit,
+    }
+}
+
+/**
+ * Finds the distance between the two coordinates. Returns result.
+ *
+ * @param c1 The first set of ijk coordinates.
+ * @param c2 The second set of ijk coordinates.
+ */
+pub fn ijkDistance(c1: &CoordIJK, c2: &CoordIJK) -> i32 {
+    let mut diff: CoordIJK = CoordIJK { i: 0, j: 0, k: 0 };
+    _ijkSub(*c1, *c2, &mut diff);
+    _ijkNormalize(&mut diff);
+    let absDiff: CoordIJK = CoordIJK {
+        i: diff.i.abs(),
+        j: diff.j.abs(),
+        k: diff.k.abs(),
+    };
+    absDiff.i.max(absDiff.j.max(absDiff.k))
+}
+
+/**
+ * Convert IJK coordinates to cube coordinates, in place
+ * @param ijk Coordinate to convert
+ */
+pub fn ijkToCube(ijk: &mut CoordIJK) {
+    ijk.i = -ijk.i + ijk.k;
+    ijk.j -= ijk.k;
+    ijk.k = -ijk.i - ijk.j;
+}
+
+/**
+ * Convert cube coordinates to IJK coordinates, in place
+ * @param ijk Coordinate to convert
+ */
+pub fn cubeToIjk(ijk: &mut CoordIJK) {
+    ijk.i = -ijk.i;
+    ijk.k = 0;
+    _ijkNormalize(ijk);
+}
+
+/**
+ * Transforms coordinates from the IJK+ coordinate system to the IJ coordinate
+ * system.
+ *
+ * @param ijk The input IJK+ coordinates
+ * @param ij The output IJ coordinates
+ */
+pub fn ijkToIj(ijk: &CoordIJK) -> CoordIJ {
+    CoordIJ {
+        i: ijk.i - ijk.k,
+        j: ijk.j - ijk.k,
+    }
+}
+
+/**
+ * Transforms coordinates from the IJ coordinate system to the IJK+ coordinate
+ * system.
+ *
+ * @param ij The input IJ coordinates
+ * @param ijk The output IJK+ coordinates
+ * @returns E_SUCCESS on success, E_FAILED if signed integer overflow would
+ * have occurred.
+ */
+pub fn ijToIjk(ij: &CoordIJ) -> Result<CoordIJK, Error> {
+    let mut ijk = CoordIJK {
+        i: ij.i,
+        j: ij.j,
+        k: 0,
+    };
+
+    if _ijkNormalizeCouldOverflow(&ijk) {
+        return Err(Error::Failed);
+    }
+
+    _ijkNormalize(&mut ijk);
+    Ok(ijk)
+}
+
+/**
+ * Returns true if _ijkNormalize with the given input could have a signed
+ * integer overflow. Assumes k is set to 0.
+ */
+pub fn _ijkNormalizeCouldOverflow(ij: &CoordIJK) -> bool {
+    // Check for the possibility of overflow
+    let (max, min) = if ij.i > ij.j {
+        (ij.i, ij.j)
+    } else {
+        (ij.j, ij.i)
     };
+    if min < 0 {
+        // Only if the min is less than 0 will the resulting number be larger
+        // than max. If min is positive, then max is also positive, and a
+        // positive signed integer minus another positive signed integer will
+        // not overflow.
+        if max.checked_add(min).is_none() {
+            // max + min would overflow
+            return true;
+        }
+        if 0i32.checked_sub(min).is_none() {
+            // 0 - INT32_MIN would overflow
+            return true;
+        }
+        if max.checked_sub(min).is_none() {
+            // max - min would overflow
+            return true;
+        }
+    }
+    false
 }
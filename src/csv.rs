@@ -0,0 +1,69 @@
+use std::io::Read;
+
+use crate::error::Error;
+use crate::h3_index::latLngToCell;
+use crate::lat_lng::LatLng;
+use crate::H3Index;
+
+/**
+ * Streams the rows of a CSV file to the cells containing the coordinates in
+ * each row. The first row is treated as a header and skipped.
+ *
+ * @param reader The CSV data.
+ * @param lat_col The zero-based index of the latitude column, in degrees.
+ * @param lng_col The zero-based index of the longitude column, in degrees.
+ * @param res The H3 resolution of the cells.
+ * @return An iterator over the cell for each row. A row fails with Failed if
+ *         it cannot be read, or LatLngDomain if a coordinate is missing or is
+ *         not a number.
+ */
+pub fn index_csv<R: Read>(
+    reader: R,
+    lat_col: usize,
+    lng_col: usize,
+    res: i32,
+) -> impl Iterator<Item = Result<H3Index, Error>> {
+    let reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(reader);
+    reader.into_records().map(move |record| {
+        let record = record.map_err(|_| Error::Failed)?;
+        let lat = _parseDegs(record.get(lat_col))?;
+        let lng = _parseDegs(record.get(lng_col))?;
+
+        let g = LatLng::from_degrees(lat, lng);
+        latLngToCell(&g, res)
+    })
+}
+
+/**
+ * Parses a coordinate in degrees from a CSV field.
+ *
+ * @param field The field, if present in the row.
+ * @return The coordinate, or LatLngDomain if it is missing or invalid.
+ */
+fn _parseDegs(field: Option<&str>) -> Result<f64, Error> {
+    field
+        .and_then(|f| f.trim().parse::<f64>().ok())
+        .ok_or(Error::LatLngDomain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexCsvRows() {
+        let data = "name,lat,lng\nsf,37.7752702151959,-122.418307270836\nbad,north,0\nshort,1\n";
+        let cells: Vec<Result<H3Index, Error>> = index_csv(data.as_bytes(), 1, 2, 9).collect();
+
+        let sf = LatLng::from_degrees(37.7752702151959, -122.418307270836);
+        assert_eq!(cells.len(), 3, "one result per data row");
+        assert_eq!(cells[0], latLngToCell(&sf, 9), "row is indexed");
+        assert_eq!(cells[1], Err(Error::LatLngDomain), "invalid coordinate");
+        assert_eq!(cells[2], Err(Error::LatLngDomain), "missing coordinate");
+
+        let cells: Vec<Result<H3Index, Error>> = index_csv(data.as_bytes(), 1, 2, 16).collect();
+        assert_eq!(cells[0], Err(Error::ResDomain), "invalid resolution");
+    }
+}
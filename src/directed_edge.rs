@@ -2,13 +2,19 @@ use enum_primitive::FromPrimitive;
 
 use crate::{
     algos::{directionForNeighbor, h3NeighborRotations},
-    constants::{H3_CELL_MODE, H3_DIRECTEDEDGE_MODE},
+    constants::{H3_CELL_MODE, H3_DIRECTEDEDGE_MODE, MAX_H3_RES},
     coord_ijk::Direction,
     error::Error,
+    face_ijk::{FaceIJK, _faceIjkPentToCellBoundary, _faceIjkToCellBoundary},
     h3_index::{
-        isPentagon, isValidCell, H3Index, H3_GET_MODE, H3_GET_RESERVED_BITS, H3_SET_MODE,
-        H3_SET_RESERVED_BITS,
+        _h3ToFaceIjk, cellAzimuthRads, cellToLatLng, cellToParent, isPentagon, isValidCell,
+        H3Index, H3_GET_HIGH_BIT, H3_GET_MODE, H3_GET_RESERVED_BITS, H3_GET_RESOLUTION,
+        H3_SET_MODE, H3_SET_RESERVED_BITS,
     },
+    iterators::IterCellsChildren,
+    lat_lng::{greatCircleMidpoint, CellBoundary, LatLng},
+    prelude::*,
+    vertex::{vertexNumForDirection, INVALID_VERTEX_NUM},
     H3_NULL,
 };
 
@@ -33,7 +39,7 @@ pub fn cellsToDirectedEdge(origin: H3Index, destination: H3Index) -> Result<H3In
     H3_SET_MODE(&mut output, H3_DIRECTEDEDGE_MODE);
     H3_SET_RESERVED_BITS(&mut output, direction as i32);
 
-    return Ok(output);
+    Ok(output)
 }
 
 /**
@@ -48,7 +54,7 @@ pub fn getDirectedEdgeOrigin(edge: H3Index) -> Result<H3Index, Error> {
     let mut origin: H3Index = edge;
     H3_SET_MODE(&mut origin, H3_CELL_MODE);
     H3_SET_RESERVED_BITS(&mut origin, 0);
-    return Ok(origin);
+    Ok(origin)
 }
 
 /**
@@ -60,16 +66,27 @@ pub fn getDirectedEdgeDestination(edge: H3Index) -> Result<H3Index, Error> {
     let direction: Direction = Direction::from_i32(H3_GET_RESERVED_BITS(edge)).unwrap();
     let mut rotations: i32 = 0;
     // Note: This call is also checking for H3_DIRECTEDEDGE_MODE
-    let mut origin: H3Index = getDirectedEdgeOrigin(edge)?;
-    return h3NeighborRotations(origin, direction, &mut rotations);
+    let origin: H3Index = getDirectedEdgeOrigin(edge)?;
+    h3NeighborRotations(origin, direction, &mut rotations)
 }
 
 /**
- * Determines if the provided H3Index is a valid directed edge index
+ * Determines if the provided H3Index is a valid directed edge index: the high
+ * bit is zero, the mode is H3_DIRECTEDEDGE_MODE, the reserved bits hold a
+ * direction that exists from the origin, and the origin is a valid cell,
+ * which checks its base cell and every resolution digit.
  * @param edge The directed edge H3Index
  * @return 1 if it is a directed edge H3Index, otherwise 0.
  */
 pub fn isValidDirectedEdge(edge: H3Index) -> bool {
+    if H3_GET_HIGH_BIT(edge) != 0 {
+        return false;
+    }
+
+    if H3_GET_MODE(edge) != H3_DIRECTEDEDGE_MODE {
+        return false;
+    }
+
     let neighborDirection = H3_GET_RESERVED_BITS(edge);
     if neighborDirection <= Direction::CenterDigit as i32
         || neighborDirection >= Direction::NUM_DIGITS as i32
@@ -77,19 +94,19 @@ pub fn isValidDirectedEdge(edge: H3Index) -> bool {
         return false;
     }
 
-    // Note: This call is also checking for H3_DIRECTEDEDGE_MODE
     let origin: H3Index = match getDirectedEdgeOrigin(edge) {
         Ok(result) => result,
-        Err(err) => {
+        Err(_err) => {
             return false;
         }
     };
 
-    if isPentagon(origin) && neighborDirection == Direction::KAxesDigit as i32 {
+    if !isValidCell(origin) {
         return false;
     }
 
-    return isValidCell(origin);
+    // the k direction is deleted from pentagons
+    !(isPentagon(origin) && neighborDirection == Direction::KAxesDigit as i32)
 }
 
 /**
@@ -101,7 +118,7 @@ pub fn isValidDirectedEdge(edge: H3Index) -> bool {
 pub fn directedEdgeToCells(edge: H3Index) -> Result<(H3Index, H3Index), Error> {
     let originResult = getDirectedEdgeOrigin(edge)?;
     let destinationResult = getDirectedEdgeDestination(edge)?;
-    return Ok((originResult, destinationResult));
+    Ok((originResult, destinationResult))
 }
 
 /**
@@ -117,24 +134,217 @@ pub fn originToDirectedEdges(origin: H3Index) -> [H3Index; 6] {
     // This is actually quite simple. Just modify the bits of the origin
     // slightly for each direction, except the 'k' direction in pentagons,
     // which is zeroed.
-    for i in 0..6 {
+    for (i, edge) in edges.iter_mut().enumerate() {
         if isPent && i == 0 {
-            edges[i] = H3_NULL;
+            *edge = H3_NULL;
         } else {
-            edges[i] = origin;
-            H3_SET_MODE(&mut edges[i], H3_DIRECTEDEDGE_MODE);
-            H3_SET_RESERVED_BITS(&mut edges[i], (i as i32) + 1);
+            *edge = origin;
+            H3_SET_MODE(edge, H3_DIRECTEDEDGE_MODE);
+            H3_SET_RESERVED_BITS(edge, (i as i32) + 1);
+        }
+    }
+    edges
+}
+
+/**
+ * Provides the coordinates defining the directed edge.
+ *
+ * While there are always 2 topological vertexes per edge, the resulting edge
+ * boundary may have an additional distortion vertex if it crosses an edge of
+ * the icosahedron.
+ *
+ * @param edge The directed edge H3Index
+ * @return The vertices of the edge, in order
+ */
+pub fn directedEdgeToBoundary(edge: H3Index) -> Result<Vec<LatLng>, Error> {
+    // Get the origin and neighbor direction from the edge
+    let direction = Direction::from_i32(H3_GET_RESERVED_BITS(edge)).unwrap();
+    let origin = getDirectedEdgeOrigin(edge)?;
+
+    // Get the start vertex for the edge
+    let startVertex = vertexNumForDirection(origin, direction);
+    if startVertex == INVALID_VERTEX_NUM {
+        // This is not actually an edge (i.e. no valid direction),
+        // so return no vertices.
+        return Err(Error::DirectedEdgeInvalid);
+    }
+
+    // Get the geo boundary for the appropriate vertexes of the origin. Note
+    // that while there are always 2 topological vertexes per edge, the
+    // resulting edge boundary may have an additional distortion vertex if it
+    // crosses an edge of the icosahedron.
+    let fijk: FaceIJK = _h3ToFaceIjk(origin)?;
+    let res = H3_GET_RESOLUTION(origin);
+    let mut cb = CellBoundary::new();
+    if isPentagon(origin) {
+        _faceIjkPentToCellBoundary(&fijk, res, startVertex, 2, &mut cb);
+    } else {
+        _faceIjkToCellBoundary(&fijk, res, startVertex, 2, &mut cb);
+    }
+    Ok(cb.to_vec())
+}
+
+/**
+ * Returns the midpoint of a directed edge, which is the point halfway along
+ * the great circle arc between the centers of the origin and destination
+ * cells. This is where the edge crosses the boundary shared by the two cells,
+ * which makes it a natural anchor for rendering flow arrows.
+ *
+ * @param edge The directed edge H3Index
+ * @return The midpoint of the edge
+ */
+pub fn edgeMidpoint(edge: H3Index) -> Result<LatLng, Error> {
+    if !isValidDirectedEdge(edge) {
+        return Err(Error::DirectedEdgeInvalid);
+    }
+    let (origin, destination) = directedEdgeToCells(edge)?;
+    greatCircleMidpoint(&cellToLatLng(origin)?, &cellToLatLng(destination)?)
+}
+
+/**
+ * Returns the bearing of a directed edge, which is the initial azimuth of the
+ * great circle arc from the center of the origin cell to the center of the
+ * destination cell.
+ *
+ * @param edge The directed edge H3Index
+ * @return The azimuth in radians clockwise from north, in [0, 2pi)
+ */
+pub fn edgeBearing(edge: H3Index) -> Result<f64, Error> {
+    if !isValidDirectedEdge(edge) {
+        return Err(Error::DirectedEdgeInvalid);
+    }
+    let (origin, destination) = directedEdgeToCells(edge)?;
+    cellAzimuthRads(origin, destination)
+}
+
+/**
+ * Maps a directed edge to the directed edge between the parents of its
+ * origin and destination, for rolling up flows along with the cells.
+ *
+ * @param edge The directed edge H3Index
+ * @param parentRes The resolution of the parent edge
+ * @return The parent directed edge, or None if the origin and destination
+ *         have the same parent, in which case the flow is internal to it
+ */
+pub fn edgeToParentEdge(edge: H3Index, parentRes: i32) -> Result<Option<H3Index>, Error> {
+    if !isValidDirectedEdge(edge) {
+        return Err(Error::DirectedEdgeInvalid);
+    }
+    let (origin, destination) = directedEdgeToCells(edge)?;
+    let originParent = cellToParent(origin, parentRes)?;
+    let destinationParent = cellToParent(destination, parentRes)?;
+    if originParent == destinationParent {
+        return Ok(None);
+    }
+    Ok(Some(cellsToDirectedEdge(originParent, destinationParent)?))
+}
+
+/**
+ * Maps a directed edge to the directed edges between the children of its
+ * origin and the children of its destination, for distributing flows along
+ * with the cells.
+ *
+ * Only the children along the shared edge are visited: a child of the origin
+ * next to a child of the destination has a parent next to the parent of that
+ * child, so each resolution is refined from the cells kept at the one above.
+ * The work grows with the number of child edges, not the number of children.
+ *
+ * @param edge The directed edge H3Index
+ * @param childRes The resolution of the child edges
+ * @return The directed edges from children of the origin to neighboring
+ *         children of the destination
+ */
+pub fn edgeToChildEdges(edge: H3Index, childRes: i32) -> Result<Vec<H3Index>, Error> {
+    if !isValidDirectedEdge(edge) {
+        return Err(Error::DirectedEdgeInvalid);
+    }
+    let (origin, destination) = directedEdgeToCells(edge)?;
+    let res = H3_GET_RESOLUTION(origin);
+    if !(0..=MAX_H3_RES).contains(&childRes) {
+        return Err(Error::ResDomain);
+    }
+    if childRes < res {
+        return Err(Error::ResMismatch);
+    }
+
+    // the children of the origin at each resolution with an edge into a
+    // child of the destination, and those edges
+    let mut border: Vec<H3Index> = vec![origin];
+    let mut out: Vec<H3Index> = vec![edge];
+    for r in (res + 1)..=childRes {
+        let mut nextBorder: Vec<H3Index> = Vec::new();
+        out.clear();
+        for &cell in &border {
+            for child in IterCellsChildren::from_parent(cell, r) {
+                let before = out.len();
+                for childEdge in originToDirectedEdges(child) {
+                    if childEdge == H3_NULL {
+                        continue;
+                    }
+                    let childDestination = getDirectedEdgeDestination(childEdge)?;
+                    if cellToParent(childDestination, res)? == destination {
+                        out.push(childEdge);
+                    }
+                }
+                if out.len() > before {
+                    nextBorder.push(child);
+                }
+            }
         }
+        border = nextBorder;
     }
-    return edges;
+    Ok(out)
+}
+
+/**
+ * Checks that a sequence of cells is a path through the grid: that every cell
+ * is valid, all are at the same resolution, and consecutive cells are
+ * neighbors.
+ *
+ * @param cells The path to check
+ * @return Ok, or the index of the first cell of the first broken link and why
+ *         it is broken (E_CELL_INVALID, E_RES_MISMATCH or E_NOT_NEIGHBORS.) An
+ *         invalid cell is reported at its own index.
+ */
+pub fn validateCellPath(cells: &[H3Index]) -> Result<(), (usize, Error)> {
+    for (i, cell) in cells.iter().enumerate() {
+        if !isValidCell(*cell) {
+            return Err((i, Error::CellInvalid));
+        }
+        if i == 0 {
+            continue;
+        }
+        let prev = cells[i - 1];
+        if H3_GET_RESOLUTION(prev) != H3_GET_RESOLUTION(*cell) {
+            return Err((i - 1, Error::ResMismatch));
+        }
+        if prev == *cell || directionForNeighbor(prev, *cell) == Direction::InvalidDigit {
+            return Err((i - 1, Error::NotNeighbors));
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Returns whether a sequence of cells is a path through the grid, with
+ * consecutive cells being neighbors at the same resolution.
+ *
+ * @param cells The path to check
+ * @return Whether the path is valid
+ */
+pub fn isValidCellPath(cells: &[H3Index]) -> bool {
+    validateCellPath(cells).is_ok()
 }
 
 #[cfg(test)]
 mod tests {
+    use core::f64::consts::PI;
+
     use crate::{
         algos::gridRingUnsafe,
+        bench_utils::{randomCells, BenchRng},
         h3_index::{latLngToCell, setH3Index},
-        lat_lng::LatLng,
+        lat_lng::{_geoAzimuthRads, _posAngleRads, geoAlmostEqual},
     };
 
     use super::*;
@@ -146,14 +356,14 @@ mod tests {
 
     #[test]
     fn cellsToDirectedEdgeAndFriends() {
-        let mut sf: H3Index = latLngToCell(&sfGeo, 9).unwrap();
+        let sf: H3Index = latLngToCell(&sfGeo, 9).unwrap();
         let ring = gridRingUnsafe(sf, 1).unwrap();
-        let mut sf2 = ring[0];
+        let sf2 = ring[0];
 
-        let mut edge = cellsToDirectedEdge(sf, sf2).unwrap();
-        let mut edgeOrigin: H3Index = getDirectedEdgeOrigin(edge).unwrap();
+        let edge = cellsToDirectedEdge(sf, sf2).unwrap();
+        let edgeOrigin: H3Index = getDirectedEdgeOrigin(edge).unwrap();
         assert_eq!(sf, edgeOrigin, "can retrieve the origin from the edge");
-        let mut edgeDestination: H3Index = getDirectedEdgeDestination(edge).unwrap();
+        let edgeDestination: H3Index = getDirectedEdgeDestination(edge).unwrap();
         assert_eq!(
             sf2, edgeDestination,
             "can retrieve the destination from the edge"
@@ -196,6 +406,55 @@ mod tests {
     }
 
     #[test]
+    fn isValidDirectedEdge_fuzz() {
+        // the definition, checked field by field
+        let expected = |edge: H3Index| -> bool {
+            let mut origin = edge;
+            H3_SET_MODE(&mut origin, H3_CELL_MODE);
+            H3_SET_RESERVED_BITS(&mut origin, 0);
+            let dir = H3_GET_RESERVED_BITS(edge);
+            H3_GET_MODE(edge) == H3_DIRECTEDEDGE_MODE
+                && (1..7).contains(&dir)
+                && isValidCell(origin)
+                && !(isPentagon(origin) && dir == 1)
+        };
+
+        let mut rng = BenchRng::new(3823);
+        let mut valid = 0;
+        for cell in randomCells(3823, 200, 5).unwrap() {
+            for edge in super::originToDirectedEdges(cell) {
+                if edge == H3_NULL {
+                    continue;
+                }
+                assert!(isValidDirectedEdge(edge), "edge of a cell is valid");
+                // flip single bits, including the high bit and every digit
+                for bit in 0..64 {
+                    let flipped = edge ^ (1 << bit);
+                    assert_eq!(
+                        isValidDirectedEdge(flipped),
+                        expected(flipped),
+                        "flipped bit {} of {:x}",
+                        bit,
+                        edge
+                    );
+                    valid += isValidDirectedEdge(flipped) as i32;
+                }
+            }
+        }
+        assert!(valid > 0, "some flips give other valid edges");
+
+        for _ in 0..10000 {
+            let mut h = rng.next_u64();
+            assert_eq!(isValidDirectedEdge(h), expected(h), "random bits");
+            // mostly valid headers, so the digits are what is tested
+            h &= !(0x1f << 59);
+            H3_SET_MODE(&mut h, H3_DIRECTEDEDGE_MODE);
+            assert_eq!(isValidDirectedEdge(h), expected(h), "random digits");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::needless_range_loop, reason = "ported from the C test suite")]
     fn originToDirectedEdges() {
         let sf = latLngToCell(&sfGeo, 9).unwrap();
         let edges = super::originToDirectedEdges(sf);
@@ -208,4 +467,208 @@ mod tests {
             assert!(sf != destination, "destination is not origin");
         }
     }
+
+    #[test]
+    fn edgeMidpointAndBearing() {
+        let sf = latLngToCell(&sfGeo, 9).unwrap();
+        let edges = super::originToDirectedEdges(sf);
+        let sfCenter = cellToLatLng(sf).unwrap();
+
+        for edge in edges {
+            let destination = getDirectedEdgeDestination(edge).unwrap();
+            let destinationCenter = cellToLatLng(destination).unwrap();
+            let reverse = cellsToDirectedEdge(destination, sf).unwrap();
+
+            let midpoint = edgeMidpoint(edge).unwrap();
+            assert!(
+                geoAlmostEqual(&midpoint, &edgeMidpoint(reverse).unwrap()),
+                "midpoint does not depend on the edge direction"
+            );
+            assert_eq!(
+                latLngToCell(&midpoint, 9).map(|h| h == sf || h == destination),
+                Ok(true),
+                "midpoint lies between the two cells"
+            );
+
+            let bearing = edgeBearing(edge).unwrap();
+            assert!((0.0..2.0 * PI).contains(&bearing), "bearing is normalized");
+            assert!(
+                (_posAngleRads(_geoAzimuthRads(&sfCenter, &destinationCenter)) - bearing).abs()
+                    < 1e-12,
+                "bearing points from origin to destination"
+            );
+            let reverseBearing = edgeBearing(reverse).unwrap();
+            assert!(
+                (_posAngleRads(reverseBearing - bearing) - PI).abs() < 1e-4,
+                "reverse edge points the opposite way"
+            );
+        }
+
+        assert_eq!(edgeMidpoint(sf), Err(Error::DirectedEdgeInvalid));
+        assert_eq!(edgeBearing(0), Err(Error::DirectedEdgeInvalid));
+    }
+
+    #[test]
+    fn validateCellPathLinks() {
+        let sf = latLngToCell(&sfGeo, 9).unwrap();
+        let ring = gridRingUnsafe(sf, 1).unwrap();
+        let path = vec![sf, ring[0], ring[1], ring[2]];
+        assert!(isValidCellPath(&path), "walk around the ring");
+        assert!(isValidCellPath(&[sf]), "single cell");
+        assert!(isValidCellPath(&[]), "empty path");
+
+        let jump = vec![sf, ring[0], ring[3]];
+        assert_eq!(
+            validateCellPath(&jump),
+            Err((1, Error::NotNeighbors)),
+            "opposite sides of the ring"
+        );
+        assert_eq!(
+            validateCellPath(&[sf, sf]),
+            Err((0, Error::NotNeighbors)),
+            "repeated cell"
+        );
+        let parent = latLngToCell(&sfGeo, 8).unwrap();
+        assert_eq!(
+            validateCellPath(&[sf, ring[0], parent]),
+            Err((1, Error::ResMismatch)),
+            "mixed resolutions"
+        );
+        assert_eq!(
+            validateCellPath(&[sf, 0]),
+            Err((1, Error::CellInvalid)),
+            "invalid cell"
+        );
+    }
+
+    #[test]
+    fn edgeToParentAndChildEdges() {
+        let sf = latLngToCell(&sfGeo, 7).unwrap();
+        for edge in super::originToDirectedEdges(sf) {
+            let (origin, destination) = directedEdgeToCells(edge).unwrap();
+            let children = edgeToChildEdges(edge, 9).unwrap();
+            assert!(!children.is_empty(), "neighbors share child edges");
+            for child in &children {
+                assert!(isValidDirectedEdge(*child), "child edge is valid");
+                assert_eq!(
+                    edgeToParentEdge(*child, 7),
+                    Ok(Some(edge)),
+                    "child edge rolls up to the edge"
+                );
+                let (childOrigin, childDestination) = directedEdgeToCells(*child).unwrap();
+                assert_eq!(cellToParent(childOrigin, 7), Ok(origin));
+                assert_eq!(cellToParent(childDestination, 7), Ok(destination));
+            }
+            assert_eq!(edgeToChildEdges(edge, 7), Ok(vec![edge]), "same resolution");
+        }
+
+        // every edge between children of the origin and the destination is
+        // found, including around a pentagon
+        let mut pentagon: H3Index = H3_NULL;
+        setH3Index(&mut pentagon, 5, 4, 0);
+        for origin in [sf, pentagon] {
+            let res = H3_GET_RESOLUTION(origin);
+            for edge in super::originToDirectedEdges(origin) {
+                if edge == H3_NULL {
+                    continue;
+                }
+                let destination = getDirectedEdgeDestination(edge).unwrap();
+                let childRes = res + 3;
+                let mut expected: Vec<H3Index> = Vec::new();
+                for child in IterCellsChildren::from_parent(origin, childRes) {
+                    for childEdge in super::originToDirectedEdges(child) {
+                        if childEdge != H3_NULL
+                            && cellToParent(getDirectedEdgeDestination(childEdge).unwrap(), res)
+                                == Ok(destination)
+                        {
+                            expected.push(childEdge);
+                        }
+                    }
+                }
+                let mut children = edgeToChildEdges(edge, childRes).unwrap();
+                children.sort();
+                expected.sort();
+                assert_eq!(children, expected, "matches checking every child");
+            }
+        }
+
+        let fine = latLngToCell(&sfGeo, 9).unwrap();
+        let internal = super::originToDirectedEdges(fine)
+            .into_iter()
+            .find(|e| {
+                let d = getDirectedEdgeDestination(*e).unwrap();
+                cellToParent(d, 8).unwrap() == cellToParent(fine, 8).unwrap()
+            })
+            .unwrap();
+        assert_eq!(edgeToParentEdge(internal, 8), Ok(None), "internal flow");
+
+        assert_eq!(edgeToParentEdge(fine, 8), Err(Error::DirectedEdgeInvalid));
+        let edge = super::originToDirectedEdges(sf)[1];
+        assert_eq!(edgeToParentEdge(edge, 8), Err(Error::ResMismatch));
+        assert_eq!(edgeToChildEdges(edge, 6), Err(Error::ResMismatch));
+        assert_eq!(edgeToChildEdges(edge, 16), Err(Error::ResDomain));
+    }
+
+    #[test]
+    fn directedEdgeToBoundary_sf() {
+        let expectedVertices: [[usize; 2]; 6] = [[3, 4], [1, 2], [2, 3], [5, 0], [4, 5], [0, 1]];
+        for res in 0..MAX_H3_RES {
+            let sf = latLngToCell(&sfGeo, res).unwrap();
+            let boundary = crate::h3_index::cellToBoundary(sf).unwrap();
+            let edges = super::originToDirectedEdges(sf);
+            for i in 0..6 {
+                let edgeBoundary = directedEdgeToBoundary(edges[i]).unwrap();
+                assert_eq!(
+                    edgeBoundary.len(),
+                    2,
+                    "Got the expected number of vertices back"
+                );
+                for j in 0..edgeBoundary.len() {
+                    assert!(
+                        geoAlmostEqual(&edgeBoundary[j], &boundary.verts[expectedVertices[i][j]]),
+                        "Got expected vertex"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn directedEdgeToBoundary_pentagons() {
+        for (res, expectedVerts) in [(1, 3), (2, 2)] {
+            let mut pentagon: H3Index = 0;
+            setH3Index(&mut pentagon, res, 24, 0);
+            let mut numEdges = 0;
+            for edge in super::originToDirectedEdges(pentagon) {
+                if edge == H3_NULL {
+                    continue;
+                }
+                numEdges += 1;
+                let edgeBoundary = directedEdgeToBoundary(edge).unwrap();
+                assert_eq!(
+                    edgeBoundary.len(),
+                    expectedVerts,
+                    "Class III pentagon edges cross an icosahedron edge"
+                );
+            }
+            assert_eq!(numEdges, 5, "pentagon has five edges");
+        }
+    }
+
+    #[test]
+    fn directedEdgeToBoundary_invalid() {
+        let sf = latLngToCell(&sfGeo, 9).unwrap();
+        let mut noDirection = super::originToDirectedEdges(sf)[0];
+        H3_SET_RESERVED_BITS(&mut noDirection, 0);
+        assert_eq!(
+            directedEdgeToBoundary(noDirection),
+            Err(Error::DirectedEdgeInvalid),
+            "edge without a direction fails"
+        );
+        assert_eq!(
+            directedEdgeToBoundary(sf),
+            Err(Error::DirectedEdgeInvalid),
+            "cell is not an edge"
+        );
+    }
 }
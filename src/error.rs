@@ -1,49 +1,205 @@
-use thiserror::Error as DeriveError;
+use core::fmt;
 
-#[derive(Debug, DeriveError, PartialEq)]
-pub enum Error {
-    #[error("The operation failed but a more specific error is not available")]
-    Failed, // 1
+/** Numeric error code, as returned by the C library. E_SUCCESS is 0. */
+pub type H3Error = u32;
 
-    #[error("Argument was outside of acceptable range (when a more specific error code is not available)")]
-    Domain, // 2
+pub const E_SUCCESS: H3Error = 0;
+pub const E_FAILED: H3Error = 1;
+pub const E_DOMAIN: H3Error = 2;
+pub const E_LATLNG_DOMAIN: H3Error = 3;
+pub const E_RES_DOMAIN: H3Error = 4;
+pub const E_CELL_INVALID: H3Error = 5;
+pub const E_DIR_EDGE_INVALID: H3Error = 6;
+pub const E_UNDIR_EDGE_INVALID: H3Error = 7;
+pub const E_VERTEX_INVALID: H3Error = 8;
+pub const E_PENTAGON: H3Error = 9;
+pub const E_DUPLICATE_INPUT: H3Error = 10;
+pub const E_NOT_NEIGHBORS: H3Error = 11;
+pub const E_RES_MISMATCH: H3Error = 12;
+pub const E_MEMORY_ALLOC: H3Error = 13;
+pub const E_MEMORY_BOUNDS: H3Error = 14;
+pub const E_OPTION_INVALID: H3Error = 15;
 
-    #[error("Latitude or longitude arguments were outside of acceptable range")]
-    LatLngDomain, // 3
+/**
+ * Returns a human-readable description of an error code, matching the C
+ * library.
+ *
+ * @param code The error code.
+ * @return The description, or "Invalid error code" for unknown codes.
+ */
+pub fn describeH3Error(code: H3Error) -> &'static str {
+    match code {
+        E_SUCCESS => "Success",
+        E_FAILED => "The operation failed but a more specific error is not available",
+        E_DOMAIN => "Argument was outside of acceptable range",
+        E_LATLNG_DOMAIN => "Latitude or longitude arguments were outside of acceptable range",
+        E_RES_DOMAIN => "Resolution argument was outside of acceptable range",
+        E_CELL_INVALID => "Cell argument was not valid",
+        E_DIR_EDGE_INVALID => "Directed edge argument was not valid",
+        E_UNDIR_EDGE_INVALID => "Undirected edge argument was not valid",
+        E_VERTEX_INVALID => "Vertex argument was not valid",
+        E_PENTAGON => "Pentagon distortion was encountered",
+        E_DUPLICATE_INPUT => "Duplicate input",
+        E_NOT_NEIGHBORS => "Cell arguments were not neighbors",
+        E_RES_MISMATCH => "Cell arguments had incompatible resolutions",
+        E_MEMORY_ALLOC => "Memory allocation failed",
+        E_MEMORY_BOUNDS => "Bounds of provided memory were insufficient",
+        E_OPTION_INVALID => "Mode or flags argument was not valid",
+        _ => "Invalid error code",
+    }
+}
 
-    #[error("Resolution argument was outside of acceptable range")]
-    ResDomain, // 4
+/**
+ * Errors returned by the library, one per non-success C error code. The
+ * discriminants are the C codes and Display uses describeH3Error.
+ */
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Error {
+    /** E_FAILED */
+    Failed = 1,
+    /** E_DOMAIN */
+    Domain = 2,
+    /** E_LATLNG_DOMAIN */
+    LatLngDomain = 3,
+    /** E_RES_DOMAIN */
+    ResDomain = 4,
+    /** E_CELL_INVALID */
+    CellInvalid = 5,
+    /** E_DIR_EDGE_INVALID */
+    DirectedEdgeInvalid = 6,
+    /** E_UNDIR_EDGE_INVALID */
+    UndirectedEdgeInvalid = 7,
+    /** E_VERTEX_INVALID */
+    VertexInvalid = 8,
+    /** E_PENTAGON */
+    Pentagon = 9,
+    /** E_DUPLICATE_INPUT */
+    DuplicateInput = 10,
+    /** E_NOT_NEIGHBORS */
+    NotNeighbors = 11,
+    /** E_RES_MISMATCH */
+    ResMismatch = 12,
+    /** E_MEMORY_ALLOC */
+    Memory = 13,
+    /** E_MEMORY_BOUNDS */
+    MemoryBounds = 14,
+    /** E_OPTION_INVALID */
+    OptionInvalid = 15,
+}
 
-    #[error("H3Index cell argument was not valid")]
-    CellInvalid, // 5
+impl Error {
+    /** The C library error code for this error. */
+    pub fn code(self) -> H3Error {
+        self as H3Error
+    }
 
-    #[error("H3Index directed edge argument was not valid")]
-    DirectedEdgeInvalid, // 6
+    /**
+     * Converts a C library error code into a result.
+     *
+     * @param code The error code.
+     * @return Ok for E_SUCCESS, the matching error otherwise. Unknown codes
+     *         are reported as Failed.
+     */
+    pub fn fromCode(code: H3Error) -> Result<(), Error> {
+        if code == E_SUCCESS {
+            return Ok(());
+        }
+        Err(Error::try_from(code).unwrap_or(Error::Failed))
+    }
+}
 
-    #[error("H3Index undirected edge argument was not valid")]
-    UndirectedEdgeInvalid, // 7
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(describeH3Error(self.code()))
+    }
+}
 
-    #[error("H3Index vertex argument was not valid")]
-    VertexInvalid, // 8
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
 
-    #[error("Pentagon distortion was encountered")]
-    Pentagon, // 9
+impl From<Error> for H3Error {
+    fn from(e: Error) -> H3Error {
+        e.code()
+    }
+}
 
-    #[error("Duplicate input was encountered in the arguments")]
-    DuplicateInput, // 10
+impl TryFrom<H3Error> for Error {
+    /** The code, if it is E_SUCCESS or not a known error. */
+    type Error = H3Error;
 
-    #[error("H3Index cell arguments were not neighbors")]
-    NotNeighbors, // 11
+    fn try_from(code: H3Error) -> Result<Error, H3Error> {
+        match code {
+            E_FAILED => Ok(Error::Failed),
+            E_DOMAIN => Ok(Error::Domain),
+            E_LATLNG_DOMAIN => Ok(Error::LatLngDomain),
+            E_RES_DOMAIN => Ok(Error::ResDomain),
+            E_CELL_INVALID => Ok(Error::CellInvalid),
+            E_DIR_EDGE_INVALID => Ok(Error::DirectedEdgeInvalid),
+            E_UNDIR_EDGE_INVALID => Ok(Error::UndirectedEdgeInvalid),
+            E_VERTEX_INVALID => Ok(Error::VertexInvalid),
+            E_PENTAGON => Ok(Error::Pentagon),
+            E_DUPLICATE_INPUT => Ok(Error::DuplicateInput),
+            E_NOT_NEIGHBORS => Ok(Error::NotNeighbors),
+            E_RES_MISMATCH => Ok(Error::ResMismatch),
+            E_MEMORY_ALLOC => Ok(Error::Memory),
+            E_MEMORY_BOUNDS => Ok(Error::MemoryBounds),
+            E_OPTION_INVALID => Ok(Error::OptionInvalid),
+            _ => Err(code),
+        }
+    }
+}
 
-    #[error("H3Index cell arguments had incompatible resolutions")]
-    ResMismatch, // 12
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[error("Necessary memory allocation failed")]
-    Memory, // 13
+    #[test]
+    fn codesRoundTrip() {
+        for code in E_FAILED..=E_OPTION_INVALID {
+            let e = Error::try_from(code).unwrap();
+            assert_eq!(e.code(), code, "code survives a round trip");
+            assert_eq!(H3Error::from(e), code, "From agrees with code");
+            assert_eq!(Error::fromCode(code), Err(e), "fromCode gives the error");
+        }
+        assert_eq!(
+            Error::try_from(E_SUCCESS),
+            Err(E_SUCCESS),
+            "success is not an error"
+        );
+        assert_eq!(Error::try_from(16), Err(16), "unknown code is rejected");
+        assert_eq!(Error::fromCode(E_SUCCESS), Ok(()), "success is ok");
+        assert_eq!(
+            Error::fromCode(16),
+            Err(Error::Failed),
+            "unknown code fails"
+        );
+    }
 
-    #[error("Bounds of provided memory were not large enough")]
-    MemoryBounds, // 14
+    #[test]
+    fn describeH3Error() {
+        assert_eq!(super::describeH3Error(E_SUCCESS), "Success");
+        assert_eq!(
+            super::describeH3Error(E_CELL_INVALID),
+            "Cell argument was not valid"
+        );
+        assert_eq!(super::describeH3Error(16), "Invalid error code");
+        for code in E_FAILED..=E_OPTION_INVALID {
+            assert_eq!(
+                Error::try_from(code).unwrap().to_string(),
+                super::describeH3Error(code),
+                "Display matches the description"
+            );
+        }
+    }
 
-    #[error("Mode or flags argument was not valid")]
-    OptionInvalid, // 15
+    #[cfg(feature = "std")]
+    #[test]
+    fn isStdError() {
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(Error::ResDomain);
+        assert_eq!(
+            boxed.to_string(),
+            "Resolution argument was outside of acceptable range",
+            "boxed error displays its description"
+        );
+    }
 }
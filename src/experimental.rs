@@ -0,0 +1,84 @@
+// The FaceIJK coordinate system the indexing is built on, for research into
+// alternative indexings on the same substrate.
+//
+// These are internal building blocks, exposed as they are used inside the
+// crate: they follow the C library's internal functions, take and return
+// unchecked values, and may change or be removed in any release without a
+// deprecation period. Only enable the experimental feature where a breaking
+// change on upgrade is acceptable.
+
+pub use crate::coord_ijk::CoordIJK;
+pub use crate::face_ijk::{
+    FaceIJK, Overage, _adjustOverageClassII, _adjustPentVertOverage, _faceIjkToGeo, _geoToFaceIjk,
+};
+pub use crate::h3_index::{_faceIjkToH3, _h3ToFaceIjk};
+
+#[cfg(test)]
+mod tests {
+    use crate::bench_utils::randomLatLngs;
+    use crate::h3_index::{cellToLatLng, isValidCell, latLngToCell};
+    use crate::lat_lng::{geoAlmostEqual, greatCircleDistanceRads};
+
+    use super::*;
+
+    #[test]
+    fn faceIjkRoundTrip() {
+        for g in randomLatLngs(5, 1000) {
+            for res in [0, 5, 10] {
+                let fijk = _geoToFaceIjk(&g, res);
+                let h = _faceIjkToH3(&fijk, res);
+                assert_eq!(Ok(h), latLngToCell(&g, res), "same cell as latLngToCell");
+                assert!(
+                    geoAlmostEqual(
+                        &_faceIjkToGeo(_h3ToFaceIjk(h).unwrap(), res),
+                        &cellToLatLng(h).unwrap()
+                    ),
+                    "same center as cellToLatLng"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn adjustOverage() {
+        // the center of face 0 is not an overage
+        let mut fijk = FaceIJK {
+            face: 0,
+            coord: CoordIJK { i: 0, j: 0, k: 0 },
+        };
+        assert_eq!(
+            _adjustOverageClassII(&mut fijk, 2, false, false),
+            Overage::NoOverage
+        );
+        assert_eq!(fijk.face, 0, "same face");
+
+        // just past the edge of face 0 is on another face
+        let mut fijk = FaceIJK {
+            face: 0,
+            coord: CoordIJK { i: 15, j: 0, k: 0 },
+        };
+        let before = _faceIjkToGeo(fijk, 2);
+        assert_eq!(
+            _adjustOverageClassII(&mut fijk, 2, false, false),
+            Overage::NewFace
+        );
+        assert_ne!(fijk.face, 0, "moved to a new face");
+        // the gnomonic projections of the faces differ past the edge, so the
+        // point is only nearby on the new face
+        let after = _faceIjkToGeo(fijk, 2);
+        assert!(
+            greatCircleDistanceRads(&after, &before) < 0.01,
+            "nearby point"
+        );
+        assert!(
+            isValidCell(_faceIjkToH3(&fijk, 2)),
+            "a cell on the new face"
+        );
+
+        let mut vertex = FaceIJK {
+            face: 0,
+            coord: CoordIJK { i: 0, j: 0, k: 0 },
+        };
+        assert_eq!(_adjustPentVertOverage(&mut vertex, 2), Overage::NoOverage);
+    }
+}
@@ -1,11 +1,17 @@
+use core::f64::consts::{FRAC_PI_2, PI};
+
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
 use crate::constants::*;
 use crate::coord_ijk::{
-    CoordIJK, _hex2dToCoordIJK, _ijkAdd, _ijkNormalize, _ijkRotate60ccw, _ijkRotate60cw, _ijkScale,
-    _ijkSub, _ijkToHex2d, _setIJK,
+    CoordIJK, _downAp3, _downAp3r, _downAp7r, _hex2dToCoordIJK, _ijkAdd, _ijkNormalize,
+    _ijkRotate60ccw, _ijkRotate60cw, _ijkScale, _ijkSub, _ijkToHex2d, _setIJK,
 };
 use crate::h3_index::isResolutionClassIII;
-use crate::lat_lng::{LatLng, _geoAzDistanceRads, _geoAzimuthRads, _posAngleRads};
-use crate::vec2d::{Vec2d, _v2dMag};
+use crate::lat_lng::{CellBoundary, LatLng, _geoAzDistanceRads, _geoAzimuthRads, _posAngleRads};
+use crate::vec2d::{Vec2d, _v2dAlmostEquals, _v2dIntersect, _v2dMag};
 use crate::vec3d::{Vec3d, _geoToVec3d, _pointSquareDist};
 
 // indexes for faceNeighbors table
@@ -17,10 +23,14 @@ const KI: usize = 2;
 const JK: usize = 3;
 
 /** Invalid face index */
-const INVALID_FACE: i32 = -1;
+pub const INVALID_FACE: i32 = -1;
 
 /** @brief icosahedron face centers in lat/lon radians */
-const faceCenterGeo: [LatLng; NUM_ICOSA_FACES as usize] = [
+#[allow(
+    clippy::excessive_precision,
+    reason = "digits as published in the C library's faceijk.c"
+)]
+pub(crate) const faceCenterGeo: [LatLng; NUM_ICOSA_FACES as usize] = [
     LatLng {
         lat: 0.803582649718989942,
         lng: 1.248397419617396099,
@@ -104,6 +114,10 @@ const faceCenterGeo: [LatLng; NUM_ICOSA_FACES as usize] = [
 ];
 
 /** @brief icosahedron face centers in x/y/z on the unit sphere */
+#[allow(
+    clippy::excessive_precision,
+    reason = "digits as published in the C library's faceijk.c"
+)]
 const faceCenterPoint: [Vec3d; NUM_ICOSA_FACES as usize] = [
     Vec3d {
         x: 0.2199307791404606,
@@ -207,6 +221,544 @@ const faceCenterPoint: [Vec3d; NUM_ICOSA_FACES as usize] = [
     }, // face 19
 ];
 
+/** Latitude bands in the closest face table. */
+const CLOSEST_FACE_LAT_BANDS: usize = 16;
+/** Longitude bands in the closest face table. */
+const CLOSEST_FACE_LNG_BANDS: usize = 32;
+
+/**
+ * @brief Candidate closest faces by latitude and longitude band.
+ *
+ * The sphere is split into 16 latitude bands from the south pole and 32
+ * longitude bands from -180 degrees. Every entry lists the icosahedron faces
+ * that can be closest to some point in that band: those whose center is
+ * within twice the band's radius of the closest distance from the band
+ * center. Most bands have one or two faces, and bands around icosahedron
+ * vertices up to five. Lists are padded to five by repeating their last face,
+ * so they can be checked without branching on their length. The
+ * closestFaceCandidatesRebuild test regenerates the table from faceCenterGeo.
+ * The closestFace benches measured about 50ns per point against 65ns for
+ * checking all 20 faces, both including the conversion to 3D.
+ */
+const closestFaceCandidates: [[u8; 5]; CLOSEST_FACE_LAT_BANDS * CLOSEST_FACE_LNG_BANDS] = [
+    [18, 19, 19, 19, 19], // lat band 0
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [17, 18, 19, 19, 19], // lat band 1
+    [17, 18, 19, 19, 19],
+    [17, 18, 19, 19, 19],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [15, 18, 19, 19, 19],
+    [15, 16, 17, 18, 19], // lat band 2
+    [15, 16, 17, 18, 19],
+    [16, 17, 18, 18, 18],
+    [16, 17, 18, 18, 18],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [17, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [18, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [18, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 16, 18, 19, 19],
+    [15, 16, 16, 16, 16], // lat band 3
+    [15, 16, 17, 17, 17],
+    [16, 17, 17, 17, 17],
+    [16, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 18, 18, 18, 18],
+    [13, 17, 18, 18, 18],
+    [13, 17, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [13, 18, 18, 18, 18],
+    [9, 13, 18, 18, 18],
+    [9, 13, 14, 18, 19],
+    [9, 13, 14, 18, 19],
+    [9, 14, 18, 19, 19],
+    [14, 19, 19, 19, 19],
+    [14, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [19, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 15, 15, 15, 15],
+    [15, 16, 16, 16, 16],
+    [15, 16, 16, 16, 16], // lat band 4
+    [16, 16, 16, 16, 16],
+    [16, 16, 16, 16, 16],
+    [16, 17, 17, 17, 17],
+    [16, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [17, 17, 17, 17, 17],
+    [12, 17, 17, 17, 17],
+    [12, 17, 18, 18, 18],
+    [8, 12, 13, 17, 18],
+    [8, 12, 13, 17, 18],
+    [8, 13, 18, 18, 18],
+    [13, 13, 13, 13, 13],
+    [13, 13, 13, 13, 13],
+    [13, 13, 13, 13, 13],
+    [13, 13, 13, 13, 13],
+    [9, 13, 13, 13, 13],
+    [9, 13, 14, 14, 14],
+    [9, 13, 14, 14, 14],
+    [9, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [14, 19, 19, 19, 19],
+    [14, 19, 19, 19, 19],
+    [14, 19, 19, 19, 19],
+    [14, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 19, 19, 19, 19],
+    [15, 15, 15, 15, 15],
+    [15, 15, 15, 15, 15],
+    [15, 15, 15, 15, 15],
+    [15, 16, 16, 16, 16],
+    [15, 16, 16, 16, 16], // lat band 5
+    [16, 16, 16, 16, 16],
+    [16, 16, 16, 16, 16],
+    [16, 16, 16, 16, 16],
+    [16, 17, 17, 17, 17],
+    [16, 17, 17, 17, 17],
+    [12, 17, 17, 17, 17],
+    [12, 17, 17, 17, 17],
+    [12, 17, 17, 17, 17],
+    [12, 17, 17, 17, 17],
+    [8, 12, 17, 17, 17],
+    [8, 12, 13, 13, 13],
+    [8, 13, 13, 13, 13],
+    [8, 13, 13, 13, 13],
+    [13, 13, 13, 13, 13],
+    [13, 13, 13, 13, 13],
+    [9, 13, 13, 13, 13],
+    [9, 13, 13, 13, 13],
+    [9, 9, 9, 9, 9],
+    [9, 14, 14, 14, 14],
+    [9, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [14, 19, 19, 19, 19],
+    [5, 10, 14, 15, 19],
+    [5, 10, 14, 15, 19],
+    [10, 15, 19, 19, 19],
+    [15, 15, 15, 15, 15],
+    [15, 15, 15, 15, 15],
+    [15, 15, 15, 15, 15],
+    [15, 16, 16, 16, 16],
+    [15, 16, 16, 16, 16], // lat band 6
+    [16, 16, 16, 16, 16],
+    [16, 16, 16, 16, 16],
+    [11, 16, 16, 16, 16],
+    [11, 16, 17, 17, 17],
+    [7, 11, 12, 16, 17],
+    [12, 17, 17, 17, 17],
+    [12, 12, 12, 12, 12],
+    [12, 12, 12, 12, 12],
+    [12, 12, 12, 12, 12],
+    [8, 12, 12, 12, 12],
+    [8, 8, 8, 8, 8],
+    [8, 8, 8, 8, 8],
+    [8, 13, 13, 13, 13],
+    [8, 13, 13, 13, 13],
+    [9, 13, 13, 13, 13],
+    [9, 13, 13, 13, 13],
+    [9, 9, 9, 9, 9],
+    [9, 9, 9, 9, 9],
+    [9, 9, 9, 9, 9],
+    [9, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [14, 14, 14, 14, 14],
+    [5, 14, 14, 14, 14],
+    [5, 14, 14, 14, 14],
+    [5, 10, 14, 19, 19],
+    [5, 10, 14, 15, 15],
+    [10, 15, 15, 15, 15],
+    [10, 15, 15, 15, 15],
+    [10, 15, 15, 15, 15],
+    [10, 15, 15, 15, 15],
+    [15, 16, 16, 16, 16],
+    [6, 11, 15, 16, 16], // lat band 7
+    [11, 16, 16, 16, 16],
+    [11, 16, 16, 16, 16],
+    [11, 16, 16, 16, 16],
+    [7, 11, 16, 17, 17],
+    [7, 11, 12, 16, 17],
+    [7, 12, 12, 12, 12],
+    [12, 12, 12, 12, 12],
+    [12, 12, 12, 12, 12],
+    [12, 12, 12, 12, 12],
+    [8, 12, 12, 12, 12],
+    [8, 8, 8, 8, 8],
+    [8, 8, 8, 8, 8],
+    [8, 8, 8, 8, 8],
+    [3, 8, 13, 13, 13],
+    [3, 4, 8, 9, 13],
+    [4, 9, 13, 13, 13],
+    [9, 9, 9, 9, 9],
+    [9, 9, 9, 9, 9],
+    [9, 9, 9, 9, 9],
+    [9, 14, 14, 14, 14],
+    [9, 14, 14, 14, 14],
+    [5, 14, 14, 14, 14],
+    [5, 14, 14, 14, 14],
+    [5, 14, 14, 14, 14],
+    [5, 10, 10, 10, 10],
+    [5, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [10, 15, 15, 15, 15],
+    [6, 10, 15, 15, 15],
+    [6, 10, 11, 15, 16],
+    [6, 11, 16, 16, 16], // lat band 8
+    [11, 11, 11, 11, 11],
+    [11, 11, 11, 11, 11],
+    [11, 11, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 12, 12, 12, 12],
+    [7, 12, 12, 12, 12],
+    [7, 12, 12, 12, 12],
+    [8, 12, 12, 12, 12],
+    [8, 12, 12, 12, 12],
+    [8, 8, 8, 8, 8],
+    [8, 8, 8, 8, 8],
+    [3, 8, 8, 8, 8],
+    [3, 8, 13, 13, 13],
+    [3, 4, 8, 9, 13],
+    [3, 4, 9, 13, 13],
+    [4, 9, 9, 9, 9],
+    [4, 9, 9, 9, 9],
+    [4, 9, 9, 9, 9],
+    [0, 4, 9, 14, 14],
+    [0, 4, 5, 9, 14],
+    [5, 14, 14, 14, 14],
+    [5, 5, 5, 5, 5],
+    [5, 5, 5, 5, 5],
+    [5, 5, 5, 5, 5],
+    [5, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [6, 10, 15, 15, 15],
+    [6, 10, 11, 15, 16],
+    [6, 11, 11, 11, 11], // lat band 9
+    [11, 11, 11, 11, 11],
+    [11, 11, 11, 11, 11],
+    [11, 11, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 7, 7, 7, 7],
+    [7, 7, 7, 7, 7],
+    [7, 12, 12, 12, 12],
+    [7, 12, 12, 12, 12],
+    [2, 7, 8, 12, 12],
+    [3, 7, 8, 12, 12],
+    [3, 8, 8, 8, 8],
+    [3, 8, 8, 8, 8],
+    [3, 8, 8, 8, 8],
+    [3, 8, 8, 8, 8],
+    [3, 4, 4, 4, 4],
+    [3, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [4, 9, 9, 9, 9],
+    [0, 4, 9, 9, 9],
+    [0, 4, 5, 9, 14],
+    [0, 5, 5, 5, 5],
+    [5, 5, 5, 5, 5],
+    [5, 5, 5, 5, 5],
+    [5, 5, 5, 5, 5],
+    [5, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [10, 10, 10, 10, 10],
+    [6, 10, 10, 10, 10],
+    [6, 10, 10, 10, 10],
+    [6, 11, 11, 11, 11],
+    [6, 11, 11, 11, 11], // lat band 10
+    [6, 11, 11, 11, 11],
+    [11, 11, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 7, 7, 7, 7],
+    [7, 7, 7, 7, 7],
+    [7, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 3, 7, 8, 12],
+    [2, 3, 7, 8, 12],
+    [2, 3, 8, 8, 8],
+    [3, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 4, 4, 4, 4],
+    [3, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 5, 5, 5, 5],
+    [0, 5, 5, 5, 5],
+    [0, 5, 5, 5, 5],
+    [0, 5, 5, 5, 5],
+    [0, 5, 10, 10, 10],
+    [5, 6, 10, 10, 10],
+    [6, 10, 10, 10, 10],
+    [6, 10, 10, 10, 10],
+    [6, 6, 6, 6, 6],
+    [6, 6, 6, 6, 6],
+    [6, 6, 6, 6, 6], // lat band 11
+    [6, 11, 11, 11, 11],
+    [6, 7, 11, 11, 11],
+    [6, 7, 11, 11, 11],
+    [7, 11, 11, 11, 11],
+    [7, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 3, 3, 3, 3],
+    [2, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 4, 4, 4, 4],
+    [3, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [4, 4, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 5, 5, 5, 5],
+    [0, 1, 5, 5, 5],
+    [0, 1, 5, 6, 10],
+    [0, 1, 5, 6, 10],
+    [1, 6, 10, 10, 10],
+    [6, 6, 6, 6, 6],
+    [6, 6, 6, 6, 6],
+    [6, 6, 6, 6, 6],
+    [1, 6, 6, 6, 6], // lat band 12
+    [1, 6, 11, 11, 11],
+    [1, 2, 6, 7, 11],
+    [1, 2, 6, 7, 11],
+    [1, 2, 7, 11, 11],
+    [2, 7, 7, 7, 7],
+    [2, 7, 7, 7, 7],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 3, 3, 3, 3],
+    [2, 3, 3, 3, 3],
+    [2, 3, 3, 3, 3],
+    [3, 3, 3, 3, 3],
+    [3, 4, 4, 4, 4],
+    [3, 4, 4, 4, 4],
+    [0, 3, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 4, 4, 4, 4],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 1, 1, 1, 1],
+    [0, 1, 6, 6, 6],
+    [0, 1, 6, 6, 6],
+    [1, 6, 6, 6, 6],
+    [1, 6, 6, 6, 6],
+    [1, 6, 6, 6, 6],
+    [1, 6, 6, 6, 6],
+    [1, 6, 6, 6, 6], // lat band 13
+    [1, 6, 6, 6, 6],
+    [1, 2, 6, 6, 6],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 3, 3, 3, 3],
+    [2, 3, 3, 3, 3],
+    [2, 3, 3, 3, 3],
+    [1, 2, 3, 4, 4],
+    [0, 1, 2, 3, 4],
+    [0, 1, 2, 3, 4],
+    [0, 1, 4, 4, 4],
+    [0, 1, 4, 4, 4],
+    [0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 6, 6, 6, 6],
+    [1, 1, 1, 1, 1], // lat band 14
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [2, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 3, 3, 3],
+    [0, 1, 2, 2, 2],
+    [0, 1, 2, 2, 2],
+    [0, 1, 2, 2, 2],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1], // lat band 15
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 2, 2, 2, 2],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+    [1, 1, 1, 1, 1],
+];
+
+#[allow(
+    clippy::excessive_precision,
+    reason = "digits as published in the C library's faceijk.c"
+)]
 const faceAxesAzRadsCII: [[f64; 3]; NUM_ICOSA_FACES as usize] = [
     [
         5.619958268523939882,
@@ -816,9 +1368,15 @@ const faceNeighbors: [[FaceOrientIJK; 4]; NUM_ICOSA_FACES as usize] = [
     ],
 ];
 
-#[derive(Copy, Clone)]
+/** @struct FaceIJK
+ * @brief Face number and ijk coordinates on that face-centered coordinate
+ * system
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct FaceIJK {
+    /** face number */
     pub face: i32,
+    /** ijk coordinates on that face */
     pub coord: CoordIJK,
 }
 
@@ -834,16 +1392,17 @@ pub struct FaceOrientIJK {
     pub ccwRot60: i32,
 }
 
-/** Digit representing overage type */
 enum_from_primitive! {
-    #[derive(PartialEq, PartialOrd, Copy, Clone)]
+    /** Digit representing overage type */
+    #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+    #[allow(clippy::enum_variant_names, reason = "follows NO_OVERAGE in the C library")]
     pub enum Overage {
-           /** No overage (on original face) */
-    NoOverage = 0,
-    /** On face edge (only occurs on substrate grids) */
-    FaceEdge = 1,
-    /** Overage on new face interior */
-    NewFace = 2
+        /** No overage (on original face) */
+        NoOverage = 0,
+        /** On face edge (only occurs on substrate grids) */
+        FaceEdge = 1,
+        /** Overage on new face interior */
+        NewFace = 2
     }
 }
 
@@ -858,13 +1417,56 @@ pub fn _geoToFaceIjk(g: &LatLng, res: i32) -> FaceIJK {
 
     // then convert to ijk+
     _hex2dToCoordIJK(v, &mut h.coord);
-    return h;
+    h
+}
+
+/**
+ * Encodes a coordinate on the sphere to the FaceIJK address of the containing
+ * cell at the specified resolution, reusing the face of a nearby point.
+ *
+ * Equivalent to _geoToFaceIjk, but cheaper when consecutive calls are for
+ * nearby points: the hint face is only checked against its three neighbors
+ * instead of all twenty faces.
+ *
+ * @param g The spherical coordinates to encode.
+ * @param v3d The 3D coordinate of g on the unit sphere.
+ * @param res The desired H3 resolution for the encoding.
+ * @param hint The face of a nearby point, or INVALID_FACE. Updated to the face
+ *             of g.
+ * @return The FaceIJK address of the containing cell.
+ */
+pub fn _geoToFaceIjkWithHint(g: &LatLng, v3d: &Vec3d, res: i32, hint: &mut i32) -> FaceIJK {
+    let mut sqd: f64 = 0.0;
+    if !_vec3dOnFace(v3d, *hint, &mut sqd) {
+        _geoVec3dToClosestFace(g, v3d, hint, &mut sqd);
+    }
+
+    let mut h: FaceIJK = FaceIJK {
+        face: *hint,
+        coord: CoordIJK { i: 0, j: 0, k: 0 },
+    };
+    let mut v: Vec2d = Vec2d { x: 0.0, y: 0.0 };
+    _geoToHex2dOnFace(g, res, h.face, sqd, &mut v);
+    _hex2dToCoordIJK(v, &mut h.coord);
+    h
 }
 
 fn _geoToHex2d(g: &LatLng, res: i32, face: &mut i32, v: &mut Vec2d) {
     let mut sqd: f64 = 0.0;
     _geoToClosestFace(g, face, &mut sqd);
+    _geoToHex2dOnFace(g, res, *face, sqd, v);
+}
 
+/**
+ * Encodes a coordinate on the sphere to the hex2d coordinates on a known face.
+ *
+ * @param g The spherical coordinates to encode.
+ * @param res The desired H3 resolution for the encoding.
+ * @param face The icosahedral face containing the spherical coordinates.
+ * @param sqd The squared euclidean distance to the face center.
+ * @param v The 2D hex coordinates of the cell containing the point.
+ */
+fn _geoToHex2dOnFace(g: &LatLng, res: i32, face: i32, sqd: f64, v: &mut Vec2d) {
     // cos(r) = 1 - 2 * sin^2(r/2) = 1 - 2 * (sqd / 4) = 1 - sqd/2
     let mut r: f64 = (1.0 - sqd / 2.0).acos();
 
@@ -876,8 +1478,8 @@ fn _geoToHex2d(g: &LatLng, res: i32, face: &mut i32, v: &mut Vec2d) {
 
     // now have face and r, now find CCW theta from CII i-axis
     let mut theta: f64 = _posAngleRads(
-        faceAxesAzRadsCII[*face as usize][0]
-            - _posAngleRads(_geoAzimuthRads(&faceCenterGeo[*face as usize], &g)),
+        faceAxesAzRadsCII[face as usize][0]
+            - _posAngleRads(_geoAzimuthRads(&faceCenterGeo[face as usize], g)),
     );
 
     // adjust theta for Class III (odd resolutions)
@@ -924,7 +1526,7 @@ pub fn _hex2dToGeo(v: &Vec2d, face: i32, res: i32, substrate: bool) -> LatLng {
     let mut theta = (v.y).atan2(v.x);
 
     // scale for current resolution length u
-    for i in 0..res {
+    for _i in 0..res {
         r /= M_SQRT7;
     }
 
@@ -951,7 +1553,7 @@ pub fn _hex2dToGeo(v: &Vec2d, face: i32, res: i32, substrate: bool) -> LatLng {
     theta = _posAngleRads(faceAxesAzRadsCII[face as usize][0] - theta);
 
     // now find the point at (r,theta) from the face center
-    return _geoAzDistanceRads(&faceCenterGeo[face as usize], theta, r);
+    _geoAzDistanceRads(&faceCenterGeo[face as usize], theta, r)
 }
 
 /**
@@ -965,7 +1567,7 @@ pub fn _hex2dToGeo(v: &Vec2d, face: i32, res: i32, substrate: bool) -> LatLng {
 pub fn _faceIjkToGeo(h: FaceIJK, res: i32) -> LatLng {
     //let mut v: Vec2d = Vec2d {x: 0, y: 0};
     let v = _ijkToHex2d(&h.coord);
-    return _hex2dToGeo(&v, h.face, res, false);
+    _hex2dToGeo(&v, h.face, res, false)
 }
 
 /**
@@ -976,13 +1578,86 @@ pub fn _faceIjkToGeo(h: FaceIJK, res: i32) -> LatLng {
  * @param face The icosahedral face containing the spherical coordinates.
  * @param sqd The squared euclidean distance to its icosahedral face center.
  */
-fn _geoToClosestFace(g: &LatLng, face: &mut i32, sqd: &mut f64) {
+pub(crate) fn _geoToClosestFace(g: &LatLng, face: &mut i32, sqd: &mut f64) {
     let mut v3d: Vec3d = Vec3d {
         x: 0.0,
         y: 0.0,
         z: 0.0,
     };
     _geoToVec3d(g, &mut v3d);
+    _geoVec3dToClosestFace(g, &v3d, face, sqd);
+}
+
+/**
+ * Determines whether a point is strictly closer to the center of a face than
+ * to the centers of the three faces sharing an edge with it. The icosahedron
+ * face is the region of the sphere closest to its center, and that region is
+ * bounded only by the adjacent faces, so this is enough to know the face
+ * contains the point.
+ *
+ * @param v3d The 3D coordinate of the point on the unit sphere.
+ * @param face The candidate face, or INVALID_FACE.
+ * @param sqd The squared euclidean distance to the face center, set if the
+ *            face contains the point.
+ * @return Whether the face contains the point.
+ */
+fn _vec3dOnFace(v3d: &Vec3d, face: i32, sqd: &mut f64) -> bool {
+    if !(0..NUM_ICOSA_FACES).contains(&face) {
+        return false;
+    }
+    let sqdFace = _pointSquareDist(faceCenterPoint[face as usize], *v3d);
+    for dir in [IJ, KI, JK] {
+        let neighbor = faceNeighbors[face as usize][dir].face;
+        if _pointSquareDist(faceCenterPoint[neighbor as usize], *v3d) <= sqdFace {
+            return false;
+        }
+    }
+    *sqd = sqdFace;
+    true
+}
+
+/**
+ * Determines the icosahedral face closest to a point, and the squared
+ * euclidean distance to that face center. Only the candidate faces for the
+ * point's latitude and longitude band are checked, unless the coordinates
+ * are outside the usual ranges.
+ *
+ * @param g The spherical coordinates of the point.
+ * @param v3d The 3D coordinate of the point on the unit sphere.
+ * @param face The icosahedral face containing the point.
+ * @param sqd The squared euclidean distance to its icosahedral face center.
+ */
+pub(crate) fn _geoVec3dToClosestFace(g: &LatLng, v3d: &Vec3d, face: &mut i32, sqd: &mut f64) {
+    if !(g.lat >= -FRAC_PI_2 && g.lat <= FRAC_PI_2 && g.lng >= -PI && g.lng <= PI) {
+        _vec3dToClosestFace(v3d, face, sqd);
+        return;
+    }
+    let latBand = (((g.lat + FRAC_PI_2) * (CLOSEST_FACE_LAT_BANDS as f64 / PI)) as usize)
+        .min(CLOSEST_FACE_LAT_BANDS - 1);
+    let lngBand = (((g.lng + PI) * (CLOSEST_FACE_LNG_BANDS as f64 / M_2PI)) as usize)
+        .min(CLOSEST_FACE_LNG_BANDS - 1);
+
+    *face = 0;
+    *sqd = 5.0f64;
+    for &f in &closestFaceCandidates[latBand * CLOSEST_FACE_LNG_BANDS + lngBand] {
+        let sqdT: f64 = _pointSquareDist(faceCenterPoint[f as usize], *v3d);
+        if sqdT < *sqd {
+            *face = f as i32;
+            *sqd = sqdT;
+        }
+    }
+}
+
+/**
+ * Determines the icosahedral face closest to a point on the unit sphere, and
+ * the squared euclidean distance to that face center.
+ *
+ * @param v3d The 3D coordinate of the point on the unit sphere.
+ * @param face The icosahedral face containing the point.
+ * @param sqd The squared euclidean distance to its icosahedral face center.
+ */
+pub(crate) fn _vec3dToClosestFace(v3d: &Vec3d, face: &mut i32, sqd: &mut f64) {
+    let v3d = *v3d;
 
     // determine the icosahedron face
     *face = 0;
@@ -1035,15 +1710,12 @@ pub fn _adjustOverageClassII(
 
         overage = Overage::NewFace;
 
-        let mut fijkOrient: &FaceOrientIJK = &faceNeighbors[0][0];
-
-        if ijk.k > 0 {
+        let fijkOrient: &FaceOrientIJK = if ijk.k > 0 {
             if ijk.j > 0 {
                 // jk "quadrant"
-                fijkOrient = &faceNeighbors[fijk.face as usize][JK];
+                &faceNeighbors[fijk.face as usize][JK]
             } else {
                 // ik "quadrant"
-                fijkOrient = &faceNeighbors[fijk.face as usize][KI];
 
                 // adjust for the pentagonal missing sequence
                 if pentLeading4 {
@@ -1057,16 +1729,17 @@ pub fn _adjustOverageClassII(
                     // translate the origin back to the center of the triangle
                     _ijkAdd(tmp, origin, ijk);
                 }
+                &faceNeighbors[fijk.face as usize][KI]
             }
         } else {
             // ij "quadrant"
-            fijkOrient = &faceNeighbors[fijk.face as usize][IJ];
-        }
+            &faceNeighbors[fijk.face as usize][IJ]
+        };
 
         fijk.face = fijkOrient.face;
 
         // rotate and translate for adjacent face
-        for i in 0..fijkOrient.ccwRot60 {
+        for _i in 0..fijkOrient.ccwRot60 {
             _ijkRotate60ccw(ijk);
         }
 
@@ -1086,5 +1759,465 @@ pub fn _adjustOverageClassII(
         }
     }
 
-    return overage;
+    overage
+}
+
+/**
+ * Returns the direction of an adjacent face relative to a given face, as an
+ * index into the faceNeighbors table.
+ *
+ * @param f The origin face.
+ * @param g The adjacent face.
+ * @return 0 if the faces are the same, IJ, KI or JK if g is adjacent to f, or
+ *         -1 if the faces are not adjacent.
+ */
+fn _adjacentFaceDir(f: i32, g: i32) -> i32 {
+    if f == g {
+        return 0;
+    }
+    for dir in [IJ, KI, JK] {
+        if faceNeighbors[f as usize][dir].face == g {
+            return dir as i32;
+        }
+    }
+    -1
+}
+
+/**
+ * Adjusts a FaceIJK address for a pentagon vertex in a substrate grid in
+ * place so that the resulting cell address is relative to the correct
+ * icosahedral face.
+ *
+ * @param fijk The FaceIJK address of the cell.
+ * @param res The H3 resolution of the cell.
+ */
+pub fn _adjustPentVertOverage(fijk: &mut FaceIJK, res: i32) -> Overage {
+    let pentLeading4 = false;
+    let mut overage;
+    loop {
+        overage = _adjustOverageClassII(fijk, res, pentLeading4, true);
+        if overage != Overage::NewFace {
+            break;
+        }
+    }
+    overage
+}
+
+/**
+ * Moves a FaceIJK address into the substrate grid used for vertices and
+ * writes the substrate coordinates of the requested vertices.
+ *
+ * @param fijk The FaceIJK address of the cell; converted to substrate
+ *        coordinates in place.
+ * @param res The H3 resolution of the cell; incremented in place for Class III.
+ * @param fijkVerts Output array for the vertices.
+ */
+fn _faceIjkToSubstrateVerts(fijk: &mut FaceIJK, res: &mut i32, fijkVerts: &mut [FaceIJK]) {
+    // the vertexes of an origin-centered cell in a Class II resolution on a
+    // substrate grid with aperture sequence 33r. The aperture 3 gets us the
+    // vertices, and the 3r gets us back to Class II.
+    // vertices listed ccw from the i-axes
+    const vertsCII: [CoordIJK; NUM_HEX_VERTS as usize] = [
+        CoordIJK { i: 2, j: 1, k: 0 }, // 0
+        CoordIJK { i: 1, j: 2, k: 0 }, // 1
+        CoordIJK { i: 0, j: 2, k: 1 }, // 2
+        CoordIJK { i: 0, j: 1, k: 2 }, // 3
+        CoordIJK { i: 1, j: 0, k: 2 }, // 4
+        CoordIJK { i: 2, j: 0, k: 1 }, // 5
+    ];
+
+    // the vertexes of an origin-centered cell in a Class III resolution on a
+    // substrate grid with aperture sequence 33r7r. The aperture 3 gets us the
+    // vertices, and the 3r7r gets us to Class II.
+    // vertices listed ccw from the i-axes
+    const vertsCIII: [CoordIJK; NUM_HEX_VERTS as usize] = [
+        CoordIJK { i: 5, j: 4, k: 0 }, // 0
+        CoordIJK { i: 1, j: 5, k: 0 }, // 1
+        CoordIJK { i: 0, j: 5, k: 4 }, // 2
+        CoordIJK { i: 0, j: 1, k: 5 }, // 3
+        CoordIJK { i: 4, j: 0, k: 5 }, // 4
+        CoordIJK { i: 5, j: 0, k: 1 }, // 5
+    ];
+
+    // get the correct set of substrate vertices for this resolution
+    let verts = if isResolutionClassIII(*res) {
+        &vertsCIII
+    } else {
+        &vertsCII
+    };
+
+    // adjust the center point to be in an aperture 33r substrate grid
+    // these should be composed for speed
+    _downAp3(&mut fijk.coord);
+    _downAp3r(&mut fijk.coord);
+
+    // if res is Class III we need to add a cw aperture 7 to get to
+    // icosahedral Class II
+    if isResolutionClassIII(*res) {
+        _downAp7r(&mut fijk.coord);
+        *res += 1;
+    }
+
+    // The center point is now in the same substrate grid as the origin
+    // cell vertices. Add the center point substate coordinates
+    // to each vertex to translate the vertices to that cell.
+    for (v, vert) in fijkVerts.iter_mut().enumerate() {
+        vert.face = fijk.face;
+        _ijkAdd(fijk.coord, verts[v], &mut vert.coord);
+        _ijkNormalize(&mut vert.coord);
+    }
+}
+
+/**
+ * Get the vertices of a cell as substrate FaceIJK addresses
+ *
+ * @param fijk The FaceIJK address of the cell.
+ * @param res The H3 resolution of the cell. This may be adjusted if
+ *            necessary for the substrate grid resolution.
+ * @param fijkVerts Output array for the vertices
+ */
+pub fn _faceIjkToVerts(
+    fijk: &mut FaceIJK,
+    res: &mut i32,
+    fijkVerts: &mut [FaceIJK; NUM_HEX_VERTS as usize],
+) {
+    _faceIjkToSubstrateVerts(fijk, res, fijkVerts);
+}
+
+/**
+ * Get the vertices of a pentagon cell as substrate FaceIJK addresses
+ *
+ * @param fijk The FaceIJK address of the cell.
+ * @param res The H3 resolution of the cell. This may be adjusted if
+ *            necessary for the substrate grid resolution.
+ * @param fijkVerts Output array for the vertices
+ */
+pub fn _faceIjkPentToVerts(
+    fijk: &mut FaceIJK,
+    res: &mut i32,
+    fijkVerts: &mut [FaceIJK; NUM_PENT_VERTS as usize],
+) {
+    // the pentagon vertices are the first five hexagon vertices
+    _faceIjkToSubstrateVerts(fijk, res, fijkVerts);
+}
+
+/**
+ * Returns the endpoints of the icosahedron face edge in the given direction,
+ * in the hex2d coordinates of a substrate grid.
+ *
+ * @param res The substrate grid resolution.
+ * @param dir The edge direction, IJ, KI or JK.
+ */
+fn _faceEdgeEndpoints(res: i32, dir: i32) -> (Vec2d, Vec2d) {
+    // find the appropriate icosa face edge vertexes
+    let maxDim = maxDimByCIIres[res as usize] as f64;
+    let v0 = Vec2d {
+        x: 3.0 * maxDim,
+        y: 0.0,
+    };
+    let v1 = Vec2d {
+        x: -1.5 * maxDim,
+        y: 3.0 * M_SQRT3_2 * maxDim,
+    };
+    let v2 = Vec2d {
+        x: -1.5 * maxDim,
+        y: -3.0 * M_SQRT3_2 * maxDim,
+    };
+
+    if dir == IJ as i32 {
+        return (v0, v1);
+    } else if dir == JK as i32 {
+        return (v1, v2);
+    }
+    // KI
+    (v2, v0)
+}
+
+/**
+ * Determines the vertexes of an icosahedron face, which are the centers of
+ * the pentagons at its corners.
+ *
+ * @param face The icosahedron face.
+ * @return The vertexes, counterclockwise.
+ */
+pub(crate) fn _faceVertexes(face: i32) -> [LatLng; 3] {
+    let (v0, v1) = _faceEdgeEndpoints(0, IJ as i32);
+    let (_, v2) = _faceEdgeEndpoints(0, JK as i32);
+    [
+        _hex2dToGeo(&v0, face, 0, true),
+        _hex2dToGeo(&v1, face, 0, true),
+        _hex2dToGeo(&v2, face, 0, true),
+    ]
+}
+
+/**
+ * Generates the cell boundary in spherical coordinates for a cell given by a
+ * FaceIJK address at a specified resolution.
+ *
+ * @param h The FaceIJK address of the cell.
+ * @param res The H3 resolution of the cell.
+ * @param start The first topological vertex to return.
+ * @param length The number of topological vertexes to return.
+ * @param g The spherical coordinates of the cell boundary.
+ */
+pub fn _faceIjkToCellBoundary(
+    h: &FaceIJK,
+    res: i32,
+    start: i32,
+    length: i32,
+    g: &mut CellBoundary,
+) {
+    let mut adjRes = res;
+    let mut centerIJK: FaceIJK = *h;
+    let mut fijkVerts: [FaceIJK; NUM_HEX_VERTS as usize] = [*h; NUM_HEX_VERTS as usize];
+    _faceIjkToVerts(&mut centerIJK, &mut adjRes, &mut fijkVerts);
+
+    // If we're returning the entire loop, we need one more iteration in case
+    // of a distortion vertex on the last edge
+    let additionalIteration = if length == NUM_HEX_VERTS { 1 } else { 0 };
+
+    // convert each vertex to lat/lng
+    // adjust the face of each vertex as appropriate and introduce
+    // edge-crossing vertices as needed
+    g.numVerts = 0;
+    let mut lastFace = -1;
+    let mut lastOverage = Overage::NoOverage;
+    for vert in start..(start + length + additionalIteration) {
+        let v = (vert % NUM_HEX_VERTS) as usize;
+
+        let mut fijk: FaceIJK = fijkVerts[v];
+
+        let pentLeading4 = false;
+        let overage = _adjustOverageClassII(&mut fijk, adjRes, pentLeading4, true);
+
+        /*
+        Check for edge-crossing. Each face of the underlying icosahedron is a
+        different projection plane. So if an edge of the hexagon crosses an
+        icosahedron edge, an additional vertex must be introduced at that
+        intersection point. Then each half of the cell edge can be projected
+        to geographic coordinates using the appropriate icosahedron face
+        projection. Note that Class II cell edges have vertices on the face
+        edge, with no edge line intersections.
+        */
+        if isResolutionClassIII(res)
+            && vert > start
+            && fijk.face != lastFace
+            && lastOverage != Overage::FaceEdge
+        {
+            // find hex2d of the two vertexes on original face
+            let lastV = (v + 5) % NUM_HEX_VERTS as usize;
+            let orig2d0 = _ijkToHex2d(&fijkVerts[lastV].coord);
+            let orig2d1 = _ijkToHex2d(&fijkVerts[v].coord);
+
+            let face2 = if lastFace == centerIJK.face {
+                fijk.face
+            } else {
+                lastFace
+            };
+            let (edge0, edge1) =
+                _faceEdgeEndpoints(adjRes, _adjacentFaceDir(centerIJK.face, face2));
+
+            // find the intersection and add the lat/lng point to the result
+            let inter = _v2dIntersect(&orig2d0, &orig2d1, &edge0, &edge1);
+            /*
+            If a point of intersection occurs at a hexagon vertex, then each
+            adjacent hexagon edge will lie completely on a single icosahedron
+            face, and no additional vertex is required.
+            */
+            let isIntersectionAtVertex =
+                _v2dAlmostEquals(&orig2d0, &inter) || _v2dAlmostEquals(&orig2d1, &inter);
+            if !isIntersectionAtVertex {
+                g.verts[g.numVerts] = _hex2dToGeo(&inter, centerIJK.face, adjRes, true);
+                g.numVerts += 1;
+            }
+        }
+
+        // convert vertex to lat/lng and add to the result
+        // vert == start + NUM_HEX_VERTS is only used to test for possible
+        // intersection on last edge
+        if vert < start + NUM_HEX_VERTS {
+            let vec = _ijkToHex2d(&fijk.coord);
+            g.verts[g.numVerts] = _hex2dToGeo(&vec, fijk.face, adjRes, true);
+            g.numVerts += 1;
+        }
+
+        lastFace = fijk.face;
+        lastOverage = overage;
+    }
+}
+
+/**
+ * Generates the cell boundary in spherical coordinates for a pentagonal cell
+ * given by a FaceIJK address at a specified resolution.
+ *
+ * @param h The FaceIJK address of the pentagonal cell.
+ * @param res The H3 resolution of the cell.
+ * @param start The first topological vertex to return.
+ * @param length The number of topological vertexes to return.
+ * @param g The spherical coordinates of the cell boundary.
+ */
+pub fn _faceIjkPentToCellBoundary(
+    h: &FaceIJK,
+    res: i32,
+    start: i32,
+    length: i32,
+    g: &mut CellBoundary,
+) {
+    let mut adjRes = res;
+    let mut centerIJK: FaceIJK = *h;
+    let mut fijkVerts: [FaceIJK; NUM_PENT_VERTS as usize] = [*h; NUM_PENT_VERTS as usize];
+    _faceIjkPentToVerts(&mut centerIJK, &mut adjRes, &mut fijkVerts);
+
+    // If we're returning the entire loop, we need one more iteration in case
+    // of a distortion vertex on the last edge
+    let additionalIteration = if length == NUM_PENT_VERTS { 1 } else { 0 };
+
+    // convert each vertex to lat/lng
+    // adjust the face of each vertex as appropriate and introduce
+    // edge-crossing vertices as needed
+    g.numVerts = 0;
+    let mut lastFijk: FaceIJK = *h;
+    for vert in start..(start + length + additionalIteration) {
+        let v = (vert % NUM_PENT_VERTS) as usize;
+
+        let mut fijk: FaceIJK = fijkVerts[v];
+
+        _adjustPentVertOverage(&mut fijk, adjRes);
+
+        // all Class III pentagon edges cross icosa edges
+        // note that Class II pentagons have vertices on the edge,
+        // not edge intersections
+        if isResolutionClassIII(res) && vert > start {
+            // find hex2d of the two vertexes on the last face
+
+            let mut tmpFijk: FaceIJK = fijk;
+
+            let orig2d0 = _ijkToHex2d(&lastFijk.coord);
+
+            let currentToLastDir = _adjacentFaceDir(tmpFijk.face, lastFijk.face);
+
+            let fijkOrient: &FaceOrientIJK =
+                &faceNeighbors[tmpFijk.face as usize][currentToLastDir as usize];
+
+            tmpFijk.face = fijkOrient.face;
+            let ijk: &mut CoordIJK = &mut tmpFijk.coord;
+
+            // rotate and translate for adjacent face
+            for _i in 0..fijkOrient.ccwRot60 {
+                _ijkRotate60ccw(ijk);
+            }
+
+            let mut transVec: CoordIJK = fijkOrient.translate;
+            _ijkScale(&mut transVec, unitScaleByCIIres[adjRes as usize] * 3);
+            _ijkAdd(*ijk, transVec, ijk);
+            _ijkNormalize(ijk);
+
+            let orig2d1 = _ijkToHex2d(ijk);
+
+            let (edge0, edge1) =
+                _faceEdgeEndpoints(adjRes, _adjacentFaceDir(tmpFijk.face, fijk.face));
+
+            // find the intersection and add the lat/lng point to the result
+            let inter = _v2dIntersect(&orig2d0, &orig2d1, &edge0, &edge1);
+            g.verts[g.numVerts] = _hex2dToGeo(&inter, tmpFijk.face, adjRes, true);
+            g.numVerts += 1;
+        }
+
+        // convert vertex to lat/lng and add to the result
+        // vert == start + NUM_PENT_VERTS is only used to test for possible
+        // intersection on last edge
+        if vert < start + NUM_PENT_VERTS {
+            let vec = _ijkToHex2d(&fijk.coord);
+            g.verts[g.numVerts] = _hex2dToGeo(&vec, fijk.face, adjRes, true);
+            g.numVerts += 1;
+        }
+
+        lastFijk = fijk;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench_utils::randomLatLngs;
+    use crate::lat_lng::greatCircleDistanceRads;
+
+    use super::*;
+
+    #[test]
+    fn closestFaceCandidatesRebuild() {
+        for latBand in 0..CLOSEST_FACE_LAT_BANDS {
+            for lngBand in 0..CLOSEST_FACE_LNG_BANDS {
+                let south = -FRAC_PI_2 + PI * latBand as f64 / CLOSEST_FACE_LAT_BANDS as f64;
+                let north = -FRAC_PI_2 + PI * (latBand + 1) as f64 / CLOSEST_FACE_LAT_BANDS as f64;
+                let west = -PI + M_2PI * lngBand as f64 / CLOSEST_FACE_LNG_BANDS as f64;
+                let east = -PI + M_2PI * (lngBand + 1) as f64 / CLOSEST_FACE_LNG_BANDS as f64;
+                let center = LatLng {
+                    lat: (south + north) / 2.0,
+                    lng: (west + east) / 2.0,
+                };
+                let radius = [(south, west), (south, east), (north, west), (north, east)]
+                    .iter()
+                    .map(|&(lat, lng)| greatCircleDistanceRads(&center, &LatLng { lat, lng }))
+                    .fold(0.0, f64::max);
+                let dists: Vec<f64> = faceCenterGeo
+                    .iter()
+                    .map(|c| greatCircleDistanceRads(&center, c))
+                    .collect();
+                let closest = dists.iter().copied().fold(f64::MAX, f64::min);
+                let faces: Vec<u8> = (0..NUM_ICOSA_FACES as u8)
+                    .filter(|&f| dists[f as usize] <= closest + 2.0 * radius)
+                    .collect();
+                assert!(faces.len() <= 5, "at most five candidates");
+
+                let mut expected = [0u8; 5];
+                for (i, face) in expected.iter_mut().enumerate() {
+                    *face = faces[i.min(faces.len() - 1)];
+                }
+                assert_eq!(
+                    closestFaceCandidates[latBand * CLOSEST_FACE_LNG_BANDS + lngBand],
+                    expected,
+                    "candidates for band {} {}",
+                    latBand,
+                    lngBand
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn closestFaceMatchesScan() {
+        let mut points = randomLatLngs(1, 100_000);
+        // points on and around the band boundaries, including the poles and
+        // the antimeridian
+        let latSteps = 4 * CLOSEST_FACE_LAT_BANDS as i32;
+        let lngSteps = 4 * CLOSEST_FACE_LNG_BANDS as i32;
+        for i in 0..=latSteps {
+            for j in 0..=lngSteps {
+                for eps in [-1e-12, 0.0, 1e-12] {
+                    points.push(LatLng {
+                        lat: (-FRAC_PI_2 + PI * i as f64 / latSteps as f64 + eps)
+                            .clamp(-FRAC_PI_2, FRAC_PI_2),
+                        lng: (-PI + M_2PI * j as f64 / lngSteps as f64 + eps).clamp(-PI, PI),
+                    });
+                }
+            }
+        }
+        // outside the usual ranges
+        points.push(LatLng { lat: 0.5, lng: 7.0 });
+        points.push(LatLng { lat: 2.0, lng: 0.5 });
+
+        for g in &points {
+            let mut v3d = Vec3d {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+            _geoToVec3d(g, &mut v3d);
+            let (mut face, mut sqd) = (0, 0.0);
+            _geoVec3dToClosestFace(g, &v3d, &mut face, &mut sqd);
+            let (mut scanFace, mut scanSqd) = (0, 0.0);
+            _vec3dToClosestFace(&v3d, &mut scanFace, &mut scanSqd);
+            assert_eq!((face, sqd), (scanFace, scanSqd), "same face at {:?}", g);
+        }
+    }
 }
@@ -0,0 +1,282 @@
+// GeoJSON geometry input for polygonToCells. A Polygon or MultiPolygon is
+// taken either as a geojson::Geometry or as coordinates already parsed with
+// whichever JSON library the caller uses, such as serde_json into
+// Vec<Vec<[f64; 2]>>. Positions are longitude then latitude, in degrees, and
+// rings repeat their first vertex.
+
+use ::geojson::{Geometry, GeometryValue, Position};
+
+use crate::constants::M_PI_180;
+use crate::error::Error;
+use crate::h3_index::H3Index;
+use crate::lat_lng::LatLng;
+use crate::polygon::{GeoLoop, GeoMultiPolygon, GeoPolygon, multiPolygonToCells};
+use crate::prelude::*;
+
+/**
+ * Converts a GeoJSON linear ring to a loop, dropping the repeated closing
+ * vertex.
+ *
+ * @param ring The [lng, lat] positions, in degrees.
+ * @return The loop, in radians, or LatLngDomain if a position is not finite.
+ */
+fn _ringToGeoLoop(ring: &[[f64; 2]]) -> Result<GeoLoop, Error> {
+    let mut verts: Vec<LatLng> = Vec::with_capacity(ring.len());
+    for &[lng, lat] in ring {
+        if !lat.is_finite() || !lng.is_finite() {
+            return Err(Error::LatLngDomain);
+        }
+        verts.push(LatLng {
+            lat: lat * M_PI_180,
+            lng: lng * M_PI_180,
+        });
+    }
+    if verts.len() > 1 && verts.first() == verts.last() {
+        verts.pop();
+    }
+    Ok(GeoLoop { verts })
+}
+
+/**
+ * Converts the coordinates of a GeoJSON Polygon, an outer ring followed by
+ * holes. The rings may wind either way, since as in the GeoJSON
+ * specification the first ring is the outer one.
+ *
+ * @param rings The rings of [lng, lat] positions, in degrees.
+ * @return The polygon, in radians, Failed if there is no outer ring, or
+ *         LatLngDomain if a position is not finite.
+ */
+pub fn geoJsonToPolygon(rings: &[Vec<[f64; 2]>]) -> Result<GeoPolygon, Error> {
+    let (outer, holes) = rings.split_first().ok_or(Error::Failed)?;
+    Ok(GeoPolygon {
+        geoloop: _ringToGeoLoop(outer)?,
+        holes: holes
+            .iter()
+            .map(|hole| _ringToGeoLoop(hole))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/**
+ * Converts the coordinates of a GeoJSON MultiPolygon. The coordinates of a
+ * Polygon can be passed as a MultiPolygon of one, with
+ * `core::slice::from_ref`.
+ *
+ * @param polygons The rings of each polygon, as for geoJsonToPolygon.
+ * @return The polygons, in radians, or the errors of geoJsonToPolygon.
+ */
+pub fn geoJsonToMultiPolygon(polygons: &[Vec<Vec<[f64; 2]>>]) -> Result<GeoMultiPolygon, Error> {
+    Ok(GeoMultiPolygon {
+        polygons: polygons
+            .iter()
+            .map(|rings| geoJsonToPolygon(rings))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/**
+ * Finds the cells whose centers are inside the polygons of a GeoJSON
+ * MultiPolygon, as multiPolygonToCells does.
+ *
+ * @param polygons The rings of each polygon, as for geoJsonToPolygon.
+ * @param res The resolution of the cells.
+ * @return The cells in ascending order without duplicates, or the errors of
+ *         geoJsonToMultiPolygon and multiPolygonToCells.
+ */
+pub fn geoJsonToCells(polygons: &[Vec<Vec<[f64; 2]>>], res: i32) -> Result<Vec<H3Index>, Error> {
+    multiPolygonToCells(&geoJsonToMultiPolygon(polygons)?, res)
+}
+
+/**
+ * Converts the rings of a geojson Polygon to [lng, lat] coordinates.
+ *
+ * @param rings The rings of positions.
+ * @return The rings, or LatLngDomain if a position has fewer than two values.
+ */
+fn _positionsToRings(rings: &[Vec<Position>]) -> Result<Vec<Vec<[f64; 2]>>, Error> {
+    rings
+        .iter()
+        .map(|ring| {
+            ring.iter()
+                .map(|position| match position.as_slice() {
+                    [lng, lat, ..] => Ok([*lng, *lat]),
+                    _ => Err(Error::LatLngDomain),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/**
+ * Converts a geojson Polygon or MultiPolygon geometry. A Polygon becomes a
+ * MultiPolygon of one.
+ *
+ * @param geometry The geometry.
+ * @return The polygons, in radians, Domain if the geometry is not a Polygon
+ *         or MultiPolygon, or the errors of geoJsonToPolygon.
+ */
+pub fn geometryToMultiPolygon(geometry: &Geometry) -> Result<GeoMultiPolygon, Error> {
+    match &geometry.value {
+        GeometryValue::Polygon { coordinates } => {
+            geoJsonToMultiPolygon(&[_positionsToRings(coordinates)?])
+        }
+        GeometryValue::MultiPolygon { coordinates } => geoJsonToMultiPolygon(
+            &coordinates
+                .iter()
+                .map(|rings| _positionsToRings(rings))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        _ => Err(Error::Domain),
+    }
+}
+
+/**
+ * Finds the cells whose centers are inside a geojson Polygon or MultiPolygon
+ * geometry, as multiPolygonToCells does.
+ *
+ * @param geometry The geometry.
+ * @param res The resolution of the cells.
+ * @return The cells in ascending order without duplicates, or the errors of
+ *         geometryToMultiPolygon and multiPolygonToCells.
+ */
+pub fn geometryToCells(geometry: &Geometry, res: i32) -> Result<Vec<H3Index>, Error> {
+    multiPolygonToCells(&geometryToMultiPolygon(geometry)?, res)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell_set::CellSet;
+    use crate::polygon::polygonToCells;
+
+    use super::*;
+
+    fn sf() -> Vec<Vec<[f64; 2]>> {
+        vec![vec![
+            [-122.408_986_699_997_21, 37.813_318_999_983_24],
+            [-122.380_543_610_699_93, 37.786_630_200_000_72],
+            [-122.354_473_699_999_36, 37.719_806_199_997_85],
+            [-122.512_343_699_998_4, 37.707_613_199_997_57],
+            [-122.524_718_700_002_2, 37.783_587_199_997_17],
+            [-122.479_876_700_000_9, 37.815_157_199_999_845],
+            [-122.408_986_699_997_21, 37.813_318_999_983_24],
+        ]]
+    }
+
+    #[test]
+    fn geoJsonPolygon() {
+        let polygon = geoJsonToPolygon(&sf()).unwrap();
+        assert_eq!(polygon.geoloop.verts.len(), 6, "closing vertex dropped");
+        assert!(
+            (polygon.geoloop.verts[0].lat - 37.813_318_999_983_24 * M_PI_180).abs() < 1e-15,
+            "latitude second, in radians"
+        );
+
+        let cells = geoJsonToCells(core::slice::from_ref(&sf()), 9).unwrap();
+        let mut expected = polygonToCells(&polygon, 9).unwrap();
+        expected.sort();
+        assert_eq!(cells, expected, "same cells as polygonToCells");
+        assert_eq!(cells.len(), 1253, "cells in the SF polygon");
+    }
+
+    #[test]
+    fn geoJsonMultiPolygonWithHole() {
+        // two squares, the first with a hole, wound opposite ways
+        let coordinates = vec![
+            vec![
+                vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]],
+                vec![
+                    [0.25, 0.25],
+                    [0.75, 0.25],
+                    [0.75, 0.75],
+                    [0.25, 0.75],
+                    [0.25, 0.25],
+                ],
+            ],
+            vec![vec![
+                [2.0, 0.0],
+                [3.0, 0.0],
+                [3.0, 1.0],
+                [2.0, 1.0],
+                [2.0, 0.0],
+            ]],
+        ];
+        let multiPolygon = geoJsonToMultiPolygon(&coordinates).unwrap();
+        assert_eq!(multiPolygon.polygons.len(), 2, "two polygons");
+        assert_eq!(multiPolygon.polygons[0].holes.len(), 1, "first has a hole");
+
+        let cells = geoJsonToCells(&coordinates, 6).unwrap();
+        let mut expected = CellSet::new();
+        for polygon in &multiPolygon.polygons {
+            expected.extend(polygonToCells(polygon, 6).unwrap());
+        }
+        assert_eq!(cells, expected.into_iter().collect::<Vec<H3Index>>());
+        let withoutHole = GeoPolygon {
+            geoloop: multiPolygon.polygons[0].geoloop.clone(),
+            holes: vec![],
+        };
+        assert!(
+            polygonToCells(&withoutHole, 6).unwrap().len()
+                > polygonToCells(&multiPolygon.polygons[0], 6).unwrap().len(),
+            "the hole removes cells"
+        );
+    }
+
+    #[test]
+    fn geoJsonGeometry() {
+        let polygon = Geometry::new_polygon(sf());
+        assert_eq!(
+            geometryToCells(&polygon, 9),
+            geoJsonToCells(&[sf()], 9),
+            "same cells as the coordinates"
+        );
+
+        let multiPolygon: Geometry = r#"{
+            "type": "MultiPolygon",
+            "coordinates": [
+                [[[0, 0], [0, 1], [1, 1], [1, 0], [0, 0]]],
+                [[[2, 0], [3, 0], [3, 1], [2, 1], [2, 0]]]
+            ]
+        }"#
+        .parse()
+        .unwrap();
+        let converted = geometryToMultiPolygon(&multiPolygon).unwrap();
+        assert_eq!(converted.polygons.len(), 2, "two polygons");
+        assert_eq!(
+            geometryToCells(&multiPolygon, 6),
+            multiPolygonToCells(&converted, 6),
+            "same cells as multiPolygonToCells"
+        );
+
+        assert_eq!(
+            geometryToCells(&Geometry::new_point([0.0, 0.0]), 6),
+            Err(Error::Domain),
+            "not a polygon"
+        );
+        assert_eq!(
+            geometryToCells(&Geometry::new_polygon([vec![vec![0.0], vec![1.0]]]), 6),
+            Err(Error::LatLngDomain),
+            "position without a latitude"
+        );
+    }
+
+    #[test]
+    fn geoJsonInvalid() {
+        assert_eq!(geoJsonToPolygon(&[]), Err(Error::Failed), "no outer ring");
+        assert_eq!(
+            geoJsonToMultiPolygon(&[vec![]]),
+            Err(Error::Failed),
+            "polygon without an outer ring"
+        );
+        assert_eq!(
+            geoJsonToPolygon(&[vec![[0.0, f64::NAN], [0.0, 1.0], [1.0, 0.0]]]),
+            Err(Error::LatLngDomain),
+            "bad position"
+        );
+        assert_eq!(
+            geoJsonToCells(&[sf()], 16),
+            Err(Error::ResDomain),
+            "invalid resolution"
+        );
+        assert_eq!(geoJsonToCells(&[], 5), Ok(vec![]), "empty multipolygon");
+    }
+}
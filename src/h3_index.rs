@@ -1,3 +1,10 @@
+use alloc::collections::BTreeMap;
+use core::f64::consts::{FRAC_PI_2, PI};
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
 use enum_primitive::FromPrimitive;
 use num::pow;
 
@@ -5,14 +12,30 @@ use crate::base_cells::{
     _baseCellIsCwOffset, _faceIjkToBaseCell, _faceIjkToBaseCellCCWrot60, _isBaseCellPentagon,
     baseCellData, MAX_FACE_COORD,
 };
+use crate::bbox::{BBox, bboxHeightRads, bboxWidthRads};
 use crate::coord_ijk::{
     CoordIJK, Direction, _downAp7, _downAp7r, _ijkNormalize, _ijkSub, _neighbor, _rotate60ccw,
     _rotate60cw, _unitIjkToDigit, _upAp7, _upAp7r,
 };
+use crate::cell_set::CellSet;
+#[cfg(feature = "coarse-cache")]
+use crate::coarse_cache::{cachedCellToBoundary, cachedCellToLatLng};
+use crate::directed_edge::isValidDirectedEdge;
 use crate::error::Error;
-use crate::face_ijk::{FaceIJK, Overage, _adjustOverageClassII, _faceIjkToGeo, _geoToFaceIjk};
+use crate::face_ijk::{
+    _adjustOverageClassII, _adjustPentVertOverage, _faceIjkPentToCellBoundary, _faceIjkPentToVerts,
+    _faceIjkToCellBoundary, _faceIjkToGeo, _faceIjkToVerts, _geoToFaceIjk, _geoToFaceIjkWithHint,
+    FaceIJK, Overage, INVALID_FACE,
+};
 use crate::iterators::IterCellsChildren;
-use crate::lat_lng::LatLng;
+use crate::lat_lng::{
+    _densifyLoop, _geoAzimuthRads, _posAngleRads, constrainLng, greatCircleDistanceRads,
+    CellBoundary, LatLng,
+};
+use crate::polygon::{GeoLoop, bboxFromGeoLoop};
+use crate::prelude::*;
+use crate::vec3d::{Vec3d, _geoToVec3d, _vec3dCross, _vec3dDot, _vec3dToGeo};
+use crate::vertex::isValidVertex;
 use crate::{constants::*, H3_NULL};
 
 /** H3 index with mode 0, res 0, base cell 0, and 7 for all index digits. */
@@ -82,7 +105,7 @@ pub type H3Index = u64;
  * Gets the highest bit of the H3 index.
  */
 pub fn H3_GET_HIGH_BIT(h3: H3Index) -> i32 {
-    return (((h3) & H3_HIGH_BIT_MASK) >> H3_MAX_OFFSET) as i32;
+    (((h3) & H3_HIGH_BIT_MASK) >> H3_MAX_OFFSET) as i32
 }
 
 /**
@@ -96,7 +119,7 @@ pub fn H3_SET_HIGH_BIT(h3: &mut H3Index, v: i32) {
  * Gets the integer mode of h3.
  */
 pub fn H3_GET_MODE(h3: H3Index) -> i32 {
-    return (((h3) & H3_MODE_MASK) >> H3_MODE_OFFSET) as i32;
+    (((h3) & H3_MODE_MASK) >> H3_MODE_OFFSET) as i32
 }
 
 /**
@@ -110,7 +133,7 @@ pub fn H3_SET_MODE(h3: &mut H3Index, v: i32) {
  * Gets the integer base cell of h3.
  */
 pub fn H3_GET_BASE_CELL(h3: H3Index) -> i32 {
-    return (((h3) & H3_BC_MASK) >> H3_BC_OFFSET) as i32;
+    (((h3) & H3_BC_MASK) >> H3_BC_OFFSET) as i32
 }
 
 /**
@@ -124,7 +147,7 @@ pub fn H3_SET_BASE_CELL(h3: &mut H3Index, bc: i32) {
  * Gets the integer resolution of h3.
  */
 pub fn H3_GET_RESOLUTION(h3: H3Index) -> i32 {
-    return (((h3) & H3_RES_MASK) >> H3_RES_OFFSET) as i32;
+    (((h3) & H3_RES_MASK) >> H3_RES_OFFSET) as i32
 }
 
 /**
@@ -138,10 +161,8 @@ pub fn H3_SET_RESOLUTION(h3: &mut H3Index, res: i32) {
  * Gets the resolution res integer digit (0-7) of h3.
  */
 pub fn H3_GET_INDEX_DIGIT(h3: H3Index, res: i32) -> Direction {
-    return Direction::from_u64(
-        ((h3) >> ((MAX_H3_RES - (res)) * H3_PER_DIGIT_OFFSET)) & H3_DIGIT_MASK,
-    )
-    .unwrap_or(Direction::InvalidDigit);
+    Direction::from_u64(((h3) >> ((MAX_H3_RES - (res)) * H3_PER_DIGIT_OFFSET)) & H3_DIGIT_MASK)
+        .unwrap_or(Direction::InvalidDigit)
 }
 
 /**
@@ -164,7 +185,7 @@ pub fn H3_SET_RESERVED_BITS(h3: &mut H3Index, v: i32) {
  * Gets a value in the reserved space. Should always be zero for valid indexes.
  */
 pub fn H3_GET_RESERVED_BITS(h3: H3Index) -> i32 {
-    return ((h3 & H3_RESERVED_MASK) >> H3_RESERVED_OFFSET) as i32;
+    ((h3 & H3_RESERVED_MASK) >> H3_RESERVED_OFFSET) as i32
 }
 
 /**
@@ -173,7 +194,186 @@ pub fn H3_GET_RESERVED_BITS(h3: H3Index) -> i32 {
  * @return The resolution of the H3 index argument.
  */
 pub fn getResolution(h: H3Index) -> i32 {
-    return H3_GET_RESOLUTION(h);
+    H3_GET_RESOLUTION(h)
+}
+
+/**
+ * Returns the base cell number of an H3 index.
+ * @param h The H3 index.
+ * @return The base cell number of the H3 index argument.
+ */
+pub fn getBaseCellNumber(h: H3Index) -> i32 {
+    H3_GET_BASE_CELL(h)
+}
+
+/**
+ * Converts an H3 index into its canonical string form, the lowercase
+ * hexadecimal digits without any prefix (e.g. "8928308280fffff").
+ *
+ * @param h The H3 index to convert.
+ * @return The string representation of the index.
+ */
+pub fn h3ToString(h: H3Index) -> String {
+    format!("{:x}", h)
+}
+
+/**
+ * Converts a string representation of an H3 index into an H3 index. As with
+ * the C library, surrounding whitespace and a "0x" prefix are accepted and
+ * upper case digits are allowed. The index itself is not validated.
+ *
+ * @param str The string representation of an H3 index.
+ * @return The H3 index, or Failed if the string is not hexadecimal or does
+ *         not fit in 64 bits.
+ */
+pub fn stringToH3(str: &str) -> Result<H3Index, Error> {
+    let trimmed = str.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::Failed);
+    }
+    H3Index::from_str_radix(digits, 16).map_err(|_| Error::Failed)
+}
+
+/**
+ * Packs H3 indexes into bytes, 8 big endian (network order) bytes per index,
+ * for binary files and network protocols.
+ *
+ * @param h3s The H3 indexes.
+ * @return The bytes.
+ */
+pub fn h3ArrayToBeBytes(h3s: &[H3Index]) -> Vec<u8> {
+    h3s.iter().flat_map(|h| h.to_be_bytes()).collect()
+}
+
+/**
+ * Packs H3 indexes into bytes, 8 little endian bytes per index, matching the
+ * in-memory layout of the indexes on most machines.
+ *
+ * @param h3s The H3 indexes.
+ * @return The bytes.
+ */
+pub fn h3ArrayToLeBytes(h3s: &[H3Index]) -> Vec<u8> {
+    h3s.iter().flat_map(|h| h.to_le_bytes()).collect()
+}
+
+/**
+ * Unpacks H3 indexes packed by h3ArrayToBeBytes. The indexes are not
+ * validated.
+ *
+ * @param bytes The bytes, 8 per index.
+ * @return The H3 indexes, or Domain if the length is not a multiple of 8.
+ */
+pub fn beBytesToH3Array(bytes: &[u8]) -> Result<Vec<H3Index>, Error> {
+    if !bytes.len().is_multiple_of(8) {
+        return Err(Error::Domain);
+    }
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|b| H3Index::from_be_bytes(b.try_into().unwrap()))
+        .collect())
+}
+
+/**
+ * Unpacks H3 indexes packed by h3ArrayToLeBytes. The indexes are not
+ * validated.
+ *
+ * @param bytes The bytes, 8 per index.
+ * @return The H3 indexes, or Domain if the length is not a multiple of 8.
+ */
+pub fn leBytesToH3Array(bytes: &[u8]) -> Result<Vec<H3Index>, Error> {
+    if !bytes.len().is_multiple_of(8) {
+        return Err(Error::Domain);
+    }
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|b| H3Index::from_le_bytes(b.try_into().unwrap()))
+        .collect())
+}
+
+/**
+ * An H3 index that prints and parses in its canonical string form.
+ *
+ * H3Index is a plain integer, so it prints in decimal by default; wrap it in
+ * H3Hex to get the representation used by the C library and other bindings.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct H3Hex(pub H3Index);
+
+impl fmt::Display for H3Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl FromStr for H3Hex {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<H3Hex, Error> {
+        Ok(H3Hex(stringToH3(s)?))
+    }
+}
+
+impl From<H3Hex> for H3Index {
+    fn from(h: H3Hex) -> H3Index {
+        h.0
+    }
+}
+
+enum_from_primitive! {
+    /** Kind of object an H3 index refers to, as encoded in its mode bits. */
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum IndexMode {
+        /** H3 cell (hexagon or pentagon) */
+        Cell = 1,
+        /** H3 directed edge */
+        DirectedEdge = 2,
+        /** H3 undirected edge */
+        UndirectedEdge = 3,
+        /** H3 vertex */
+        Vertex = 4,
+    }
+}
+
+/**
+ * Returns the mode of an H3 index without validating the rest of it.
+ * @param h The H3 index.
+ * @return The mode, or None if the mode bits do not name a known mode.
+ */
+pub fn modeOf(h: H3Index) -> Option<IndexMode> {
+    IndexMode::from_i32(H3_GET_MODE(h))
+}
+
+/**
+ * Validates an arbitrary H3 index with the validator for its mode.
+ *
+ * Undirected edges are reserved by the index format but have no
+ * representation yet, so they never validate.
+ *
+ * @param h The H3 index to validate.
+ * @return The mode of the index if it is valid, otherwise the error for the
+ *         mode it claims; Failed if the mode is unknown.
+ */
+pub fn validate(h: H3Index) -> Result<IndexMode, Error> {
+    let mode = modeOf(h).ok_or(Error::Failed)?;
+    let valid = match mode {
+        IndexMode::Cell => isValidCell(h),
+        IndexMode::DirectedEdge => isValidDirectedEdge(h),
+        IndexMode::UndirectedEdge => false,
+        IndexMode::Vertex => isValidVertex(h),
+    };
+    if valid {
+        return Ok(mode);
+    }
+    Err(match mode {
+        IndexMode::Cell => Error::CellInvalid,
+        IndexMode::DirectedEdge => Error::DirectedEdgeInvalid,
+        IndexMode::UndirectedEdge => Error::UndirectedEdgeInvalid,
+        IndexMode::Vertex => Error::VertexInvalid,
+    })
 }
 
 /**
@@ -195,14 +395,14 @@ pub fn isValidCell(h: H3Index) -> bool {
     }
 
     let baseCell = H3_GET_BASE_CELL(h);
-    if baseCell < 0 || baseCell >= NUM_BASE_CELLS {
+    if !(0..NUM_BASE_CELLS).contains(&baseCell) {
         // LCOV_EXCL_BR_LINE
         // Base cells less than zero can not be represented in an index
         return false;
     }
 
     let res = H3_GET_RESOLUTION(h);
-    if res < 0 || res > MAX_H3_RES {
+    if !(0..=MAX_H3_RES).contains(&res) {
         // LCOV_EXCL_BR_LINE
         // Resolutions less than zero can not be represented in an index
         return false;
@@ -231,7 +431,7 @@ pub fn isValidCell(h: H3Index) -> bool {
         }
     }
 
-    return true;
+    true
 }
 
 /**
@@ -252,6 +452,46 @@ pub fn setH3Index(hp: &mut H3Index, res: i32, base_cell: i32, init_digit: i32) {
     *hp = h;
 }
 
+/**
+ * cellToParent produces the parent index for a given H3 index
+ *
+ * @param h H3Index to find parent of
+ * @param parentRes The resolution to switch to (parent, grandparent, etc)
+ *
+ * @return H3Index of the parent, or an error if you try to find a parent of a
+ * higher resolution than the current index
+ */
+pub fn cellToParent(h: H3Index, parentRes: i32) -> Result<H3Index, Error> {
+    if !(0..=MAX_H3_RES).contains(&parentRes) {
+        return Err(Error::ResDomain);
+    }
+    _cellToParentInRange(h, parentRes)
+}
+
+/**
+ * Produces the parent index like cellToParent, for a parent resolution
+ * already known to be from 0 to 15.
+ *
+ * @param h H3Index to find parent of
+ * @param parentRes The resolution to switch to (parent, grandparent, etc)
+ *
+ * @return H3Index of the parent, or ResMismatch if parentRes is finer than h
+ */
+pub(crate) fn _cellToParentInRange(h: H3Index, parentRes: i32) -> Result<H3Index, Error> {
+    let childRes = H3_GET_RESOLUTION(h);
+    if parentRes > childRes {
+        return Err(Error::ResMismatch);
+    } else if parentRes == childRes {
+        return Ok(h);
+    }
+    let mut parentH = h;
+    H3_SET_RESOLUTION(&mut parentH, parentRes);
+    for i in (parentRes + 1)..=childRes {
+        H3_SET_INDEX_DIGIT(&mut parentH, i, H3_DIGIT_MASK as i32);
+    }
+    Ok(parentH)
+}
+
 /**
  * Determines whether one resolution is a valid child resolution for a cell.
  * Each resolution is considered a valid child resolution of itself.
@@ -266,7 +506,7 @@ pub fn _hasChildAtRes(h: H3Index, childRes: i32) -> bool {
     if childRes < parentRes || childRes > MAX_H3_RES {
         return false;
     }
-    return true;
+    true
 }
 
 /**
@@ -286,10 +526,12 @@ pub fn cellToChildrenSize(h: H3Index, childRes: i32) -> Result<i64, Error> {
 
     let n = (childRes - H3_GET_RESOLUTION(h)) as u32;
 
+    // at most 7^15 children, but checked in case of a larger span
+    let hexagons: i64 = 7i64.checked_pow(n).ok_or(Error::MemoryBounds)?;
     if isPentagon(h) {
-        return Ok((1 + 5 * ((7i32.pow(n) - 1) / 6)) as i64);
+        Ok(1 + 5 * ((hexagons - 1) / 6))
     } else {
-        return Ok(7i32.pow(n) as i64);
+        Ok(hexagons)
     }
 }
 
@@ -303,13 +545,382 @@ pub fn cellToChildrenSize(h: H3Index, childRes: i32) -> Result<i64, Error> {
  * @param children H3Index* the memory to store the resulting addresses in
  */
 pub fn cellToChildren(h: H3Index, childRes: i32) -> Result<Vec<H3Index>, Error> {
+    // an invalid childRes yields no children rather than an error, as in C
+    let size = cellToChildrenSize(h, childRes).unwrap_or(0);
     let mut children = Vec::<H3Index>::new();
+    children
+        .try_reserve_exact(size as usize)
+        .map_err(|_| Error::MemoryBounds)?;
     for child in IterCellsChildren::from_parent(h, childRes) {
         // (IterCellsChildren iter = iterInitParent(h, childRes); iter.h;
         //iterStepChild(&iter)) {
         children.push(child);
     }
-    return Ok(children);
+    Ok(children)
+}
+
+/**
+ * cellToChildrenInto writes the children of the given cell at the specified
+ * resolution into a caller-provided buffer, for callers that want to reuse
+ * memory between calls.
+ *
+ * @param h H3Index to find the children of
+ * @param childRes int the child level to produce
+ * @param out the buffer, at least cellToChildrenSize(h, childRes) long
+ * @return the number of children written, ResDomain if childRes is not a
+ *         child resolution of h, or MemoryBounds if out is too short
+ */
+pub fn cellToChildrenInto(h: H3Index, childRes: i32, out: &mut [H3Index]) -> Result<usize, Error> {
+    let size = cellToChildrenSize(h, childRes)? as usize;
+    if out.len() < size {
+        return Err(Error::MemoryBounds);
+    }
+    let mut i: usize = 0;
+    for child in IterCellsChildren::from_parent(h, childRes) {
+        out[i] = child;
+        i += 1;
+    }
+    Ok(i)
+}
+
+/**
+ * uncompactCellsSize takes a compacted set of hexagons and provides
+ * the exact size of the uncompacted set of hexagons.
+ *
+ * @param   compactedSet  Set of hexagons
+ * @param   res           The hexagon resolution to decompress to
+ * @return  The number of hexagons to allocate memory for, or ResMismatch if
+ *          a hexagon is finer than res
+ */
+pub fn uncompactCellsSize(compactedSet: &[H3Index], res: i32) -> Result<i64, Error> {
+    let mut numOut: i64 = 0;
+    for &h in compactedSet {
+        if h == H3_NULL {
+            continue;
+        }
+        let childrenSize = match cellToChildrenSize(h, res) {
+            Ok(size) => size,
+            // The parent res does not contain `res`.
+            Err(_) => return Err(Error::ResMismatch),
+        };
+        numOut += childrenSize;
+    }
+    Ok(numOut)
+}
+
+/**
+ * uncompactCells takes a compressed set of cells and expands back to the
+ * original set of cells.
+ *
+ * Skips elements that are H3_NULL (i.e., 0).
+ *
+ * @param   compactedSet  Set of compacted cells
+ * @param   res           The H3 resolution to decompress to
+ * @return  The uncompacted cells, or ResMismatch if a cell is finer than res
+ */
+pub fn uncompactCells(compactedSet: &[H3Index], res: i32) -> Result<Vec<H3Index>, Error> {
+    let numOut = uncompactCellsSize(compactedSet, res)?;
+    let mut outSet = Vec::<H3Index>::new();
+    outSet
+        .try_reserve_exact(numOut as usize)
+        .map_err(|_| Error::MemoryBounds)?;
+    for &h in compactedSet {
+        if h == H3_NULL {
+            continue;
+        }
+        if !_hasChildAtRes(h, res) {
+            return Err(Error::ResMismatch);
+        }
+        outSet.extend(IterCellsChildren::from_parent(h, res));
+    }
+    Ok(outSet)
+}
+
+/**
+ * uncompactCellsInto expands a compacted set of cells into a caller-provided
+ * buffer.
+ *
+ * Skips elements that are H3_NULL (i.e., 0).
+ *
+ * @param   compactedSet  Set of compacted cells
+ * @param   res           The H3 resolution to decompress to
+ * @param   out           The buffer, at least uncompactCellsSize long
+ * @return  The number of cells written, ResMismatch if a cell is finer than
+ *          res, or MemoryBounds if out is too short
+ */
+pub fn uncompactCellsInto(
+    compactedSet: &[H3Index],
+    res: i32,
+    out: &mut [H3Index],
+) -> Result<usize, Error> {
+    let numOut = uncompactCellsSize(compactedSet, res)? as usize;
+    if out.len() < numOut {
+        return Err(Error::MemoryBounds);
+    }
+    let mut i: usize = 0;
+    for &h in compactedSet {
+        if h == H3_NULL {
+            continue;
+        }
+        for child in IterCellsChildren::from_parent(h, res) {
+            out[i] = child;
+            i += 1;
+        }
+    }
+    Ok(i)
+}
+
+/**
+ * Maps cells of mixed resolutions to a single resolution: finer cells are
+ * replaced by their parent and coarser cells by their children at that
+ * resolution. Duplicates, such as siblings mapping to the same parent, are
+ * removed.
+ *
+ * @param cells The cells to map
+ * @param res The target resolution
+ * @return The cells at the target resolution
+ */
+pub fn normalizeToRes(cells: &[H3Index], res: i32) -> Result<CellSet, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+
+    let mut out = CellSet::new();
+    for &h in cells {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        if H3_GET_RESOLUTION(h) >= res {
+            out.insert(cellToParent(h, res)?);
+        } else {
+            out.extend(IterCellsChildren::from_parent(h, res));
+        }
+    }
+    Ok(out)
+}
+
+/**
+ * Changes the resolution of a collection of cells in one direction: when the
+ * target is finer the cells are uncompacted to their children, and when it is
+ * coarser they are replaced by their parents. Unlike normalizeToRes, a
+ * collection with cells on both sides of the target is rejected, since that
+ * usually means the wrong dataset or resolution was given.
+ *
+ * @param cells The cells to change, which may be of mixed resolutions
+ * @param res The target resolution
+ * @return The cells at the target resolution in ascending order without
+ *         duplicates, ResDomain if res is invalid, CellInvalid if a cell is
+ *         invalid, or ResMismatch if some cells are finer and some coarser
+ *         than res
+ */
+pub fn changeResolution(cells: &[H3Index], res: i32) -> Result<Vec<H3Index>, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let mut finer = false;
+    let mut coarser = false;
+    for &h in cells {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        let cellRes = H3_GET_RESOLUTION(h);
+        finer |= cellRes > res;
+        coarser |= cellRes < res;
+    }
+    if finer && coarser {
+        return Err(Error::ResMismatch);
+    }
+    Ok(normalizeToRes(cells, res)?.into_iter().collect())
+}
+
+/**
+ * Groups cells by their parent at a coarser resolution, for rolling up
+ * per-cell values.
+ *
+ * @param cells The cells to group, which may be of mixed resolutions as long
+ *              as none is coarser than parentRes
+ * @param parentRes The resolution of the parents
+ * @return The cells of each parent, in input order, keyed and ordered by
+ *         parent
+ */
+pub fn groupByParent(
+    cells: &[H3Index],
+    parentRes: i32,
+) -> Result<BTreeMap<H3Index, Vec<H3Index>>, Error> {
+    let mut groups: BTreeMap<H3Index, Vec<H3Index>> = BTreeMap::new();
+    for &h in cells {
+        if !isValidCell(h) {
+            return Err(Error::CellInvalid);
+        }
+        groups
+            .entry(cellToParent(h, parentRes)?)
+            .or_default()
+            .push(h);
+    }
+    Ok(groups)
+}
+
+/**
+ * compactCells takes a set of hexagons all at the same resolution and
+ * compresses them by pruning full child branches to the parent level. This is
+ * also done for all parents recursively to get the minimum number of hex
+ * addresses that perfectly cover the defined space.
+ * @param h3Set Set of hexagons
+ * @return The compacted set of hexagons, or DuplicateInput if a hexagon
+ *         appears more often than it can, CellInvalid if reserved bits are
+ *         set, or ResMismatch if the resolutions differ
+ */
+pub fn compactCells(h3Set: &[H3Index]) -> Result<Vec<H3Index>, Error> {
+    let numHexes = h3Set.len();
+    if numHexes == 0 {
+        return Ok(Vec::new());
+    }
+    let mut res = H3_GET_RESOLUTION(h3Set[0]);
+    if res == 0 {
+        // No compaction possible, just copy the set to output
+        return Ok(h3Set.to_vec());
+    }
+    let mut remainingHexes: Vec<H3Index> = h3Set.to_vec();
+    let mut hashSetArray: Vec<H3Index> = vec![H3_NULL; numHexes];
+    let mut compactedSet: Vec<H3Index> = Vec::with_capacity(numHexes);
+    let mut numRemainingHexes = numHexes;
+    while numRemainingHexes > 0 {
+        res = H3_GET_RESOLUTION(remainingHexes[0]);
+        let parentRes = res - 1;
+
+        // If parentRes is less than zero, we've compacted all the way up to
+        // the base cells. Time to process the remaining cells.
+        if parentRes >= 0 {
+            // Put the parents of the hexagons into the temp array
+            // via a hashing mechanism, and use the reserved bits
+            // to track how many times a parent is duplicated
+            for &currIndex in &remainingHexes[..numRemainingHexes] {
+                if currIndex == H3_NULL {
+                    continue;
+                }
+                // If the reserved bits were set by the caller, the
+                // algorithm below may encounter undefined behavior
+                // because it expects to have set the reserved bits
+                // itself.
+                if H3_GET_RESERVED_BITS(currIndex) != 0 {
+                    return Err(Error::CellInvalid);
+                }
+
+                // Can fail because of incompatible resolutions.
+                let mut parent = cellToParent(currIndex, parentRes)?;
+                // Modulus hash the parent into the temp array
+                let mut loc = (parent % numRemainingHexes as u64) as usize;
+                let mut loopCount = 0;
+                while hashSetArray[loc] != H3_NULL {
+                    if loopCount > numRemainingHexes {
+                        // LCOV_EXCL_START
+                        // This case should not be possible because at most
+                        // one index is placed into hashSetArray per
+                        // numRemainingHexes.
+                        return Err(Error::Failed);
+                        // LCOV_EXCL_STOP
+                    }
+                    let tempIndex = hashSetArray[loc] & H3_RESERVED_MASK_NEGATIVE;
+                    if tempIndex == parent {
+                        let count = H3_GET_RESERVED_BITS(hashSetArray[loc]) + 1;
+                        let mut limitCount = 7;
+                        if isPentagon(tempIndex & H3_RESERVED_MASK_NEGATIVE) {
+                            limitCount -= 1;
+                        }
+                        // One is added to count for this check to match one
+                        // being added to count later in this function when
+                        // checking for all children being present.
+                        if count + 1 > limitCount {
+                            // Only possible on duplicate input
+                            return Err(Error::DuplicateInput);
+                        }
+                        H3_SET_RESERVED_BITS(&mut parent, count);
+                        hashSetArray[loc] = H3_NULL;
+                    } else {
+                        loc = (loc + 1) % numRemainingHexes;
+                    }
+                    loopCount += 1;
+                }
+                hashSetArray[loc] = parent;
+            }
+        }
+
+        // Determine which parent hexagons have a complete set
+        // of children and put them in the compactableHexes array
+        // Somehow all pentagons; conservative
+        let maxCompactableCount = numRemainingHexes / 6;
+        if maxCompactableCount == 0 {
+            compactedSet.extend_from_slice(&remainingHexes[..numRemainingHexes]);
+            break;
+        }
+        let mut compactableHexes: Vec<H3Index> = Vec::with_capacity(maxCompactableCount);
+        for slot in hashSetArray[..numRemainingHexes].iter_mut() {
+            if *slot == H3_NULL {
+                continue;
+            }
+            let mut count = H3_GET_RESERVED_BITS(*slot) + 1;
+            // Include the deleted direction for pentagons as implicitly "there"
+            if isPentagon(*slot & H3_RESERVED_MASK_NEGATIVE) {
+                // We need this later on, no need to recalculate
+                H3_SET_RESERVED_BITS(slot, count);
+                // Increment count after setting the reserved bits,
+                // since count is already incremented above, so it
+                // will be the expected value for a complete hexagon.
+                count += 1;
+            }
+            if count == 7 {
+                // Bingo! Full set!
+                compactableHexes.push(*slot & H3_RESERVED_MASK_NEGATIVE);
+            }
+        }
+        // Uncompactable hexes are placed directly into the compactedSet
+        // array and don't need to be copied any more
+        for &currIndex in &remainingHexes[..numRemainingHexes] {
+            if currIndex == H3_NULL {
+                continue;
+            }
+            let mut isUncompactable = true;
+            // Resolution 0 index is always uncompactable, and trying to take
+            // its parent will fail.
+            if parentRes >= 0 {
+                let parent = cellToParent(currIndex, parentRes)?;
+                // Modulus hash the parent into the temp array
+                // to determine if this index was included in
+                // the compactableHexes array
+                let mut loc = (parent % numRemainingHexes as u64) as usize;
+                let mut loopCount = 0;
+                loop {
+                    if loopCount > numRemainingHexes {
+                        // LCOV_EXCL_START
+                        return Err(Error::Failed);
+                        // LCOV_EXCL_STOP
+                    }
+                    let tempIndex = hashSetArray[loc] & H3_RESERVED_MASK_NEGATIVE;
+                    if tempIndex == parent {
+                        let count = H3_GET_RESERVED_BITS(hashSetArray[loc]) + 1;
+                        if count == 7 {
+                            isUncompactable = false;
+                        }
+                        break;
+                    } else {
+                        loc = (loc + 1) % numRemainingHexes;
+                    }
+                    loopCount += 1;
+                    if hashSetArray[loc] == parent {
+                        break;
+                    }
+                }
+            }
+            if isUncompactable {
+                compactedSet.push(currIndex);
+            }
+        }
+        // Set up for the next loop
+        hashSetArray.iter_mut().for_each(|h| *h = H3_NULL);
+        numRemainingHexes = compactableHexes.len();
+        remainingHexes[..numRemainingHexes].copy_from_slice(&compactableHexes);
+    }
+    Ok(compactedSet)
 }
 
 /**
@@ -329,7 +940,7 @@ pub fn _zeroIndexDigits(h: H3Index, start: i32, end: i32) -> H3Index {
     m <<= H3_PER_DIGIT_OFFSET * (MAX_H3_RES - end);
     m = !m;
 
-    return h & m;
+    h & m
 }
 
 /**
@@ -344,15 +955,191 @@ pub fn _zeroIndexDigits(h: H3Index, start: i32, end: i32) -> H3Index {
  * @returns E_SUCCESS (0) on success, another value otherwise
  */
 pub fn latLngToCell(g: &LatLng, res: i32) -> Result<H3Index, Error> {
-    if res < 0 || res > MAX_H3_RES {
+    if !(0..=MAX_H3_RES).contains(&res) {
         return Err(Error::ResDomain);
     }
+    _latLngToCellInRange(g, res)
+}
+
+/**
+ * Encodes a coordinate on the sphere like latLngToCell, for a resolution
+ * already known to be from 0 to 15.
+ *
+ * @param g The spherical coordinates to encode.
+ * @param res The desired H3 resolution for the encoding.
+ * @return The encoded H3Index, or LatLngDomain if g is not finite.
+ */
+pub(crate) fn _latLngToCellInRange(g: &LatLng, res: i32) -> Result<H3Index, Error> {
     if !g.lat.is_finite() || !g.lng.is_finite() {
         return Err(Error::LatLngDomain);
     }
 
-    let fijk: FaceIJK = _geoToFaceIjk(g, res);
-    return Ok(_faceIjkToH3(&fijk, res));
+    // the face lookup and projection work in the usual coordinate ranges
+    let fijk: FaceIJK = _geoToFaceIjk(&g.normalized(), res);
+    if res <= MAX_COARSE_RES {
+        if let Some(h) = _coarseFaceIjkToH3(&fijk, res) {
+            return Ok(h);
+        }
+    }
+    Ok(_faceIjkToH3(&fijk, res))
+}
+
+/** Finest resolution resolved by table lookup in latLngToCell. Finding the
+ * face dominates at res 0, so the lookup only pays off at res 1, where the
+ * latLngToCell/res1 bench drops from about 305ns to 240ns per point. */
+const MAX_COARSE_RES: i32 = 1;
+
+/** Largest normalized ijk component held in the coarse lookup tables; the
+ * tables cover every coordinate on the face triangle at res 0 and 1. */
+#[cfg(feature = "std")]
+const MAX_COARSE_COORD: i32 = 7;
+
+/** Cells by face and normalized ijk coordinates for res 0 and 1, indexed by
+ * _coarseSlot. */
+#[cfg(feature = "std")]
+static COARSE_CELLS: [OnceLock<Vec<H3Index>>; (MAX_COARSE_RES + 1) as usize] =
+    [OnceLock::new(), OnceLock::new()];
+
+/**
+ * Returns the coarse table slot of a FaceIJK address, or None if the
+ * coordinates are outside the table.
+ */
+#[cfg(feature = "std")]
+fn _coarseSlot(fijk: &FaceIJK) -> Option<usize> {
+    let c = &fijk.coord;
+    if c.i > MAX_COARSE_COORD || c.j > MAX_COARSE_COORD || c.k > MAX_COARSE_COORD {
+        return None;
+    }
+    let dim = (MAX_COARSE_COORD + 1) as usize;
+    Some(((fijk.face as usize * dim + c.i as usize) * dim + c.j as usize) * dim + c.k as usize)
+}
+
+/**
+ * Converts a FaceIJK address at res 0 or 1 to the corresponding H3Index with
+ * a table lookup instead of building the index digit by digit. The table
+ * for the resolution is built from _faceIjkToH3 on first use.
+ *
+ * @param fijk The FaceIJK address, with normalized coordinates.
+ * @param res The cell resolution, at most MAX_COARSE_RES.
+ * @return The encoded H3Index, or None if the coordinates are outside the
+ *         table.
+ */
+#[cfg(feature = "std")]
+fn _coarseFaceIjkToH3(fijk: &FaceIJK, res: i32) -> Option<H3Index> {
+    let slot = _coarseSlot(fijk)?;
+    let table = COARSE_CELLS[res as usize].get_or_init(|| {
+        let dim = (MAX_COARSE_COORD + 1) as usize;
+        let mut table = vec![H3_NULL; NUM_ICOSA_FACES as usize * dim * dim * dim];
+        for face in 0..NUM_ICOSA_FACES {
+            for i in 0..=MAX_COARSE_COORD {
+                for j in 0..=MAX_COARSE_COORD {
+                    for k in 0..=MAX_COARSE_COORD {
+                        let mut fijk = FaceIJK {
+                            face,
+                            coord: CoordIJK { i, j, k },
+                        };
+                        _ijkNormalize(&mut fijk.coord);
+                        if fijk.coord.i != i || fijk.coord.j != j || fijk.coord.k != k {
+                            // only normalized coordinates are looked up
+                            continue;
+                        }
+                        table[_coarseSlot(&fijk).unwrap()] = _faceIjkToH3(&fijk, res);
+                    }
+                }
+            }
+        }
+        table
+    });
+    Some(table[slot])
+}
+
+/** Without std there is nowhere to keep the tables, and latLngToCell
+ * must not allocate, so every resolution takes the general path. */
+#[cfg(not(feature = "std"))]
+fn _coarseFaceIjkToH3(_fijk: &FaceIJK, _res: i32) -> Option<H3Index> {
+    None
+}
+
+/**
+ * Returns the dimensions of the raster sampled by gridSample.
+ *
+ * @param bbox The extent of the raster.
+ * @param stepDegs The pixel size, in degrees.
+ * @return The number of rows and columns of pixels.
+ */
+pub fn gridSampleSize(bbox: &BBox, stepDegs: f64) -> Result<(usize, usize), Error> {
+    if !stepDegs.is_finite() || stepDegs <= 0.0 {
+        return Err(Error::Domain);
+    }
+    if !bbox.north.is_finite()
+        || !bbox.south.is_finite()
+        || !bbox.east.is_finite()
+        || !bbox.west.is_finite()
+        || bbox.north < bbox.south
+    {
+        return Err(Error::LatLngDomain);
+    }
+
+    let step = stepDegs * M_PI_180;
+    // extents that are a whole number of pixels should not gain a sliver
+    // row or column from floating point error
+    let rows = (bboxHeightRads(bbox) / step - 1e-9).ceil().max(0.0) as usize;
+    let cols = (bboxWidthRads(bbox) / step - 1e-9).ceil().max(0.0) as usize;
+    Ok((rows, cols))
+}
+
+/**
+ * Samples a regular lat/lng raster, finding the cell containing the center of
+ * each pixel. Pixels are `stepDegs` degrees square, starting from the
+ * north-west corner of the bounding box; the last row and column may extend
+ * past the south and east edges.
+ *
+ * Per-row and per-column trigonometry is shared between pixels, and each
+ * pixel starts its face search at the face of the previous pixel, so this is
+ * considerably cheaper than calling latLngToCell for every pixel.
+ *
+ * @param bbox The extent of the raster.
+ * @param stepDegs The pixel size, in degrees.
+ * @param res The H3 resolution of the cells.
+ * @return The containing cells, as rows from north to south of pixels from
+ *         west to east.
+ */
+pub fn gridSample(bbox: &BBox, stepDegs: f64, res: i32) -> Result<Vec<Vec<H3Index>>, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+
+    let (rows, cols) = gridSampleSize(bbox, stepDegs)?;
+    let step = stepDegs * M_PI_180;
+
+    // cos and sin of the pixel center longitude for each column
+    let mut lngs: Vec<(f64, f64, f64)> = Vec::with_capacity(cols);
+    for c in 0..cols {
+        let lng = constrainLng(bbox.west + (c as f64 + 0.5) * step);
+        lngs.push((lng, lng.cos(), lng.sin()));
+    }
+
+    let mut out: Vec<Vec<H3Index>> = Vec::with_capacity(rows);
+    let mut face: i32 = INVALID_FACE;
+    for r in 0..rows {
+        let lat = bbox.north - (r as f64 + 0.5) * step;
+        let cosLat = lat.cos();
+        let sinLat = lat.sin();
+
+        let mut row: Vec<H3Index> = Vec::with_capacity(cols);
+        for &(lng, cosLng, sinLng) in &lngs {
+            let g = LatLng { lat, lng };
+            let v3d = Vec3d {
+                x: cosLng * cosLat,
+                y: sinLng * cosLat,
+                z: sinLat,
+            };
+            let fijk: FaceIJK = _geoToFaceIjkWithHint(&g, &v3d, res, &mut face);
+            row.push(_faceIjkToH3(&fijk, res));
+        }
+        out.push(row);
+    }
+    Ok(out)
 }
 
 /**
@@ -363,7 +1150,17 @@ pub fn latLngToCell(g: &LatLng, res: i32) -> Result<H3Index, Error> {
  *         a Class II grid.
  */
 pub fn isResolutionClassIII(res: i32) -> bool {
-    return (res % 2) != 0;
+    (res % 2) != 0
+}
+
+/**
+ * Returns whether or not an H3 index is in a Class III resolution, whose
+ * cells are rotated relative to those of the Class II resolutions.
+ * @param h The H3 index.
+ * @return true if the index is in a Class III resolution.
+ */
+pub fn isResClassIII(h: H3Index) -> bool {
+    isResolutionClassIII(H3_GET_RESOLUTION(h))
 }
 
 /**
@@ -373,8 +1170,8 @@ pub fn isResolutionClassIII(res: i32) -> bool {
  * @return Returns 1 if it is a pentagon, otherwise 0.
  */
 pub fn isPentagon(h: H3Index) -> bool {
-    return _isBaseCellPentagon(H3_GET_BASE_CELL(h))
-        && (_h3LeadingNonZeroDigit(h) == Direction::CenterDigit);
+    _isBaseCellPentagon(H3_GET_BASE_CELL(h))
+        && (_h3LeadingNonZeroDigit(h) == Direction::CenterDigit)
 }
 
 /**
@@ -390,7 +1187,7 @@ pub fn _h3LeadingNonZeroDigit(h: H3Index) -> Direction {
     }
 
     // if we're here it's all 0's
-    return Direction::CenterDigit;
+    Direction::CenterDigit
 }
 
 /**
@@ -419,7 +1216,7 @@ pub fn _h3RotatePent60ccw(mut h: H3Index) -> H3Index {
             }
         }
     }
-    return h;
+    h
 }
 
 /**
@@ -448,7 +1245,7 @@ pub fn _h3RotatePent60cw(mut h: H3Index) -> H3Index {
             }
         }
     }
-    return h;
+    h
 }
 
 /**
@@ -462,7 +1259,7 @@ pub fn _h3Rotate60ccw(mut h: H3Index) -> H3Index {
         H3_SET_INDEX_DIGIT(&mut h, r, _rotate60ccw(oldDigit) as i32);
     }
 
-    return h;
+    h
 }
 
 /**
@@ -476,7 +1273,7 @@ pub fn _h3Rotate60cw(mut h: H3Index) -> H3Index {
         H3_SET_INDEX_DIGIT(&mut h, r, _rotate60cw(oldIndex) as i32);
     }
 
-    return h;
+    h
 }
 
 /**
@@ -575,7 +1372,7 @@ pub fn _faceIjkToH3(fijk: &FaceIJK, res: i32) -> H3Index {
         }
     }
 
-    return h;
+    h
 }
 
 /**
@@ -609,7 +1406,7 @@ pub fn _h3ToFaceIjkWithInitializedFijk(h: H3Index, fijk: &mut FaceIJK) -> bool {
         _neighbor(&mut fijk.coord, H3_GET_INDEX_DIGIT(h, r));
     }
 
-    return possibleOverage;
+    possibleOverage
 }
 
 /**
@@ -619,7 +1416,7 @@ pub fn _h3ToFaceIjkWithInitializedFijk(h: H3Index, fijk: &mut FaceIJK) -> bool {
  */
 pub fn _h3ToFaceIjk(mut h: H3Index) -> Result<FaceIJK, Error> {
     let baseCell = H3_GET_BASE_CELL(h);
-    if baseCell < 0 || baseCell >= NUM_BASE_CELLS {
+    if !(0..NUM_BASE_CELLS).contains(&baseCell) {
         // LCOV_EXCL_BR_LINE
         // Base cells less than zero can not be represented in an index
         return Err(Error::CellInvalid);
@@ -666,7 +1463,7 @@ pub fn _h3ToFaceIjk(mut h: H3Index) -> Result<FaceIJK, Error> {
     } else if res != H3_GET_RESOLUTION(h) {
         fijk.coord = origIJK;
     }
-    return Ok(fijk);
+    Ok(fijk)
 }
 
 /**
@@ -676,29 +1473,431 @@ pub fn _h3ToFaceIjk(mut h: H3Index) -> Result<FaceIJK, Error> {
  * @param g The spherical coordinates of the H3 cell center.
  */
 pub fn cellToLatLng(h3: H3Index) -> Result<LatLng, Error> {
-    let mut fijk: FaceIJK = _h3ToFaceIjk(h3)?;
-    let geo = _faceIjkToGeo(fijk, H3_GET_RESOLUTION(h3));
-    return Ok(geo);
+    #[cfg(feature = "coarse-cache")]
+    if let Some(g) = cachedCellToLatLng(h3) {
+        return Ok(g);
+    }
+    _cellToLatLngUncached(h3)
 }
 
 /**
- * Validate a child position in the context of a given parent, returning
- * an error if validation fails.
+ * Determines the spherical coordinates of the center point of an H3 index
+ * through the full FaceIJK pipeline, bypassing the coarse cell cache.
+ *
+ * @param h3 The H3 index.
+ * @return The spherical coordinates of the H3 cell center.
  */
-pub fn validateChildPos(childPos: i64, parent: H3Index, childRes: i32) -> Result<(), Error> {
-    let maxChildCount = cellToChildrenSize(parent, childRes)?;
-    if childPos < 0 || childPos >= maxChildCount {
-        return Err(Error::Domain);
-    }
-    Ok(())
+pub(crate) fn _cellToLatLngUncached(h3: H3Index) -> Result<LatLng, Error> {
+    let fijk: FaceIJK = _h3ToFaceIjk(h3)?;
+    let geo = _faceIjkToGeo(fijk, H3_GET_RESOLUTION(h3));
+    Ok(geo)
 }
 
 /**
- * Returns the child cell at a given position within an ordered list of all
- * children at the specified resolution */
+ * Determines the center point of an H3 index as a 3D coordinate on the unit
+ * sphere, with x towards latitude and longitude 0, y towards longitude 90
+ * degrees east and z towards the north pole.
+ *
+ * @param h3 The H3 index.
+ * @return The 3D coordinate of the H3 cell center.
+ */
+pub fn cellToVec3d(h3: H3Index) -> Result<Vec3d, Error> {
+    let mut v = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    _geoToVec3d(&cellToLatLng(h3)?, &mut v);
+    Ok(v)
+}
+
+/**
+ * Determines the center point of an H3 index as earth-centered, earth-fixed
+ * coordinates in kilometers, on the spherical earth model used for areas and
+ * distances (not the WGS84 ellipsoid).
+ *
+ * @param h3 The H3 index.
+ * @return The coordinate of the H3 cell center, in kilometers.
+ */
+pub fn cellToEcefKm(h3: H3Index) -> Result<Vec3d, Error> {
+    let v = cellToVec3d(h3)?;
+    Ok(Vec3d {
+        x: v.x * EARTH_RADIUS_KM,
+        y: v.y * EARTH_RADIUS_KM,
+        z: v.z * EARTH_RADIUS_KM,
+    })
+}
+
+/**
+ * Determines the cell boundary in spherical coordinates for an H3 index.
+ *
+ * @param h3 The H3 index.
+ * @return The boundary of the H3 cell in spherical coordinates.
+ */
+pub fn cellToBoundary(h3: H3Index) -> Result<CellBoundary, Error> {
+    #[cfg(feature = "coarse-cache")]
+    if let Some(cb) = cachedCellToBoundary(h3) {
+        return Ok(cb);
+    }
+    _cellToBoundaryUncached(h3)
+}
+
+/**
+ * Determines the cell boundary in spherical coordinates for an H3 index,
+ * with extra points interpolated along each great circle edge. Renderers
+ * that draw straight lines between vertices in a projected map then show
+ * coarse cells with curved edges rather than chords.
+ *
+ * @param h3 The H3 index.
+ * @param segmentsPerEdge The number of segments each edge is split into;
+ * 1 gives the plain boundary.
+ * @return The boundary in ccw order, starting at the first vertex of
+ * cellToBoundary, without a repeated closing vertex, or Domain if
+ * segmentsPerEdge is 0 or more than MAX_SEGMENTS_PER_EDGE.
+ */
+pub fn cellToBoundaryDensified(h3: H3Index, segmentsPerEdge: u32) -> Result<Vec<LatLng>, Error> {
+    _densifyLoop(&cellToBoundary(h3)?.to_vec(), segmentsPerEdge)
+}
+
+/**
+ * Determines the cell boundary in spherical coordinates for an H3 index
+ * through the full FaceIJK pipeline, bypassing the coarse cell cache.
+ *
+ * @param h3 The H3 index.
+ * @return The boundary of the H3 cell in spherical coordinates.
+ */
+pub(crate) fn _cellToBoundaryUncached(h3: H3Index) -> Result<CellBoundary, Error> {
+    let fijk: FaceIJK = _h3ToFaceIjk(h3)?;
+    let mut cb = CellBoundary::new();
+    if isPentagon(h3) {
+        _faceIjkPentToCellBoundary(&fijk, H3_GET_RESOLUTION(h3), 0, NUM_PENT_VERTS, &mut cb);
+    } else {
+        _faceIjkToCellBoundary(&fijk, H3_GET_RESOLUTION(h3), 0, NUM_HEX_VERTS, &mut cb);
+    }
+    Ok(cb)
+}
+
+/**
+ * Determines the latitude reached by the great circle arc between two
+ * boundary vertices at its most poleward point, if that point lies strictly
+ * between the vertices.
+ *
+ * @param a The first vertex.
+ * @param b The second vertex.
+ * @param north Whether to look for the northernmost or southernmost point.
+ * @return The latitude of the extreme interior point, if any.
+ */
+fn _arcExtremeLat(a: &LatLng, b: &LatLng, north: bool) -> Option<f64> {
+    let mut va = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let mut vb = va;
+    _geoToVec3d(a, &mut va);
+    _geoToVec3d(b, &mut vb);
+
+    let n = _vec3dCross(&va, &vb);
+    let nLen2 = _vec3dDot(&n, &n);
+    if nLen2 < EPSILON {
+        return None;
+    }
+
+    // project the pole onto the plane of the great circle
+    let pole = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: if north { 1.0 } else { -1.0 },
+    };
+    let s = _vec3dDot(&pole, &n) / nLen2;
+    let p = Vec3d {
+        x: pole.x - s * n.x,
+        y: pole.y - s * n.y,
+        z: pole.z - s * n.z,
+    };
+    if _vec3dDot(&p, &p) < EPSILON {
+        return None;
+    }
+
+    // the extreme point is on the arc if it is between a and b
+    if _vec3dDot(&_vec3dCross(&va, &p), &n) <= 0.0 || _vec3dDot(&_vec3dCross(&p, &vb), &n) <= 0.0 {
+        return None;
+    }
+    Some(_vec3dToGeo(&p).lat)
+}
+
+/**
+ * Determines a tight bounding box for a cell without returning its boundary.
+ * Cell edges are great circle arcs, so the box accounts for edges that bulge
+ * poleward of their vertices, and cells containing a pole span all
+ * longitudes.
+ *
+ * The box is returned as its south-west and north-east corners. When the cell
+ * crosses the antimeridian the west longitude is greater than the east
+ * longitude.
+ *
+ * @param h The H3 cell.
+ * @return The south-west and north-east corners, in radians.
+ */
+pub fn cellBounds(h: H3Index) -> Result<(LatLng, LatLng), Error> {
+    if !isValidCell(h) {
+        return Err(Error::CellInvalid);
+    }
+    let cb = cellToBoundary(h)?;
+    let verts = &cb.verts[..cb.numVerts];
+    let mut bbox = bboxFromGeoLoop(&GeoLoop {
+        verts: verts.to_vec(),
+    });
+
+    for i in 0..verts.len() {
+        let a = &verts[i];
+        let b = &verts[(i + 1) % verts.len()];
+        if let Some(lat) = _arcExtremeLat(a, b, true) {
+            bbox.north = bbox.north.max(lat);
+        }
+        if let Some(lat) = _arcExtremeLat(a, b, false) {
+            bbox.south = bbox.south.min(lat);
+        }
+    }
+
+    // a cell containing a pole covers every longitude
+    let res = H3_GET_RESOLUTION(h);
+    let northPole = LatLng {
+        lat: FRAC_PI_2,
+        lng: 0.0,
+    };
+    let southPole = LatLng {
+        lat: -FRAC_PI_2,
+        lng: 0.0,
+    };
+    let hasNorthPole = latLngToCell(&northPole, res)? == h;
+    let hasSouthPole = latLngToCell(&southPole, res)? == h;
+    if hasNorthPole {
+        bbox.north = FRAC_PI_2;
+    }
+    if hasSouthPole {
+        bbox.south = -FRAC_PI_2;
+    }
+    if hasNorthPole || hasSouthPole {
+        bbox.west = -PI;
+        bbox.east = PI;
+    }
+
+    Ok((
+        LatLng {
+            lat: bbox.south,
+            lng: bbox.west,
+        },
+        LatLng {
+            lat: bbox.north,
+            lng: bbox.east,
+        },
+    ))
+}
+
+/**
+ * Determines the orientation of a cell on the sphere: the azimuth of the
+ * i-axis of the cell's icosahedral face coordinate system, measured at the
+ * cell center. Because the icosahedral faces are not aligned with north, the
+ * azimuth varies from cell to cell; rotating per-cell content by it keeps the
+ * content aligned with the grid.
+ *
+ * @param h The H3 cell.
+ * @return The azimuth in radians clockwise from true north, in [0, 2pi).
+ */
+pub fn cellAxisAzimuth(h: H3Index) -> Result<f64, Error> {
+    if !isValidCell(h) {
+        return Err(Error::CellInvalid);
+    }
+    let res = H3_GET_RESOLUTION(h);
+    let fijk: FaceIJK = _h3ToFaceIjk(h)?;
+    let center = _faceIjkToGeo(fijk, res);
+
+    // step one unit along the i-axis in the same face coordinate system
+    let mut axis: FaceIJK = fijk;
+    _neighbor(&mut axis.coord, Direction::IAxesDigit);
+    let axisGeo = _faceIjkToGeo(axis, res);
+
+    Ok(_posAngleRads(_geoAzimuthRads(&center, &axisGeo)))
+}
+
+/**
+ * Returns the initial bearing of the great circle arc from the center of one
+ * cell to the center of another, such as the direction of travel between
+ * consecutive cells of a trace. The cells may be at different resolutions.
+ *
+ * @param origin The cell to measure from.
+ * @param destination The cell to measure to.
+ * @return The azimuth in radians clockwise from true north, in [0, 2pi), or
+ *         0 if the cells have the same center.
+ */
+pub fn cellAzimuthRads(origin: H3Index, destination: H3Index) -> Result<f64, Error> {
+    if !isValidCell(origin) || !isValidCell(destination) {
+        return Err(Error::CellInvalid);
+    }
+    let originGeo = cellToLatLng(origin)?;
+    let destinationGeo = cellToLatLng(destination)?;
+    if greatCircleDistanceRads(&originGeo, &destinationGeo) < EPSILON {
+        return Ok(0.0);
+    }
+    Ok(_posAngleRads(_geoAzimuthRads(&originGeo, &destinationGeo)))
+}
+
+/**
+ * Returns the maximum number of icosahedron faces the given H3 index may
+ * intersect.
+ *
+ * @param h3 The H3 index.
+ * @return The number of faces, 5 for a pentagon and 2 otherwise.
+ */
+pub fn maxFaceCount(h3: H3Index) -> Result<i32, Error> {
+    // a pentagon always intersects 5 faces
+    if isPentagon(h3) {
+        return Ok(5);
+    }
+    // an individual hexagon can intersect at most 2 faces
+    Ok(2)
+}
+
+/**
+ * Find all icosahedron faces intersected by a given H3 index, represented
+ * as integers from 0-19.
+ *
+ * @param h3 The H3 index.
+ * @return The faces, at most maxFaceCount(h3) of them, in no particular
+ *         order.
+ */
+pub fn getIcosahedronFaces(h3: H3Index) -> Result<Vec<i32>, Error> {
+    if !isValidCell(h3) {
+        return Err(Error::CellInvalid);
+    }
+    let mut res = H3_GET_RESOLUTION(h3);
+    let isPent = isPentagon(h3);
+
+    // We can't use the vertex-based approach here for class II pentagons,
+    // because all their vertices are on the icosahedron edges. Their
+    // direct child pentagons cross the same faces, so use those instead.
+    if isPent && !isResolutionClassIII(res) {
+        // Note that this would not work for res 15, but this is only run on
+        // Class II pentagons, it should never be invoked for a res 15 index.
+        return getIcosahedronFaces(childPosToCell(0, h3, res + 1)?);
+    }
+
+    // convert to FaceIJK
+    let mut fijk = _h3ToFaceIjk(h3)?;
+
+    // Get all vertices as FaceIJK addresses. For simplicity, always
+    // initialize the array with 6 verts, ignoring the last one for pentagons
+    let mut fijkVerts = [fijk; NUM_HEX_VERTS as usize];
+    let vertexCount = if isPent {
+        let mut pentVerts = [fijk; NUM_PENT_VERTS as usize];
+        _faceIjkPentToVerts(&mut fijk, &mut res, &mut pentVerts);
+        fijkVerts[..NUM_PENT_VERTS as usize].copy_from_slice(&pentVerts);
+        NUM_PENT_VERTS as usize
+    } else {
+        _faceIjkToVerts(&mut fijk, &mut res, &mut fijkVerts);
+        NUM_HEX_VERTS as usize
+    };
+
+    let faceCount = maxFaceCount(h3)? as usize;
+    let mut out: Vec<i32> = Vec::with_capacity(faceCount);
+    for vert in &mut fijkVerts[..vertexCount] {
+        // Adjust overage, determining whether this vertex is
+        // on another face
+        if isPent {
+            _adjustPentVertOverage(vert, res);
+        } else {
+            _adjustOverageClassII(vert, res, false, true);
+        }
+
+        if !out.contains(&vert.face) {
+            if out.len() >= faceCount {
+                return Err(Error::Failed);
+            }
+            out.push(vert.face);
+        }
+    }
+    Ok(out)
+}
+
+/**
+ * Returns the position of the child cell within an ordered list of all
+ * children of the cell's parent at the specified resolution parentRes.
+ *
+ * @param child The child cell.
+ * @param parentRes The resolution of the parent.
+ * @return The position, the inverse of childPosToCell.
+ */
+pub fn cellToChildPos(child: H3Index, parentRes: i32) -> Result<i64, Error> {
+    let childRes = H3_GET_RESOLUTION(child);
+    // Get the parent at res. This will catch any resolution errors
+    let originalParent = cellToParent(child, parentRes)?;
+
+    // Walk up the resolution digits, incrementing the index
+    let mut out: i64 = 0;
+    if isPentagon(originalParent) {
+        // Pentagon logic. Pentagon parents skip the 1 digit, so the offsets
+        // are different from hexagons
+        for res in ((parentRes + 1)..=childRes).rev() {
+            let parent = cellToParent(child, res - 1)?;
+            let parentIsPentagon = isPentagon(parent);
+            let rawDigit = H3_GET_INDEX_DIGIT(child, res) as i32;
+            // Validate the digit before proceeding
+            if rawDigit == Direction::InvalidDigit as i32
+                || (parentIsPentagon && rawDigit == Direction::KAxesDigit as i32)
+            {
+                return Err(Error::CellInvalid);
+            }
+            let digit = if parentIsPentagon && rawDigit > 0 {
+                rawDigit - 1
+            } else {
+                rawDigit
+            };
+            if digit != Direction::CenterDigit as i32 {
+                let hexChildCount: i64 = pow(7, (childRes - res) as usize);
+                // The offset for the 0-digit slot depends on whether the
+                // current index is the child of a pentagon. If so, the offset
+                // is based on the count of pentagon children, otherwise,
+                // hexagon children.
+                let centerCount = if parentIsPentagon {
+                    1 + (5 * (hexChildCount - 1)) / 6
+                } else {
+                    hexChildCount
+                };
+                out += centerCount + (digit as i64 - 1) * hexChildCount;
+            }
+        }
+    } else {
+        // Hexagon logic. Offsets are simple powers of 7
+        for res in ((parentRes + 1)..=childRes).rev() {
+            let digit = H3_GET_INDEX_DIGIT(child, res) as i32;
+            if digit == Direction::InvalidDigit as i32 {
+                return Err(Error::CellInvalid);
+            }
+            out += digit as i64 * pow(7i64, (childRes - res) as usize);
+        }
+    }
+    Ok(out)
+}
+
+/**
+ * Validate a child position in the context of a given parent, returning
+ * an error if validation fails.
+ */
+pub fn validateChildPos(childPos: i64, parent: H3Index, childRes: i32) -> Result<(), Error> {
+    let maxChildCount = cellToChildrenSize(parent, childRes)?;
+    if childPos < 0 || childPos >= maxChildCount {
+        return Err(Error::Domain);
+    }
+    Ok(())
+}
+
+/**
+ * Returns the child cell at a given position within an ordered list of all
+ * children at the specified resolution */
 pub fn childPosToCell(childPos: i64, parent: H3Index, childRes: i32) -> Result<H3Index, Error> {
     // Validate resolution
-    if childRes < 0 || childRes > MAX_H3_RES {
+    if !(0..=MAX_H3_RES).contains(&childRes) {
         return Err(Error::ResDomain);
     }
     // Validate parent resolution
@@ -707,7 +1906,7 @@ pub fn childPosToCell(childPos: i64, parent: H3Index, childRes: i32) -> Result<H
         return Err(Error::ResMismatch);
     }
     // Validate child pos
-    let _childPosErr = validateChildPos(childPos, parent, childRes)?;
+    validateChildPos(childPos, parent, childRes)?;
 
     let resOffset = childRes - parentRes;
 
@@ -750,18 +1949,20 @@ pub fn childPosToCell(childPos: i64, parent: H3Index, childRes: i32) -> Result<H
         }
     }
 
-    return Ok(child);
+    Ok(child)
 }
 
 #[cfg(test)]
 mod tests {
-    use num::Float;
+    use num_traits::Float;
 
-    use crate::lat_lng::{geoAlmostEqualThreshold, setGeoDegs};
+    #[allow(deprecated)]
+    use crate::lat_lng::setGeoDegs;
+    use crate::lat_lng::{geoAlmostEqualThreshold, greatCircleInterpolate, MAX_SEGMENTS_PER_EDGE};
 
     use super::*;
 
-    fn assertNoDuplicates(cells: &Vec<H3Index>) {
+    fn assertNoDuplicates(cells: &[H3Index]) {
         for i in 0..cells.len() {
             if cells[i] == H3_NULL {
                 continue;
@@ -810,7 +2011,7 @@ mod tests {
         expectedError: Result<i64, Error>,
         expected: Vec<H3Index>,
     ) {
-        let numChildren: i64 = 0;
+        let _numChildren: i64 = 0;
         let numChildrenError = cellToChildrenSize(h, res);
 
         assert_eq!(numChildrenError, expectedError, "Expected error code");
@@ -823,6 +2024,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn latLngToCellExtremeCoordinates() {
         // Check that none of these cause crashes.
         let g = LatLng {
@@ -840,6 +2042,22 @@ mod tests {
         let mut g4 = LatLng { lat: 0.0, lng: 0.0 };
         setGeoDegs(&mut g4, 2.0, -3E39);
         latLngToCell(&g4, 0).unwrap();
+
+        // coordinates past a pole or the antimeridian index as the same point
+        for res in [0, 5, 10, 15] {
+            assert_eq!(
+                latLngToCell(&LatLng::from_degrees(100.0, 10.0), res),
+                latLngToCell(&LatLng::from_degrees(80.0, -170.0), res),
+                "over the north pole at res {}",
+                res
+            );
+            assert_eq!(
+                latLngToCell(&LatLng::from_degrees(-30.0, 400.0), res),
+                latLngToCell(&LatLng::from_degrees(-30.0, 40.0), res),
+                "wrapped longitude at res {}",
+                res
+            );
+        }
     }
 
     #[test]
@@ -897,8 +2115,7 @@ mod tests {
     fn isValidCellAtResolution() {
         for i in 0..(MAX_H3_RES + 1) {
             let g: LatLng = LatLng { lat: 0.0, lng: 0.0 };
-            let mut h3: H3Index = 0;
-            h3 = latLngToCell(&g, i).unwrap();
+            let h3: H3Index = latLngToCell(&g, i).unwrap();
             assert!(isValidCell(h3), "isValidCell failed on resolution {}", i);
         }
     }
@@ -907,7 +2124,7 @@ mod tests {
     fn cellToChildrenSize_hexagon() {
         let h: H3Index = 0x87283080dffffff; // res 7 *hexagon*
 
-        let mut sz: i64 = 0;
+        let mut sz: i64;
         assert!(
             cellToChildrenSize(h, 3) == Err(Error::ResDomain),
             "got expected size for coarser res"
@@ -924,7 +2141,7 @@ mod tests {
     fn cellToChildrenSize_pentagon() {
         let h: H3Index = 0x870800000ffffff; // res 7 *pentagon*
 
-        let mut sz: i64 = 0;
+        let mut sz: i64;
         assert!(
             cellToChildrenSize(h, 3) == Err(Error::ResDomain),
             "got expected size for coarser res"
@@ -934,10 +2151,33 @@ mod tests {
         sz = cellToChildrenSize(h, 8).unwrap();
         assert_eq!(sz, 6, "got expected size for child res");
         sz = cellToChildrenSize(h, 9).unwrap();
+        assert_eq!(sz, (5 * 7) + 6, "got expected size for grandchild res");
+    }
+
+    #[test]
+    fn cellToChildrenSize_largest() {
+        let hexagon: H3Index = 0x8029fffffffffff; // res 0 *hexagon*
+        let pentagon: H3Index = 0x8009fffffffffff; // res 0 *pentagon*
+
+        assert_eq!(
+            cellToChildrenSize(hexagon, MAX_H3_RES),
+            Ok(4747561509943),
+            "got expected size for res 0 hexagon at finest res"
+        );
+        assert_eq!(
+            cellToChildrenSize(pentagon, MAX_H3_RES),
+            Ok(3956301258286),
+            "got expected size for res 0 pentagon at finest res"
+        );
+        let total: i64 = crate::base_cells::getRes0Cells()
+            .unwrap()
+            .iter()
+            .map(|&h| cellToChildrenSize(h, MAX_H3_RES).unwrap())
+            .sum();
         assert_eq!(
-            sz,
-            (5 * 7) + (1 * 6),
-            "got expected size for grandchild res"
+            total,
+            2 + 120 * 7i64.pow(MAX_H3_RES as u32),
+            "children of the base cells are all cells"
         );
     }
 
@@ -1071,4 +2311,994 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn cellAxisAzimuthClassRotation() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        for res in 0..MAX_H3_RES {
+            let h = latLngToCell(&sf, res).unwrap();
+            let child = latLngToCell(&cellToLatLng(h).unwrap(), res + 1).unwrap();
+
+            let az = cellAxisAzimuth(h).unwrap();
+            let childAz = cellAxisAzimuth(child).unwrap();
+            assert!((0.0..M_2PI).contains(&az), "azimuth is normalized");
+
+            // Class II and Class III axes differ by the aperture 7 rotation,
+            // modulo the 60 degree symmetry of the hexagon.
+            let diff = _posAngleRads(childAz - az) % (M_2PI / 6.0);
+            let rot = M_AP7_ROT_RADS;
+            assert!(
+                (diff - rot).abs() < 1e-2 || (diff - (M_2PI / 6.0 - rot)).abs() < 1e-2,
+                "child axis is rotated by the aperture 7 angle at res {}",
+                res
+            );
+        }
+
+        assert_eq!(cellAxisAzimuth(0), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn gridSampleMatchesLatLngToCell() {
+        let boxes = [
+            // whole globe, crossing every face
+            BBox {
+                north: FRAC_PI_2,
+                south: -FRAC_PI_2,
+                east: PI,
+                west: -PI,
+            },
+            // crossing the antimeridian
+            BBox {
+                north: 0.3,
+                south: -0.2,
+                east: -3.0,
+                west: 3.0,
+            },
+        ];
+        for bbox in &boxes {
+            for res in [0, 3, 9] {
+                let grid = gridSample(bbox, 2.5, res).unwrap();
+                assert!(!grid.is_empty(), "raster has rows");
+                for (r, row) in grid.iter().enumerate() {
+                    for (c, cell) in row.iter().enumerate() {
+                        let g = LatLng {
+                            lat: bbox.north - (r as f64 + 0.5) * 2.5 * M_PI_180,
+                            lng: constrainLng(bbox.west + (c as f64 + 0.5) * 2.5 * M_PI_180),
+                        };
+                        assert_eq!(
+                            *cell,
+                            latLngToCell(&g, res).unwrap(),
+                            "sample matches latLngToCell"
+                        );
+                    }
+                }
+            }
+        }
+
+        assert_eq!(gridSampleSize(&boxes[0], 10.0), Ok((18, 36)), "size");
+        let grid = gridSample(&boxes[0], 10.0, 0).unwrap();
+        assert_eq!(grid.len(), 18, "rows cover the height");
+        assert_eq!(grid[0].len(), 36, "columns cover the width");
+        let grid = gridSample(&boxes[1], 1.0, 0).unwrap();
+        assert_eq!(grid[0].len(), 17, "columns wrap the antimeridian");
+
+        assert_eq!(gridSample(&boxes[0], 0.0, 0), Err(Error::Domain));
+        assert_eq!(gridSample(&boxes[0], 1.0, 16), Err(Error::ResDomain));
+        let mut bad = boxes[0];
+        bad.north = f64::NAN;
+        assert_eq!(gridSample(&bad, 1.0, 0), Err(Error::LatLngDomain));
+    }
+
+    #[test]
+    fn normalizeToResMixed() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let fine = latLngToCell(&sf, 9).unwrap();
+        let fineSibling = cellToChildren(cellToParent(fine, 8).unwrap(), 9).unwrap()[3];
+        let target = latLngToCell(&sf, 7).unwrap();
+        let coarse = latLngToCell(
+            &LatLng {
+                lat: 0.6,
+                lng: -2.0,
+            },
+            5,
+        )
+        .unwrap();
+
+        let out = normalizeToRes(&[fine, fineSibling, target, coarse], 7).unwrap();
+        let mut expected: CellSet = cellToChildren(coarse, 7).unwrap().into_iter().collect();
+        expected.insert(target);
+        assert_eq!(
+            out, expected,
+            "parents and children at the target resolution"
+        );
+        assert_eq!(
+            out.len(),
+            50,
+            "siblings and the target cell are deduplicated"
+        );
+
+        assert_eq!(normalizeToRes(&[fine], 16), Err(Error::ResDomain));
+        assert_eq!(normalizeToRes(&[0], 7), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn changeResolutionDirections() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let fine = latLngToCell(&sf, 9).unwrap();
+        let fineSibling = cellToChildren(cellToParent(fine, 8).unwrap(), 9).unwrap()[3];
+        let target = latLngToCell(&sf, 7).unwrap();
+
+        assert_eq!(
+            changeResolution(&[fine, fineSibling, target], 7),
+            Ok(vec![target]),
+            "parents, deduplicated"
+        );
+        let mut children = cellToChildren(target, 9).unwrap();
+        children.sort();
+        assert_eq!(
+            changeResolution(&[target, fine], 9),
+            Ok(children),
+            "uncompacted, deduplicated"
+        );
+        assert_eq!(
+            changeResolution(&[fine, target], 8),
+            Err(Error::ResMismatch),
+            "mixed directions"
+        );
+        assert_eq!(changeResolution(&[], 8), Ok(vec![]), "empty");
+        assert_eq!(changeResolution(&[fine], -1), Err(Error::ResDomain));
+        assert_eq!(changeResolution(&[0], 7), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn groupByParentRollup() {
+        let parent: H3Index = 0x85283473fffffff;
+        let mut cells = cellToChildren(parent, 7).unwrap();
+        let other = cellToChildren(0x85283477fffffff, 6).unwrap();
+        cells.extend(&other);
+        cells.push(childPosToCell(0, parent, 9).unwrap());
+
+        let groups = groupByParent(&cells, 5).unwrap();
+        assert_eq!(groups.len(), 2, "two parents");
+        assert_eq!(groups[&parent].len(), 50, "49 children and a grandchild");
+        assert_eq!(groups[&0x85283477fffffff], other, "in input order");
+        assert_eq!(
+            groups.values().map(|g| g.len()).sum::<usize>(),
+            cells.len(),
+            "every cell in one group"
+        );
+
+        assert_eq!(
+            groupByParent(&[parent], 6),
+            Err(Error::ResMismatch),
+            "coarser cell"
+        );
+        assert_eq!(
+            groupByParent(&[0], 5),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+        assert!(groupByParent(&[], 5).unwrap().is_empty(), "no cells");
+    }
+
+    fn assertInBounds(bounds: &(LatLng, LatLng), p: &LatLng, msg: &str) {
+        let (sw, ne) = bounds;
+        let tol = 1e-12;
+        assert!(
+            p.lat >= sw.lat - tol && p.lat <= ne.lat + tol,
+            "{}: lat",
+            msg
+        );
+        let inLng = if sw.lng > ne.lng {
+            p.lng >= sw.lng - tol || p.lng <= ne.lng + tol
+        } else {
+            p.lng >= sw.lng - tol && p.lng <= ne.lng + tol
+        };
+        assert!(inLng, "{}: lng", msg);
+    }
+
+    #[test]
+    fn cellBoundsContainBoundary() {
+        let mut cells: Vec<H3Index> = Vec::new();
+        for baseCell in 0..NUM_BASE_CELLS {
+            let mut h: H3Index = H3_INIT;
+            H3_SET_MODE(&mut h, H3_CELL_MODE);
+            H3_SET_RESOLUTION(&mut h, 0);
+            H3_SET_BASE_CELL(&mut h, baseCell);
+            for res in 0..3 {
+                cells.extend(cellToChildren(h, res).unwrap());
+            }
+        }
+
+        for h in cells {
+            let bounds = cellBounds(h).unwrap();
+            assert!(bounds.0.lat <= bounds.1.lat, "south below north");
+            assertInBounds(&bounds, &cellToLatLng(h).unwrap(), "center in bounds");
+
+            let cb = cellToBoundary(h).unwrap();
+            for i in 0..cb.numVerts {
+                let a = cb.verts[i];
+                let b = cb.verts[(i + 1) % cb.numVerts];
+                assertInBounds(&bounds, &a, "vertex in bounds");
+                for step in 1..8 {
+                    let p = greatCircleInterpolate(&a, &b, step as f64 / 8.0).unwrap();
+                    assertInBounds(&bounds, &p, "edge point in bounds");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cellBoundsSpecialCases() {
+        let antimeridian = LatLng::from_degrees(10.0, 180.0);
+        let h = latLngToCell(&antimeridian, 3).unwrap();
+        let (sw, ne) = cellBounds(h).unwrap();
+        assert!(sw.lng > ne.lng, "transmeridian cell has west > east");
+        assert!(sw.lng - ne.lng > PI, "transmeridian cell is narrow");
+
+        let pole = LatLng {
+            lat: FRAC_PI_2,
+            lng: 0.0,
+        };
+        for res in 0..4 {
+            let h = latLngToCell(&pole, res).unwrap();
+            let (sw, ne) = cellBounds(h).unwrap();
+            assert_eq!(ne.lat, FRAC_PI_2, "polar cell reaches the pole");
+            assert_eq!(
+                (sw.lng, ne.lng),
+                (-PI, PI),
+                "polar cell spans all longitudes"
+            );
+        }
+
+        assert_eq!(cellBounds(0), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn coarseLatLngToCellMatchesGeneral() {
+        let steps = 180;
+        for res in 0..=MAX_COARSE_RES {
+            for y in 0..=steps {
+                for x in 0..steps * 2 {
+                    let g = LatLng {
+                        lat: -FRAC_PI_2 + PI * y as f64 / steps as f64,
+                        lng: -PI + PI * x as f64 / steps as f64,
+                    };
+                    let fijk = _geoToFaceIjk(&g, res);
+                    #[cfg(feature = "std")]
+                    assert!(_coarseSlot(&fijk).is_some(), "face coordinates in table");
+                    assert_eq!(
+                        latLngToCell(&g, res).unwrap(),
+                        _faceIjkToH3(&fijk, res),
+                        "table lookup matches the general path"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validateModes() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let cell = latLngToCell(&sf, 9).unwrap();
+        assert_eq!(modeOf(cell), Some(IndexMode::Cell), "mode of a cell");
+        assert_eq!(validate(cell), Ok(IndexMode::Cell), "cell validates");
+
+        let edge = crate::directed_edge::originToDirectedEdges(cell)[0];
+        assert_eq!(
+            validate(edge),
+            Ok(IndexMode::DirectedEdge),
+            "edge validates"
+        );
+
+        let mut badCell = cell;
+        H3_SET_RESERVED_BITS(&mut badCell, 1);
+        assert_eq!(validate(badCell), Err(Error::CellInvalid), "reserved bits");
+
+        let mut badEdge = edge;
+        H3_SET_RESERVED_BITS(&mut badEdge, 0);
+        assert_eq!(
+            validate(badEdge),
+            Err(Error::DirectedEdgeInvalid),
+            "no direction"
+        );
+
+        let mut undirected = cell;
+        H3_SET_MODE(&mut undirected, H3_EDGE_MODE);
+        assert_eq!(modeOf(undirected), Some(IndexMode::UndirectedEdge));
+        assert_eq!(validate(undirected), Err(Error::UndirectedEdgeInvalid));
+
+        let vertex = crate::vertex::cellToVertex(cell, 0).unwrap();
+        assert_eq!(validate(vertex), Ok(IndexMode::Vertex), "vertex validates");
+        let mut badVertex = vertex;
+        H3_SET_RESERVED_BITS(&mut badVertex, 7);
+        assert_eq!(validate(badVertex), Err(Error::VertexInvalid), "bad vertex");
+
+        let mut unknown = cell;
+        H3_SET_MODE(&mut unknown, 0);
+        assert_eq!(modeOf(unknown), None, "mode 0 is not a known mode");
+        assert_eq!(validate(unknown), Err(Error::Failed), "unknown mode");
+        assert_eq!(validate(0), Err(Error::Failed), "null index");
+    }
+
+    #[test]
+    fn isResClassIII_matchesResolution() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        for res in 0..=MAX_H3_RES {
+            let h = latLngToCell(&sf, res).unwrap();
+            assert_eq!(
+                isResClassIII(h),
+                res % 2 == 1,
+                "odd resolutions are Class III"
+            );
+        }
+    }
+
+    #[test]
+    fn cellToBoundaryHexagon() {
+        let expected: [(f64, f64); 6] = [
+            (37.271355866731895, -121.91508032705622),
+            (37.353926450852256, -121.86222328902491),
+            (37.42834118609435, -121.92354999630156),
+            (37.42012867767778, -122.03773496427027),
+            (37.33755608435298, -122.09042892904397),
+            (37.26319797461824, -122.02910130919001),
+        ];
+        let cb = cellToBoundary(0x85283473fffffff).unwrap();
+        assert_eq!(cb.numVerts, 6, "hexagon has 6 vertices");
+        for (i, (lat, lng)) in expected.iter().enumerate() {
+            let v = LatLng::from_degrees(*lat, *lng);
+            assert!(
+                geoAlmostEqualThreshold(&cb.verts[i], &v, 1e-12),
+                "vertex {} matches the reference",
+                i
+            );
+        }
+        assert_eq!(cb.to_vec().len(), 6, "to_vec trims unused vertices");
+    }
+
+    #[test]
+    fn cellToBoundaryPentagons() {
+        let classII = cellToBoundary(0x8009fffffffffff).unwrap();
+        assert_eq!(classII.numVerts, 5, "Class II pentagon has 5 vertices");
+
+        // every edge of a Class III pentagon crosses an icosahedron edge
+        let classIII = cellToBoundary(0x81083ffffffffff).unwrap();
+        assert_eq!(classIII.numVerts, 10, "Class III pentagon has 10 vertices");
+
+        let center = cellToLatLng(0x81083ffffffffff).unwrap();
+        for v in classIII.to_vec() {
+            let p = greatCircleInterpolate(&v, &center, 0.02).unwrap();
+            assert_eq!(
+                latLngToCell(&p, 1).unwrap(),
+                0x81083ffffffffff,
+                "vertices are on the pentagon"
+            );
+        }
+    }
+
+    #[test]
+    fn cellToBoundaryClassIIIEdgeCrossing() {
+        // a Class III hexagon whose edges cross an icosahedron edge gains
+        // distortion vertices
+        let mut maxVerts = 0;
+        for h in cellToChildren(0x8009fffffffffff, 1).unwrap() {
+            maxVerts = maxVerts.max(cellToBoundary(h).unwrap().numVerts);
+        }
+        assert!(maxVerts > 6, "distortion vertices are added");
+
+        let mut bad: H3Index = 0x85283473fffffff;
+        H3_SET_BASE_CELL(&mut bad, NUM_BASE_CELLS + 1);
+        assert_eq!(cellToBoundary(bad), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn cellAzimuthRads_directions() {
+        let south = latLngToCell(&LatLng::from_degrees(10.0, 20.0), 5).unwrap();
+        let north = latLngToCell(&LatLng::from_degrees(20.0, 20.0), 5).unwrap();
+        let east = latLngToCell(&LatLng::from_degrees(10.0, 30.0), 5).unwrap();
+        // cell centers are a few km from the points, so allow for that
+        let close = |a: f64, b: f64| {
+            let d = _posAngleRads(a - b);
+            d.min(M_2PI - d) < 0.02
+        };
+        assert!(
+            close(cellAzimuthRads(south, north).unwrap(), 0.0),
+            "due north"
+        );
+        assert!(
+            close(cellAzimuthRads(north, south).unwrap(), PI),
+            "due south"
+        );
+        let az = cellAzimuthRads(south, east).unwrap();
+        assert!(
+            az > 1.4 && az < FRAC_PI_2,
+            "east, curving north of the parallel"
+        );
+        assert_eq!(cellAzimuthRads(south, south), Ok(0.0), "same cell");
+        assert_eq!(
+            cellAzimuthRads(south, childPosToCell(0, south, 9).unwrap()),
+            Ok(0.0),
+            "same center"
+        );
+        // the res 3 center is up to a hundred km away
+        let az = cellAzimuthRads(cellToParent(north, 3).unwrap(), south).unwrap();
+        assert!((az - PI).abs() < 0.15, "mixed resolutions");
+        assert_eq!(cellAzimuthRads(south, 0), Err(Error::CellInvalid));
+        assert_eq!(cellAzimuthRads(0, south), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn cellToVec3d_center() {
+        let h = 0x85283473fffffff;
+        let v = cellToVec3d(h).unwrap();
+        assert!(
+            ((v.x * v.x + v.y * v.y + v.z * v.z) - 1.0).abs() < 1e-12,
+            "on the unit sphere"
+        );
+        assert!(
+            geoAlmostEqualThreshold(&_vec3dToGeo(&v), &cellToLatLng(h).unwrap(), 1e-12),
+            "same point as the center"
+        );
+        let ecef = cellToEcefKm(h).unwrap();
+        assert!(
+            (ecef.z - v.z * EARTH_RADIUS_KM).abs() < 1e-9,
+            "scaled to km"
+        );
+        assert!(
+            ecef.x < 0.0 && ecef.y < 0.0 && ecef.z > 0.0,
+            "california octant"
+        );
+
+        let north =
+            cellToVec3d(latLngToCell(&LatLng::from_degrees(90.0, 0.0), 5).unwrap()).unwrap();
+        assert!(north.z > 0.99, "towards the north pole");
+        let mut bad: H3Index = h;
+        H3_SET_BASE_CELL(&mut bad, NUM_BASE_CELLS + 1);
+        assert_eq!(cellToVec3d(bad), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn cellToBoundaryDensified_edges() {
+        for h in [0x8009fffffffffff, 0x81083ffffffffff, 0x85283473fffffff] {
+            let cb = cellToBoundary(h).unwrap();
+            assert_eq!(
+                cellToBoundaryDensified(h, 1).unwrap(),
+                cb.to_vec(),
+                "one segment is the plain boundary"
+            );
+
+            let dense = cellToBoundaryDensified(h, 4).unwrap();
+            assert_eq!(dense.len(), cb.numVerts * 4, "3 points added per edge");
+            for i in 0..cb.numVerts {
+                let a = cb.verts[i];
+                let b = cb.verts[(i + 1) % cb.numVerts];
+                assert_eq!(dense[i * 4], a, "vertices are kept");
+                let mut va = Vec3d {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                };
+                let mut vb = va;
+                _geoToVec3d(&a, &mut va);
+                _geoToVec3d(&b, &mut vb);
+                let normal = _vec3dCross(&va, &vb);
+                for p in &dense[i * 4 + 1..i * 4 + 4] {
+                    let mut vp = va;
+                    _geoToVec3d(p, &mut vp);
+                    assert!(
+                        _vec3dDot(&normal, &vp).abs() < 1e-12,
+                        "points are on the great circle of the edge"
+                    );
+                }
+                let len = greatCircleDistanceRads(&a, &b);
+                assert!(
+                    (greatCircleDistanceRads(&a, &dense[i * 4 + 2]) - len / 2.0).abs() < 1e-12,
+                    "points are evenly spaced"
+                );
+            }
+        }
+
+        assert_eq!(
+            cellToBoundaryDensified(0x85283473fffffff, 0),
+            Err(Error::Domain),
+            "needs a segment per edge"
+        );
+        assert_eq!(
+            cellToBoundaryDensified(0x85283473fffffff, MAX_SEGMENTS_PER_EDGE)
+                .unwrap()
+                .len(),
+            6 * MAX_SEGMENTS_PER_EDGE as usize,
+            "up to the limit"
+        );
+        assert_eq!(
+            cellToBoundaryDensified(0x85283473fffffff, u32::MAX),
+            Err(Error::Domain),
+            "too many segments"
+        );
+        let mut bad: H3Index = 0x85283473fffffff;
+        H3_SET_BASE_CELL(&mut bad, NUM_BASE_CELLS + 1);
+        assert_eq!(
+            cellToBoundaryDensified(bad, 4),
+            Err(Error::CellInvalid),
+            "invalid cell"
+        );
+    }
+
+    #[test]
+    #[allow(
+        clippy::approx_constant,
+        reason = "the C test suite uses this truncated pi"
+    )]
+    fn cellToParent_ancestorsForEachRes() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: 2.0 * 3.14159 - 2.1364398519396,
+        };
+        for res in 1..15 {
+            for step in 0..res {
+                let child = latLngToCell(&sf, res).unwrap();
+                let parent = cellToParent(child, res - step).unwrap();
+                let comparisonParent = latLngToCell(&sf, res - step).unwrap();
+                assert_eq!(parent, comparisonParent, "Got expected parent");
+            }
+        }
+    }
+
+    #[test]
+    #[allow(
+        clippy::approx_constant,
+        reason = "the C test suite uses this truncated pi"
+    )]
+    fn cellToParent_invalidInputs() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: 2.0 * 3.14159 - 2.1364398519396,
+        };
+        let child = latLngToCell(&sf, 5).unwrap();
+        assert_eq!(
+            cellToParent(child, 6),
+            Err(Error::ResMismatch),
+            "Higher resolution fails"
+        );
+        assert_eq!(
+            cellToParent(child, -1),
+            Err(Error::ResDomain),
+            "Invalid resolution fails"
+        );
+        assert_eq!(
+            cellToParent(child, 15),
+            Err(Error::ResMismatch),
+            "Invalid resolution fails"
+        );
+        assert_eq!(
+            cellToParent(child, 16),
+            Err(Error::ResDomain),
+            "Invalid resolution fails"
+        );
+    }
+
+    #[test]
+    #[allow(
+        clippy::approx_constant,
+        reason = "the C test suite uses this truncated pi"
+    )]
+    fn compactCells_roundtrip() {
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: 2.0 * 3.14159 - 2.1364398519396,
+        };
+        let k = 9;
+        let hexCount = crate::algos::maxGridDiskSize(k);
+        let expectedCompactCount = 73;
+
+        // Generate a set of hexagons to compact
+        let sfHex = latLngToCell(&sf, 9).unwrap();
+        let sunnyvaleExpanded: Vec<H3Index> = crate::algos::gridDiskDistances(sfHex, k)
+            .unwrap()
+            .iter()
+            .map(|(h, _)| *h)
+            .collect();
+        assert_eq!(sunnyvaleExpanded.len(), hexCount, "disk has no pentagons");
+
+        let compressed = compactCells(&sunnyvaleExpanded).unwrap();
+        assert_eq!(
+            compressed.len(),
+            expectedCompactCount,
+            "got expected compacted count"
+        );
+
+        let mut decompressed = uncompactCells(&compressed, 9).unwrap();
+        assert_eq!(
+            uncompactCellsSize(&compressed, 9),
+            Ok(hexCount as i64),
+            "got expected uncompacted size"
+        );
+        let mut expected = sunnyvaleExpanded.clone();
+        expected.sort();
+        decompressed.sort();
+        assert_eq!(decompressed, expected, "got expected cells back");
+    }
+
+    #[test]
+    fn compactCells_res0() {
+        let mut res0Hexes: Vec<H3Index> = Vec::new();
+        for i in 0..NUM_BASE_CELLS {
+            let mut h: H3Index = 0;
+            setH3Index(&mut h, 0, i, 0);
+            res0Hexes.push(h);
+        }
+        let compressed = compactCells(&res0Hexes).unwrap();
+        assert_eq!(compressed, res0Hexes, "got expected compacted cells");
+    }
+
+    #[test]
+    fn compactCells_uncompactable() {
+        let hexagons: Vec<H3Index> = vec![0x8a28308280fffff, 0x8a28308280bffff, 0x8a28308280effff];
+        let compressed = compactCells(&hexagons).unwrap();
+        assert_eq!(compressed, hexagons, "got expected uncompacted cells");
+    }
+
+    #[test]
+    fn compactCells_duplicateMinimum() {
+        // Test that the minimum number of duplicate hexagons causes failure
+        let res = 10;
+        // Arbitrary index
+        let mut h3: H3Index = 0;
+        setH3Index(&mut h3, res, 0, 2);
+        let mut children = cellToChildren(h3, res + 1).unwrap();
+        // duplicate one index
+        children.push(children[0]);
+        assert_eq!(
+            compactCells(&children),
+            Err(Error::DuplicateInput),
+            "compactCells fails on duplicate input"
+        );
+    }
+
+    #[test]
+    fn compactCells_duplicatePentagonLimit() {
+        // Test that the minimum number of duplicate hexagons causes failure
+        let res = 10;
+        // Arbitrary pentagon
+        let mut h3: H3Index = 0;
+        setH3Index(&mut h3, res, 4, 0);
+        let mut children = cellToChildren(h3, res + 1).unwrap();
+        // duplicate one index
+        let mut duplicate: H3Index = h3;
+        H3_SET_RESOLUTION(&mut duplicate, res + 1);
+        H3_SET_INDEX_DIGIT(&mut duplicate, res + 1, Direction::JAxesDigit as i32);
+        children.push(duplicate);
+        assert_eq!(
+            compactCells(&children),
+            Err(Error::DuplicateInput),
+            "compactCells fails on duplicate input (pentagon parent)"
+        );
+    }
+
+    #[test]
+    fn compactCells_empty() {
+        assert_eq!(
+            compactCells(&[]),
+            Ok(Vec::new()),
+            "compactCells succeeds on empty input"
+        );
+    }
+
+    #[test]
+    fn compactCells_reservedBitsSet() {
+        let mut h: H3Index = 0;
+        setH3Index(&mut h, 1, 10, 0);
+        let mut cells = vec![h; 7];
+        H3_SET_RESERVED_BITS(&mut cells[1], 1);
+        assert_eq!(
+            compactCells(&cells),
+            Err(Error::CellInvalid),
+            "compactCells fails on reserved bits"
+        );
+    }
+
+    #[test]
+    fn compactCells_parentError() {
+        let mut res0: H3Index = 0;
+        setH3Index(&mut res0, 0, 0, 0);
+        let mut res2: H3Index = 0;
+        setH3Index(&mut res2, 2, 0, 0);
+        assert_eq!(
+            compactCells(&[res2, res0]),
+            Err(Error::ResMismatch),
+            "compactCells fails on mixed resolutions"
+        );
+    }
+
+    #[test]
+    fn uncompactCells_wrongRes() {
+        let mut someHexagons: Vec<H3Index> = Vec::new();
+        for i in 0..NUM_BASE_CELLS {
+            let mut h: H3Index = 0;
+            setH3Index(&mut h, 5, i, 0);
+            someHexagons.push(h);
+        }
+        assert_eq!(
+            uncompactCellsSize(&someHexagons, 4),
+            Err(Error::ResMismatch),
+            "uncompactCellsSize fails when given illogical resolutions"
+        );
+        assert_eq!(
+            uncompactCells(&someHexagons, 4),
+            Err(Error::ResMismatch),
+            "uncompactCells fails when given illogical resolutions"
+        );
+        assert_eq!(
+            uncompactCells(&someHexagons, -1),
+            Err(Error::ResMismatch),
+            "uncompactCells fails when given illogical resolutions"
+        );
+        assert_eq!(
+            uncompactCells(&someHexagons, MAX_H3_RES + 1),
+            Err(Error::ResMismatch),
+            "uncompactCells fails when given illegal resolutions"
+        );
+    }
+
+    #[test]
+    fn uncompactCells_onlyZero() {
+        // 0 is a special index and should be skipped
+        assert_eq!(uncompactCellsSize(&[0], 0), Ok(0), "got expected size");
+        assert_eq!(uncompactCells(&[0], 0), Ok(Vec::new()), "got no cells");
+    }
+
+    #[test]
+    fn uncompactCells_tooLarge() {
+        // every base cell to res 15 is petabytes, more than can be addressed
+        let res0 = crate::base_cells::getRes0Cells().unwrap();
+        assert_eq!(
+            uncompactCells(&res0, MAX_H3_RES),
+            Err(Error::MemoryBounds),
+            "fails rather than aborting on a huge expansion"
+        );
+    }
+
+    #[test]
+    fn uncompactCells_withZero() {
+        let mut h: H3Index = 0;
+        setH3Index(&mut h, 1, 4, 0);
+        let cells = [h, 0];
+        assert_eq!(
+            uncompactCellsSize(&cells, 2),
+            Ok(6),
+            "pentagon has 6 children"
+        );
+        assert_eq!(
+            uncompactCells(&cells, 2).unwrap(),
+            cellToChildren(h, 2).unwrap(),
+            "zero is skipped"
+        );
+        assert_eq!(uncompactCells(&cells, 1), Ok(vec![h]), "same resolution");
+    }
+
+    #[test]
+    fn uncompactCells_pentagon() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 1, 4, 0);
+        for res in 1..8 {
+            let expected = if res == 1 {
+                1
+            } else {
+                1 + 5 * (7i64.pow(res as u32 - 1) - 1) / 6
+            };
+            assert_eq!(
+                uncompactCellsSize(&[pentagon], res),
+                Ok(expected),
+                "got expected size for pentagon"
+            );
+            assert_eq!(
+                uncompactCells(&[pentagon], res).unwrap().len() as i64,
+                expected,
+                "got expected number of children"
+            );
+        }
+    }
+
+    #[test]
+    fn h3ToString_roundTrip() {
+        let h: H3Index = 0x8928308280fffff;
+        assert_eq!(h3ToString(h), "8928308280fffff", "canonical string form");
+        assert_eq!(
+            stringToH3("8928308280fffff"),
+            Ok(h),
+            "parses canonical form"
+        );
+        assert_eq!(
+            stringToH3(" 0x8928308280FFFFF\n"),
+            Ok(h),
+            "parses C-style input"
+        );
+        assert_eq!(h3ToString(0), "0", "null index prints as zero");
+        assert_eq!(
+            stringToH3(&h3ToString(u64::MAX)),
+            Ok(u64::MAX),
+            "all 64 bits survive a round trip"
+        );
+    }
+
+    #[test]
+    fn h3ArrayBytes_roundTrip() {
+        let h3s: [H3Index; 3] = [0x85283473fffffff, 0x8f2830828052d25, H3_NULL];
+        let be = h3ArrayToBeBytes(&h3s);
+        assert_eq!(be.len(), 24, "8 bytes each");
+        assert_eq!(
+            &be[..8],
+            &[0x08, 0x52, 0x83, 0x47, 0x3f, 0xff, 0xff, 0xff],
+            "network order"
+        );
+        assert_eq!(beBytesToH3Array(&be).unwrap(), h3s, "big endian round trip");
+
+        let le = h3ArrayToLeBytes(&h3s);
+        assert_eq!(
+            &le[..8],
+            &[0xff, 0xff, 0xff, 0x3f, 0x47, 0x83, 0x52, 0x08],
+            "little endian"
+        );
+        assert_eq!(
+            leBytesToH3Array(&le).unwrap(),
+            h3s,
+            "little endian round trip"
+        );
+
+        assert_eq!(beBytesToH3Array(&[]), Ok(vec![]), "empty");
+        assert_eq!(beBytesToH3Array(&be[..23]), Err(Error::Domain), "truncated");
+        assert_eq!(leBytesToH3Array(&le[1..]), Err(Error::Domain), "truncated");
+    }
+
+    #[test]
+    fn stringToH3_invalid() {
+        for s in [
+            "",
+            "0x",
+            "zzz",
+            "-1",
+            "+1",
+            "8928308280fffff0000",
+            "89283 08280fffff",
+        ] {
+            assert_eq!(stringToH3(s), Err(Error::Failed), "{:?} is rejected", s);
+        }
+    }
+
+    #[test]
+    fn h3Hex_displayFromStr() {
+        let h = H3Hex(0x85283473fffffff);
+        assert_eq!(h.to_string(), "85283473fffffff", "Display uses hex");
+        assert_eq!(
+            "85283473fffffff".parse::<H3Hex>(),
+            Ok(h),
+            "FromStr parses hex"
+        );
+        assert_eq!(H3Index::from(h), 0x85283473fffffff, "unwraps to the index");
+        assert!(
+            "not an index".parse::<H3Hex>().is_err(),
+            "FromStr rejects garbage"
+        );
+    }
+
+    #[test]
+    fn cellToChildrenInto() {
+        let h: H3Index = 0x85283473fffffff;
+        let mut out = [H3_NULL; 64];
+        let n = super::cellToChildrenInto(h, 7, &mut out).unwrap();
+        assert_eq!(n, 49, "wrote every child");
+        assert_eq!(
+            out[..n].to_vec(),
+            cellToChildren(h, 7).unwrap(),
+            "same children as cellToChildren"
+        );
+        assert_eq!(out[n], H3_NULL, "rest of the buffer untouched");
+
+        assert_eq!(
+            super::cellToChildrenInto(h, 7, &mut out[..48]),
+            Err(Error::MemoryBounds),
+            "short buffer fails"
+        );
+        assert_eq!(
+            super::cellToChildrenInto(h, 4, &mut out),
+            Err(Error::ResDomain),
+            "coarser res fails"
+        );
+
+        let pentagon: H3Index = 0x8009fffffffffff;
+        let n = super::cellToChildrenInto(pentagon, 2, &mut out).unwrap();
+        assert_eq!(n, 41, "pentagon children");
+    }
+
+    #[test]
+    fn uncompactCellsInto() {
+        let compacted = [0x85283473fffffffu64, H3_NULL, 0x8009fffffffffff];
+        let expected = uncompactCells(&compacted, 6).unwrap();
+        let mut out = vec![H3_NULL; expected.len()];
+        let n = super::uncompactCellsInto(&compacted, 6, &mut out).unwrap();
+        assert_eq!(n, expected.len(), "wrote every cell");
+        assert_eq!(out, expected, "same cells as uncompactCells");
+
+        assert_eq!(
+            super::uncompactCellsInto(&compacted, 6, &mut out[1..]),
+            Err(Error::MemoryBounds),
+            "short buffer fails"
+        );
+        assert_eq!(
+            super::uncompactCellsInto(&compacted, 4, &mut out),
+            Err(Error::ResMismatch),
+            "coarser res fails"
+        );
+    }
+
+    #[test]
+    fn getIcosahedronFaces() {
+        assert_eq!(
+            super::getIcosahedronFaces(0x85283473fffffff).map(|faces| faces.len()),
+            Ok(1),
+            "single face hexagon"
+        );
+        for res in 0..=2 {
+            for h in crate::iterators::IterCellsResolution::from_res(res) {
+                let faces = super::getIcosahedronFaces(h).unwrap();
+                let maxFaces = maxFaceCount(h).unwrap() as usize;
+                assert!(faces.len() <= maxFaces, "at most maxFaceCount faces");
+                if isPentagon(h) {
+                    assert_eq!(faces.len(), 5, "pentagons touch five faces");
+                }
+
+                // points just inside the boundary are on one of the faces
+                let center = cellToLatLng(h).unwrap();
+                let boundary = cellToBoundary(h).unwrap();
+                for v in &boundary.verts[..boundary.numVerts] {
+                    let inside = greatCircleInterpolate(&center, v, 0.95).unwrap();
+                    let mut face = INVALID_FACE;
+                    let mut sqd: f64 = 0.0;
+                    crate::face_ijk::_geoToClosestFace(&inside, &mut face, &mut sqd);
+                    assert!(faces.contains(&face), "face of {:x} is found", h);
+                }
+            }
+        }
+        assert_eq!(super::getIcosahedronFaces(0), Err(Error::CellInvalid));
+    }
+
+    #[test]
+    fn cellToChildPos() {
+        let mut pentagon: H3Index = H3_NULL;
+        setH3Index(&mut pentagon, 2, 4, 0);
+        for parent in [0x85283473fffffffu64, pentagon, 0x8009fffffffffff] {
+            let parentRes = H3_GET_RESOLUTION(parent);
+            for childRes in parentRes..=(parentRes + 3) {
+                for (pos, child) in IterCellsChildren::from_parent(parent, childRes).enumerate() {
+                    assert_eq!(
+                        super::cellToChildPos(child, parentRes),
+                        Ok(pos as i64),
+                        "position in child order"
+                    );
+                    assert_eq!(childPosToCell(pos as i64, parent, childRes), Ok(child));
+                }
+            }
+        }
+        assert_eq!(
+            super::cellToChildPos(0x85283473fffffff, 6),
+            Err(Error::ResMismatch),
+            "parent finer than child"
+        );
+    }
 }
@@ -0,0 +1,188 @@
+// The icosahedron the grid is projected from, for custom projections and
+// debugging output that need the same face geometry as the indexing.
+
+use crate::constants::NUM_ICOSA_FACES;
+use crate::error::Error;
+use crate::face_ijk::{_faceVertexes, _geoToClosestFace, faceCenterGeo};
+use crate::lat_lng::{_densifyLoop, LatLng};
+use crate::polygon::GeoLoop;
+
+/**
+ * Finds the icosahedron face whose center is closest to a point, which is the
+ * face the point is projected onto when indexing it.
+ *
+ * @param g The point, in radians. Coordinates outside the usual ranges are
+ *          normalized first.
+ * @return The face, from 0 to 19, and the squared euclidean distance on the
+ *         unit sphere from the point to the face center, or LatLngDomain if
+ *         the point is not finite.
+ */
+pub fn closestFace(g: &LatLng) -> Result<(i32, f64), Error> {
+    if !g.lat.is_finite() || !g.lng.is_finite() {
+        return Err(Error::LatLngDomain);
+    }
+    let mut face: i32 = 0;
+    let mut sqd: f64 = 0.0;
+    _geoToClosestFace(&g.normalized(), &mut face, &mut sqd);
+    Ok((face, sqd))
+}
+
+/**
+ * Returns the center of an icosahedron face.
+ *
+ * @param face The face, from 0 to 19.
+ * @return The center of the face, or Domain if the face is invalid.
+ */
+pub fn faceCenter(face: i32) -> Result<LatLng, Error> {
+    if !(0..NUM_ICOSA_FACES).contains(&face) {
+        return Err(Error::Domain);
+    }
+    Ok(faceCenterGeo[face as usize])
+}
+
+/**
+ * Returns the vertexes of an icosahedron face, which are the centers of the
+ * pentagons at its corners.
+ *
+ * @param face The face, from 0 to 19.
+ * @return The vertexes, counterclockwise, or Domain if the face is invalid.
+ */
+pub fn faceVertexes(face: i32) -> Result<[LatLng; 3], Error> {
+    if !(0..NUM_ICOSA_FACES).contains(&face) {
+        return Err(Error::Domain);
+    }
+    Ok(_faceVertexes(face))
+}
+
+/**
+ * Returns the boundary of an icosahedron face as a loop, such as for drawing
+ * the faces over a map. The edges of a face are great circle arcs, so each
+ * is split into segments, which keeps them close to the arc when the loop is
+ * drawn or used as a polygon in lat/lng.
+ *
+ * @param face The face, from 0 to 19.
+ * @param segmentsPerEdge The number of segments for each edge; 1 gives the
+ *                        three vertexes.
+ * @return The counterclockwise loop, or Domain if the face is invalid or
+ *         segmentsPerEdge is 0 or more than MAX_SEGMENTS_PER_EDGE.
+ */
+pub fn faceBoundary(face: i32, segmentsPerEdge: u32) -> Result<GeoLoop, Error> {
+    Ok(GeoLoop {
+        verts: _densifyLoop(&faceVertexes(face)?, segmentsPerEdge)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base_cells::getPentagons;
+    use crate::bench_utils::randomLatLngs;
+    use crate::h3_index::cellToLatLng;
+    use crate::lat_lng::{geoAlmostEqual, greatCircleDistanceRads};
+    use crate::vec3d::{Vec3d, _geoToVec3d, _pointSquareDist, _vec3dCross, _vec3dDot};
+
+    use super::*;
+
+    fn minus(a: &Vec3d, b: &Vec3d) -> Vec3d {
+        Vec3d {
+            x: a.x - b.x,
+            y: a.y - b.y,
+            z: a.z - b.z,
+        }
+    }
+
+    fn toVec3d(g: &LatLng) -> Vec3d {
+        let mut v = Vec3d {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        _geoToVec3d(g, &mut v);
+        v
+    }
+
+    #[test]
+    fn faceGeometry() {
+        let pentagonCenters: Vec<LatLng> = getPentagons(0)
+            .unwrap()
+            .iter()
+            .map(|&h| cellToLatLng(h).unwrap())
+            .collect();
+        for face in 0..NUM_ICOSA_FACES {
+            let center = faceCenter(face).unwrap();
+            let verts = faceVertexes(face).unwrap();
+            let d0 = greatCircleDistanceRads(&center, &verts[0]);
+            for v in &verts {
+                assert!(
+                    pentagonCenters.iter().any(|p| geoAlmostEqual(p, v)),
+                    "vertex is a pentagon center"
+                );
+                assert!(
+                    (greatCircleDistanceRads(&center, v) - d0).abs() < 1e-12,
+                    "vertexes are equidistant from the center"
+                );
+            }
+
+            let boundary = faceBoundary(face, 4).unwrap();
+            assert_eq!(boundary.verts.len(), 12, "4 segments per edge");
+            assert!(
+                geoAlmostEqual(&boundary.verts[4], &verts[1]),
+                "second vertex"
+            );
+            // counterclockwise seen from outside the sphere, around the center
+            let [a, b, c] = verts.map(|v| toVec3d(&v));
+            let normal = _vec3dCross(&minus(&b, &a), &minus(&c, &a));
+            assert!(
+                _vec3dDot(&normal, &toVec3d(&center)) > 0.0,
+                "counterclockwise"
+            );
+        }
+        assert_eq!(faceCenter(20), Err(Error::Domain));
+        assert_eq!(faceVertexes(-1), Err(Error::Domain));
+        assert_eq!(faceBoundary(0, 0).map(|_| ()), Err(Error::Domain));
+        assert_eq!(
+            faceBoundary(0, u32::MAX).map(|_| ()),
+            Err(Error::Domain),
+            "too many segments"
+        );
+    }
+
+    #[test]
+    fn closestFaceIsNearest() {
+        for (f, center) in faceCenterGeo.iter().enumerate() {
+            let (face, sqd) = closestFace(center).unwrap();
+            assert_eq!(face, f as i32, "face of its own center");
+            assert!(sqd < 1e-24, "at the center");
+        }
+
+        for g in randomLatLngs(3, 1000) {
+            let (face, sqd) = closestFace(&g).unwrap();
+            assert!((0..NUM_ICOSA_FACES).contains(&face), "valid face");
+            let v = toVec3d(&g);
+            let expected = _pointSquareDist(toVec3d(&faceCenterGeo[face as usize]), v);
+            assert!(
+                (sqd - expected).abs() < 1e-12,
+                "distance to the face center"
+            );
+            for center in &faceCenterGeo {
+                assert!(
+                    sqd <= _pointSquareDist(toVec3d(center), v) + 1e-12,
+                    "no face center is closer"
+                );
+            }
+        }
+
+        let past = LatLng::from_degrees(100.0, 10.0);
+        assert_eq!(
+            closestFace(&past).unwrap().0,
+            closestFace(&LatLng::from_degrees(80.0, -170.0)).unwrap().0,
+            "normalized"
+        );
+        assert_eq!(
+            closestFace(&LatLng {
+                lat: f64::NAN,
+                lng: 0.0
+            }),
+            Err(Error::LatLngDomain)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+// Inspection of the raw fields of an H3 index, for tooling that needs to
+// examine indexes which may not be valid cells.
+
+pub use crate::constants::{H3_CELL_MODE, H3_DIRECTEDEDGE_MODE, H3_EDGE_MODE, H3_VERTEX_MODE};
+pub use crate::coord_ijk::Direction;
+use crate::constants::MAX_H3_RES;
+use crate::error::Error;
+use crate::h3_index::{H3Index, H3_GET_INDEX_DIGIT, H3_GET_MODE, H3_GET_RESERVED_BITS};
+
+/**
+ * Returns the mode of an H3 index, such as H3_CELL_MODE or
+ * H3_DIRECTEDEDGE_MODE. The mode is not checked, so it may be any value from
+ * 0 to 15.
+ *
+ * @param h The H3 index.
+ * @return The mode bits.
+ */
+pub fn getMode(h: H3Index) -> i32 {
+    H3_GET_MODE(h)
+}
+
+/**
+ * Returns the reserved bits of an H3 index. These are zero for cells, and
+ * hold the edge direction or vertex number of directed edges and vertexes.
+ *
+ * @param h The H3 index.
+ * @return The reserved bits.
+ */
+pub fn getReservedBits(h: H3Index) -> i32 {
+    H3_GET_RESERVED_BITS(h)
+}
+
+/**
+ * Returns the digit of an H3 index at a resolution. Digits finer than the
+ * resolution of the index are InvalidDigit in valid indexes.
+ *
+ * @param h The H3 index.
+ * @param res The resolution of the digit, from 1 to 15.
+ * @return The digit, or ResDomain if res is out of range.
+ */
+pub fn getIndexDigit(h: H3Index, res: i32) -> Result<Direction, Error> {
+    if !(1..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    Ok(H3_GET_INDEX_DIGIT(h, res))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directed_edge::cellsToDirectedEdge;
+    use crate::algos::gridDisk;
+    use crate::h3_index::getResolution;
+
+    use super::*;
+
+    #[test]
+    fn inspectFields() {
+        let h: H3Index = 0x85283473fffffff;
+        assert_eq!(getMode(h), H3_CELL_MODE, "cell mode");
+        assert_eq!(getReservedBits(h), 0, "cells have no reserved bits");
+        let digits: Vec<Direction> = (1..=MAX_H3_RES)
+            .map(|r| getIndexDigit(h, r).unwrap())
+            .collect();
+        assert_eq!(
+            digits[..5],
+            [
+                Direction::CenterDigit,
+                Direction::IJAxesDigit,
+                Direction::IAxesDigit,
+                Direction::JKAxesDigit,
+                Direction::IAxesDigit,
+            ],
+            "digits of the cell"
+        );
+        assert!(
+            digits[getResolution(h) as usize..]
+                .iter()
+                .all(|&d| d == Direction::InvalidDigit),
+            "unused digits are 7"
+        );
+        assert_eq!(getIndexDigit(h, 0), Err(Error::ResDomain));
+        assert_eq!(getIndexDigit(h, 16), Err(Error::ResDomain));
+
+        let neighbor = gridDisk(h, 1).unwrap()[1];
+        let edge = cellsToDirectedEdge(h, neighbor).unwrap();
+        assert_eq!(getMode(edge), H3_DIRECTEDEDGE_MODE, "edge mode");
+        assert!(
+            (1..=6).contains(&getReservedBits(edge)),
+            "edges store their direction"
+        );
+    }
+}
@@ -1,26 +1,67 @@
+use core::iter::FusedIterator;
+
 use crate::{
-    constants::MAX_H3_RES,
+    constants::{MAX_H3_RES, NUM_BASE_CELLS},
     coord_ijk::Direction,
     h3_index::{
-        H3Index, _zeroIndexDigits, isPentagon, H3_GET_INDEX_DIGIT, H3_GET_RESOLUTION,
-        H3_PER_DIGIT_OFFSET, H3_SET_RESOLUTION,
+        H3Index, _zeroIndexDigits, isPentagon, setH3Index, H3_GET_INDEX_DIGIT, H3_GET_RESOLUTION,
+        H3_PER_DIGIT_OFFSET, H3_SET_INDEX_DIGIT, H3_SET_RESOLUTION,
     },
     H3_NULL,
 };
 
+/**
+ * Iterates over the children of a cell at a finer resolution, in ascending
+ * index order, without allocating. This is the streaming equivalent of
+ * cellToChildren.
+ *
+ * The number of remaining children is always known, so the iterator is
+ * exact-size, and it can also be consumed from the back. On 32-bit targets a
+ * span of more than 11 resolutions has more children than fit in a usize;
+ * size_hint then has no upper bound and len panics.
+ */
 pub struct IterCellsChildren {
     h: H3Index,
     _parentRes: i32,
     _skipDigit: i32,
+    /** the last child not yet produced from the back */
+    _back: H3Index,
+    /** children not yet produced from either end */
+    _remaining: u64,
 }
 
 impl IterCellsChildren {
-    pub fn from_parent(mut h: H3Index, childRes: i32) -> IterCellsChildren {
+    /**
+     * Iterates over the children of a cell.
+     *
+     * @param h The parent cell.
+     * @param childRes The resolution of the children.
+     * @return The iterator, which is empty if childRes is coarser than the
+     *         parent or invalid, or the parent is H3_NULL.
+     */
+    pub fn from_parent(h: H3Index, childRes: i32) -> IterCellsChildren {
+        if childRes > MAX_H3_RES || h == H3_NULL {
+            return Self::_null_iter();
+        }
+        Self::_from_parent_in_range(h, childRes)
+    }
+
+    /**
+     * Iterates over the children of a cell like from_parent, for a parent
+     * that is not H3_NULL and a child resolution already known to be at most
+     * 15.
+     *
+     * @param h The parent cell.
+     * @param childRes The resolution of the children.
+     * @return The iterator, which is empty if childRes is coarser than the
+     *         parent.
+     */
+    pub(crate) fn _from_parent_in_range(mut h: H3Index, childRes: i32) -> IterCellsChildren {
         //IterCellsChildren it;
 
         let _parentRes = H3_GET_RESOLUTION(h);
 
-        if childRes < _parentRes || childRes > MAX_H3_RES || h == H3_NULL {
+        if childRes < _parentRes {
             return Self::_null_iter();
         }
 
@@ -32,43 +73,110 @@ impl IterCellsChildren {
             false => -1,
         };
 
-        return IterCellsChildren {
+        let mut _back = h;
+        for res in (_parentRes + 1)..=childRes {
+            H3_SET_INDEX_DIGIT(&mut _back, res, Direction::IJAxesDigit as i32);
+        }
+
+        let n7 = 7u64.pow((childRes - _parentRes) as u32);
+        let _remaining = match _skipDigit {
+            -1 => n7,
+            // the center child, plus five of the six outer subtrees
+            _ => 1 + 5 * (n7 - 1) / 6,
+        };
+
+        IterCellsChildren {
             h,
             _parentRes,
             _skipDigit,
-        };
+            _back,
+            _remaining,
+        }
     }
 
-    pub fn _null_iter() -> IterCellsChildren {
-        return IterCellsChildren {
+    /**
+     * Iterates over the children of a base cell.
+     *
+     * @param baseCellNum The base cell number, 0 to 121.
+     * @param childRes The resolution of the children.
+     * @return The iterator, which is empty if either argument is invalid.
+     */
+    pub fn from_base_cell_num(baseCellNum: i32, childRes: i32) -> IterCellsChildren {
+        if !(0..NUM_BASE_CELLS).contains(&baseCellNum) {
+            return Self::_null_iter();
+        }
+        let mut baseCell: H3Index = 0;
+        setH3Index(&mut baseCell, 0, baseCellNum, 0);
+        Self::from_parent(baseCell, childRes)
+    }
+
+    fn _null_iter() -> IterCellsChildren {
+        IterCellsChildren {
             h: H3_NULL,
             _parentRes: -1,
             _skipDigit: -1,
-        };
+            _back: H3_NULL,
+            _remaining: 0,
+        }
     }
 
     // extract the `res` digit (0--7) of the current cell
-    pub fn _getResDigit(&self, res: i32) -> Direction {
-        return H3_GET_INDEX_DIGIT(self.h, res);
+    fn _getResDigit(&self, res: i32) -> Direction {
+        H3_GET_INDEX_DIGIT(self.h, res)
     }
 
     // increment the digit (0--7) at location `res`
     // H3_PER_DIGIT_OFFSET == 3
-    pub fn _incrementResDigit(&mut self, res: i32) {
+    fn _incrementResDigit(&mut self, res: i32) {
         let mut val: H3Index = 1;
         val <<= H3_PER_DIGIT_OFFSET * (MAX_H3_RES - res);
         self.h += val;
     }
+
+    // move `_back` to the previous child, skipping the deleted subtree of a
+    // pentagon
+    fn _stepBack(&mut self) {
+        let childRes = H3_GET_RESOLUTION(self._back);
+        for i in ((self._parentRes + 1)..=childRes).rev() {
+            let digit = H3_GET_INDEX_DIGIT(self._back, i) as i32;
+            if digit > 0 {
+                H3_SET_INDEX_DIGIT(&mut self._back, i, digit - 1);
+                break;
+            }
+            H3_SET_INDEX_DIGIT(&mut self._back, i, Direction::IJAxesDigit as i32);
+        }
+
+        if self._skipDigit == -1 {
+            return;
+        }
+        // Children of a pentagon never have 1 as their first nonzero digit
+        // below the parent. Going backwards, the child before such a subtree
+        // has 0 in that digit and 6 in all finer digits.
+        for i in (self._parentRes + 1)..=childRes {
+            let digit = H3_GET_INDEX_DIGIT(self._back, i);
+            if digit == Direction::CenterDigit {
+                continue;
+            }
+            if digit == Direction::PENTAGON_SKIPPED_DIGIT {
+                H3_SET_INDEX_DIGIT(&mut self._back, i, Direction::CenterDigit as i32);
+                for j in (i + 1)..=childRes {
+                    H3_SET_INDEX_DIGIT(&mut self._back, j, Direction::IJAxesDigit as i32);
+                }
+            }
+            break;
+        }
+    }
 }
 
 impl Iterator for IterCellsChildren {
     type Item = H3Index;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // once h == H3_NULL, the iterator returns an infinite sequence of H3_NULL
-        if self.h == H3_NULL {
+        // the front and back have met, or the iterator was empty to begin with
+        if self._remaining == 0 {
             return None;
         }
+        self._remaining -= 1;
 
         let ret = self.h;
 
@@ -80,7 +188,7 @@ impl Iterator for IterCellsChildren {
             //(int i = childRes; i >= it->_parentRes; i--) {
             if i == self._parentRes {
                 // if we're modifying the parent resolution digit, then we're done
-                *self = IterCellsChildren::_null_iter();
+                self.h = H3_NULL;
                 return Some(ret);
             }
 
@@ -104,6 +212,231 @@ impl Iterator for IterCellsChildren {
                 break;
             }
         }
-        return Some(ret);
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self._remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for IterCellsChildren {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self._remaining == 0 {
+            return None;
+        }
+        self._remaining -= 1;
+
+        let ret = self._back;
+        if self._remaining > 0 {
+            self._stepBack();
+        }
+        Some(ret)
+    }
+}
+
+/** Exact as long as the count fits in a usize, which it always does on 64-bit
+ * targets, as there are at most 7^15 children. */
+impl ExactSizeIterator for IterCellsChildren {
+    fn len(&self) -> usize {
+        usize::try_from(self._remaining).expect("more children than fit in a usize")
+    }
+}
+
+impl FusedIterator for IterCellsChildren {}
+
+/**
+ * Iterates over every cell at a resolution, in ascending base cell order and
+ * then in child order within each base cell. Cells are produced one at a time,
+ * so the whole grid can be streamed without building a vector.
+ */
+pub struct IterCellsResolution {
+    _baseCellNum: i32,
+    _res: i32,
+    _itC: IterCellsChildren,
+}
+
+impl IterCellsResolution {
+    /**
+     * Iterates over all cells at the given resolution.
+     *
+     * @param res The resolution.
+     * @return The iterator, which is empty if res is invalid.
+     */
+    pub fn from_res(res: i32) -> IterCellsResolution {
+        IterCellsResolution {
+            _baseCellNum: 0,
+            _res: res,
+            _itC: IterCellsChildren::from_base_cell_num(0, res),
+        }
+    }
+}
+
+impl Iterator for IterCellsResolution {
+    type Item = H3Index;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(h) = self._itC.next() {
+                return Some(h);
+            }
+            if self._baseCellNum >= NUM_BASE_CELLS - 1 {
+                return None;
+            }
+            self._baseCellNum += 1;
+            self._itC = IterCellsChildren::from_base_cell_num(self._baseCellNum, self._res);
+        }
+    }
+}
+
+/**
+ * Iterates over the children of a cell at a finer resolution together with
+ * their child positions, in ascending index order, without allocating. The
+ * positions are those of childPosToCell, which number the children in
+ * ascending index order, so they count up from 0.
+ *
+ * @param parent The parent cell.
+ * @param childRes The resolution of the children.
+ * @return The iterator over (position, child) pairs, which is empty under the
+ *         same conditions as IterCellsChildren::from_parent.
+ */
+pub fn children_with_pos(
+    parent: H3Index,
+    childRes: i32,
+) -> impl DoubleEndedIterator<Item = (i64, H3Index)> + ExactSizeIterator + FusedIterator {
+    IterCellsChildren::from_parent(parent, childRes)
+        .enumerate()
+        .map(|(pos, h)| (pos as i64, h))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::h3_index::{
+        cellToChildren, cellToChildrenSize, childPosToCell, getResolution, isValidCell,
+    };
+
+    use super::*;
+
+    #[test]
+    fn iterCellsResolution() {
+        for res in 0..4 {
+            let cells: Vec<H3Index> = IterCellsResolution::from_res(res).collect();
+            assert_eq!(
+                cells.len() as i64,
+                2 + 120 * 7i64.pow(res as u32),
+                "got every cell at res {}",
+                res
+            );
+            for (i, &h) in cells.iter().enumerate() {
+                assert!(isValidCell(h), "cell is valid");
+                assert_eq!(getResolution(h), res, "cell has the requested res");
+                if i > 0 {
+                    assert!(cells[i - 1] < h, "cells are ascending and unique");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iterCellsResolution_invalid() {
+        assert_eq!(
+            IterCellsResolution::from_res(-1).next(),
+            None,
+            "negative res"
+        );
+        assert_eq!(
+            IterCellsResolution::from_res(MAX_H3_RES + 1).next(),
+            None,
+            "res too fine"
+        );
+    }
+
+    #[test]
+    fn fromBaseCellNum_invalid() {
+        assert_eq!(IterCellsChildren::from_base_cell_num(-1, 1).next(), None);
+        assert_eq!(
+            IterCellsChildren::from_base_cell_num(NUM_BASE_CELLS, 1).next(),
+            None
+        );
+    }
+
+    #[test]
+    fn iterCellsChildren_exactSize() {
+        for parent in [0x85283473fffffffu64, 0x8009fffffffffff, 0x821c07fffffffff] {
+            let parentRes = getResolution(parent);
+            for childRes in parentRes..(parentRes + 4) {
+                let mut iter = IterCellsChildren::from_parent(parent, childRes);
+                let expected = cellToChildrenSize(parent, childRes).unwrap() as usize;
+                assert_eq!(iter.len(), expected, "len matches cellToChildrenSize");
+                iter.next();
+                assert_eq!(iter.len(), expected - 1, "len counts down");
+                assert_eq!(iter.count(), expected - 1, "len is exact");
+            }
+        }
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            IterCellsChildren::from_parent(0x8029fffffffffff, MAX_H3_RES).len(),
+            7usize.pow(15),
+            "largest count fits on 64-bit targets"
+        );
+    }
+
+    #[test]
+    fn childrenWithPos() {
+        for parent in [0x85283473fffffffu64, 0x8009fffffffffff, 0x821c07fffffffff] {
+            let parentRes = getResolution(parent);
+            for childRes in parentRes..(parentRes + 4) {
+                let pairs: Vec<(i64, H3Index)> = children_with_pos(parent, childRes).collect();
+                assert_eq!(
+                    pairs.len() as i64,
+                    cellToChildrenSize(parent, childRes).unwrap(),
+                    "one pair per child"
+                );
+                for &(pos, h) in &pairs {
+                    assert_eq!(
+                        childPosToCell(pos, parent, childRes),
+                        Ok(h),
+                        "position matches childPosToCell"
+                    );
+                }
+                let last = children_with_pos(parent, childRes).next_back();
+                assert_eq!(last, pairs.last().copied(), "positions from the back");
+            }
+        }
+        assert_eq!(children_with_pos(0x85283473fffffff, 4).len(), 0, "coarser");
+    }
+
+    #[test]
+    fn iterCellsChildren_doubleEnded() {
+        for parent in [0x85283473fffffffu64, 0x8009fffffffffff, 0x821c07fffffffff] {
+            let parentRes = getResolution(parent);
+            for childRes in parentRes..(parentRes + 4) {
+                let forward = cellToChildren(parent, childRes).unwrap();
+                let mut backward: Vec<H3Index> = IterCellsChildren::from_parent(parent, childRes)
+                    .rev()
+                    .collect();
+                backward.reverse();
+                assert_eq!(backward, forward, "reverse order matches forward order");
+
+                // consume from both ends until they meet
+                let mut iter = IterCellsChildren::from_parent(parent, childRes);
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                while let Some(h) = iter.next() {
+                    front.push(h);
+                    match iter.next_back() {
+                        Some(h) => back.push(h),
+                        None => break,
+                    }
+                }
+                back.reverse();
+                front.extend(back);
+                assert_eq!(front, forward, "both ends meet without overlap");
+                assert_eq!(iter.next(), None, "iterator is fused");
+            }
+        }
     }
 }
@@ -1,13 +1,19 @@
-use std::f64::consts::{FRAC_PI_2, PI};
+use core::f64::consts::{FRAC_PI_2, PI};
 
-use num::Float;
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
 
 use crate::constants::*;
+use crate::error::Error;
+use crate::prelude::*;
+use crate::vec3d::{Vec3d, _geoToVec3d, _vec3dToGeo};
 
 #[doc = " @struct LatLng"]
 #[doc = "@brief latitude/longitude in radians"]
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct LatLng {
     #[doc = "< latitude in radians"]
     pub lat: f64,
@@ -15,6 +21,83 @@ pub struct LatLng {
     pub lng: f64,
 }
 
+impl LatLng {
+    /**
+     * Creates coordinates from decimal degrees.
+     *
+     * @param lat The latitude in decimal degrees.
+     * @param lng The longitude in decimal degrees.
+     */
+    pub fn from_degrees(lat: f64, lng: f64) -> LatLng {
+        LatLng {
+            lat: lat.to_radians(),
+            lng: lng.to_radians(),
+        }
+    }
+
+    /** Returns the latitude in decimal degrees. */
+    pub fn lat_degrees(&self) -> f64 {
+        self.lat.to_degrees()
+    }
+
+    /** Returns the longitude in decimal degrees. */
+    pub fn lng_degrees(&self) -> f64 {
+        self.lng.to_degrees()
+    }
+
+    /**
+     * Returns the same point with its latitude in [-90, 90] degrees and its
+     * longitude in [-180, 180] degrees. A latitude past a pole continues down
+     * the other side, on the opposite meridian, so 100 degrees north at 10
+     * degrees east is 80 degrees north at 170 degrees west.
+     */
+    pub fn normalized(&self) -> LatLng {
+        let (lat, overPole) = _reflectLat(self.lat);
+        let lng = if overPole { self.lng + PI } else { self.lng };
+        LatLng {
+            lat,
+            lng: normalizeLng(lng),
+        }
+    }
+}
+
+/** Maximum number of cell boundary vertices; worst case is pentagon:
+ *  5 original verts + 5 edge crossings
+ */
+pub const MAX_CELL_BNDRY_VERTS: usize = 10;
+
+/** @struct CellBoundary
+    @brief cell boundary in latitude/longitude
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CellBoundary {
+    /** number of vertices */
+    pub numVerts: usize,
+    /** vertices in ccw order */
+    pub verts: [LatLng; MAX_CELL_BNDRY_VERTS],
+}
+
+impl Default for CellBoundary {
+    fn default() -> CellBoundary {
+        CellBoundary::new()
+    }
+}
+
+impl CellBoundary {
+    /** Creates an empty boundary. */
+    pub fn new() -> CellBoundary {
+        CellBoundary {
+            numVerts: 0,
+            verts: [LatLng { lat: 0.0, lng: 0.0 }; MAX_CELL_BNDRY_VERTS],
+        }
+    }
+
+    /** Returns the vertices in use as a vector. */
+    pub fn to_vec(&self) -> Vec<LatLng> {
+        self.verts[..self.numVerts].to_vec()
+    }
+}
+
 /** epsilon of ~0.1mm in degrees */
 const EPSILON_DEG: f64 = 0.000000001;
 /** epsilon of ~0.1mm in radians */
@@ -31,7 +114,7 @@ pub fn _posAngleRads(rads: f64) -> f64 {
     if rads >= M_2PI {
         tmp -= M_2PI;
     }
-    return tmp;
+    tmp
 }
 
 /**
@@ -45,7 +128,7 @@ pub fn _posAngleRads(rads: f64) -> f64 {
  *         of each other.
  */
 pub fn geoAlmostEqualThreshold(p1: &LatLng, p2: &LatLng, threshold: f64) -> bool {
-    return (p1.lat - p2.lat).abs() < threshold && (p1.lng - p2.lng).abs() < threshold;
+    (p1.lat - p2.lat).abs() < threshold && (p1.lng - p2.lng).abs() < threshold
 }
 
 /**
@@ -58,18 +141,20 @@ pub fn geoAlmostEqualThreshold(p1: &LatLng, p2: &LatLng, threshold: f64) -> bool
 *         of each other.
 */
 pub fn geoAlmostEqual(p1: &LatLng, p2: &LatLng) -> bool {
-    return geoAlmostEqualThreshold(p1, p2, EPSILON_RAD);
+    geoAlmostEqualThreshold(p1, p2, EPSILON_RAD)
 }
 
 /**
- * Set the components of spherical coordinates in decimal degrees.
+ * Set the components of spherical coordinates in decimal degrees. Prefer
+ * LatLng::from_degrees, which returns the coordinates directly.
  *
  * @param p The spherical coordinates.
  * @param latDegs The desired latitude in decimal degrees.
  * @param lngDegs The desired longitude in decimal degrees.
  */
+#[deprecated(note = "use LatLng::from_degrees")]
 pub fn setGeoDegs(p: &mut LatLng, lat_degs: f64, lng_degs: f64) {
-    _setGeoRads(p, lat_degs.to_radians(), lng_degs.to_radians());
+    *p = LatLng::from_degrees(lat_degs, lng_degs);
 }
 
 /**
@@ -84,6 +169,67 @@ pub fn _setGeoRads(p: &mut LatLng, lat_rads: f64, lng_rads: f64) {
     p.lng = lng_rads;
 }
 
+/**
+ * Reflects a latitude past a pole back into [-pi/2, pi/2].
+ *
+ * @param lat The latitude in radians.
+ * @return The reflected latitude, and whether it went over a pole an odd
+ *         number of times, in which case the longitude is on the opposite
+ *         meridian.
+ */
+fn _reflectLat(lat: f64) -> (f64, bool) {
+    if (-FRAC_PI_2..=FRAC_PI_2).contains(&lat) || !lat.is_finite() {
+        return (lat, false);
+    }
+    // into (-pi, pi], then reflect the parts past the poles
+    let mut lat = lat % M_2PI;
+    if lat > PI {
+        lat -= M_2PI;
+    } else if lat <= -PI {
+        lat += M_2PI;
+    }
+    if lat > FRAC_PI_2 {
+        return (PI - lat, true);
+    }
+    if lat < -FRAC_PI_2 {
+        return (-PI - lat, true);
+    }
+    (lat, false)
+}
+
+/**
+ * Normalizes a latitude into [-pi/2, pi/2], reflecting latitudes past a pole
+ * as if travelling over it. Unlike constrainLat, 100 degrees north becomes
+ * 80 degrees north rather than 80 degrees south. Travelling over a pole also
+ * moves to the opposite meridian, which LatLng::normalized accounts for.
+ *
+ * @param lat The latitude in radians.
+ * @return The normalized latitude in radians.
+ */
+pub fn normalizeLat(lat: f64) -> f64 {
+    _reflectLat(lat).0
+}
+
+/**
+ * Normalizes a longitude into [-pi, pi]. Unlike constrainLng, this takes
+ * constant time for longitudes of any magnitude.
+ *
+ * @param lng The longitude in radians.
+ * @return The normalized longitude in radians.
+ */
+pub fn normalizeLng(lng: f64) -> f64 {
+    if (-PI..=PI).contains(&lng) || !lng.is_finite() {
+        return lng;
+    }
+    let mut lng = lng % M_2PI;
+    if lng > PI {
+        lng -= M_2PI;
+    } else if lng < -PI {
+        lng += M_2PI;
+    }
+    lng
+}
+
 /**
  * constrainLat makes sure latitudes are in the proper bounds
  *
@@ -92,9 +238,9 @@ pub fn _setGeoRads(p: &mut LatLng, lat_rads: f64, lng_rads: f64) {
  */
 pub fn constrainLat(mut lat: f64) -> f64 {
     while lat > FRAC_PI_2 {
-        lat = lat - PI;
+        lat -= PI;
     }
-    return lat;
+    lat
 }
 
 /**
@@ -105,12 +251,12 @@ pub fn constrainLat(mut lat: f64) -> f64 {
  */
 pub fn constrainLng(mut lng: f64) -> f64 {
     while lng > PI {
-        lng = lng - (2.0 * PI);
+        lng -= 2.0 * PI;
     }
     while lng < -PI {
-        lng = lng + (2.0 * PI);
+        lng += 2.0 * PI;
     }
-    return lng;
+    lng
 }
 
 /**
@@ -121,9 +267,8 @@ pub fn constrainLng(mut lng: f64) -> f64 {
  * @return The azimuth in radians from p1 to p2.
  */
 pub fn _geoAzimuthRads(p1: &LatLng, p2: &LatLng) -> f64 {
-    return (p2.lat.cos() * (p2.lng - p1.lng).sin()).atan2(
-        p1.lat.cos() * p2.lat.sin() - p1.lat.sin() * p2.lat.cos() * (p2.lng - p1.lng).cos(),
-    );
+    (p2.lat.cos() * (p2.lng - p1.lng).sin())
+        .atan2(p1.lat.cos() * p2.lat.sin() - p1.lat.sin() * p2.lat.cos() * (p2.lng - p1.lng).cos())
 }
 
 /**
@@ -143,10 +288,6 @@ pub fn _geoAzDistanceRads(p1: &LatLng, mut az: f64, distance: f64) -> LatLng {
         return *p1;
     }
 
-    let mut sinlat = 0.0f64;
-    let mut sinlng = 0.0f64;
-    let mut coslng = 0.0f64;
-
     az = _posAngleRads(az);
 
     // check for due north/south azimuth
@@ -172,13 +313,9 @@ pub fn _geoAzDistanceRads(p1: &LatLng, mut az: f64, distance: f64) -> LatLng {
         }
     } else {
         // not due north or south
-        sinlat = (p1.lat).sin() * (distance).cos() + (p1.lat).cos() * (distance).sin() * (az).cos();
-        if sinlat > 1.0 {
-            sinlat = 1.0;
-        }
-        if sinlat < -1.0 {
-            sinlat = -1.0;
-        }
+        let sinlat = ((p1.lat).sin() * (distance).cos()
+            + (p1.lat).cos() * (distance).sin() * (az).cos())
+        .clamp(-1.0, 1.0);
         p2.lat = (sinlat).asin();
         if (p2.lat - FRAC_PI_2).abs() < EPSILON {
             // north pole
@@ -189,24 +326,369 @@ pub fn _geoAzDistanceRads(p1: &LatLng, mut az: f64, distance: f64) -> LatLng {
             p2.lat = -FRAC_PI_2;
             p2.lng = 0.0;
         } else {
-            sinlng = (az).sin() * (distance).sin() / (p2.lat).cos();
-            coslng = ((distance).cos() - (p1.lat).sin() * (p2.lat).sin())
+            let sinlng = ((az).sin() * (distance).sin() / (p2.lat).cos()).clamp(-1.0, 1.0);
+            let coslng = (((distance).cos() - (p1.lat).sin() * (p2.lat).sin())
                 / (p1.lat).cos()
-                / (p2.lat).cos();
-            if sinlng > 1.0 {
-                sinlng = 1.0;
-            }
-            if sinlng < -1.0 {
-                sinlng = -1.0;
-            }
-            if coslng > 1.0 {
-                coslng = 1.0;
-            }
-            if coslng < -1.0 {
-                coslng = -1.0;
-            }
+                / (p2.lat).cos())
+            .clamp(-1.0, 1.0);
             p2.lng = constrainLng(p1.lng + (sinlng.atan2(coslng)));
         }
     }
-    return p2;
+    p2
+}
+
+/**
+ * The great circle distance in radians between two spherical coordinates.
+ *
+ * This function uses the Haversine formula.
+ * For math details, see:
+ *     https://en.wikipedia.org/wiki/Haversine_formula
+ *     https://www.movable-type.co.uk/scripts/latlong.html
+ *
+ * @param  a  the first lat/lng pair (in radians)
+ * @param  b  the second lat/lng pair (in radians)
+ *
+ * @return    the great circle distance in radians between a and b
+ */
+pub fn greatCircleDistanceRads(a: &LatLng, b: &LatLng) -> f64 {
+    let sinLat = ((b.lat - a.lat) * 0.5).sin();
+    let sinLng = ((b.lng - a.lng) * 0.5).sin();
+
+    let A = sinLat * sinLat + a.lat.cos() * b.lat.cos() * sinLng * sinLng;
+
+    2.0 * A.sqrt().atan2((1.0 - A).sqrt())
+}
+
+/**
+ * The great circle distance in kilometers between two spherical coordinates.
+ */
+pub fn greatCircleDistanceKm(a: &LatLng, b: &LatLng) -> f64 {
+    greatCircleDistanceRads(a, b) * EARTH_RADIUS_KM
+}
+
+/**
+ * Computes the azimuth, or initial bearing, of the great circle arc from one
+ * point to another.
+ *
+ * @param a The origin spherical coordinates.
+ * @param b The destination spherical coordinates.
+ * @return The azimuth in radians clockwise from true north, in [0, 2pi).
+ */
+pub fn azimuth(a: &LatLng, b: &LatLng) -> f64 {
+    _posAngleRads(_geoAzimuthRads(a, b))
+}
+
+/**
+ * Computes the azimuth, or initial bearing, of the great circle arc from one
+ * point to another in degrees.
+ *
+ * @param a The origin spherical coordinates.
+ * @param b The destination spherical coordinates.
+ * @return The azimuth in degrees clockwise from true north, in [0, 360).
+ */
+pub fn azimuthDegs(a: &LatLng, b: &LatLng) -> f64 {
+    azimuth(a, b) * M_180_PI
+}
+
+/**
+ * Computes the point reached by travelling along a great circle from a point
+ * with a given initial azimuth, for a given distance.
+ *
+ * @param p The origin spherical coordinates.
+ * @param azimuth The initial azimuth in radians clockwise from true north.
+ * @param distance The great circle distance in radians, must be non-negative.
+ * @return The destination spherical coordinates, or Domain if the azimuth or
+ *         distance are not finite or the distance is negative.
+ */
+pub fn destination(p: &LatLng, azimuth: f64, distance: f64) -> Result<LatLng, Error> {
+    if !azimuth.is_finite() || !distance.is_finite() || distance < 0.0 {
+        return Err(Error::Domain);
+    }
+    if !p.lat.is_finite() || !p.lng.is_finite() {
+        return Err(Error::LatLngDomain);
+    }
+    Ok(_geoAzDistanceRads(p, azimuth, distance))
+}
+
+/**
+ * Computes the point reached by travelling along a great circle from a point
+ * with a given initial azimuth, for a given distance, both in degrees.
+ *
+ * @param p The origin spherical coordinates.
+ * @param azimuthDegs The initial azimuth in degrees clockwise from true north.
+ * @param distanceDegs The great circle distance in degrees of arc, must be
+ *                     non-negative.
+ * @return The destination spherical coordinates.
+ */
+pub fn destinationDegs(p: &LatLng, azimuthDegs: f64, distanceDegs: f64) -> Result<LatLng, Error> {
+    destination(p, azimuthDegs * M_PI_180, distanceDegs * M_PI_180)
+}
+
+/**
+ * Computes the point a fraction of the way along the great circle arc between
+ * two points.
+ *
+ * @param a The first spherical coordinates.
+ * @param b The second spherical coordinates.
+ * @param f The fraction of the way from a to b, between 0 and 1.
+ * @return The interpolated spherical coordinates, or Domain if f is out of
+ *         range or a and b are antipodal, in which case the arc between them
+ *         is not unique.
+ */
+pub fn greatCircleInterpolate(a: &LatLng, b: &LatLng, f: f64) -> Result<LatLng, Error> {
+    if !(0.0..=1.0).contains(&f) {
+        return Err(Error::Domain);
+    }
+
+    let mut v1 = Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let mut v2 = v1;
+    _geoToVec3d(a, &mut v1);
+    _geoToVec3d(b, &mut v2);
+
+    let dot = v1.x * v2.x + v1.y * v2.y + v1.z * v2.z;
+    let cross = Vec3d {
+        x: v1.y * v2.z - v1.z * v2.y,
+        y: v1.z * v2.x - v1.x * v2.z,
+        z: v1.x * v2.y - v1.y * v2.x,
+    };
+    let sinTheta = (cross.x * cross.x + cross.y * cross.y + cross.z * cross.z).sqrt();
+    if sinTheta < EPSILON_RAD {
+        if dot < 0.0 {
+            return Err(Error::Domain);
+        }
+        return Ok(*a);
+    }
+
+    // spherical linear interpolation of the unit vectors
+    let theta = sinTheta.atan2(dot);
+    let s1 = ((1.0 - f) * theta).sin() / sinTheta;
+    let s2 = (f * theta).sin() / sinTheta;
+    let v = Vec3d {
+        x: s1 * v1.x + s2 * v2.x,
+        y: s1 * v1.y + s2 * v2.y,
+        z: s1 * v1.z + s2 * v2.z,
+    };
+    Ok(_vec3dToGeo(&v))
+}
+
+/**
+ * Computes the midpoint of the great circle arc between two points.
+ *
+ * @param a The first spherical coordinates.
+ * @param b The second spherical coordinates.
+ * @return The midpoint, or Domain if a and b are antipodal.
+ */
+pub fn greatCircleMidpoint(a: &LatLng, b: &LatLng) -> Result<LatLng, Error> {
+    greatCircleInterpolate(a, b, 0.5)
+}
+
+/** Largest number of segments per edge when densifying a boundary, which
+ * keeps a densified hexagon under 400,000 points. */
+pub const MAX_SEGMENTS_PER_EDGE: u32 = 1 << 16;
+
+/**
+ * Splits each edge of a loop into great circle segments.
+ *
+ * @param verts The vertices of the loop, without a repeated closing vertex.
+ * @param segmentsPerEdge The number of segments for each edge; 1 gives the
+ *                        vertices.
+ * @return The loop, starting at the first vertex, or Domain if
+ *         segmentsPerEdge is 0 or more than MAX_SEGMENTS_PER_EDGE, or if an
+ *         edge joins antipodal points.
+ */
+pub(crate) fn _densifyLoop(verts: &[LatLng], segmentsPerEdge: u32) -> Result<Vec<LatLng>, Error> {
+    if segmentsPerEdge == 0 || segmentsPerEdge > MAX_SEGMENTS_PER_EDGE {
+        return Err(Error::Domain);
+    }
+    let mut out = Vec::with_capacity(verts.len() * segmentsPerEdge as usize);
+    for i in 0..verts.len() {
+        let a = &verts[i];
+        let b = &verts[(i + 1) % verts.len()];
+        out.push(*a);
+        for step in 1..segmentsPerEdge {
+            out.push(greatCircleInterpolate(
+                a,
+                b,
+                step as f64 / segmentsPerEdge as f64,
+            )?);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn azimuthAndDestination() {
+        let a = LatLng::from_degrees(10.0, 20.0);
+        let b = LatLng::from_degrees(10.0, 10.0);
+
+        let az = azimuth(&a, &b);
+        assert!((0.0..M_2PI).contains(&az), "azimuth is normalized");
+        assert!(
+            (azimuthDegs(&a, &b) - 270.0).abs() < 1.0,
+            "west is roughly 270 degrees"
+        );
+        let b = LatLng::from_degrees(40.0, 20.0);
+        assert!(azimuthDegs(&a, &b).abs() < EPSILON_DEG, "due north");
+
+        let p = destinationDegs(&a, 0.0, 30.0).unwrap();
+        assert!(geoAlmostEqual(&p, &b), "due north 30 degrees");
+        let p = destination(&a, 1.0, 0.5).unwrap();
+        assert!(
+            (azimuth(&a, &p) - 1.0).abs() < EPSILON_RAD,
+            "destination is along the azimuth"
+        );
+
+        assert_eq!(
+            destination(&a, 0.0, -1.0),
+            Err(Error::Domain),
+            "negative distance"
+        );
+        assert_eq!(
+            destination(&a, f64::NAN, 1.0),
+            Err(Error::Domain),
+            "invalid azimuth"
+        );
+    }
+
+    #[test]
+    fn greatCircleInterpolateAlongEquator() {
+        let a = LatLng::from_degrees(0.0, 10.0);
+        let b = LatLng::from_degrees(0.0, 50.0);
+
+        let expected = LatLng::from_degrees(0.0, 20.0);
+        assert!(
+            geoAlmostEqual(&greatCircleInterpolate(&a, &b, 0.25).unwrap(), &expected),
+            "quarter point is on the equator"
+        );
+        let expected = LatLng::from_degrees(0.0, 30.0);
+        assert!(
+            geoAlmostEqual(&greatCircleMidpoint(&a, &b).unwrap(), &expected),
+            "midpoint is on the equator"
+        );
+        assert!(
+            geoAlmostEqual(&greatCircleInterpolate(&a, &b, 0.0).unwrap(), &a),
+            "start point"
+        );
+        assert!(
+            geoAlmostEqual(&greatCircleInterpolate(&a, &b, 1.0).unwrap(), &b),
+            "end point"
+        );
+        assert_eq!(greatCircleMidpoint(&a, &a), Ok(a), "coincident points");
+    }
+
+    #[test]
+    fn normalize() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        assert_eq!(normalizeLat(0.5), 0.5, "in range");
+        assert!(
+            close(normalizeLat(100.0 * M_PI_180), 80.0 * M_PI_180),
+            "over the north pole"
+        );
+        assert!(
+            close(normalizeLat(-100.0 * M_PI_180), -80.0 * M_PI_180),
+            "over the south pole"
+        );
+        assert!(
+            close(normalizeLat(200.0 * M_PI_180), -20.0 * M_PI_180),
+            "past the equator"
+        );
+        assert!(
+            close(normalizeLat(360.0 * M_PI_180), 0.0),
+            "all the way around"
+        );
+        assert!(
+            close(normalizeLat(-270.0 * M_PI_180), 90.0 * M_PI_180),
+            "to the pole"
+        );
+
+        assert_eq!(normalizeLng(-PI), -PI, "in range");
+        assert!(
+            close(normalizeLng(190.0 * M_PI_180), -170.0 * M_PI_180),
+            "wraps east"
+        );
+        assert!(
+            close(normalizeLng(-190.0 * M_PI_180), 170.0 * M_PI_180),
+            "wraps west"
+        );
+        assert!(
+            close(normalizeLng(720.0 * M_PI_180 + 0.1), 0.1),
+            "wraps twice"
+        );
+        assert!(normalizeLng(1e45).abs() <= PI, "large values");
+        assert!(normalizeLng(f64::NAN).is_nan(), "not finite");
+
+        let g = LatLng::from_degrees(100.0, 10.0).normalized();
+        let expected = LatLng::from_degrees(80.0, -170.0);
+        assert!(
+            geoAlmostEqual(&g, &expected),
+            "over the pole to the opposite meridian"
+        );
+        let g = LatLng::from_degrees(10.0, 370.0).normalized();
+        assert!(
+            geoAlmostEqual(&g, &LatLng::from_degrees(10.0, 10.0)),
+            "wrapped longitude"
+        );
+        for &(lat, lng) in &[(100.0, 10.0), (-250.0, 45.0), (1000.0, -1000.0)] {
+            let g = LatLng::from_degrees(lat, lng);
+            let mut a = Vec3d {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+            let mut b = a;
+            _geoToVec3d(&g, &mut a);
+            _geoToVec3d(&g.normalized(), &mut b);
+            assert!(
+                (a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12 && (a.z - b.z).abs() < 1e-12,
+                "same point on the sphere"
+            );
+        }
+    }
+
+    #[test]
+    fn degrees() {
+        let g = LatLng::from_degrees(37.7749, -122.4194);
+        assert!((g.lat - 37.7749 * M_PI_180).abs() < 1e-15, "lat in radians");
+        assert!(
+            (g.lng + 122.4194 * M_PI_180).abs() < 1e-15,
+            "lng in radians"
+        );
+        assert!((g.lat_degrees() - 37.7749).abs() < 1e-12, "lat round trip");
+        assert!((g.lng_degrees() + 122.4194).abs() < 1e-12, "lng round trip");
+
+        #[allow(deprecated)]
+        {
+            let mut p = LatLng { lat: 0.0, lng: 0.0 };
+            setGeoDegs(&mut p, 37.7749, -122.4194);
+            assert_eq!(p, g, "same as setGeoDegs");
+        }
+    }
+
+    #[test]
+    fn greatCircleInterpolateOverPole() {
+        let a = LatLng::from_degrees(80.0, 0.0);
+        let b = LatLng::from_degrees(80.0, 180.0);
+
+        let mid = greatCircleMidpoint(&a, &b).unwrap();
+        assert!(
+            (mid.lat - FRAC_PI_2).abs() < EPSILON_RAD,
+            "arc between opposite meridians passes over the pole"
+        );
+
+        let b = LatLng::from_degrees(-80.0, 180.0);
+        assert_eq!(greatCircleMidpoint(&a, &b), Err(Error::Domain), "antipodal");
+        assert_eq!(
+            greatCircleInterpolate(&a, &a, 1.5),
+            Err(Error::Domain),
+            "fraction"
+        );
+    }
 }
@@ -1,17 +1,63 @@
+// This crate is a close port of the C reference implementation and keeps its
+// naming and control flow so the two can be compared side by side.
+#![allow(non_snake_case, non_upper_case_globals)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 use h3_index::H3Index;
 
 pub mod algos;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod base_cells;
+pub mod bbox;
+#[cfg(any(test, feature = "bench"))]
+pub mod bench_utils;
+pub mod bulk;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod cell_index;
+pub mod cell_set;
+#[cfg(feature = "coarse-cache")]
+pub mod coarse_cache;
 mod constants;
 mod coord_ijk;
+#[cfg(feature = "csv")]
+pub mod csv;
 pub mod directed_edge;
 pub mod error;
+#[cfg(feature = "experimental")]
+pub mod experimental;
 mod face_ijk;
+#[cfg(feature = "geojson")]
+pub mod geojson;
 pub mod h3_index;
+pub mod icosahedron;
+pub mod inspect;
 pub mod iterators;
 pub mod lat_lng;
+pub mod local_ij;
+pub mod measurement;
+pub mod polygon;
+mod prelude;
+#[cfg(feature = "proj")]
+pub mod proj;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod resolution;
+mod scratch;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+#[cfg(feature = "proptest")]
+pub mod testing;
 pub mod vec2d;
 pub mod vec3d;
+pub mod vertex;
+mod vertex_graph;
+pub mod wkt;
 
 #[macro_use]
 extern crate enum_primitive;
@@ -0,0 +1,1340 @@
+#[cfg(feature = "ndarray")]
+use std::collections::HashMap;
+
+#[cfg(feature = "ndarray")]
+use ndarray::Array2;
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+pub use crate::coord_ijk::CoordIJ;
+
+use crate::{
+    base_cells::{
+        _getBaseCellDirection, _getBaseCellNeighbor, _isBaseCellPentagon, _isBaseCellPolarPentagon,
+        baseCellNeighbor60CCWRots, INVALID_BASE_CELL,
+    },
+    cell_set::CellSet,
+    constants::{H3_CELL_MODE, NUM_BASE_CELLS},
+    coord_ijk::{
+        CoordIJK, Direction, _downAp7, _downAp7r, _ijkAdd, _ijkMatches, _ijkNormalize,
+        _ijkRotate60ccw, _ijkRotate60cw, _ijkSub, _neighbor, _rotate60ccw, _rotate60cw,
+        _unitIjkToDigit, _upAp7, _upAp7r, cubeToIjk, ijToIjk, ijkDistance, ijkToCube, ijkToIj,
+    },
+    error::Error,
+    face_ijk::FaceIJK,
+    h3_index::{
+        H3Index, _h3LeadingNonZeroDigit, _h3Rotate60ccw, _h3Rotate60cw, _h3RotatePent60ccw,
+        _h3RotatePent60cw, _h3ToFaceIjkWithInitializedFijk, isResolutionClassIII, H3_GET_BASE_CELL,
+        H3_GET_RESOLUTION, H3_INIT, H3_SET_BASE_CELL, H3_SET_INDEX_DIGIT, H3_SET_MODE,
+        H3_SET_RESOLUTION,
+    },
+    prelude::*,
+};
+
+/**
+ * Origin leading digit -> index leading digit -> rotations 60 cw
+ * Either being 1 (K axis) is invalid.
+ * No good default at 0.
+ */
+const PENTAGON_ROTATIONS: [[i32; 7]; 7] = [
+    [0, -1, 0, 0, 0, 0, 0],       // 0
+    [-1, -1, -1, -1, -1, -1, -1], // 1
+    [0, -1, 0, 0, 0, 1, 0],       // 2
+    [0, -1, 0, 0, 1, 1, 0],       // 3
+    [0, -1, 0, 5, 0, 0, 0],       // 4
+    [0, -1, 5, 5, 0, 0, 0],       // 5
+    [0, -1, 0, 0, 0, 0, 0],       // 6
+];
+
+/**
+ * Reverse base cell direction -> leading index digit -> rotations 60 ccw.
+ * For reversing the rotation introduced in PENTAGON_ROTATIONS when
+ * the origin is on a pentagon (regardless of the base cell of the index.)
+ */
+const PENTAGON_ROTATIONS_REVERSE: [[i32; 7]; 7] = [
+    [0, 0, 0, 0, 0, 0, 0],        // 0
+    [-1, -1, -1, -1, -1, -1, -1], // 1
+    [0, 1, 0, 0, 0, 0, 0],        // 2
+    [0, 1, 0, 0, 0, 1, 0],        // 3
+    [0, 5, 0, 0, 0, 0, 0],        // 4
+    [0, 5, 0, 5, 0, 0, 0],        // 5
+    [0, 0, 0, 0, 0, 0, 0],        // 6
+];
+
+/**
+ * Reverse base cell direction -> leading index digit -> rotations 60 ccw.
+ * For reversing the rotation introduced in PENTAGON_ROTATIONS when the index is
+ * on a pentagon and the origin is not.
+ */
+const PENTAGON_ROTATIONS_REVERSE_NONPOLAR: [[i32; 7]; 7] = [
+    [0, 0, 0, 0, 0, 0, 0],        // 0
+    [-1, -1, -1, -1, -1, -1, -1], // 1
+    [0, 1, 0, 0, 0, 0, 0],        // 2
+    [0, 1, 0, 0, 0, 1, 0],        // 3
+    [0, 5, 0, 0, 0, 0, 0],        // 4
+    [0, 1, 0, 5, 1, 1, 0],        // 5
+    [0, 0, 0, 0, 0, 0, 0],        // 6
+];
+
+/**
+ * Reverse base cell direction -> leading index digit -> rotations 60 ccw.
+ * For reversing the rotation introduced in PENTAGON_ROTATIONS when the index is
+ * on a polar pentagon and the origin is not.
+ */
+const PENTAGON_ROTATIONS_REVERSE_POLAR: [[i32; 7]; 7] = [
+    [0, 0, 0, 0, 0, 0, 0],        // 0
+    [-1, -1, -1, -1, -1, -1, -1], // 1
+    [0, 1, 1, 1, 1, 1, 1],        // 2
+    [0, 1, 0, 0, 0, 1, 0],        // 3
+    [0, 1, 0, 0, 1, 1, 1],        // 4
+    [0, 1, 0, 5, 1, 1, 0],        // 5
+    [0, 1, 1, 0, 1, 1, 1],        // 6
+];
+
+/**
+ * Prohibited directions when unfolding a pentagon.
+ *
+ * Indexes by two directions, both relative to the pentagon base cell. The first
+ * is the direction of the origin index and the second is the direction of the
+ * index to unfold. Direction refers to the direction from base cell to base
+ * cell if the indexes are on different base cells, or the leading digit if
+ * within the pentagon base cell.
+ *
+ * This previously included a Class II/Class III check but these were removed
+ * due to failure cases. It's possible this could be restricted to a narrower
+ * set of a failure cases. Currently, the logic is any unfolding across more
+ * than one icosahedron face is not permitted.
+ */
+const FAILED_DIRECTIONS: [[bool; 7]; 7] = [
+    [false, false, false, false, false, false, false], // 0
+    [false, false, false, false, false, false, false], // 1
+    [false, false, false, false, true, true, false],   // 2
+    [false, false, false, false, true, false, true],   // 3
+    [false, false, true, true, false, false, false],   // 4
+    [false, false, true, false, false, false, true],   // 5
+    [false, false, false, true, false, true, false],   // 6
+];
+
+/**
+ * Produces ijk+ coordinates for an index anchored by an origin.
+ *
+ * The coordinate space used by this function may have deleted
+ * regions or warping due to pentagonal distortion.
+ *
+ * Coordinates are only comparable if they come from the same
+ * origin index.
+ *
+ * Failure may occur if the index is too far away from the origin
+ * or if the index is on the other side of a pentagon.
+ *
+ * @param origin An anchoring index for the ijk+ coordinate system.
+ * @param index Index to find the coordinates of
+ * @param out ijk+ coordinates of the index will be placed here on success
+ * @return 0 on success, or another value on failure.
+ */
+pub fn cellToLocalIjk(origin: H3Index, h3: H3Index) -> Result<CoordIJK, Error> {
+    _cellToLocalIjkDetailed(origin, h3).map_err(|e| match e {
+        Error::NotNeighbors | Error::Pentagon => Error::Failed,
+        e => e,
+    })
+}
+
+/**
+ * cellToLocalIjk, but failures to unfold are reported as NotNeighbors when
+ * the base cells are not neighbors and Pentagon when the pentagon between
+ * the cells cannot be unfolded, instead of both as Failed.
+ */
+fn _cellToLocalIjkDetailed(origin: H3Index, mut h3: H3Index) -> Result<CoordIJK, Error> {
+    let res = H3_GET_RESOLUTION(origin);
+
+    if res != H3_GET_RESOLUTION(h3) {
+        return Err(Error::ResMismatch);
+    }
+
+    let originBaseCell = H3_GET_BASE_CELL(origin);
+    let baseCell = H3_GET_BASE_CELL(h3);
+
+    if !(0..NUM_BASE_CELLS).contains(&originBaseCell) {
+        // LCOV_EXCL_BR_LINE
+        // Base cells less than zero can not be represented in an index
+        return Err(Error::CellInvalid);
+    }
+    if !(0..NUM_BASE_CELLS).contains(&baseCell) {
+        // LCOV_EXCL_BR_LINE
+        // Base cells less than zero can not be represented in an index
+        return Err(Error::CellInvalid);
+    }
+
+    // Direction from origin base cell to index base cell
+    let mut dir = Direction::CenterDigit;
+    let mut revDir = Direction::CenterDigit;
+    if originBaseCell != baseCell {
+        dir = _getBaseCellDirection(originBaseCell, baseCell);
+        if dir == Direction::InvalidDigit {
+            // Base cells are not neighbors, can't unfold.
+            return Err(Error::NotNeighbors);
+        }
+        revDir = _getBaseCellDirection(baseCell, originBaseCell);
+        debug_assert!(revDir != Direction::InvalidDigit);
+    }
+
+    let originOnPent = _isBaseCellPentagon(originBaseCell);
+    let indexOnPent = _isBaseCellPentagon(baseCell);
+
+    let mut indexFijk = FaceIJK {
+        face: 0,
+        coord: CoordIJK { i: 0, j: 0, k: 0 },
+    };
+    if dir != Direction::CenterDigit {
+        // Rotate index into the orientation of the origin base cell.
+        // cw because we are undoing the rotation into that base cell.
+        let baseCellRotations = baseCellNeighbor60CCWRots[originBaseCell as usize][dir as usize];
+        if indexOnPent {
+            for _i in 0..baseCellRotations {
+                h3 = _h3RotatePent60cw(h3);
+
+                revDir = _rotate60cw(revDir);
+                if revDir == Direction::KAxesDigit {
+                    revDir = _rotate60cw(revDir);
+                }
+            }
+        } else {
+            for _i in 0..baseCellRotations {
+                h3 = _h3Rotate60cw(h3);
+
+                revDir = _rotate60cw(revDir);
+            }
+        }
+    }
+    // Face is unused. This produces coordinates in base cell coordinate space.
+    _h3ToFaceIjkWithInitializedFijk(h3, &mut indexFijk);
+
+    if dir != Direction::CenterDigit {
+        debug_assert!(baseCell != originBaseCell);
+        debug_assert!(!(originOnPent && indexOnPent));
+
+        let mut pentagonRotations = 0;
+        let mut directionRotations = 0;
+
+        if originOnPent {
+            let originLeadingDigit = _h3LeadingNonZeroDigit(origin) as usize;
+
+            if FAILED_DIRECTIONS[originLeadingDigit][dir as usize] {
+                // TODO: We may be unfolding the pentagon incorrectly in this
+                // case; return an error code until this is guaranteed to be
+                // correct.
+                return Err(Error::Pentagon);
+            }
+
+            directionRotations = PENTAGON_ROTATIONS[originLeadingDigit][dir as usize];
+            pentagonRotations = directionRotations;
+        } else if indexOnPent {
+            let indexLeadingDigit = _h3LeadingNonZeroDigit(h3) as usize;
+
+            if FAILED_DIRECTIONS[indexLeadingDigit][revDir as usize] {
+                // TODO: We may be unfolding the pentagon incorrectly in this
+                // case; return an error code until this is guaranteed to be
+                // correct.
+                return Err(Error::Pentagon);
+            }
+
+            pentagonRotations = PENTAGON_ROTATIONS[revDir as usize][indexLeadingDigit];
+        }
+
+        if pentagonRotations < 0 || directionRotations < 0 {
+            // This occurs when an invalid K axis digit is present
+            return Err(Error::CellInvalid);
+        }
+
+        for _i in 0..pentagonRotations {
+            _ijkRotate60cw(&mut indexFijk.coord);
+        }
+
+        let mut offset = CoordIJK { i: 0, j: 0, k: 0 };
+        _neighbor(&mut offset, dir);
+        // Scale offset based on resolution
+        for r in (0..res).rev() {
+            if isResolutionClassIII(r + 1) {
+                // rotate ccw
+                _downAp7(&mut offset);
+            } else {
+                // rotate cw
+                _downAp7r(&mut offset);
+            }
+        }
+
+        for _i in 0..directionRotations {
+            _ijkRotate60cw(&mut offset);
+        }
+
+        // Perform necessary translation
+        _ijkAdd(indexFijk.coord, offset, &mut indexFijk.coord);
+        _ijkNormalize(&mut indexFijk.coord);
+    } else if originOnPent && indexOnPent {
+        // If the origin and index are on pentagon, and we checked that the base
+        // cells are the same or neighboring, then they must be the same base
+        // cell.
+        debug_assert!(baseCell == originBaseCell);
+
+        let originLeadingDigit = _h3LeadingNonZeroDigit(origin) as usize;
+        let indexLeadingDigit = _h3LeadingNonZeroDigit(h3) as usize;
+
+        if FAILED_DIRECTIONS[originLeadingDigit][indexLeadingDigit] {
+            // TODO: We may be unfolding the pentagon incorrectly in this case;
+            // return an error code until this is guaranteed to be correct.
+            return Err(Error::Pentagon);
+        }
+
+        let withinPentagonRotations = PENTAGON_ROTATIONS[originLeadingDigit][indexLeadingDigit];
+        if withinPentagonRotations < 0 {
+            // This occurs when an invalid K axis digit is present
+            return Err(Error::CellInvalid);
+        }
+
+        for _i in 0..withinPentagonRotations {
+            _ijkRotate60cw(&mut indexFijk.coord);
+        }
+    }
+
+    Ok(indexFijk.coord)
+}
+
+/**
+ * Produces an index for ijk+ coordinates anchored by an origin.
+ *
+ * The coordinate space used by this function may have deleted
+ * regions or warping due to pentagonal distortion.
+ *
+ * Failure may occur if the coordinates are too far away from the origin
+ * or if the index is on the other side of a pentagon.
+ *
+ * @param origin An anchoring index for the ijk+ coordinate system.
+ * @param ijk IJK+ Coordinates to find the index of
+ * @param out The index will be placed here on success
+ * @return 0 on success, or another value on failure.
+ */
+pub fn localIjkToCell(origin: H3Index, ijk: &CoordIJK) -> Result<H3Index, Error> {
+    let res = H3_GET_RESOLUTION(origin);
+    let originBaseCell = H3_GET_BASE_CELL(origin);
+    if !(0..NUM_BASE_CELLS).contains(&originBaseCell) {
+        // LCOV_EXCL_BR_LINE
+        // Base cells less than zero can not be represented in an index
+        return Err(Error::CellInvalid);
+    }
+    let originOnPent = _isBaseCellPentagon(originBaseCell);
+
+    // This logic is very similar to faceIjkToH3
+    // initialize the index
+    let mut out: H3Index = H3_INIT;
+    H3_SET_MODE(&mut out, H3_CELL_MODE);
+    H3_SET_RESOLUTION(&mut out, res);
+
+    // check for res 0/base cell
+    if res == 0 {
+        let dir = _unitIjkToDigit(*ijk);
+        if dir == Direction::InvalidDigit {
+            // out of range input - not a unit vector or zero vector
+            return Err(Error::Failed);
+        }
+        let newBaseCell = _getBaseCellNeighbor(originBaseCell, dir);
+        if newBaseCell == INVALID_BASE_CELL {
+            // Moving in an invalid direction off a pentagon.
+            return Err(Error::Failed);
+        }
+        H3_SET_BASE_CELL(&mut out, newBaseCell);
+        return Ok(out);
+    }
+
+    // we need to find the correct base cell offset (if any) for this H3 index;
+    // start with the passed in base cell and resolution res ijk coordinates
+    // in that base cell's coordinate system
+    let mut ijkCopy: CoordIJK = *ijk;
+
+    // build the H3Index from finest res up
+    // adjust r for the fact that the res 0 base cell offsets the indexing
+    // digits
+    for r in (0..res).rev() {
+        let lastIJK: CoordIJK = ijkCopy;
+        let mut lastCenter: CoordIJK;
+        if isResolutionClassIII(r + 1) {
+            // rotate ccw
+            _upAp7(&mut ijkCopy);
+            lastCenter = ijkCopy;
+            _downAp7(&mut lastCenter);
+        } else {
+            // rotate cw
+            _upAp7r(&mut ijkCopy);
+            lastCenter = ijkCopy;
+            _downAp7r(&mut lastCenter);
+        }
+
+        let mut diff = CoordIJK { i: 0, j: 0, k: 0 };
+        _ijkSub(lastIJK, lastCenter, &mut diff);
+        _ijkNormalize(&mut diff);
+
+        H3_SET_INDEX_DIGIT(&mut out, r + 1, _unitIjkToDigit(diff) as i32);
+    }
+
+    // ijkCopy should now hold the IJK of the base cell in the
+    // coordinate system of the current base cell
+
+    if ijkCopy.i > 1 || ijkCopy.j > 1 || ijkCopy.k > 1 {
+        // out of range input
+        return Err(Error::Failed);
+    }
+
+    // lookup the correct base cell
+    let mut dir = _unitIjkToDigit(ijkCopy);
+    let mut baseCell = _getBaseCellNeighbor(originBaseCell, dir);
+    // If baseCell is invalid, it must be because the origin base cell is a
+    // pentagon, and because pentagon base cells do not border each other,
+    // baseCell must not be a pentagon.
+    let indexOnPent = baseCell != INVALID_BASE_CELL && _isBaseCellPentagon(baseCell);
+
+    if dir != Direction::CenterDigit {
+        // If the index is in a warped direction, we need to unwarp the base
+        // cell direction. There may be further need to rotate the index digits.
+        let mut pentagonRotations = 0;
+        if originOnPent {
+            let originLeadingDigit = _h3LeadingNonZeroDigit(origin) as usize;
+            pentagonRotations = PENTAGON_ROTATIONS_REVERSE[originLeadingDigit][dir as usize];
+            for _i in 0..pentagonRotations {
+                dir = _rotate60ccw(dir);
+            }
+            // The pentagon rotations are being chosen so that dir is not the
+            // deleted direction. If it still happens, it means we're moving
+            // into a deleted subsequence, so there is no index here.
+            if dir == Direction::KAxesDigit {
+                return Err(Error::Pentagon);
+            }
+            baseCell = _getBaseCellNeighbor(originBaseCell, dir);
+
+            // indexOnPent does not need to be checked again since no pentagon
+            // base cells border each other.
+            debug_assert!(baseCell != INVALID_BASE_CELL);
+            debug_assert!(!_isBaseCellPentagon(baseCell));
+        }
+
+        // Now we can determine the relation between the origin and target base
+        // cell.
+        let baseCellRotations = baseCellNeighbor60CCWRots[originBaseCell as usize][dir as usize];
+        debug_assert!(baseCellRotations >= 0);
+
+        // Adjust for pentagon warping within the base cell. The base cell
+        // should be in the right location, so now we need to rotate the index
+        // back. We might not need to check for errors since we would just be
+        // double mapping.
+        if indexOnPent {
+            let revDir = _getBaseCellDirection(baseCell, originBaseCell);
+            debug_assert!(revDir != Direction::InvalidDigit);
+
+            // Adjust for the different coordinate space in the two base cells.
+            // This is done first because we need to do the pentagon rotations
+            // based on the leading digit in the pentagon's coordinate system.
+            for _i in 0..baseCellRotations {
+                out = _h3Rotate60ccw(out);
+            }
+
+            let indexLeadingDigit = _h3LeadingNonZeroDigit(out) as usize;
+            if _isBaseCellPolarPentagon(baseCell) {
+                pentagonRotations =
+                    PENTAGON_ROTATIONS_REVERSE_POLAR[revDir as usize][indexLeadingDigit];
+            } else {
+                pentagonRotations =
+                    PENTAGON_ROTATIONS_REVERSE_NONPOLAR[revDir as usize][indexLeadingDigit];
+            }
+            if pentagonRotations < 0 {
+                // This occurs when an invalid K axis digit is present
+                return Err(Error::CellInvalid);
+            }
+
+            for _i in 0..pentagonRotations {
+                out = _h3RotatePent60ccw(out);
+            }
+        } else {
+            for _i in 0..pentagonRotations {
+                out = _h3Rotate60ccw(out);
+            }
+
+            // Adjust for the different coordinate space in the two base cells.
+            for _i in 0..baseCellRotations {
+                out = _h3Rotate60ccw(out);
+            }
+        }
+    } else if originOnPent && indexOnPent {
+        let originLeadingDigit = _h3LeadingNonZeroDigit(origin) as usize;
+        let indexLeadingDigit = _h3LeadingNonZeroDigit(out) as usize;
+
+        let withinPentagonRotations =
+            PENTAGON_ROTATIONS_REVERSE[originLeadingDigit][indexLeadingDigit];
+        if withinPentagonRotations < 0 {
+            // This occurs when an invalid K axis digit is present
+            return Err(Error::CellInvalid);
+        }
+
+        for _i in 0..withinPentagonRotations {
+            out = _h3Rotate60ccw(out);
+        }
+    }
+
+    if indexOnPent {
+        // TODO: There are cases in cellToLocalIj which are failed but not
+        // accounted for here - instead just fail if the recovered index is
+        // invalid.
+        if _h3LeadingNonZeroDigit(out) == Direction::KAxesDigit {
+            return Err(Error::Pentagon);
+        }
+    }
+
+    H3_SET_BASE_CELL(&mut out, baseCell);
+    Ok(out)
+}
+
+/**
+ * Produces ij coordinates for an index anchored by an origin.
+ *
+ * The coordinate space used by this function may have deleted
+ * regions or warping due to pentagonal distortion.
+ *
+ * Coordinates are only comparable if they come from the same
+ * origin index.
+ *
+ * Failure may occur if the index is too far away from the origin
+ * or if the index is on the other side of a pentagon.
+ *
+ * This function's output is not guaranteed
+ * to be compatible across different versions of H3.
+ *
+ * @param origin An anchoring index for the ij coordinate system.
+ * @param index Index to find the coordinates of
+ * @return ij coordinates of the index on success
+ */
+pub fn cellToLocalIj(origin: H3Index, h3: H3Index) -> Result<CoordIJ, Error> {
+    let ijk = cellToLocalIjk(origin, h3)?;
+    Ok(ijkToIj(&ijk))
+}
+
+/**
+ * Produces an index for ij coordinates anchored by an origin.
+ *
+ * The coordinate space used by this function may have deleted
+ * regions or warping due to pentagonal distortion.
+ *
+ * Failure may occur if the index is too far away from the origin
+ * or if the index is on the other side of a pentagon.
+ *
+ * This function's output is not guaranteed
+ * to be compatible across different versions of H3.
+ *
+ * @param origin An anchoring index for the ij coordinate system.
+ * @param ij ij coordinates to index.
+ * @return The index on success
+ */
+pub fn localIjToCell(origin: H3Index, ij: &CoordIJ) -> Result<H3Index, Error> {
+    let ijk = ijToIjk(ij)?;
+    localIjkToCell(origin, &ijk)
+}
+
+/**
+ * Shifts every cell of a set by the same offset in the local IJ coordinate
+ * system anchored at origin. Stamping a pattern defined around one cell onto
+ * another location is a translation by the IJ coordinates of the destination.
+ *
+ * All cells must be at the resolution of the origin. Fails if any cell, before
+ * or after the shift, cannot be addressed from the origin (for example because
+ * it is too far away or on the other side of a pentagon.)
+ *
+ * @param set The cells to translate.
+ * @param origin An anchoring index for the ij coordinate system.
+ * @param delta The offset to apply to every cell.
+ * @return The translated cells.
+ */
+pub fn translateSet(set: &CellSet, origin: H3Index, delta: CoordIJ) -> Result<CellSet, Error> {
+    let mut out = CellSet::new();
+    for cell in set.iter() {
+        let ij = ijkToIj(&cellToLocalIjk(origin, cell)?);
+        let shifted = CoordIJ {
+            i: ij.i.checked_add(delta.i).ok_or(Error::Failed)?,
+            j: ij.j.checked_add(delta.j).ok_or(Error::Failed)?,
+        };
+        out.insert(localIjkToCell(origin, &ijToIjk(&shifted)?)?);
+    }
+    Ok(out)
+}
+
+/**
+ * Rotates every cell of a set about the origin cell in increments of 60
+ * degrees, using the local IJK coordinate system anchored at origin.
+ *
+ * All cells must be at the resolution of the origin. Fails if any cell, before
+ * or after the rotation, cannot be addressed from the origin (for example
+ * because it is too far away or on the other side of a pentagon.)
+ *
+ * @param set The cells to rotate.
+ * @param origin The cell to rotate about.
+ * @param k60 Number of 60 degree rotations; positive values rotate
+ *            counter-clockwise and negative values clockwise.
+ * @return The rotated cells.
+ */
+pub fn rotateSet(set: &CellSet, origin: H3Index, k60: i32) -> Result<CellSet, Error> {
+    let center = cellToLocalIjk(origin, origin)?;
+    let rotations = k60.rem_euclid(6);
+
+    let mut out = CellSet::new();
+    for cell in set.iter() {
+        let mut offset = CoordIJK { i: 0, j: 0, k: 0 };
+        _ijkSub(cellToLocalIjk(origin, cell)?, center, &mut offset);
+        _ijkNormalize(&mut offset);
+        for _i in 0..rotations {
+            _ijkRotate60ccw(&mut offset);
+        }
+        let mut rotated = CoordIJK { i: 0, j: 0, k: 0 };
+        _ijkAdd(center, offset, &mut rotated);
+        _ijkNormalize(&mut rotated);
+        out.insert(localIjkToCell(origin, &rotated)?);
+    }
+    Ok(out)
+}
+
+/**
+ * Computes the bounding rectangle of a set of cells in the local IJ coordinate
+ * system anchored at origin.
+ *
+ * All cells must be at the resolution of the origin. Fails if any cell cannot
+ * be addressed from the origin, or with E_DOMAIN if the set is empty.
+ *
+ * @param origin An anchoring index for the ij coordinate system.
+ * @param set The cells to bound.
+ * @return The minimum and maximum IJ coordinates, both inclusive.
+ */
+pub fn localIjBounds(origin: H3Index, set: &CellSet) -> Result<(CoordIJ, CoordIJ), Error> {
+    if set.is_empty() {
+        return Err(Error::Domain);
+    }
+
+    let mut min = CoordIJ {
+        i: i32::MAX,
+        j: i32::MAX,
+    };
+    let mut max = CoordIJ {
+        i: i32::MIN,
+        j: i32::MIN,
+    };
+    for cell in set.iter() {
+        let ij = ijkToIj(&cellToLocalIjk(origin, cell)?);
+        min.i = min.i.min(ij.i);
+        min.j = min.j.min(ij.j);
+        max.i = max.i.max(ij.i);
+        max.j = max.j.max(ij.j);
+    }
+    Ok((min, max))
+}
+
+/**
+ * Produces the grid distance between the two indexes.
+ *
+ * This function may fail to find the distance between two indexes, for
+ * example if they are very far apart. It may also fail when finding
+ * distances for indexes on opposite sides of a pentagon.
+ *
+ * @param origin Index to find the distance from.
+ * @param index Index to find the distance to.
+ * @return The distance, or an error if it could not be computed.
+ */
+pub fn gridDistance(origin: H3Index, h3: H3Index) -> Result<i64, Error> {
+    // Convert to local IJK coordinates
+    let originIjk = cellToLocalIjk(origin, origin)?;
+    let h3Ijk = cellToLocalIjk(origin, h3)?;
+    Ok(ijkDistance(&originIjk, &h3Ijk) as i64)
+}
+
+/**
+ * Produces the grid distance between the two indexes, like gridDistance,
+ * but reports why the distance could not be found so the caller can choose
+ * to retry with a different origin, such as a cell between the two.
+ *
+ * @param origin Index to find the distance from.
+ * @param index Index to find the distance to.
+ * @return The distance, or NotNeighbors if the indexes are too far apart
+ * (their base cells are not neighbors), Pentagon if the path between them
+ * crosses a pentagon that cannot be unfolded, or another error for invalid
+ * input.
+ */
+pub fn gridDistanceLocalIj(origin: H3Index, h3: H3Index) -> Result<i64, Error> {
+    let originIjk = _cellToLocalIjkDetailed(origin, origin)?;
+    let h3Ijk = _cellToLocalIjkDetailed(origin, h3)?;
+    Ok(ijkDistance(&originIjk, &h3Ijk) as i64)
+}
+
+/**
+ * Number of indexes in a line from the start index to the end index,
+ * to be used for allocating memory. Returns an error if the line
+ * cannot be computed.
+ *
+ * @param start Start index of the line
+ * @param end End index of the line
+ * @return Size of the line
+ */
+pub fn gridPathCellsSize(start: H3Index, end: H3Index) -> Result<i64, Error> {
+    let distance = gridDistance(start, end)?;
+    Ok(distance + 1)
+}
+
+/**
+ * Given cube coords as doubles, round to valid integer coordinates. Algorithm
+ * from https://www.redblobgames.com/grids/hexagons/#rounding
+ * @param i   Floating-point I coord
+ * @param j   Floating-point J coord
+ * @param k   Floating-point K coord
+ * @param ijk IJK coord struct, modified in place
+ */
+fn cubeRound(i: f64, j: f64, k: f64, ijk: &mut CoordIJK) {
+    let mut ri = i.round() as i32;
+    let mut rj = j.round() as i32;
+    let mut rk = k.round() as i32;
+
+    let iDiff = (ri as f64 - i).abs();
+    let jDiff = (rj as f64 - j).abs();
+    let kDiff = (rk as f64 - k).abs();
+
+    // Round, maintaining valid cube coords
+    if iDiff > jDiff && iDiff > kDiff {
+        ri = -rj - rk;
+    } else if jDiff > kDiff {
+        rj = -ri - rk;
+    } else {
+        rk = -ri - rj;
+    }
+
+    ijk.i = ri;
+    ijk.j = rj;
+    ijk.k = rk;
+}
+
+/**
+ * Given two H3 indexes, return the line of indexes between them (inclusive).
+ *
+ * This function may fail to find the line between two indexes, for
+ * example if they are very far apart. It may also fail when finding
+ * distances for indexes on opposite sides of a pentagon.
+ *
+ * Notes:
+ *
+ *  - The specific output of this function should not be considered stable
+ *    across library versions. The only guarantees the library provides are
+ *    that the line length will be `gridDistance(start, end) + 1` and that
+ *    every index in the line will be a neighbor of the preceding index.
+ *  - Lines are drawn in grid space, and may not correspond exactly to either
+ *    Cartesian lines or great arcs.
+ *
+ * @param start Start index of the line
+ * @param end End index of the line
+ * @return The cells of the line, from start to end
+ */
+pub fn gridPathCells(start: H3Index, end: H3Index) -> Result<Vec<H3Index>, Error> {
+    let distance = gridDistance(start, end)?;
+
+    // Get IJK coords for the start and end. We've already confirmed
+    // that these can be calculated with the distance check above.
+    let mut startIjk = cellToLocalIjk(start, start)?;
+    let mut endIjk = cellToLocalIjk(start, end)?;
+
+    // Convert IJK to cube coordinates suitable for linear interpolation
+    ijkToCube(&mut startIjk);
+    ijkToCube(&mut endIjk);
+
+    let step = |from: i32, to: i32| -> f64 {
+        if distance == 0 {
+            return 0.0;
+        }
+        (to - from) as f64 / distance as f64
+    };
+    let iStep = step(startIjk.i, endIjk.i);
+    let jStep = step(startIjk.j, endIjk.j);
+    let kStep = step(startIjk.k, endIjk.k);
+
+    let mut out: Vec<H3Index> = Vec::with_capacity(distance as usize + 1);
+    let mut currentIjk: CoordIJK = startIjk;
+    for n in 0..=distance {
+        cubeRound(
+            startIjk.i as f64 + iStep * n as f64,
+            startIjk.j as f64 + jStep * n as f64,
+            startIjk.k as f64 + kStep * n as f64,
+            &mut currentIjk,
+        );
+        // Convert cube -> ijk -> h3 index
+        cubeToIjk(&mut currentIjk);
+        out.push(localIjkToCell(start, &currentIjk)?);
+    }
+    Ok(out)
+}
+
+/**
+ * Simplifies a path of cells by removing the cells in the middle of straight
+ * runs, where consecutive steps are in the same direction. Only the first and
+ * last cell of each run are kept, and the removed cells are exactly those
+ * gridPathCells produces between the remaining ones, as each run is a
+ * straight line in the local IJK coordinates anchored at its first cell.
+ *
+ * Steps that cannot be compared in local IJK coordinates, such as those
+ * crossing pentagon distortion, end the run so the cells around them are
+ * kept.
+ *
+ * @param cells The path, with consecutive cells being neighbors.
+ * @return The simplified path.
+ */
+pub fn simplifyPath(cells: &[H3Index]) -> Vec<H3Index> {
+    if cells.len() <= 2 {
+        return cells.to_vec();
+    }
+
+    let mut out: Vec<H3Index> = Vec::with_capacity(cells.len());
+    out.push(cells[0]);
+    // steps are compared in the coordinate system of the start of the run,
+    // which is also the one gridPathCells uses when expanding it again
+    let mut runStart = cells[0];
+    for i in 1..(cells.len() - 1) {
+        if _isStraightStep(runStart, cells[i - 1], cells[i], cells[i + 1]) {
+            continue;
+        }
+        out.push(cells[i]);
+        runStart = cells[i];
+    }
+    out.push(cells[cells.len() - 1]);
+    out
+}
+
+/**
+ * Whether the step from a to b is a unit step in the same direction as the
+ * step from b to c, in local IJK coordinates anchored at origin.
+ */
+fn _isStraightStep(origin: H3Index, a: H3Index, b: H3Index, c: H3Index) -> bool {
+    let coords = (
+        cellToLocalIjk(origin, a),
+        cellToLocalIjk(origin, b),
+        cellToLocalIjk(origin, c),
+    );
+    if let (Ok(a), Ok(b), Ok(c)) = coords {
+        let mut first = CoordIJK { i: 0, j: 0, k: 0 };
+        let mut second = CoordIJK { i: 0, j: 0, k: 0 };
+        _ijkSub(b, a, &mut first);
+        _ijkSub(c, b, &mut second);
+        _ijkNormalize(&mut first);
+        _ijkNormalize(&mut second);
+        return _unitIjkToDigit(first) != Direction::InvalidDigit && _ijkMatches(first, second);
+    }
+    false
+}
+
+/** Largest number of elements rasterizeToIj allocates; the matrix spans
+ * the IJ extent of the cells, so a few far apart cells can ask for far more
+ * memory than their count suggests. */
+#[cfg(feature = "ndarray")]
+pub const MAX_RASTER_ELEMENTS: usize = 1 << 24;
+
+/**
+ * Rasterizes values attached to cells into a dense matrix laid out on the
+ * local IJ coordinate system anchored at origin. The element at `[[r, c]]`
+ * holds the value of the cell at IJ coordinates `(min.i + r, min.j + c)`, or
+ * None where no cell has a value (including positions that fall in the deleted
+ * region of a pentagon.)
+ *
+ * All cells must be at the resolution of the origin. Fails if any cell cannot
+ * be addressed from the origin, with E_DOMAIN if there are no values, or with
+ * E_MEMORY_BOUNDS if the matrix would have more than MAX_RASTER_ELEMENTS
+ * elements.
+ *
+ * @param origin An anchoring index for the ij coordinate system.
+ * @param values The value of each cell.
+ * @return The IJ coordinates of element `[[0, 0]]` and the matrix.
+ */
+#[cfg(feature = "ndarray")]
+pub fn rasterizeToIj(
+    origin: H3Index,
+    values: &HashMap<H3Index, f64>,
+) -> Result<(CoordIJ, Array2<Option<f64>>), Error> {
+    let cells: CellSet = values.keys().copied().collect();
+    let (min, max) = localIjBounds(origin, &cells)?;
+
+    let rows = (max.i as i64 - min.i as i64 + 1) as usize;
+    let cols = (max.j as i64 - min.j as i64 + 1) as usize;
+    match rows.checked_mul(cols) {
+        Some(len) if len <= MAX_RASTER_ELEMENTS => {}
+        _ => return Err(Error::MemoryBounds),
+    }
+    let mut out = Array2::from_elem((rows, cols), None);
+    for (cell, value) in values {
+        let ij = ijkToIj(&cellToLocalIjk(origin, *cell)?);
+        out[[(ij.i - min.i) as usize, (ij.j - min.j) as usize]] = Some(*value);
+    }
+    Ok((min, out))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algos::gridDiskDistances,
+        base_cells::getPentagons,
+        h3_index::{latLngToCell, setH3Index},
+        lat_lng::LatLng,
+    };
+
+    use super::*;
+
+    static sfGeo: LatLng = LatLng {
+        lat: 0.659966917655,
+        lng: -2.1364398519396,
+    };
+
+    #[test]
+    fn localIjkRoundTrip() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        for (cell, _distance) in gridDiskDistances(origin, 3).unwrap() {
+            let ijk = cellToLocalIjk(origin, cell).unwrap();
+            assert_eq!(
+                localIjkToCell(origin, &ijk),
+                Ok(cell),
+                "round trips through local ijk"
+            );
+        }
+        let ijk = cellToLocalIjk(origin, origin).unwrap();
+        assert_eq!(
+            localIjkToCell(origin, &ijk),
+            Ok(origin),
+            "origin maps to itself"
+        );
+    }
+
+    #[test]
+    fn translateSetRoundTrip() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let set: CellSet = gridDiskDistances(origin, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(cell, _distance)| cell)
+            .collect();
+
+        let identity = translateSet(&set, origin, CoordIJ { i: 0, j: 0 }).unwrap();
+        assert_eq!(identity, set, "zero offset is the identity");
+
+        let shifted = translateSet(&set, origin, CoordIJ { i: 5, j: -3 }).unwrap();
+        assert_eq!(shifted.len(), set.len(), "no cells are lost");
+        assert!(
+            !shifted.contains(origin),
+            "origin is moved out of a disk far enough away"
+        );
+        let back = translateSet(&shifted, origin, CoordIJ { i: -5, j: 3 }).unwrap();
+        assert_eq!(back, set, "translations are reversible");
+
+        let center = ijkToIj(&cellToLocalIjk(origin, origin).unwrap());
+        let stamped = translateSet(
+            &CellSet::from_iter([origin]),
+            origin,
+            CoordIJ { i: 1, j: 0 },
+        )
+        .unwrap();
+        let expected = localIjkToCell(
+            origin,
+            &ijToIjk(&CoordIJ {
+                i: center.i + 1,
+                j: center.j,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(stamped.contains(expected), "origin is moved by the offset");
+    }
+
+    #[test]
+    fn translateSetFailures() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let coarse = latLngToCell(&sfGeo, 8).unwrap();
+        assert_eq!(
+            translateSet(
+                &CellSet::from_iter([coarse]),
+                origin,
+                CoordIJ { i: 1, j: 0 }
+            ),
+            Err(Error::ResMismatch),
+            "cells must be at the origin resolution"
+        );
+
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 0, 4, 0);
+        assert!(
+            translateSet(
+                &CellSet::from_iter([pentagon]),
+                pentagon,
+                CoordIJ { i: -1, j: -1 }
+            )
+            .is_err(),
+            "cannot move into the deleted pentagon direction"
+        );
+    }
+
+    #[test]
+    fn rotateSetAboutOrigin() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let disk: CellSet = gridDiskDistances(origin, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(cell, _distance)| cell)
+            .collect();
+        assert_eq!(
+            rotateSet(&disk, origin, 1).unwrap(),
+            disk,
+            "disks are symmetric under rotation"
+        );
+
+        let arm = translateSet(
+            &CellSet::from_iter([origin]),
+            origin,
+            CoordIJ { i: 2, j: 0 },
+        )
+        .unwrap();
+        let mut seen = CellSet::new();
+        for k60 in 0..6 {
+            let rotated = rotateSet(&arm, origin, k60).unwrap();
+            assert_eq!(rotated.len(), 1, "a single cell stays a single cell");
+            seen.extend(rotated.iter());
+            assert_eq!(
+                rotateSet(&rotated, origin, -k60).unwrap(),
+                arm,
+                "counter-rotation restores the set"
+            );
+        }
+        assert_eq!(seen.len(), 6, "six distinct orientations");
+        assert_eq!(
+            rotateSet(&arm, origin, 6).unwrap(),
+            arm,
+            "a full turn is the identity"
+        );
+        assert_eq!(
+            rotateSet(&arm, origin, 2).unwrap(),
+            rotateSet(&arm, origin, -4).unwrap(),
+            "rotations are taken modulo a full turn"
+        );
+    }
+
+    #[test]
+    fn localIjBoundsOfSets() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let center = ijkToIj(&cellToLocalIjk(origin, origin).unwrap());
+        assert_eq!(
+            localIjBounds(origin, &CellSet::from_iter([origin])),
+            Ok((center, center)),
+            "a single cell is its own bounds"
+        );
+
+        let disk: CellSet = gridDiskDistances(origin, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(cell, _distance)| cell)
+            .collect();
+        let (min, max) = localIjBounds(origin, &disk).unwrap();
+        assert_eq!(
+            (max.i - min.i, max.j - min.j),
+            (4, 4),
+            "a k = 2 disk spans 5 cells on each axis"
+        );
+        assert!(min.i <= center.i && center.i <= max.i);
+        assert!(min.j <= center.j && center.j <= max.j);
+
+        let shifted = translateSet(&disk, origin, CoordIJ { i: 3, j: -1 }).unwrap();
+        assert_eq!(
+            localIjBounds(origin, &shifted),
+            Ok((
+                CoordIJ {
+                    i: min.i + 3,
+                    j: min.j - 1
+                },
+                CoordIJ {
+                    i: max.i + 3,
+                    j: max.j - 1
+                }
+            )),
+            "bounds follow translation"
+        );
+
+        assert_eq!(
+            localIjBounds(origin, &CellSet::new()),
+            Err(Error::Domain),
+            "empty sets have no bounds"
+        );
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn rasterizeDisk() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let values: HashMap<H3Index, f64> = gridDiskDistances(origin, 1)
+            .unwrap()
+            .into_iter()
+            .map(|(cell, distance)| (cell, distance as f64))
+            .collect();
+
+        let (min, raster) = rasterizeToIj(origin, &values).unwrap();
+        assert_eq!(raster.dim(), (3, 3), "a k = 1 disk fits a 3x3 matrix");
+        assert_eq!(
+            raster.iter().filter(|v| v.is_some()).count(),
+            7,
+            "every cell is placed once"
+        );
+        let center = ijkToIj(&cellToLocalIjk(origin, origin).unwrap());
+        assert_eq!(
+            raster[[(center.i - min.i) as usize, (center.j - min.j) as usize]],
+            Some(0.0),
+            "origin value is at its IJ position"
+        );
+
+        assert_eq!(
+            rasterizeToIj(origin, &HashMap::new()),
+            Err(Error::Domain),
+            "nothing to rasterize"
+        );
+
+        // two res 15 cells tens of kilometers apart span billions of elements
+        let near = latLngToCell(&sfGeo, 15).unwrap();
+        let far = latLngToCell(
+            &LatLng {
+                lat: sfGeo.lat + 0.005,
+                lng: sfGeo.lng + 0.005,
+            },
+            15,
+        )
+        .unwrap();
+        let values: HashMap<H3Index, f64> = [(near, 1.0), (far, 2.0)].into_iter().collect();
+        assert!(
+            cellToLocalIjk(near, far).is_ok(),
+            "cells share a local IJ system"
+        );
+        assert_eq!(
+            rasterizeToIj(near, &values),
+            Err(Error::MemoryBounds),
+            "oversized raster"
+        );
+    }
+
+    #[test]
+    fn simplifyPathRuns() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let start = cellToLocalIjk(origin, origin).unwrap();
+
+        // five steps along i, then four along j
+        let mut path: Vec<H3Index> = Vec::new();
+        let mut ijk = start;
+        path.push(localIjkToCell(origin, &ijk).unwrap());
+        for dir in [Direction::IAxesDigit; 5]
+            .iter()
+            .chain([Direction::JAxesDigit; 4].iter())
+        {
+            _neighbor(&mut ijk, *dir);
+            path.push(localIjkToCell(origin, &ijk).unwrap());
+        }
+
+        let simplified = simplifyPath(&path);
+        assert_eq!(
+            simplified,
+            vec![path[0], path[5], path[9]],
+            "keeps the ends of each run"
+        );
+
+        // alternating steps have no runs to remove
+        let zigzag: Vec<H3Index> = vec![path[0], path[1], path[0], path[1]];
+        assert_eq!(simplifyPath(&zigzag), zigzag, "nothing to simplify");
+        assert_eq!(simplifyPath(&path[..2]), path[..2].to_vec(), "short path");
+        assert!(simplifyPath(&[]).is_empty(), "empty path");
+    }
+
+    #[test]
+    fn gridDistanceMatchesDisk() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        for (cell, distance) in gridDiskDistances(origin, 4).unwrap() {
+            assert_eq!(
+                gridDistance(origin, cell),
+                Ok(distance as i64),
+                "distance matches the disk"
+            );
+            assert_eq!(
+                gridDistance(cell, origin),
+                Ok(distance as i64),
+                "distance is symmetric"
+            );
+        }
+
+        let coarse = latLngToCell(&sfGeo, 8).unwrap();
+        assert_eq!(
+            gridDistance(origin, coarse),
+            Err(Error::ResMismatch),
+            "resolutions must match"
+        );
+    }
+
+    #[test]
+    fn gridDistanceLocalIjErrors() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        for (cell, distance) in gridDiskDistances(origin, 2).unwrap() {
+            assert_eq!(
+                gridDistanceLocalIj(origin, cell),
+                Ok(distance as i64),
+                "distance matches the disk"
+            );
+        }
+
+        let antipode = LatLng {
+            lat: -sfGeo.lat,
+            lng: sfGeo.lng + core::f64::consts::PI,
+        };
+        let far = latLngToCell(&antipode, 9).unwrap();
+        assert_eq!(
+            gridDistanceLocalIj(origin, far),
+            Err(Error::NotNeighbors),
+            "too far apart"
+        );
+        assert_eq!(
+            gridDistance(origin, far),
+            Err(Error::Failed),
+            "still E_FAILED"
+        );
+
+        // some pairs of cells around a pentagon cannot be unfolded
+        let pentagon = getPentagons(2).unwrap()[0];
+        let disk: Vec<H3Index> = gridDiskDistances(pentagon, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(h, _)| h)
+            .collect();
+        let mut crossings = 0;
+        for &a in &disk {
+            for &b in &disk {
+                let result = gridDistanceLocalIj(a, b);
+                if result == Err(Error::Pentagon) {
+                    crossings += 1;
+                    assert_eq!(gridDistance(a, b), Err(Error::Failed), "still E_FAILED");
+                } else {
+                    assert_eq!(result, gridDistance(a, b), "same as gridDistance");
+                }
+            }
+        }
+        assert!(crossings > 0, "some pairs cross the pentagon");
+
+        let coarse = latLngToCell(&sfGeo, 8).unwrap();
+        assert_eq!(
+            gridDistanceLocalIj(origin, coarse),
+            Err(Error::ResMismatch),
+            "resolutions must match"
+        );
+    }
+
+    #[test]
+    fn gridPathCellsLine() {
+        let start = latLngToCell(&sfGeo, 9).unwrap();
+        for (end, distance) in gridDiskDistances(start, 5).unwrap() {
+            let path = gridPathCells(start, end).unwrap();
+            assert_eq!(
+                gridPathCellsSize(start, end),
+                Ok(distance as i64 + 1),
+                "size is distance + 1"
+            );
+            assert_eq!(
+                path.len(),
+                distance as usize + 1,
+                "line has expected length"
+            );
+            assert_eq!(path[0], start, "line starts at start");
+            assert_eq!(path[path.len() - 1], end, "line ends at end");
+            for pair in path.windows(2) {
+                assert_eq!(
+                    gridDistance(pair[0], pair[1]),
+                    Ok(1),
+                    "consecutive cells are neighbors"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn simplifyPathExpandsBack() {
+        let start = latLngToCell(&sfGeo, 9).unwrap();
+        let mut path: Vec<H3Index> = Vec::new();
+        let mut ijk = cellToLocalIjk(start, start).unwrap();
+        path.push(start);
+        for dir in [
+            Direction::IAxesDigit,
+            Direction::IAxesDigit,
+            Direction::IAxesDigit,
+            Direction::JAxesDigit,
+            Direction::JAxesDigit,
+            Direction::IAxesDigit,
+            Direction::KAxesDigit,
+            Direction::KAxesDigit,
+            Direction::KAxesDigit,
+        ] {
+            _neighbor(&mut ijk, dir);
+            path.push(localIjkToCell(start, &ijk).unwrap());
+        }
+
+        let simplified = simplifyPath(&path);
+        assert!(simplified.len() < path.len(), "path was simplified");
+        let mut expanded: Vec<H3Index> = vec![simplified[0]];
+        for pair in simplified.windows(2) {
+            expanded.extend(&gridPathCells(pair[0], pair[1]).unwrap()[1..]);
+        }
+        assert_eq!(expanded, path, "gridPathCells restores the removed cells");
+    }
+
+    #[test]
+    fn localIjRoundTrip() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        for (cell, distance) in gridDiskDistances(origin, 3).unwrap() {
+            let ij = cellToLocalIj(origin, cell).unwrap();
+            assert_eq!(
+                localIjToCell(origin, &ij),
+                Ok(cell),
+                "round trips through local ij"
+            );
+            let center = cellToLocalIj(origin, origin).unwrap();
+            let di = ij.i - center.i;
+            let dj = ij.j - center.j;
+            // hex distance in axial coordinates
+            let hexDistance = (di.abs() + dj.abs() + (di - dj).abs()) / 2;
+            assert_eq!(hexDistance as u32, distance, "ij distance matches");
+        }
+    }
+
+    #[test]
+    fn localIjPentagon() {
+        // base cell 4 is a pentagon
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 5, 4, 0);
+        for (cell, _distance) in gridDiskDistances(pentagon, 1).unwrap() {
+            let ij = cellToLocalIj(pentagon, cell).unwrap();
+            assert_eq!(
+                localIjToCell(pentagon, &ij),
+                Ok(cell),
+                "round trips around a pentagon"
+            );
+        }
+    }
+
+    #[test]
+    fn localIjInvalid() {
+        let origin = latLngToCell(&sfGeo, 9).unwrap();
+        let coarse = latLngToCell(&sfGeo, 8).unwrap();
+        assert_eq!(
+            cellToLocalIj(origin, coarse),
+            Err(Error::ResMismatch),
+            "resolutions must match"
+        );
+        let overflow = CoordIJ {
+            i: i32::MAX,
+            j: i32::MIN,
+        };
+        assert_eq!(
+            localIjToCell(origin, &overflow),
+            Err(Error::Failed),
+            "out of range coordinates fail"
+        );
+    }
+}
@@ -0,0 +1,444 @@
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::constants::*;
+use crate::directed_edge::directedEdgeToBoundary;
+use crate::error::Error;
+use crate::h3_index::{cellToBoundary, cellToLatLng, H3Index};
+use crate::lat_lng::{greatCircleDistanceRads, LatLng};
+
+/** average hexagon area in square kilometers, by resolution */
+#[allow(
+    clippy::excessive_precision,
+    reason = "table generated by the C library's tooling"
+)]
+const areasKm2: [f64; 16] = [
+    4.357449416078383e+06,
+    6.097884417941332e+05,
+    8.680178039899720e+04,
+    1.239343465508816e+04,
+    1.770347654491307e+03,
+    2.529038581819449e+02,
+    3.612906216441245e+01,
+    5.161293359717191e+00,
+    7.373275975944177e-01,
+    1.053325134272067e-01,
+    1.504750190766435e-02,
+    2.149643129451879e-03,
+    3.070918756316060e-04,
+    4.387026794728296e-05,
+    6.267181135324313e-06,
+    8.953115907605790e-07,
+];
+
+/** average hexagon area in square meters, by resolution */
+#[allow(
+    clippy::excessive_precision,
+    reason = "table generated by the C library's tooling"
+)]
+const areasM2: [f64; 16] = [
+    4.357449416078390e+12,
+    6.097884417941339e+11,
+    8.680178039899731e+10,
+    1.239343465508818e+10,
+    1.770347654491309e+09,
+    2.529038581819452e+08,
+    3.612906216441250e+07,
+    5.161293359717198e+06,
+    7.373275975944188e+05,
+    1.053325134272069e+05,
+    1.504750190766437e+04,
+    2.149643129451882e+03,
+    3.070918756316063e+02,
+    4.387026794728301e+01,
+    6.267181135324322e+00,
+    8.953115907605802e-01,
+];
+
+/** average hexagon edge length in kilometers, by resolution */
+const lensKm: [f64; 16] = [
+    1281.256011,
+    483.0568391,
+    182.5129565,
+    68.97922179,
+    26.07175968,
+    9.854090990,
+    3.724532667,
+    1.406475763,
+    0.531414010,
+    0.200786148,
+    0.075863783,
+    0.028663897,
+    0.010830188,
+    0.004092010,
+    0.001546100,
+    0.000584169,
+];
+
+/** average hexagon edge length in meters, by resolution */
+const lensM: [f64; 16] = [
+    1281256.011,
+    483056.8391,
+    182512.9565,
+    68979.22179,
+    26071.75968,
+    9854.090990,
+    3724.532667,
+    1406.475763,
+    531.414010,
+    200.786148,
+    75.863783,
+    28.663897,
+    10.830188,
+    4.092010,
+    1.546100,
+    0.584169,
+];
+
+/**
+ * Average hexagon area in square kilometers at the given resolution.
+ * Excludes pentagons.
+ *
+ * @param res The resolution.
+ * @return The average area, or ResDomain if res is out of range.
+ */
+pub fn getHexagonAreaAvgKm2(res: i32) -> Result<f64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    Ok(areasKm2[res as usize])
+}
+
+/**
+ * Average hexagon area in square meters at the given resolution.
+ * Excludes pentagons.
+ *
+ * @param res The resolution.
+ * @return The average area, or ResDomain if res is out of range.
+ */
+pub fn getHexagonAreaAvgM2(res: i32) -> Result<f64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    Ok(areasM2[res as usize])
+}
+
+/**
+ * Average hexagon edge length in kilometers at the given resolution.
+ * Excludes pentagons.
+ *
+ * @param res The resolution.
+ * @return The average edge length, or ResDomain if res is out of range.
+ */
+pub fn getHexagonEdgeLengthAvgKm(res: i32) -> Result<f64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    Ok(lensKm[res as usize])
+}
+
+/**
+ * Average hexagon edge length in meters at the given resolution.
+ * Excludes pentagons.
+ *
+ * @param res The resolution.
+ * @return The average edge length, or ResDomain if res is out of range.
+ */
+pub fn getHexagonEdgeLengthAvgM(res: i32) -> Result<f64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    Ok(lensM[res as usize])
+}
+
+/**
+ * Surface area in radians^2 of spherical triangle on unit sphere.
+ *
+ * For the math, see:
+ * https://en.wikipedia.org/wiki/Spherical_trigonometry#Area_and_spherical_excess
+ *
+ * @param   a  length of triangle side A in radians
+ * @param   b  length of triangle side B in radians
+ * @param   c  length of triangle side C in radians
+ *
+ * @return     area in radians^2 of triangle on unit sphere
+ */
+pub fn triangleEdgeLengthsToArea(mut a: f64, mut b: f64, mut c: f64) -> f64 {
+    let mut s = (a + b + c) / 2.0;
+
+    a = (s - a) / 2.0;
+    b = (s - b) / 2.0;
+    c = (s - c) / 2.0;
+    s /= 2.0;
+
+    4.0 * (s.tan() * a.tan() * b.tan() * c.tan()).sqrt().atan()
+}
+
+/**
+ * Compute area in radians^2 of a spherical triangle, given its vertices.
+ *
+ * @param   a  vertex lat/lng in radians
+ * @param   b  vertex lat/lng in radians
+ * @param   c  vertex lat/lng in radians
+ *
+ * @return     area of triangle on unit sphere, in radians^2
+ */
+pub fn triangleArea(a: &LatLng, b: &LatLng, c: &LatLng) -> f64 {
+    triangleEdgeLengthsToArea(
+        greatCircleDistanceRads(a, b),
+        greatCircleDistanceRads(b, c),
+        greatCircleDistanceRads(c, a),
+    )
+}
+
+/**
+ * Area of H3 cell in radians^2.
+ *
+ * The area is calculated by breaking the cell into spherical triangles and
+ * summing up their areas. Note that some H3 cells (hexagons and pentagons)
+ * are irregular, and have more than 6 or 5 sides.
+ *
+ * @param   cell  H3 cell
+ *
+ * @return        cell area in radians^2
+ */
+pub fn cellAreaRads2(cell: H3Index) -> Result<f64, Error> {
+    let c = cellToLatLng(cell)?;
+    let cb = cellToBoundary(cell)?;
+
+    let mut area: f64 = 0.0;
+    for i in 0..cb.numVerts {
+        let j = (i + 1) % cb.numVerts;
+        area += triangleArea(&cb.verts[i], &cb.verts[j], &c);
+    }
+
+    Ok(area)
+}
+
+/**
+ * Area of H3 cell in kilometers^2.
+ */
+pub fn cellAreaKm2(cell: H3Index) -> Result<f64, Error> {
+    Ok(cellAreaRads2(cell)? * EARTH_RADIUS_KM * EARTH_RADIUS_KM)
+}
+
+/**
+ * Area of H3 cell in meters^2.
+ */
+pub fn cellAreaM2(cell: H3Index) -> Result<f64, Error> {
+    Ok(cellAreaKm2(cell)? * 1000.0 * 1000.0)
+}
+
+/**
+ * Length of a directed edge in radians.
+ *
+ * @param   edge  H3 directed edge
+ *
+ * @return        length in radians
+ */
+pub fn edgeLengthRads(edge: H3Index) -> Result<f64, Error> {
+    let verts = directedEdgeToBoundary(edge)?;
+
+    let mut length: f64 = 0.0;
+    for i in 0..verts.len() - 1 {
+        length += greatCircleDistanceRads(&verts[i], &verts[i + 1]);
+    }
+
+    Ok(length)
+}
+
+/**
+ * Length of a directed edge in kilometers.
+ */
+pub fn edgeLengthKm(edge: H3Index) -> Result<f64, Error> {
+    Ok(edgeLengthRads(edge)? * EARTH_RADIUS_KM)
+}
+
+/**
+ * Length of a directed edge in meters.
+ */
+pub fn edgeLengthM(edge: H3Index) -> Result<f64, Error> {
+    Ok(edgeLengthKm(edge)? * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use crate::base_cells::getRes0Cells;
+    use crate::directed_edge::originToDirectedEdges;
+    use crate::H3_NULL;
+    use crate::h3_index::{cellToChildren, latLngToCell, setH3Index};
+
+    use super::*;
+
+    #[test]
+    fn triangleEdgeLengthsToArea_octant() {
+        // one eighth of the sphere: all sides and angles are right angles
+        let area = triangleEdgeLengthsToArea(PI / 2.0, PI / 2.0, PI / 2.0);
+        assert!((area - PI / 2.0).abs() < 1e-12, "octant has area pi/2");
+    }
+
+    #[test]
+    fn cellAreaRads2_sumsToSphere() {
+        for res in 0..3 {
+            let mut total: f64 = 0.0;
+            for res0 in getRes0Cells().unwrap() {
+                for cell in cellToChildren(res0, res).unwrap() {
+                    total += cellAreaRads2(cell).unwrap();
+                }
+            }
+            assert!(
+                (total - 4.0 * PI).abs() < 1e-9,
+                "cell areas at res {} sum to the sphere",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn cellArea_units() {
+        let sf = LatLng::from_degrees(37.7752702151959, -122.418307270836);
+        let cell = latLngToCell(&sf, 9).unwrap();
+
+        let rads2 = cellAreaRads2(cell).unwrap();
+        let km2 = cellAreaKm2(cell).unwrap();
+        let m2 = cellAreaM2(cell).unwrap();
+
+        assert!(
+            (km2 - rads2 * EARTH_RADIUS_KM * EARTH_RADIUS_KM).abs() < 1e-12,
+            "km2 scales rads2"
+        );
+        assert!((m2 - km2 * 1e6).abs() < 1e-6, "m2 scales km2");
+        // average res 9 hexagon is about 0.1053 km2
+        assert!(km2 > 0.08 && km2 < 0.13, "res 9 area is plausible");
+    }
+
+    #[test]
+    fn cellArea_invalid() {
+        let mut invalid: H3Index = 0;
+        setH3Index(&mut invalid, 0, NUM_BASE_CELLS, 0);
+        assert!(cellAreaRads2(invalid).is_err(), "invalid base cell fails");
+        assert!(cellAreaKm2(invalid).is_err(), "invalid base cell fails");
+        assert!(cellAreaM2(invalid).is_err(), "invalid base cell fails");
+    }
+
+    #[test]
+    fn edgeLength_matchesBoundary() {
+        let sf = LatLng::from_degrees(37.7752702151959, -122.418307270836);
+        for res in 0..MAX_H3_RES {
+            let cell = latLngToCell(&sf, res).unwrap();
+            let boundary = cellToBoundary(cell).unwrap();
+            let mut perimeter: f64 = 0.0;
+            for i in 0..boundary.numVerts {
+                let j = (i + 1) % boundary.numVerts;
+                perimeter += greatCircleDistanceRads(&boundary.verts[i], &boundary.verts[j]);
+            }
+
+            let mut total: f64 = 0.0;
+            for edge in originToDirectedEdges(cell) {
+                let rads = edgeLengthRads(edge).unwrap();
+                assert!(rads > 0.0, "edge has positive length");
+                assert!(
+                    (edgeLengthKm(edge).unwrap() - rads * EARTH_RADIUS_KM).abs() < 1e-9,
+                    "km scales rads"
+                );
+                assert!(
+                    (edgeLengthM(edge).unwrap() - edgeLengthKm(edge).unwrap() * 1000.0).abs()
+                        < 1e-6,
+                    "m scales km"
+                );
+                total += rads;
+            }
+            assert!(
+                (total - perimeter).abs() < 1e-12,
+                "edges at res {} sum to the cell perimeter",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn edgeLength_pentagonClassIII() {
+        let mut pentagon: H3Index = 0;
+        setH3Index(&mut pentagon, 1, 4, 0);
+        let boundary = cellToBoundary(pentagon).unwrap();
+        let mut perimeter: f64 = 0.0;
+        for i in 0..boundary.numVerts {
+            let j = (i + 1) % boundary.numVerts;
+            perimeter += greatCircleDistanceRads(&boundary.verts[i], &boundary.verts[j]);
+        }
+
+        let mut total: f64 = 0.0;
+        for edge in originToDirectedEdges(pentagon) {
+            if edge != H3_NULL {
+                total += edgeLengthRads(edge).unwrap();
+            }
+        }
+        assert!(
+            (total - perimeter).abs() < 1e-12,
+            "distorted edges sum to the pentagon perimeter"
+        );
+    }
+
+    #[test]
+    fn edgeLength_invalid() {
+        let sf = LatLng::from_degrees(37.7752702151959, -122.418307270836);
+        let cell = latLngToCell(&sf, 9).unwrap();
+        assert_eq!(
+            edgeLengthRads(cell),
+            Err(Error::DirectedEdgeInvalid),
+            "cell is not an edge"
+        );
+        assert!(edgeLengthKm(cell).is_err(), "cell is not an edge");
+        assert!(edgeLengthM(cell).is_err(), "cell is not an edge");
+    }
+
+    #[test]
+    fn hexagonAverages_decreaseWithRes() {
+        for res in 0..MAX_H3_RES {
+            let area = getHexagonAreaAvgKm2(res).unwrap();
+            let nextArea = getHexagonAreaAvgKm2(res + 1).unwrap();
+            assert!(
+                (area / nextArea - 7.0).abs() < 0.2,
+                "area shrinks by an aperture of 7"
+            );
+            assert!(
+                getHexagonEdgeLengthAvgKm(res + 1).unwrap()
+                    < getHexagonEdgeLengthAvgKm(res).unwrap(),
+                "edge length shrinks"
+            );
+        }
+        for res in 0..=MAX_H3_RES {
+            let km2 = getHexagonAreaAvgKm2(res).unwrap();
+            let m2 = getHexagonAreaAvgM2(res).unwrap();
+            assert!((m2 / (km2 * 1e6) - 1.0).abs() < 1e-9, "m2 agrees with km2");
+            let km = getHexagonEdgeLengthAvgKm(res).unwrap();
+            let m = getHexagonEdgeLengthAvgM(res).unwrap();
+            assert!((m / (km * 1e3) - 1.0).abs() < 1e-6, "m agrees with km");
+        }
+    }
+
+    #[test]
+    fn hexagonAverages_matchExact() {
+        let sf = LatLng::from_degrees(37.7752702151959, -122.418307270836);
+        let cell = latLngToCell(&sf, 9).unwrap();
+        let area = cellAreaKm2(cell).unwrap();
+        let avg = getHexagonAreaAvgKm2(9).unwrap();
+        assert!(
+            (area / avg - 1.0).abs() < 0.25,
+            "exact area is near the average"
+        );
+    }
+
+    #[test]
+    fn hexagonAverages_resDomain() {
+        for res in [-1, MAX_H3_RES + 1] {
+            assert_eq!(getHexagonAreaAvgKm2(res), Err(Error::ResDomain));
+            assert_eq!(getHexagonAreaAvgM2(res), Err(Error::ResDomain));
+            assert_eq!(getHexagonEdgeLengthAvgKm(res), Err(Error::ResDomain));
+            assert_eq!(getHexagonEdgeLengthAvgM(res), Err(Error::ResDomain));
+        }
+    }
+}
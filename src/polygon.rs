@@ -0,0 +1,2309 @@
+use core::f64::consts::{FRAC_PI_2, PI};
+
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::algos::gridDiskDistances;
+use crate::bbox::{
+    BBox, bboxContains, bboxHeightRads, bboxIsTransmeridian, bboxLngOffset, bboxNormalizeLng,
+    bboxOverlapsBBox, bboxWidthRads,
+};
+use crate::cell_set::{CellSet, CompactCellSet};
+use crate::constants::*;
+use crate::directed_edge::{cellsToDirectedEdge, directedEdgeToBoundary};
+use crate::error::Error;
+use crate::base_cells::getRes0Cells;
+use crate::h3_index::{
+    H3Index, H3_GET_RESOLUTION, cellToBoundary, cellToLatLng, latLngToCell, setH3Index,
+};
+use crate::iterators::IterCellsChildren;
+use crate::lat_lng::{LatLng, constrainLng, greatCircleDistanceRads, greatCircleInterpolate};
+use crate::local_ij::gridPathCells;
+use crate::prelude::*;
+use crate::scratch::{ScratchSet, withCellScratch};
+use crate::vec3d::{Vec3d, _geoToVec3d, _vec3dCross, _vec3dDot};
+use crate::H3_NULL;
+
+/** Extra cells to allow for in maxPolygonToCellsSize, for small polygons
+ * near icosahedron edges at odd resolutions */
+const POLYGON_TO_CELLS_BUFFER: i64 = 12;
+
+/** @struct GeoLoop
+ *  @brief similar to GeoJSON linear ring, but without the repeated closing
+ *  vertex
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GeoLoop {
+    /** vertices, in radians */
+    pub verts: Vec<LatLng>,
+}
+
+/** @struct GeoPolygon
+ *  @brief Simplified core of GeoJSON Polygon coordinates definition
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GeoPolygon {
+    /** exterior boundary of the polygon */
+    pub geoloop: GeoLoop,
+    /** interior boundaries (holes) in the polygon */
+    pub holes: Vec<GeoLoop>,
+}
+
+/** @struct GeoMultiPolygon
+ *  @brief Simplified core of GeoJSON MultiPolygon coordinates definition
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct GeoMultiPolygon {
+    /** the polygons */
+    pub polygons: Vec<GeoPolygon>,
+}
+
+/**
+ * Create a bounding box from a GeoLoop. When the loop crosses the
+ * antimeridian the west longitude is greater than the east longitude.
+ * @param geoloop Input GeoLoop
+ * @return Output bbox
+ */
+pub fn bboxFromGeoLoop(geoloop: &GeoLoop) -> BBox {
+    // Early exit if there are no vertices
+    if geoloop.verts.is_empty() {
+        return BBox {
+            north: 0.0,
+            south: 0.0,
+            east: 0.0,
+            west: 0.0,
+        };
+    }
+
+    let mut bbox = BBox {
+        north: -f64::MAX,
+        south: f64::MAX,
+        east: -f64::MAX,
+        west: f64::MAX,
+    };
+    let mut minPosLng = f64::MAX;
+    let mut maxNegLng = -f64::MAX;
+    let mut isTransmeridian = false;
+
+    let numVerts = geoloop.verts.len();
+    for i in 0..numVerts {
+        let coord = geoloop.verts[i];
+        let next = geoloop.verts[(i + 1) % numVerts];
+
+        let lat = coord.lat;
+        let lng = constrainLng(coord.lng);
+        if lat < bbox.south {
+            bbox.south = lat;
+        }
+        if lng < bbox.west {
+            bbox.west = lng;
+        }
+        if lat > bbox.north {
+            bbox.north = lat;
+        }
+        if lng > bbox.east {
+            bbox.east = lng;
+        }
+        // Save the min positive and max negative longitude for
+        // use in the transmeridian case
+        if lng > 0.0 && lng < minPosLng {
+            minPosLng = lng;
+        }
+        if lng < 0.0 && lng > maxNegLng {
+            maxNegLng = lng;
+        }
+        // check for arcs > 180 degrees longitude, flagging as transmeridian
+        if (lng - constrainLng(next.lng)).abs() > PI {
+            isTransmeridian = true;
+        }
+    }
+    // Swap east and west if transmeridian
+    if isTransmeridian {
+        bbox.east = maxNegLng;
+        bbox.west = minPosLng;
+    }
+    bbox
+}
+
+/**
+ * Whether a GeoLoop crosses the antimeridian, in which case its longitudes
+ * are compared after bboxNormalizeLng.
+ * @param geoloop Input GeoLoop
+ * @return Whether the loop is transmeridian
+ */
+pub fn geoLoopIsTransmeridian(geoloop: &GeoLoop) -> bool {
+    bboxIsTransmeridian(&bboxFromGeoLoop(geoloop))
+}
+
+/**
+ * Normalize the longitudes of a GeoLoop into a single frame, so that they
+ * increase eastward across the antimeridian without a jump. Longitudes of a
+ * transmeridian loop are in [0, 360) degrees, and of other loops in
+ * [-180, 180] degrees.
+ * @param geoloop Input GeoLoop
+ * @return The loop with normalized longitudes
+ */
+pub fn normalizeGeoLoop(geoloop: &GeoLoop) -> GeoLoop {
+    let isTransmeridian = geoLoopIsTransmeridian(geoloop);
+    GeoLoop {
+        verts: geoloop
+            .verts
+            .iter()
+            .map(|g| LatLng {
+                lat: g.lat,
+                lng: bboxNormalizeLng(g.lng, isTransmeridian),
+            })
+            .collect(),
+    }
+}
+
+/**
+ * Create bounding boxes for a GeoPolygon, the outer loop first followed by
+ * the holes, as used by pointInsidePolygon
+ * @param polygon Input GeoPolygon
+ * @return Output bboxes, one for the outer loop and one for each hole
+ */
+pub fn bboxesFromGeoPolygon(polygon: &GeoPolygon) -> Vec<BBox> {
+    let mut bboxes = Vec::with_capacity(1 + polygon.holes.len());
+    bboxes.push(bboxFromGeoLoop(&polygon.geoloop));
+    for hole in &polygon.holes {
+        bboxes.push(bboxFromGeoLoop(hole));
+    }
+    bboxes
+}
+
+/**
+ * Take a given GeoLoop data structure and check if it
+ * contains a given geo coordinate. The bbox is from bboxFromGeoLoop, so it
+ * can be computed once when checking many coordinates against a loop.
+ *
+ * @param geoloop The geoloop
+ * @param bbox The bbox for the loop
+ * @param coord The coordinate to check
+ * @return Whether the point is contained
+ */
+pub fn pointInsideGeoLoop(geoloop: &GeoLoop, bbox: &BBox, coord: &LatLng) -> bool {
+    // fail fast if we're outside the bounding box
+    if !bboxContains(bbox, coord) {
+        return false;
+    }
+    let isTransmeridian = bboxIsTransmeridian(bbox);
+    let mut contains = false;
+
+    let mut lat = coord.lat;
+    let mut lng = bboxNormalizeLng(coord.lng, isTransmeridian);
+
+    let numVerts = geoloop.verts.len();
+    for i in 0..numVerts {
+        let mut a = geoloop.verts[i];
+        let mut b = geoloop.verts[(i + 1) % numVerts];
+
+        // Ray casting algo requires the second point to always be higher
+        // than the first, so swap if needed
+        if a.lat > b.lat {
+            core::mem::swap(&mut a, &mut b);
+        }
+
+        // If the latitude matches exactly, we'll hit an edge case where
+        // the ray passes through the vertex twice on successive segment
+        // checks. To avoid this, adjust the latiude northward if needed.
+        //
+        // NOTE: This currently means that a point at the north pole cannot
+        // be contained in any polygon. This is acceptable in current usage,
+        // because the point we test in this function at present is always
+        // a cell center or vertex, and no cell has a center or vertex on the
+        // north pole. If we need to expand this algo to more generic uses we
+        // might need to handle this edge case.
+        if lat == a.lat || lat == b.lat {
+            lat += f64::EPSILON;
+        }
+
+        // If we're totally above or below the latitude ranges, the test
+        // ray cannot intersect the line segment, so let's move on
+        if lat < a.lat || lat > b.lat {
+            continue;
+        }
+
+        let aLng = bboxNormalizeLng(a.lng, isTransmeridian);
+        let bLng = bboxNormalizeLng(b.lng, isTransmeridian);
+
+        // Rays are cast in the longitudinal direction, in case a point
+        // exactly matches, to decide tiebreakers, bias westerly
+        if aLng == lng || bLng == lng {
+            lng -= f64::EPSILON;
+        }
+
+        // For the latitude of the point, compute the longitude of the
+        // point that lies on the line segment defined by a and b
+        // This is done by computing the percent above a the lat is,
+        // and traversing the same percent in the longitudinal direction
+        // of a to b
+        let ratio = (lat - a.lat) / (b.lat - a.lat);
+        let testLng = bboxNormalizeLng(aLng + (bLng - aLng) * ratio, isTransmeridian);
+
+        // Intersection of the ray
+        if testLng > lng {
+            contains = !contains;
+        }
+    }
+
+    contains
+}
+
+/**
+ * pointInsidePolygon takes a given GeoPolygon data structure and
+ * checks if it contains a given geo coordinate.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param bboxes The bboxes for the main geoloop and each of its holes, from
+ *               bboxesFromGeoPolygon
+ * @param coord The coordinate to check
+ * @return Whether the point is contained
+ */
+pub fn pointInsidePolygon(polygon: &GeoPolygon, bboxes: &[BBox], coord: &LatLng) -> bool {
+    // Start with contains state of primary geoloop
+    let mut contains = pointInsideGeoLoop(&polygon.geoloop, &bboxes[0], coord);
+
+    // If the point is contained in the primary geoloop, but there are holes in
+    // the geoloop iterate over all holes and determine if the point is
+    // contained in any hole
+    if contains {
+        for (i, hole) in polygon.holes.iter().enumerate() {
+            // If the point is contained in any hole, it is not contained
+            if pointInsideGeoLoop(hole, &bboxes[i + 1], coord) {
+                contains = false;
+                break;
+            }
+        }
+    }
+
+    contains
+}
+
+/**
+ * Whether a GeoLoop contains a coordinate. When checking many coordinates,
+ * compute the bbox once and use pointInsideGeoLoop instead.
+ *
+ * @param geoloop The geoloop
+ * @param coord The coordinate to check
+ * @return Whether the point is contained
+ */
+pub fn geoLoopContainsLatLng(geoloop: &GeoLoop, coord: &LatLng) -> bool {
+    pointInsideGeoLoop(geoloop, &bboxFromGeoLoop(geoloop), coord)
+}
+
+/**
+ * Whether a GeoPolygon contains a coordinate, that is whether the outer loop
+ * contains it and none of the holes do. When checking many coordinates,
+ * compute the bboxes once and use pointInsidePolygon instead.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param coord The coordinate to check
+ * @return Whether the point is contained
+ */
+pub fn polygonContainsLatLng(polygon: &GeoPolygon, coord: &LatLng) -> bool {
+    pointInsidePolygon(polygon, &bboxesFromGeoPolygon(polygon), coord)
+}
+
+/**
+ * Whether the winding order of a given loop is clockwise. In GeoJSON,
+ * clockwise loops are always inner loops (holes).
+ *
+ * @param geoloop The loop to check
+ * @return Whether the loop is clockwise
+ */
+pub(crate) fn isClockwiseGeoLoop(geoloop: &GeoLoop) -> bool {
+    let isTransmeridian = geoLoopIsTransmeridian(geoloop);
+    let mut sum = 0.0;
+    let numVerts = geoloop.verts.len();
+    for i in 0..numVerts {
+        let a = geoloop.verts[i];
+        let b = geoloop.verts[(i + 1) % numVerts];
+        sum += (bboxNormalizeLng(b.lng, isTransmeridian)
+            - bboxNormalizeLng(a.lng, isTransmeridian))
+            * (b.lat + a.lat);
+    }
+    sum > 0.0
+}
+
+/**
+ * Builds a multipolygon from loops, treating counterclockwise loops as outer
+ * loops and clockwise loops as holes. Each hole is assigned to the smallest
+ * outer loop containing it.
+ *
+ * @param loops The loops
+ * @return The multipolygon, or Failed if a hole is not inside any outer loop
+ */
+pub(crate) fn normalizeMultiPolygon(loops: Vec<GeoLoop>) -> Result<GeoMultiPolygon, Error> {
+    let mut polygons: Vec<GeoPolygon> = Vec::new();
+    let mut holes: Vec<GeoLoop> = Vec::new();
+    for geoloop in loops {
+        if isClockwiseGeoLoop(&geoloop) {
+            holes.push(geoloop);
+        } else {
+            polygons.push(GeoPolygon {
+                geoloop,
+                holes: Vec::new(),
+            });
+        }
+    }
+
+    let bboxes: Vec<BBox> = polygons
+        .iter()
+        .map(|polygon| bboxFromGeoLoop(&polygon.geoloop))
+        .collect();
+    for hole in holes {
+        // Test the middle of an edge, as holes can touch their outer loop at
+        // a vertex
+        let test = greatCircleInterpolate(&hole.verts[0], &hole.verts[1 % hole.verts.len()], 0.5)?;
+        let mut container: Option<usize> = None;
+        for (i, polygon) in polygons.iter().enumerate() {
+            if !pointInsideGeoLoop(&polygon.geoloop, &bboxes[i], &test) {
+                continue;
+            }
+            let smaller = match container {
+                Some(j) => {
+                    bboxWidthRads(&bboxes[i]) * bboxHeightRads(&bboxes[i])
+                        < bboxWidthRads(&bboxes[j]) * bboxHeightRads(&bboxes[j])
+                }
+                None => true,
+            };
+            if smaller {
+                container = Some(i);
+            }
+        }
+        match container {
+            Some(i) => polygons[i].holes.push(hole),
+            None => return Err(Error::Failed),
+        }
+    }
+    Ok(GeoMultiPolygon { polygons })
+}
+
+/**
+ * Returns the approximate radius, in radians, of the smallest cells at a
+ * resolution. Pentagons are the most distorted cells, so the radius is that
+ * of a hexagon spaced like the first pentagon and its neighbors.
+ *
+ * @param res The resolution
+ * @return The radius in radians
+ */
+fn _pentagonRadiusRads(res: i32) -> Result<f64, Error> {
+    let mut pentagon: H3Index = H3_NULL;
+    setH3Index(&mut pentagon, res, 4, 0);
+    let center = cellToLatLng(pentagon)?;
+
+    for (neighbor, distance) in gridDiskDistances(pentagon, 1)? {
+        if distance == 1 {
+            // hexagon centers are sqrt(3) times the radius apart
+            let spacing = greatCircleDistanceRads(&center, &cellToLatLng(neighbor)?);
+            return Ok(spacing / (2.0 * M_SQRT3_2));
+        }
+    }
+    Err(Error::Failed)
+}
+
+/** Bound on the distance from the center of any cell to its vertices, as a
+ * multiple of _pentagonRadiusRads at the same resolution. The largest cells
+ * at res 0 to 3 reach about 1.55 times it. */
+const MAX_CELL_RADIUS_FACTOR: f64 = 2.0;
+
+/**
+ * Returns a lower bound on the area of the cells at a resolution, in
+ * radians squared, given their _pentagonRadiusRads.
+ *
+ * Area of a regular hexagon is 3/2*sqrt(3) * r * r. The pentagon has the most
+ * distortion (smallest edges) and shares its edges with hexagons, so the
+ * most-distorted hexagons have this area, shrunk by 20% off chance that the
+ * bounding box perfectly bounds a pentagon.
+ */
+fn _minCellAreaRads2(pentagonRadius: f64) -> f64 {
+    0.8 * (2.59807621135 * pentagonRadius * pentagonRadius)
+}
+
+/**
+ * Validates a polygon and resolution for the polygonToCells functions.
+ *
+ * @param polygon The polygon
+ * @param res The resolution
+ */
+fn _validatePolygonArgs(polygon: &GeoPolygon, res: i32) -> Result<(), Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    for geoloop in core::iter::once(&polygon.geoloop).chain(polygon.holes.iter()) {
+        for vert in &geoloop.verts {
+            if !vert.lat.is_finite() || !vert.lng.is_finite() {
+                return Err(Error::LatLngDomain);
+            }
+        }
+    }
+    Ok(())
+}
+
+/**
+ * maxPolygonToCellsSize returns the number of cells to allocate space for
+ * when performing a polygonToCells on the given GeoJSON-like data structure.
+ *
+ * The estimate is based on the area of the bounding box of the outer loop
+ * and on the area of the smallest cells at the resolution.
+ *
+ * @param polygon A GeoJSON-like data structure indicating the poly to fill
+ * @param res Hexagon resolution (0-15)
+ * @return number of cells to allocate for
+ */
+pub fn maxPolygonToCellsSize(polygon: &GeoPolygon, res: i32) -> Result<i64, Error> {
+    _validatePolygonArgs(polygon, res)?;
+    let bbox = bboxFromGeoLoop(&polygon.geoloop);
+
+    // Get the area of the pentagon as the maximally-distorted area possible
+    let pentagonArea = _minCellAreaRads2(_pentagonRadiusRads(res)?);
+
+    // Then get the area of the bounding box of the geoloop in question
+    let p1 = LatLng {
+        lat: bbox.north,
+        lng: bbox.east,
+    };
+    let p2 = LatLng {
+        lat: bbox.south,
+        lng: bbox.west,
+    };
+    let d = greatCircleDistanceRads(&p1, &p2);
+    let mut d1 = bboxWidthRads(&bbox);
+    let mut d2 = (p1.lat - p2.lat).abs();
+    if d1 < d2 {
+        core::mem::swap(&mut d1, &mut d2);
+    }
+    // Derived constant based on: https://math.stackexchange.com/a/1921940
+    // Clamped to 3 as higher values tend to rapidly drag the estimate to zero.
+    let a = d * d / (d1 / d2).abs().min(3.0);
+
+    // Divide the two to get an estimate of the number of hexagons needed
+    let estimateDouble = (a / pentagonArea).ceil();
+    if !estimateDouble.is_finite() {
+        return Err(Error::Failed);
+    }
+    let mut numHexagons = (estimateDouble as i64).max(1);
+
+    // This algorithm assumes that the number of vertices is usually less than
+    // the number of hexagons, but when it's wrong, this will keep it from
+    // failing
+    let mut totalVerts = polygon.geoloop.verts.len() as i64;
+    for hole in &polygon.holes {
+        totalVerts += hole.verts.len() as i64;
+    }
+    if numHexagons < totalVerts {
+        numHexagons = totalVerts;
+    }
+    // When the polygon is very small, near an icosahedron edge and is an odd
+    // resolution, the line tracing needs an extra buffer than the estimator
+    // function provides (but beefing that up to cover causes most situations
+    // to overallocate memory)
+    numHexagons += POLYGON_TO_CELLS_BUFFER;
+    Ok(numHexagons)
+}
+
+/**
+ * _getEdgeHexagons takes a given geoloop ring and traces it with cells,
+ * adding cells not previously seen to the search list.
+ *
+ * @param geoloop The geoloop to trace
+ * @param res The resolution
+ * @param sampleSpacing The distance in radians between samples along edges
+ * @param seen The cells traced so far
+ * @param search The cells to search from
+ */
+fn _getEdgeHexagons(
+    geoloop: &GeoLoop,
+    res: i32,
+    sampleSpacing: f64,
+    seen: &mut ScratchSet,
+    search: &mut Vec<H3Index>,
+) -> Result<(), Error> {
+    let numVerts = geoloop.verts.len();
+    for i in 0..numVerts {
+        let origin = geoloop.verts[i];
+        let destination = geoloop.verts[(i + 1) % numVerts];
+
+        let distance = greatCircleDistanceRads(&origin, &destination);
+        let numHexesEstimate = ((distance / sampleSpacing).ceil() as i64).max(1);
+        for j in 0..numHexesEstimate {
+            let interpolate =
+                greatCircleInterpolate(&origin, &destination, j as f64 / numHexesEstimate as f64)?;
+            let pointHex = latLngToCell(&interpolate, res)?;
+            if seen.insert(pointHex) {
+                search.push(pointHex);
+            }
+        }
+    }
+    Ok(())
+}
+
+/**
+ * How the polygonToCells functions decide whether a cell is in a polygon.
+ * The values match the containment mode flags of the C library.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContainmentMode {
+    /** The center of the cell is contained in the polygon */
+    #[default]
+    CenterContained = 0,
+    /** The whole cell is contained in the polygon */
+    FullyContained = 1,
+    /** Any part of the cell overlaps the polygon */
+    Overlapping = 2,
+    /** Any part of the bounding box of the cell overlaps the polygon */
+    OverlappingBbox = 3,
+}
+
+impl ContainmentMode {
+    /**
+     * Reads the containment mode from the flags of the C polygonToCells.
+     *
+     * @param flags The flags, of which only the mode may be set.
+     * @return The mode, or OptionInvalid for unknown flags.
+     */
+    pub fn from_flags(flags: u32) -> Result<ContainmentMode, Error> {
+        match flags {
+            0 => Ok(ContainmentMode::CenterContained),
+            1 => Ok(ContainmentMode::FullyContained),
+            2 => Ok(ContainmentMode::Overlapping),
+            3 => Ok(ContainmentMode::OverlappingBbox),
+            _ => Err(Error::OptionInvalid),
+        }
+    }
+}
+
+/**
+ * Whether the line segments from a to b and from c to d cross, treating
+ * coordinates as planar like the point in polygon tests, with longitudes
+ * already normalized.
+ */
+fn _segmentsCross(a: &LatLng, b: &LatLng, c: &LatLng, d: &LatLng) -> bool {
+    let orient = |p: &LatLng, q: &LatLng, r: &LatLng| -> f64 {
+        (q.lng - p.lng) * (r.lat - p.lat) - (q.lat - p.lat) * (r.lng - p.lng)
+    };
+    let d1 = orient(c, d, a);
+    let d2 = orient(c, d, b);
+    let d3 = orient(a, b, c);
+    let d4 = orient(a, b, d);
+    d1 * d2 <= 0.0
+        && d3 * d4 <= 0.0
+        // collinear segments only cross if their extents overlap
+        && a.lat.min(b.lat) <= c.lat.max(d.lat)
+        && c.lat.min(d.lat) <= a.lat.max(b.lat)
+        && a.lng.min(b.lng) <= c.lng.max(d.lng)
+        && c.lng.min(d.lng) <= a.lng.max(b.lng)
+}
+
+/**
+ * Whether any edge of a loop crosses any edge of another, such as a cell
+ * boundary.
+ *
+ * @param geoloop The loop
+ * @param bbox The bbox of the loop
+ * @param other The other loop
+ * @param otherBbox The bbox of the other loop
+ */
+fn _geoLoopCrossesGeoLoop(
+    geoloop: &GeoLoop,
+    bbox: &BBox,
+    other: &GeoLoop,
+    otherBbox: &BBox,
+) -> bool {
+    if !bboxOverlapsBBox(bbox, otherBbox) {
+        return false;
+    }
+    let isTransmeridian = bboxIsTransmeridian(bbox) || bboxIsTransmeridian(otherBbox);
+    let normalize = |g: &LatLng| LatLng {
+        lat: g.lat,
+        lng: bboxNormalizeLng(g.lng, isTransmeridian),
+    };
+    let others: Vec<LatLng> = other.verts.iter().map(normalize).collect();
+    let n = geoloop.verts.len();
+    let m = others.len();
+    for i in 0..n {
+        let a = normalize(&geoloop.verts[i]);
+        let b = normalize(&geoloop.verts[(i + 1) % n]);
+        if a.lat.max(b.lat) < otherBbox.south || a.lat.min(b.lat) > otherBbox.north {
+            continue;
+        }
+        for j in 0..m {
+            if _segmentsCross(&a, &b, &others[j], &others[(j + 1) % m]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/**
+ * Tests a cell against a polygon for a containment mode other than
+ * CenterContained.
+ *
+ * @param polygon The polygon
+ * @param bboxes The bboxes of the polygon loops
+ * @param h The cell
+ * @param center The center of the cell
+ * @param mode The containment mode
+ */
+fn _cellInPolygon(
+    polygon: &GeoPolygon,
+    bboxes: &[BBox],
+    h: H3Index,
+    center: &LatLng,
+    mode: ContainmentMode,
+) -> Result<bool, Error> {
+    let centerInside = pointInsidePolygon(polygon, bboxes, center);
+    if mode == ContainmentMode::CenterContained {
+        return Ok(centerInside);
+    }
+    if mode == ContainmentMode::FullyContained && !centerInside {
+        return Ok(false);
+    }
+    if mode == ContainmentMode::Overlapping && centerInside {
+        return Ok(true);
+    }
+
+    let boundary = cellToBoundary(h)?;
+    let mut cell = GeoLoop {
+        verts: boundary.to_vec(),
+    };
+    let mut cellBbox = bboxFromGeoLoop(&cell);
+    if mode == ContainmentMode::OverlappingBbox {
+        // the cell's bounding box stands in for the cell
+        cell = GeoLoop {
+            verts: vec![
+                LatLng {
+                    lat: cellBbox.south,
+                    lng: cellBbox.west,
+                },
+                LatLng {
+                    lat: cellBbox.south,
+                    lng: cellBbox.east,
+                },
+                LatLng {
+                    lat: cellBbox.north,
+                    lng: cellBbox.east,
+                },
+                LatLng {
+                    lat: cellBbox.north,
+                    lng: cellBbox.west,
+                },
+            ],
+        };
+        cellBbox = bboxFromGeoLoop(&cell);
+        if centerInside {
+            return Ok(true);
+        }
+    }
+
+    let loops = core::iter::once(&polygon.geoloop).chain(polygon.holes.iter());
+    let crosses = loops
+        .zip(bboxes.iter())
+        .any(|(geoloop, bbox)| _geoLoopCrossesGeoLoop(geoloop, bbox, &cell, &cellBbox));
+    if mode == ContainmentMode::FullyContained {
+        // with no edge crossing, the cell is either inside the outer loop or
+        // around all of it; once inside, the only way out is a hole inside
+        // the cell
+        return Ok(!crosses
+            && pointInsideGeoLoop(&polygon.geoloop, &bboxes[0], &cell.verts[0])
+            && polygon.holes.iter().all(|hole| {
+                hole.verts.is_empty() || !pointInsideGeoLoop(&cell, &cellBbox, &hole.verts[0])
+            }));
+    }
+    // overlapping: an edge crosses, or one is inside the other
+    Ok(crosses
+        || pointInsidePolygon(polygon, bboxes, &cell.verts[0])
+        || pointInsideGeoLoop(&cell, &cellBbox, &polygon.geoloop.verts[0]))
+}
+
+/**
+ * polygonToCellsInto adds the cells whose centers are contained in a
+ * GeoJSON-like polygon to an existing set.
+ *
+ * The loops of the polygon are traced with cells, and the polygon is flood
+ * filled from there, so cells already in the set do not need to be removed
+ * first and coverage of several polygons can be accumulated.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @param set The set to add the cells to
+ */
+pub fn polygonToCellsInto(polygon: &GeoPolygon, res: i32, set: &mut CellSet) -> Result<(), Error> {
+    polygonToCellsWithModeInto(polygon, res, ContainmentMode::CenterContained, set)
+}
+
+/**
+ * polygonToCellsWithModeInto adds the cells in a GeoJSON-like polygon to an
+ * existing set, like polygonToCellsInto, with a choice of how cells on the
+ * edges of the polygon are treated.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @param mode Which cells are in the polygon
+ * @param set The set to add the cells to
+ */
+pub fn polygonToCellsWithModeInto(
+    polygon: &GeoPolygon,
+    res: i32,
+    mode: ContainmentMode,
+    set: &mut CellSet,
+) -> Result<(), Error> {
+    _validatePolygonArgs(polygon, res)?;
+    if polygon.geoloop.verts.is_empty() {
+        return Ok(());
+    }
+
+    let bboxes = bboxesFromGeoPolygon(polygon);
+    // Sample edges about every pentagon diameter, as pentagons are the
+    // smallest cells at the resolution
+    let sampleSpacing = 2.0 * _pentagonRadiusRads(res)?;
+
+    withCellScratch(|visited| {
+        // 1. Trace the hexagons along the polygon defining the outer geoloop
+        // and the holes, and add them to the search list.
+        let mut search: Vec<H3Index> = Vec::new();
+        _getEdgeHexagons(&polygon.geoloop, res, sampleSpacing, visited, &mut search)?;
+        for hole in &polygon.holes {
+            _getEdgeHexagons(hole, res, sampleSpacing, visited, &mut search)?;
+        }
+
+        // 2. The traced cells are search starting points, not results, so
+        // forget them and examine each (including the traced cells
+        // themselves) as a neighbor of the search cells.
+        visited.clear();
+
+        // 3. Flood fill: any neighbor of a search cell whose center is inside
+        // the polygon is added to the output and searched in the next pass.
+        // The overlapping modes spread through the overlapping cells instead,
+        // and fully contained cells are a subset of the center contained
+        // ones.
+        let spreadMode = match mode {
+            ContainmentMode::FullyContained => ContainmentMode::CenterContained,
+            _ => mode,
+        };
+        let mut next: Vec<H3Index> = Vec::new();
+        while !search.is_empty() {
+            for &h in &search {
+                for (neighbor, _) in gridDiskDistances(h, 1)? {
+                    if !visited.insert(neighbor) {
+                        continue;
+                    }
+                    let center = cellToLatLng(neighbor)?;
+                    if !_cellInPolygon(polygon, &bboxes, neighbor, &center, spreadMode)? {
+                        continue;
+                    }
+                    if spreadMode == mode
+                        || _cellInPolygon(polygon, &bboxes, neighbor, &center, mode)?
+                    {
+                        set.insert(neighbor);
+                    }
+                    next.push(neighbor);
+                }
+            }
+            core::mem::swap(&mut search, &mut next);
+            next.clear();
+        }
+        Ok(())
+    })
+}
+
+/**
+ * polygonToCells takes a given GeoJSON-like data structure and returns the
+ * cells whose centers are contained in it.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @return The cells, in ascending index order
+ */
+pub fn polygonToCells(polygon: &GeoPolygon, res: i32) -> Result<Vec<H3Index>, Error> {
+    let mut set = CellSet::new();
+    polygonToCellsInto(polygon, res, &mut set)?;
+    Ok(set.iter().collect())
+}
+
+/**
+ * polygonToCellsWithMode returns the cells in a GeoJSON-like polygon, with a
+ * choice of how cells on the edges of the polygon are treated. Center
+ * containment can leave parts of the polygon uncovered at coarse
+ * resolutions; the overlapping modes cover all of it.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @param mode Which cells are in the polygon
+ * @return The cells, in ascending index order
+ */
+pub fn polygonToCellsWithMode(
+    polygon: &GeoPolygon,
+    res: i32,
+    mode: ContainmentMode,
+) -> Result<Vec<H3Index>, Error> {
+    let mut set = CellSet::new();
+    polygonToCellsWithModeInto(polygon, res, mode, &mut set)?;
+    Ok(set.iter().collect())
+}
+
+/**
+ * multiPolygonToCells returns the cells whose centers are contained in any
+ * polygon of a GeoJSON-like multipolygon. Cells of polygons that overlap or
+ * share edges are returned once.
+ *
+ * @param multiPolygon The polygons defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @return The cells, in ascending index order
+ */
+pub fn multiPolygonToCells(
+    multiPolygon: &GeoMultiPolygon,
+    res: i32,
+) -> Result<Vec<H3Index>, Error> {
+    multiPolygonToCellsWithMode(multiPolygon, res, ContainmentMode::CenterContained)
+}
+
+/**
+ * maxMultiPolygonToCellsSize returns the number of cells to allocate space
+ * for when performing a multiPolygonToCells, the sum of the
+ * maxPolygonToCellsSize of its polygons.
+ *
+ * @param multiPolygon The polygons defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @return number of cells to allocate for
+ */
+pub fn maxMultiPolygonToCellsSize(multiPolygon: &GeoMultiPolygon, res: i32) -> Result<i64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let mut total: i64 = 0;
+    for polygon in &multiPolygon.polygons {
+        total = total
+            .checked_add(maxPolygonToCellsSize(polygon, res)?)
+            .ok_or(Error::MemoryBounds)?;
+    }
+    Ok(total)
+}
+
+/**
+ * multiPolygonToCellsWithMode returns the cells in any polygon of a
+ * GeoJSON-like multipolygon, like multiPolygonToCells, with a choice of how
+ * cells on the edges of the polygons are treated.
+ *
+ * @param multiPolygon The polygons defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @param mode Which cells are in the polygons
+ * @return The cells, in ascending index order
+ */
+pub fn multiPolygonToCellsWithMode(
+    multiPolygon: &GeoMultiPolygon,
+    res: i32,
+    mode: ContainmentMode,
+) -> Result<Vec<H3Index>, Error> {
+    let mut set = CellSet::new();
+    for polygon in &multiPolygon.polygons {
+        polygonToCellsWithModeInto(polygon, res, mode, &mut set)?;
+    }
+    Ok(set.iter().collect())
+}
+
+/** How far the centers of the descendants of a cell can be from its center,
+ * as a multiple of the distance to its furthest vertex R. Each resolution
+ * moves a descendant at most one neighbor, √3 times its own circumradius,
+ * from the center of its parent, and circumradii shrink by √7 per
+ * resolution, so on the plane the descendants stay within
+ * √3 R / (√7 - 1) ≈ 1.05 R. The rest is room for the distortion of the
+ * projection, which the descendantRadiusFactor test checks at every
+ * resolution. */
+const DESCENDANT_RADIUS_FACTOR: f64 = 1.2;
+
+/**
+ * Returns a bound on the distance from the center of a cell to the centers
+ * of all of its descendants.
+ *
+ * @param h The cell
+ * @param center The center of the cell
+ * @return The distance in radians
+ */
+fn _descendantRadiusRads(h: H3Index, center: &LatLng) -> Result<f64, Error> {
+    let boundary = cellToBoundary(h)?;
+    let mut radius: f64 = 0.0;
+    for v in &boundary.verts[..boundary.numVerts] {
+        radius = radius.max(greatCircleDistanceRads(center, v));
+    }
+    Ok(DESCENDANT_RADIUS_FACTOR * radius)
+}
+
+/** How a spherical cap relates to a bounding box. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CapInBBox {
+    Inside,
+    Outside,
+    Overlapping,
+}
+
+/**
+ * Determines whether a spherical cap is inside, outside or overlapping a
+ * bounding box.
+ *
+ * @param bbox The bounding box.
+ * @param center The center of the cap.
+ * @param radius The radius of the cap, in radians.
+ */
+fn _capInBBox(bbox: &BBox, center: &LatLng, radius: f64) -> CapInBBox {
+    if center.lat - radius > bbox.north || center.lat + radius < bbox.south {
+        return CapInBBox::Outside;
+    }
+    let inLats = center.lat - radius >= bbox.south && center.lat + radius <= bbox.north;
+    let width = bboxWidthRads(bbox);
+    if width >= M_2PI {
+        return if inLats {
+            CapInBBox::Inside
+        } else {
+            CapInBBox::Overlapping
+        };
+    }
+    if center.lat.abs() + radius >= FRAC_PI_2 {
+        // covers a pole, and so every longitude
+        return CapInBBox::Overlapping;
+    }
+
+    // the longitude of the center east of the west edge, in [0, 2pi), and
+    // the longitude half-width of the cap
+    let x = bboxLngOffset(bbox, center.lng);
+    let halfWidth = (radius.sin() / center.lat.cos()).min(1.0).asin();
+    if x - halfWidth > width && x + halfWidth < M_2PI {
+        return CapInBBox::Outside;
+    }
+    if inLats && x - halfWidth >= 0.0 && x + halfWidth <= width {
+        return CapInBBox::Inside;
+    }
+    CapInBBox::Overlapping
+}
+
+/**
+ * Validates the arguments of bboxToCells and returns the rectangle as a
+ * bounding box.
+ *
+ * @param min The south-west corner, in radians.
+ * @param max The north-east corner, in radians.
+ * @param res The resolution
+ */
+fn _bboxFromCorners(min: &LatLng, max: &LatLng, res: i32) -> Result<BBox, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    for g in [min, max] {
+        if !g.lat.is_finite() || !g.lng.is_finite() || g.lat.abs() > FRAC_PI_2 {
+            return Err(Error::LatLngDomain);
+        }
+    }
+    if min.lat > max.lat {
+        return Err(Error::Domain);
+    }
+    if max.lng - min.lng >= M_2PI {
+        Ok(BBox {
+            north: max.lat,
+            south: min.lat,
+            east: PI,
+            west: -PI,
+        })
+    } else {
+        Ok(BBox {
+            north: max.lat,
+            south: min.lat,
+            east: constrainLng(max.lng),
+            west: constrainLng(min.lng),
+        })
+    }
+}
+
+/**
+ * maxBboxToCellsSize returns an upper bound on the number of cells
+ * bboxToCells returns for a lat/lng rectangle.
+ *
+ * Every cell with its center in the rectangle lies within the rectangle grown
+ * by the largest cell radius, so the area of the grown rectangle divided by
+ * the smallest cell area bounds the count.
+ *
+ * @param min The south-west corner, in radians.
+ * @param max The north-east corner, in radians.
+ * @param res The Hexagon resolution (0-15)
+ * @return number of cells to allocate for
+ */
+pub fn maxBboxToCellsSize(min: &LatLng, max: &LatLng, res: i32) -> Result<i64, Error> {
+    let bbox = _bboxFromCorners(min, max, res)?;
+    let pentagonRadius = _pentagonRadiusRads(res)?;
+    let radius = MAX_CELL_RADIUS_FACTOR * pentagonRadius;
+
+    let north = (bbox.north + radius).min(FRAC_PI_2);
+    let south = (bbox.south - radius).max(-FRAC_PI_2);
+    let maxLat = north.abs().max(south.abs());
+    let mut width = bboxWidthRads(&bbox);
+    if maxLat < FRAC_PI_2 {
+        width += 2.0 * (radius.sin() / maxLat.cos()).min(1.0).asin();
+    }
+    if maxLat >= FRAC_PI_2 || width > M_2PI {
+        width = M_2PI;
+    }
+    let area = (north.sin() - south.sin()) * width;
+
+    let numCells = 2 + 120 * 7i64.pow(res as u32);
+    let estimate = (area / _minCellAreaRads2(pentagonRadius)).ceil();
+    Ok((estimate as i64).min(numCells))
+}
+
+/**
+ * maxPolygonToCellsSizeWithMode returns the number of cells to allocate
+ * space for when performing a polygonToCellsWithMode.
+ *
+ * Center containment uses the maxPolygonToCellsSize estimate. The cells of
+ * the other modes all have their bounding box overlap the bounding box of
+ * the outer loop, so their centers are in that box grown by the largest cell
+ * radius, and maxBboxToCellsSize of the box bounds their number.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @param mode Which cells are in the polygon
+ * @return number of cells to allocate for
+ */
+pub fn maxPolygonToCellsSizeWithMode(
+    polygon: &GeoPolygon,
+    res: i32,
+    mode: ContainmentMode,
+) -> Result<i64, Error> {
+    if mode == ContainmentMode::CenterContained {
+        return maxPolygonToCellsSize(polygon, res);
+    }
+    _validatePolygonArgs(polygon, res)?;
+    let bbox = bboxFromGeoLoop(&polygon.geoloop);
+    maxBboxToCellsSize(
+        &LatLng {
+            lat: bbox.south,
+            lng: bbox.west,
+        },
+        &LatLng {
+            lat: bbox.north,
+            lng: bbox.east,
+        },
+        res,
+    )
+}
+
+/**
+ * bboxToCells returns the cells whose centers are contained in a lat/lng
+ * rectangle, like polygonToCells for the rectangle but with edges along
+ * meridians and parallels rather than great circles.
+ *
+ * The rectangle is scanned directly, top down from the base cells: a cell
+ * whose descendants are all inside the rectangle contributes all of its
+ * children at the resolution without looking at them, a cell entirely
+ * outside is dropped, and only the cells straddling the edges are split
+ * further. Most of the work is therefore along the edges, and no loop
+ * tracing, flood fill or point in polygon tests are needed.
+ *
+ * @param min The south-west corner, in radians.
+ * @param max The north-east corner, in radians. A box with max.lng less than
+ *            min.lng crosses the antimeridian.
+ * @param res The Hexagon resolution (0-15)
+ * @return The cells, in ascending index order
+ */
+pub fn bboxToCells(min: &LatLng, max: &LatLng, res: i32) -> Result<Vec<H3Index>, Error> {
+    let bbox = _bboxFromCorners(min, max, res)?;
+
+    let mut out: Vec<H3Index> = Vec::new();
+    let mut search: Vec<H3Index> = getRes0Cells()?;
+    while let Some(h) = search.pop() {
+        let center = cellToLatLng(h)?;
+        let cellRes = H3_GET_RESOLUTION(h);
+        if cellRes == res {
+            if bboxContains(&bbox, &center) {
+                out.push(h);
+            }
+            continue;
+        }
+
+        match _capInBBox(&bbox, &center, _descendantRadiusRads(h, &center)?) {
+            CapInBBox::Inside => out.extend(IterCellsChildren::from_parent(h, res)),
+            CapInBBox::Outside => {}
+            CapInBBox::Overlapping => {
+                search.extend(IterCellsChildren::from_parent(h, cellRes + 1));
+            }
+        }
+    }
+    out.sort_unstable();
+    Ok(out)
+}
+
+/**
+ * Returns the lat/lng rectangle bounding a spherical cap, as a loop, or None
+ * if the cap covers a pole.
+ */
+fn _capBoundingLoop(center: &LatLng, radius: f64) -> Option<GeoLoop> {
+    if center.lat.abs() + radius >= FRAC_PI_2 {
+        return None;
+    }
+    let halfWidth = (radius.sin() / center.lat.cos()).min(1.0).asin();
+    let (south, north) = (center.lat - radius, center.lat + radius);
+    let (west, east) = (
+        constrainLng(center.lng - halfWidth),
+        constrainLng(center.lng + halfWidth),
+    );
+    Some(GeoLoop {
+        verts: vec![
+            LatLng {
+                lat: south,
+                lng: west,
+            },
+            LatLng {
+                lat: south,
+                lng: east,
+            },
+            LatLng {
+                lat: north,
+                lng: east,
+            },
+            LatLng {
+                lat: north,
+                lng: west,
+            },
+        ],
+    })
+}
+
+/**
+ * polygonToCompactCells returns the cells whose centers are contained in a
+ * GeoJSON-like polygon in compacted form, the same cells as
+ * compactCells(polygonToCells(polygon, res)), without producing the
+ * uncompacted cells.
+ *
+ * Cells are examined top down from the base cells. A cell is kept whole if
+ * the rectangle around the centers of all of its descendants is inside the
+ * polygon, crosses none of its edges and contains none of its vertices,
+ * including those of the holes, dropped if the rectangle is outside, and
+ * otherwise split. Only the cells along the edges of the
+ * polygon are split down to the resolution, so the work and the output grow
+ * with the perimeter of the polygon rather than its area.
+ *
+ * @param polygon The geoloop and holes defining the relevant area
+ * @param res The Hexagon resolution (0-15)
+ * @return The compacted cells, in ascending index order
+ */
+pub fn polygonToCompactCells(polygon: &GeoPolygon, res: i32) -> Result<Vec<H3Index>, Error> {
+    _validatePolygonArgs(polygon, res)?;
+    if polygon.geoloop.verts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let bboxes = bboxesFromGeoPolygon(polygon);
+    let loops: Vec<&GeoLoop> = core::iter::once(&polygon.geoloop)
+        .chain(polygon.holes.iter())
+        .collect();
+
+    let mut out: Vec<H3Index> = Vec::new();
+    let mut search: Vec<H3Index> = getRes0Cells()?;
+    while let Some(h) = search.pop() {
+        let center = cellToLatLng(h)?;
+        let centerInside = pointInsidePolygon(polygon, &bboxes, &center);
+        let cellRes = H3_GET_RESOLUTION(h);
+        if cellRes == res {
+            if centerInside {
+                out.push(h);
+            }
+            continue;
+        }
+
+        let mut split = true;
+        if let Some(rect) = _capBoundingLoop(&center, _descendantRadiusRads(h, &center)?) {
+            let rectBbox = bboxFromGeoLoop(&rect);
+            let crosses = loops
+                .iter()
+                .zip(bboxes.iter())
+                .any(|(geoloop, bbox)| _geoLoopCrossesGeoLoop(geoloop, bbox, &rect, &rectBbox));
+            // with no crossing, the rectangle is all inside or all outside
+            // unless it contains a whole loop, the outer one or a hole
+            split = crosses
+                || loops.iter().any(|geoloop| {
+                    geoloop
+                        .verts
+                        .iter()
+                        .any(|v| pointInsideGeoLoop(&rect, &rectBbox, v))
+                });
+            if !split && centerInside {
+                out.push(h);
+            }
+        }
+        if split {
+            search.extend(IterCellsChildren::from_parent(h, cellRes + 1));
+        }
+    }
+    // siblings that were examined separately may all be in
+    Ok(CompactCellSet::from_cells(&out)?.into_iter().collect())
+}
+
+/** Maximum halvings of a segment in polylineToCells, beyond any resolution's
+ * need; past it the gap is filled with gridPathCells. */
+const MAX_POLYLINE_SPLIT_DEPTH: i32 = 64;
+
+/**
+ * Whether the great circle arcs from a to b and from c to d cross, including
+ * at their endpoints. Both arcs must be much shorter than a hemisphere.
+ */
+fn _arcsCross(a: &LatLng, b: &LatLng, c: &LatLng, d: &LatLng) -> bool {
+    let mut v = [Vec3d {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    }; 4];
+    for (g, out) in [a, b, c, d].into_iter().zip(v.iter_mut()) {
+        _geoToVec3d(g, out);
+    }
+    // each arc's endpoints are on opposite sides of the other's great circle
+    let n1 = _vec3dCross(&v[0], &v[1]);
+    let n2 = _vec3dCross(&v[2], &v[3]);
+    _vec3dDot(&n1, &v[2]) * _vec3dDot(&n1, &v[3]) <= 0.0
+        && _vec3dDot(&n2, &v[0]) * _vec3dDot(&n2, &v[1]) <= 0.0
+        // and not the antipodal intersection
+        && _vec3dDot(&v[0], &v[2]) > 0.0
+}
+
+/**
+ * Adds the cells along the great circle arc from a to b to the path, given
+ * the cells containing a and b, by halving the arc until each piece goes
+ * straight from a cell to a neighbor across their shared edge.
+ *
+ * @param a The start of the arc, in cell ha.
+ * @param b The end of the arc, in cell hb.
+ * @param res The resolution
+ * @param depth How many times the segment has been halved
+ * @param path The path, ending with ha. Cells after ha up to hb are appended.
+ */
+fn _tracePolylineArc(
+    a: &LatLng,
+    b: &LatLng,
+    ha: H3Index,
+    hb: H3Index,
+    res: i32,
+    depth: i32,
+    path: &mut Vec<H3Index>,
+) -> Result<(), Error> {
+    if ha == hb {
+        return Ok(());
+    }
+    if let Ok(edge) = cellsToDirectedEdge(ha, hb) {
+        // the arc can pass through the corner of a third cell on its way to
+        // the neighbor, in which case it does not cross the shared edge
+        let edgeVerts = directedEdgeToBoundary(edge)?;
+        if edgeVerts.windows(2).any(|e| _arcsCross(a, b, &e[0], &e[1])) {
+            path.push(hb);
+            return Ok(());
+        }
+    }
+    if depth >= MAX_POLYLINE_SPLIT_DEPTH {
+        path.extend(&gridPathCells(ha, hb)?[1..]);
+        return Ok(());
+    }
+    let mid = greatCircleInterpolate(a, b, 0.5)?;
+    let hm = latLngToCell(&mid, res)?;
+    _tracePolylineArc(a, &mid, ha, hm, res, depth + 1, path)?;
+    _tracePolylineArc(&mid, b, hm, hb, res, depth + 1, path)?;
+    Ok(())
+}
+
+/**
+ * maxPolylineToCellsSize returns an upper bound on the number of cells
+ * polylineToCells returns for a polyline.
+ *
+ * The cells crossed by an arc have their centers within the largest cell
+ * radius of it, so their number is bounded by the area of that band divided
+ * by the smallest cell area.
+ *
+ * @param points The vertices of the line, in radians
+ * @param res The Hexagon resolution (0-15)
+ * @return number of cells to allocate for
+ */
+pub fn maxPolylineToCellsSize(points: &[LatLng], res: i32) -> Result<i64, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    if points.is_empty() {
+        return Ok(0);
+    }
+    let pentagonRadius = _pentagonRadiusRads(res)?;
+    let radius = MAX_CELL_RADIUS_FACTOR * pentagonRadius;
+    let cellArea = _minCellAreaRads2(pentagonRadius);
+
+    let mut total = 1.0;
+    for arc in points.windows(2) {
+        let length = greatCircleDistanceRads(&arc[0], &arc[1]);
+        if !length.is_finite() {
+            return Err(Error::LatLngDomain);
+        }
+        total += ((2.0 * radius * length + PI * radius * radius) / cellArea).ceil();
+    }
+    if total >= i64::MAX as f64 {
+        return Err(Error::MemoryBounds);
+    }
+    Ok(total as i64)
+}
+
+/**
+ * polylineToCells traces a polyline, such as a GPS trace, with cells: every
+ * cell crossed by the great circle arcs between consecutive points is
+ * included, in order along the line.
+ *
+ * @param points The vertices of the line, in radians
+ * @param res The Hexagon resolution (0-15)
+ * @return The cells in the order they are crossed, where each cell is a
+ *         neighbor of the one before. Cells are repeated if the line returns
+ *         to them.
+ */
+pub fn polylineToCells(points: &[LatLng], res: i32) -> Result<Vec<H3Index>, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let Some((first, rest)) = points.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut prev = first;
+    let mut path: Vec<H3Index> = vec![latLngToCell(prev, res)?];
+    for point in rest {
+        let h = latLngToCell(point, res)?;
+        let last = path[path.len() - 1];
+        _tracePolylineArc(prev, point, last, h, res, 0, &mut path)?;
+        prev = point;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base_cells::getPentagons;
+    use crate::bench_utils::randomCells;
+    use crate::h3_index::{cellToChildren, compactCells, uncompactCells};
+    use crate::iterators::IterCellsResolution;
+
+    use super::*;
+
+    fn degLoop(coords: &[(f64, f64)]) -> GeoLoop {
+        let mut verts = Vec::new();
+        for &(lat, lng) in coords {
+            let g = LatLng::from_degrees(lat, lng);
+            verts.push(g);
+        }
+        GeoLoop { verts }
+    }
+
+    fn sfPolygon() -> GeoPolygon {
+        GeoPolygon {
+            geoloop: GeoLoop {
+                verts: vec![
+                    LatLng {
+                        lat: 0.659966917655,
+                        lng: -2.1364398519396,
+                    },
+                    LatLng {
+                        lat: 0.6595011102219,
+                        lng: -2.1359434279405,
+                    },
+                    LatLng {
+                        lat: 0.6583348114025,
+                        lng: -2.1354884206045,
+                    },
+                    LatLng {
+                        lat: 0.6581220034068,
+                        lng: -2.1382437718946,
+                    },
+                    LatLng {
+                        lat: 0.6594479998527,
+                        lng: -2.1384597563896,
+                    },
+                    LatLng {
+                        lat: 0.6599990002976,
+                        lng: -2.1376771158464,
+                    },
+                ],
+            },
+            holes: vec![],
+        }
+    }
+
+    #[test]
+    fn polygonToCellsSf() {
+        let sf = sfPolygon();
+        let cells = polygonToCells(&sf, 9).unwrap();
+        assert_eq!(cells.len(), 1253, "got expected polygonToCells size");
+        assert!(
+            maxPolygonToCellsSize(&sf, 9).unwrap() >= cells.len() as i64,
+            "estimate covers the result"
+        );
+
+        let bboxes = bboxesFromGeoPolygon(&sf);
+        for h in &cells {
+            assert!(
+                pointInsidePolygon(&sf, &bboxes, &cellToLatLng(*h).unwrap()),
+                "center is inside"
+            );
+        }
+    }
+
+    #[test]
+    fn polygonToCellsIntoAccumulates() {
+        let a = GeoPolygon {
+            geoloop: degLoop(&[(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)]),
+            holes: vec![],
+        };
+        let b = GeoPolygon {
+            geoloop: degLoop(&[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)]),
+            holes: vec![],
+        };
+        let cellsA = polygonToCells(&a, 5).unwrap();
+        let cellsB = polygonToCells(&b, 5).unwrap();
+
+        let mut set = CellSet::new();
+        polygonToCellsInto(&a, 5, &mut set).unwrap();
+        polygonToCellsInto(&b, 5, &mut set).unwrap();
+
+        let union: CellSet = cellsA.iter().chain(cellsB.iter()).copied().collect();
+        assert_eq!(set, union, "set is the union of both coverages");
+        assert!(
+            set.len() < cellsA.len() + cellsB.len(),
+            "overlap is deduped"
+        );
+    }
+
+    #[test]
+    fn polygonToCellsEmpty() {
+        let empty = GeoPolygon::default();
+        assert_eq!(polygonToCells(&empty, 9), Ok(vec![]), "empty polygon");
+        assert_eq!(polygonToCells(&sfPolygon(), 16), Err(Error::ResDomain));
+
+        let mut invalid = sfPolygon();
+        invalid.geoloop.verts[0].lat = f64::NAN;
+        assert_eq!(polygonToCells(&invalid, 9), Err(Error::LatLngDomain));
+    }
+
+    fn bruteBboxToCells(min: &LatLng, max: &LatLng, candidates: &[H3Index]) -> Vec<H3Index> {
+        let bbox = BBox {
+            north: max.lat,
+            south: min.lat,
+            east: max.lng,
+            west: min.lng,
+        };
+        let mut out: Vec<H3Index> = candidates
+            .iter()
+            .copied()
+            .filter(|&h| bboxContains(&bbox, &cellToLatLng(h).unwrap()))
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn maxCellRadiusFactor() {
+        for res in 0..=3 {
+            let radius = MAX_CELL_RADIUS_FACTOR * _pentagonRadiusRads(res).unwrap();
+            for h in IterCellsResolution::from_res(res) {
+                let center = cellToLatLng(h).unwrap();
+                let boundary = cellToBoundary(h).unwrap();
+                for v in &boundary.verts[..boundary.numVerts] {
+                    assert!(
+                        greatCircleDistanceRads(&center, v) <= radius,
+                        "vertex within the bound at res {}",
+                        res
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn descendantRadiusFactor() {
+        // If every grandchild d of a cell c satisfies
+        // dist(c, d) + bound(d) <= bound(c), then by induction from res 15,
+        // which has no descendants, every descendant of c is within bound(c).
+        // Intermediate resolutions are covered because a center child has
+        // the same center as its parent.
+        let bound = |h: H3Index| {
+            let center = cellToLatLng(h).unwrap();
+            (center, _descendantRadiusRads(h, &center).unwrap())
+        };
+        for res in 0..MAX_H3_RES {
+            let mut cells = getPentagons(res).unwrap();
+            if res == 0 {
+                cells.extend(getRes0Cells().unwrap());
+            } else {
+                cells.extend(randomCells(res as u64, 200, res).unwrap());
+            }
+            let childRes = (res + 2).min(MAX_H3_RES);
+            for &h in &cells {
+                let (center, radius) = bound(h);
+                for child in cellToChildren(h, childRes).unwrap() {
+                    let (childCenter, childRadius) = bound(child);
+                    assert!(
+                        greatCircleDistanceRads(&center, &childCenter) + childRadius <= radius,
+                        "descendants of {:x} within the bound at res {}",
+                        h,
+                        res
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bboxToCells_matchesBruteForce() {
+        // south, west, north, east in degrees
+        let boxes = [
+            (30.0, -125.0, 50.0, -100.0),
+            // across the antimeridian
+            (-20.0, 170.0, 10.0, -160.0),
+            // around a pole
+            (70.0, -180.0, 90.0, 180.0),
+            // thinner than a cell
+            (10.0, 0.0, 10.1, 40.0),
+            // around the res 0 pentagon at 58.28N 10.54E
+            (50.0, 0.0, 65.0, 20.0),
+        ];
+        for res in 2..=3 {
+            let all: Vec<H3Index> = IterCellsResolution::from_res(res).collect();
+            for &(south, west, north, east) in &boxes {
+                let min = LatLng::from_degrees(south, west);
+                let max = LatLng::from_degrees(north, east);
+                let cells = bboxToCells(&min, &max, res).unwrap();
+                assert_eq!(
+                    cells,
+                    bruteBboxToCells(&min, &max, &all),
+                    "box {:?} at res {}",
+                    (south, west, north, east),
+                    res
+                );
+                assert!(
+                    cells.len() as i64 <= maxBboxToCellsSize(&min, &max, res).unwrap(),
+                    "size bound holds"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bboxToCells_fine() {
+        let min = LatLng::from_degrees(37.77, -122.42);
+        let max = LatLng::from_degrees(37.79, -122.40);
+        let center = LatLng {
+            lat: (min.lat + max.lat) / 2.0,
+            lng: (min.lng + max.lng) / 2.0,
+        };
+        let candidates: Vec<H3Index> = gridDiskDistances(latLngToCell(&center, 9).unwrap(), 12)
+            .unwrap()
+            .into_iter()
+            .map(|(h, _)| h)
+            .collect();
+        let cells = bboxToCells(&min, &max, 9).unwrap();
+        assert_eq!(
+            cells,
+            bruteBboxToCells(&min, &max, &candidates),
+            "res 9 box"
+        );
+        assert!(cells.len() > 20, "box spans many cells");
+        let bound = maxBboxToCellsSize(&min, &max, 9).unwrap();
+        assert!(
+            cells.len() as i64 <= bound && bound < 4 * cells.len() as i64,
+            "size bound holds and is not far off"
+        );
+    }
+
+    #[test]
+    fn bboxToCells_world() {
+        let min = LatLng {
+            lat: -FRAC_PI_2,
+            lng: -PI,
+        };
+        let max = LatLng {
+            lat: FRAC_PI_2,
+            lng: PI,
+        };
+        assert_eq!(
+            bboxToCells(&min, &max, 0).unwrap().len(),
+            122,
+            "all res 0 cells"
+        );
+        assert_eq!(
+            maxBboxToCellsSize(&min, &max, 0),
+            Ok(122),
+            "bound is capped at the number of cells"
+        );
+        assert_eq!(
+            bboxToCells(&min, &max, 16),
+            Err(Error::ResDomain),
+            "invalid res"
+        );
+        assert_eq!(
+            maxBboxToCellsSize(&max, &min, 0),
+            Err(Error::Domain),
+            "size checks arguments"
+        );
+        assert_eq!(
+            bboxToCells(&max, &min, 0),
+            Err(Error::Domain),
+            "south of north"
+        );
+        let nan = LatLng {
+            lat: f64::NAN,
+            lng: 0.0,
+        };
+        assert_eq!(
+            bboxToCells(&nan, &max, 0),
+            Err(Error::LatLngDomain),
+            "invalid corner"
+        );
+    }
+
+    #[test]
+    fn polylineToCells_contiguous() {
+        // a trace crossing the antimeridian, passing near a pentagon at
+        // 58.28N 10.54E and doubling back
+        let line = degLoop(&[
+            (37.77, -122.42),
+            (37.80, -122.27),
+            (51.5, -179.9),
+            (50.0, 175.0),
+            (58.0, 10.0),
+            (58.6, 11.0),
+            (58.0, 10.0),
+        ])
+        .verts;
+        for res in [2, 5, 7] {
+            let path = polylineToCells(&line, res).unwrap();
+            assert!(
+                path.len() as i64 <= maxPolylineToCellsSize(&line, res).unwrap(),
+                "size bound holds at res {}",
+                res
+            );
+            assert_eq!(
+                path[0],
+                latLngToCell(&line[0], res).unwrap(),
+                "starts at the start"
+            );
+            assert_eq!(
+                path[path.len() - 1],
+                latLngToCell(&line[line.len() - 1], res).unwrap(),
+                "ends at the end"
+            );
+            for pair in path.windows(2) {
+                assert!(
+                    cellsToDirectedEdge(pair[0], pair[1]).is_ok(),
+                    "consecutive cells are neighbors at res {}",
+                    res
+                );
+            }
+            // every point along the line is in a traced cell
+            let traced: CellSet = path.iter().copied().collect();
+            for segment in line.windows(2) {
+                for i in 0..=200 {
+                    let g =
+                        greatCircleInterpolate(&segment[0], &segment[1], i as f64 / 200.0).unwrap();
+                    assert!(traced.contains(latLngToCell(&g, res).unwrap()), "no gaps");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn polylineToCells_edgeCases() {
+        assert_eq!(polylineToCells(&[], 5), Ok(vec![]), "empty line");
+        assert_eq!(maxPolylineToCellsSize(&[], 5), Ok(0), "empty line size");
+        let point = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        assert_eq!(
+            polylineToCells(&[point, point], 9),
+            Ok(vec![latLngToCell(&point, 9).unwrap()]),
+            "repeated point is one cell"
+        );
+        assert_eq!(
+            polylineToCells(&[point], 16),
+            Err(Error::ResDomain),
+            "invalid res"
+        );
+        let nan = LatLng {
+            lat: f64::NAN,
+            lng: 0.0,
+        };
+        assert_eq!(
+            polylineToCells(&[point, nan], 9),
+            Err(Error::LatLngDomain),
+            "invalid point"
+        );
+        assert_eq!(
+            maxPolylineToCellsSize(&[point, nan], 9),
+            Err(Error::LatLngDomain),
+            "invalid point size"
+        );
+    }
+
+    #[test]
+    fn polygonToCellsWithMode_nested() {
+        let sf = GeoPolygon {
+            geoloop: degLoop(&[
+                (37.813_318_999_983_24, -122.408_986_699_997_21),
+                (37.719_806_199_997_85, -122.354_473_699_999_36),
+                (37.815_157_199_999_845, -122.479_876_700_000_9),
+            ]),
+            holes: vec![degLoop(&[
+                (37.7869802, -122.4471197),
+                (37.7664102, -122.4590777),
+                (37.7710682, -122.4137097),
+            ])],
+        };
+        let bboxes = bboxesFromGeoPolygon(&sf);
+        for res in [6, 7, 8] {
+            let modes = [
+                ContainmentMode::FullyContained,
+                ContainmentMode::CenterContained,
+                ContainmentMode::Overlapping,
+                ContainmentMode::OverlappingBbox,
+            ];
+            let sets: Vec<CellSet> = modes
+                .iter()
+                .map(|&mode| {
+                    polygonToCellsWithMode(&sf, res, mode)
+                        .unwrap()
+                        .into_iter()
+                        .collect()
+                })
+                .collect();
+            assert_eq!(
+                sets[1],
+                polygonToCells(&sf, res).unwrap().into_iter().collect(),
+                "center mode is polygonToCells"
+            );
+            for (&mode, set) in modes.iter().zip(&sets) {
+                assert!(
+                    maxPolygonToCellsSizeWithMode(&sf, res, mode).unwrap() >= set.len() as i64,
+                    "{:?} size bound at res {}",
+                    mode,
+                    res
+                );
+            }
+            for i in 1..sets.len() {
+                assert!(
+                    sets[i - 1].iter().all(|h| sets[i].contains(h)),
+                    "{:?} within {:?} at res {}",
+                    modes[i - 1],
+                    modes[i],
+                    res
+                );
+            }
+
+            for h in sets[0].iter() {
+                let boundary = cellToBoundary(h).unwrap();
+                assert!(
+                    boundary
+                        .to_vec()
+                        .iter()
+                        .all(|v| pointInsidePolygon(&sf, &bboxes, v)),
+                    "fully contained cells have their vertices inside"
+                );
+            }
+            // every point of the polygon, including its edges, is covered
+            for geoloop in core::iter::once(&sf.geoloop).chain(sf.holes.iter()) {
+                for i in 0..geoloop.verts.len() {
+                    let a = geoloop.verts[i];
+                    let b = geoloop.verts[(i + 1) % geoloop.verts.len()];
+                    for j in 0..=100 {
+                        let f = j as f64 / 100.0;
+                        let g = LatLng {
+                            lat: a.lat + (b.lat - a.lat) * f,
+                            lng: a.lng + (b.lng - a.lng) * f,
+                        };
+                        assert!(
+                            sets[2].contains(latLngToCell(&g, res).unwrap()),
+                            "overlapping covers the edges"
+                        );
+                    }
+                }
+            }
+        }
+        assert!(
+            polygonToCellsWithMode(&sf, 6, ContainmentMode::FullyContained)
+                .unwrap()
+                .is_empty(),
+            "no res 6 cell fits between the hole and the edge"
+        );
+    }
+
+    #[test]
+    fn polygonToCellsWithMode_smallPolygon() {
+        let center = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let cell = latLngToCell(&center, 4).unwrap();
+        let cellCenter = cellToLatLng(cell).unwrap();
+        let d = 0.0001;
+        let tiny = GeoPolygon {
+            geoloop: GeoLoop {
+                verts: vec![
+                    LatLng {
+                        lat: cellCenter.lat + 2.0 * d,
+                        lng: cellCenter.lng + 2.0 * d,
+                    },
+                    LatLng {
+                        lat: cellCenter.lat + 2.0 * d,
+                        lng: cellCenter.lng + 3.0 * d,
+                    },
+                    LatLng {
+                        lat: cellCenter.lat + 3.0 * d,
+                        lng: cellCenter.lng + 3.0 * d,
+                    },
+                ],
+            },
+            holes: vec![],
+        };
+        assert!(
+            polygonToCells(&tiny, 4).unwrap().is_empty(),
+            "center of the cell is not inside"
+        );
+        assert_eq!(
+            polygonToCellsWithMode(&tiny, 4, ContainmentMode::Overlapping),
+            Ok(vec![cell]),
+            "overlaps the containing cell"
+        );
+        assert!(
+            polygonToCellsWithMode(&tiny, 4, ContainmentMode::FullyContained)
+                .unwrap()
+                .is_empty(),
+            "no cell is fully contained"
+        );
+        assert_eq!(
+            ContainmentMode::from_flags(2),
+            Ok(ContainmentMode::Overlapping)
+        );
+        assert_eq!(ContainmentMode::from_flags(4), Err(Error::OptionInvalid));
+    }
+
+    #[test]
+    fn polygonToCellsWithMode_polygonInsideCell() {
+        // a square about 20 m across around the center of a res 5 cell
+        let cell: H3Index = 0x85283083fffffff;
+        let center = cellToLatLng(cell).unwrap();
+        let d = 1.5e-6;
+        let square = GeoPolygon {
+            geoloop: GeoLoop {
+                verts: vec![
+                    LatLng {
+                        lat: center.lat - d,
+                        lng: center.lng - d,
+                    },
+                    LatLng {
+                        lat: center.lat - d,
+                        lng: center.lng + d,
+                    },
+                    LatLng {
+                        lat: center.lat + d,
+                        lng: center.lng + d,
+                    },
+                    LatLng {
+                        lat: center.lat + d,
+                        lng: center.lng - d,
+                    },
+                ],
+            },
+            holes: vec![],
+        };
+        assert_eq!(
+            polygonToCells(&square, 5),
+            Ok(vec![cell]),
+            "center of the cell is inside"
+        );
+        assert_eq!(
+            polygonToCellsWithMode(&square, 5, ContainmentMode::Overlapping),
+            Ok(vec![cell]),
+            "overlaps the containing cell"
+        );
+        assert!(
+            polygonToCellsWithMode(&square, 5, ContainmentMode::FullyContained)
+                .unwrap()
+                .is_empty(),
+            "the cell is larger than the polygon"
+        );
+    }
+
+    #[test]
+    fn polygonToCells_holeBoundaries() {
+        // a park with a lake in it
+        let park = degLoop(&[
+            (37.76, -122.51),
+            (37.76, -122.45),
+            (37.775, -122.45),
+            (37.775, -122.51),
+        ]);
+        let lake = degLoop(&[
+            (37.765, -122.49),
+            (37.77, -122.49),
+            (37.77, -122.47),
+            (37.765, -122.47),
+        ]);
+        let donut = GeoPolygon {
+            geoloop: park.clone(),
+            holes: vec![lake.clone()],
+        };
+        let solid = GeoPolygon {
+            geoloop: park,
+            holes: vec![],
+        };
+        let lakeBbox = bboxFromGeoLoop(&lake);
+        let inLake = |g: &LatLng| pointInsideGeoLoop(&lake, &lakeBbox, g);
+
+        for res in 7..=10 {
+            let cells = polygonToCells(&donut, res).unwrap();
+            let expected: Vec<H3Index> = polygonToCells(&solid, res)
+                .unwrap()
+                .into_iter()
+                .filter(|&h| !inLake(&cellToLatLng(h).unwrap()))
+                .collect();
+            assert_eq!(
+                cells, expected,
+                "centers in the lake are removed at {}",
+                res
+            );
+
+            // cells straddling the shore overlap the park, cells out in the
+            // lake do not, and fully contained cells stay on dry land
+            let overlapping =
+                polygonToCellsWithMode(&donut, res, ContainmentMode::Overlapping).unwrap();
+            let full =
+                polygonToCellsWithMode(&donut, res, ContainmentMode::FullyContained).unwrap();
+            let mut straddling = 0;
+            for h in polygonToCells(&solid, res).unwrap() {
+                let verts = cellToBoundary(h).unwrap().to_vec();
+                let wet = verts.iter().filter(|g| inLake(g)).count();
+                if wet == verts.len() {
+                    assert!(
+                        overlapping.binary_search(&h).is_err(),
+                        "cells in the lake don't overlap at {}",
+                        res
+                    );
+                } else if wet > 0 {
+                    straddling += 1;
+                    assert!(
+                        overlapping.binary_search(&h).is_ok(),
+                        "shore cells overlap at {}",
+                        res
+                    );
+                    assert!(
+                        full.binary_search(&h).is_err(),
+                        "shore cells are not fully contained at {}",
+                        res
+                    );
+                }
+            }
+            assert!(straddling > 0, "some cells straddle the shore");
+            assert!(full.iter().all(|h| cells.binary_search(h).is_ok()));
+        }
+
+        // a pond smaller than a cell, off its center
+        let cell = polygonToCellsWithMode(&solid, 8, ContainmentMode::FullyContained).unwrap()[0];
+        let center = cellToLatLng(cell).unwrap();
+        let d = 0.00002;
+        let pond = GeoLoop {
+            verts: vec![
+                LatLng {
+                    lat: center.lat + d,
+                    lng: center.lng + d,
+                },
+                LatLng {
+                    lat: center.lat + d,
+                    lng: center.lng + 2.0 * d,
+                },
+                LatLng {
+                    lat: center.lat + 2.0 * d,
+                    lng: center.lng + 2.0 * d,
+                },
+            ],
+        };
+        let withPond = GeoPolygon {
+            geoloop: solid.geoloop.clone(),
+            holes: vec![pond],
+        };
+        assert!(
+            polygonToCells(&withPond, 8).unwrap().contains(&cell),
+            "the center is still on land"
+        );
+        assert!(
+            !polygonToCellsWithMode(&withPond, 8, ContainmentMode::FullyContained)
+                .unwrap()
+                .contains(&cell),
+            "but the cell is not fully contained"
+        );
+    }
+
+    #[test]
+    fn multiPolygonToCells_union() {
+        // a quadrilateral split into two along a diagonal
+        let corners = [
+            (37.813_318_999_983_24, -122.408_986_699_997_21),
+            (37.719_806_199_997_85, -122.354_473_699_999_36),
+            (37.707_613_199_997_57, -122.512_343_699_998_4),
+            (37.815_157_199_999_845, -122.479_876_700_000_9),
+        ];
+        let whole = GeoPolygon {
+            geoloop: degLoop(&corners),
+            holes: vec![],
+        };
+        let halves = GeoMultiPolygon {
+            polygons: vec![
+                GeoPolygon {
+                    geoloop: degLoop(&[corners[0], corners[1], corners[2]]),
+                    holes: vec![],
+                },
+                GeoPolygon {
+                    geoloop: degLoop(&[corners[2], corners[3], corners[0]]),
+                    holes: vec![],
+                },
+            ],
+        };
+        for res in [7, 9] {
+            let expected = polygonToCells(&whole, res).unwrap();
+            assert_eq!(
+                multiPolygonToCells(&halves, res),
+                Ok(expected.clone()),
+                "halves cover the whole"
+            );
+            assert_eq!(
+                maxMultiPolygonToCellsSize(&halves, res),
+                Ok(maxPolygonToCellsSize(&halves.polygons[0], res).unwrap()
+                    + maxPolygonToCellsSize(&halves.polygons[1], res).unwrap()),
+                "size is the sum over the polygons"
+            );
+            let overlapping = GeoMultiPolygon {
+                polygons: vec![whole.clone(), halves.polygons[0].clone()],
+            };
+            assert_eq!(
+                multiPolygonToCells(&overlapping, res),
+                Ok(expected),
+                "overlap is counted once"
+            );
+
+            let mut overlappingCells = CellSet::new();
+            for polygon in &halves.polygons {
+                overlappingCells.extend(
+                    polygonToCellsWithMode(polygon, res, ContainmentMode::Overlapping).unwrap(),
+                );
+            }
+            assert_eq!(
+                multiPolygonToCellsWithMode(&halves, res, ContainmentMode::Overlapping),
+                Ok(overlappingCells.iter().collect()),
+                "cells on the shared edge are returned once"
+            );
+        }
+        assert_eq!(
+            multiPolygonToCells(&GeoMultiPolygon::default(), 9),
+            Ok(vec![]),
+            "no polygons"
+        );
+        assert_eq!(
+            multiPolygonToCells(&halves, 16),
+            Err(Error::ResDomain),
+            "invalid resolution"
+        );
+        assert_eq!(
+            maxMultiPolygonToCellsSize(&GeoMultiPolygon::default(), 16),
+            Err(Error::ResDomain),
+            "invalid resolution size"
+        );
+    }
+
+    #[test]
+    fn polygonToCompactCells_matchesCompacted() {
+        let sf = GeoPolygon {
+            geoloop: degLoop(&[
+                (37.813_318_999_983_24, -122.408_986_699_997_21),
+                (37.719_806_199_997_85, -122.354_473_699_999_36),
+                (37.815_157_199_999_845, -122.479_876_700_000_9),
+            ]),
+            holes: vec![degLoop(&[
+                (37.7869802, -122.4471197),
+                (37.7664102, -122.4590777),
+                (37.7710682, -122.4137097),
+            ])],
+        };
+        // large, across the antimeridian and around a pentagon
+        let pacific = GeoPolygon {
+            geoloop: degLoop(&[
+                (-10.0, 170.0),
+                (-10.0, -170.0),
+                (15.0, -165.0),
+                (10.0, 175.0),
+            ]),
+            holes: vec![],
+        };
+        let pentagon = GeoPolygon {
+            geoloop: degLoop(&[(50.0, 0.0), (50.0, 20.0), (65.0, 20.0), (65.0, 0.0)]),
+            holes: vec![],
+        };
+        // a hole small enough to fit inside cells that are otherwise whole
+        let donut = GeoPolygon {
+            geoloop: degLoop(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)]),
+            holes: vec![degLoop(&[(4.5, 4.5), (4.5, 5.5), (5.5, 5.5), (5.5, 4.5)])],
+        };
+        for (polygon, res) in [
+            (&sf, 9),
+            (&pacific, 4),
+            (&pentagon, 4),
+            (&donut, 4),
+            (&donut, 5),
+        ] {
+            let mut expected = compactCells(&polygonToCells(polygon, res).unwrap()).unwrap();
+            expected.sort();
+            let compacted = polygonToCompactCells(polygon, res).unwrap();
+            assert_eq!(
+                compacted, expected,
+                "same cells as compacting polygonToCells"
+            );
+            assert!(
+                compacted.iter().any(|&h| H3_GET_RESOLUTION(h) < res),
+                "interior is coarser"
+            );
+        }
+        assert!(
+            polygonToCompactCells(&GeoPolygon::default(), 5)
+                .unwrap()
+                .is_empty(),
+            "empty polygon"
+        );
+        assert_eq!(
+            polygonToCompactCells(&sf, 16),
+            Err(Error::ResDomain),
+            "invalid res"
+        );
+    }
+
+    #[test]
+    fn transmeridianLoops() {
+        let min = LatLng::from_degrees(-10.0, 170.0);
+        let max = LatLng::from_degrees(10.0, -170.0);
+        let expected = bboxToCells(&min, &max, 3).unwrap();
+
+        // the same box with wrapped longitudes, with longitudes past 180
+        // degrees as in some GeoJSON, and with vertices on the antimeridian
+        let loops = [
+            vec![
+                (-10.0, 170.0),
+                (-10.0, -170.0),
+                (10.0, -170.0),
+                (10.0, 170.0),
+            ],
+            vec![(-10.0, 170.0), (-10.0, 190.0), (10.0, 190.0), (10.0, 170.0)],
+            vec![
+                (-10.0, -190.0),
+                (-10.0, -170.0),
+                (10.0, -170.0),
+                (10.0, -190.0),
+            ],
+            vec![
+                (-10.0, 170.0),
+                (-10.0, 180.0),
+                (-10.0, -170.0),
+                (10.0, -170.0),
+                (10.0, -180.0),
+                (10.0, 170.0),
+            ],
+        ];
+        for verts in &loops {
+            let polygon = GeoPolygon {
+                geoloop: degLoop(verts),
+                holes: vec![],
+            };
+            let bbox = bboxFromGeoLoop(&polygon.geoloop);
+            assert!(bboxIsTransmeridian(&bbox), "bbox is transmeridian");
+            assert!(
+                (bboxWidthRads(&bbox) - 20.0 * M_PI_180).abs() < 1e-12,
+                "bbox is 20 degrees wide"
+            );
+            assert!(
+                geoLoopIsTransmeridian(&polygon.geoloop),
+                "loop is transmeridian"
+            );
+            let normalized = normalizeGeoLoop(&polygon.geoloop);
+            assert!(
+                normalized
+                    .verts
+                    .iter()
+                    .all(|g| (170.0 * M_PI_180 - 1e-12..=190.0 * M_PI_180 + 1e-12).contains(&g.lng)),
+                "normalized longitudes are in one frame"
+            );
+            assert!(!isClockwiseGeoLoop(&polygon.geoloop), "counterclockwise");
+            assert_eq!(
+                polygonToCells(&polygon, 3).unwrap(),
+                expected,
+                "polygonToCells of {:?}",
+                verts
+            );
+            assert_eq!(
+                uncompactCells(&polygonToCompactCells(&polygon, 3).unwrap(), 3)
+                    .unwrap()
+                    .into_iter()
+                    .collect::<CellSet>(),
+                expected.iter().copied().collect(),
+                "polygonToCompactCells of {:?}",
+                verts
+            );
+            assert!(
+                maxPolygonToCellsSize(&polygon, 3).unwrap() >= expected.len() as i64,
+                "size estimate is enough"
+            );
+        }
+    }
+
+    #[test]
+    fn containsLatLng() {
+        let deg = |lat: f64, lng: f64| LatLng::from_degrees(lat, lng);
+        let polygon = GeoPolygon {
+            geoloop: degLoop(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)]),
+            holes: vec![degLoop(&[(4.0, 4.0), (4.0, 6.0), (6.0, 6.0), (6.0, 4.0)])],
+        };
+        assert!(polygonContainsLatLng(&polygon, &deg(2.0, 2.0)), "inside");
+        assert!(
+            !polygonContainsLatLng(&polygon, &deg(5.0, 5.0)),
+            "in the hole"
+        );
+        assert!(
+            geoLoopContainsLatLng(&polygon.holes[0], &deg(5.0, 5.0)),
+            "in the hole loop"
+        );
+        assert!(!polygonContainsLatLng(&polygon, &deg(2.0, 12.0)), "outside");
+        assert!(!polygonContainsLatLng(&polygon, &deg(-2.0, 2.0)), "below");
+
+        // across the antimeridian, including when the point is given with a
+        // longitude on the other side
+        let loop_ = degLoop(&[
+            (-10.0, 170.0),
+            (-10.0, -170.0),
+            (10.0, -170.0),
+            (10.0, 170.0),
+        ]);
+        assert!(
+            geoLoopContainsLatLng(&loop_, &deg(0.0, 175.0)),
+            "east of the antimeridian"
+        );
+        assert!(
+            geoLoopContainsLatLng(&loop_, &deg(0.0, -175.0)),
+            "west of the antimeridian"
+        );
+        assert!(
+            geoLoopContainsLatLng(&loop_, &deg(0.0, 185.0)),
+            "past 180 degrees"
+        );
+        assert!(
+            !geoLoopContainsLatLng(&loop_, &deg(0.0, 0.0)),
+            "other side of the globe"
+        );
+
+        let empty = GeoLoop { verts: vec![] };
+        assert!(!geoLoopContainsLatLng(&empty, &deg(0.0, 0.0)), "empty loop");
+    }
+}
@@ -0,0 +1,7 @@
+// Allocating types used throughout the crate. These are in the standard
+// prelude, but have to be imported from alloc when building without std.
+
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
@@ -0,0 +1,213 @@
+use ::proj::Proj;
+
+use crate::constants::*;
+use crate::error::Error;
+use crate::h3_index::latLngToCell;
+use crate::lat_lng::LatLng;
+use crate::H3Index;
+
+/** The CRS H3 coordinates are defined in. */
+const WGS84: &str = "EPSG:4326";
+
+/**
+ * A pair of transformations between a coordinate reference system and WGS84,
+ * for indexing data that is not in geographic coordinates.
+ *
+ * Coordinates in the other CRS are in its own axis order as (x, y), e.g.
+ * (easting, northing) for a projected CRS or (longitude, latitude) in degrees
+ * for a geographic one.
+ */
+pub struct CrsTransform {
+    toWgs84: Proj,
+    fromWgs84: Proj,
+}
+
+impl CrsTransform {
+    /**
+     * Creates the transformations for a CRS.
+     *
+     * @param crs Any CRS definition accepted by PROJ, e.g. "EPSG:27700" or a
+     *            PROJ string.
+     * @return The transformations, or OptionInvalid if PROJ cannot create
+     *         them.
+     */
+    pub fn new(crs: &str) -> Result<CrsTransform, Error> {
+        let toWgs84 = Proj::new_known_crs(crs, WGS84, None).map_err(|_| Error::OptionInvalid)?;
+        let fromWgs84 = Proj::new_known_crs(WGS84, crs, None).map_err(|_| Error::OptionInvalid)?;
+        Ok(CrsTransform { toWgs84, fromWgs84 })
+    }
+
+    /**
+     * Reprojects a coordinate to spherical coordinates.
+     *
+     * @param x The first coordinate in the CRS.
+     * @param y The second coordinate in the CRS.
+     * @return The spherical coordinates in radians, or LatLngDomain if the
+     *         coordinate cannot be reprojected.
+     */
+    pub fn to_lat_lng(&self, x: f64, y: f64) -> Result<LatLng, Error> {
+        let (lngDeg, latDeg) = self
+            .toWgs84
+            .convert((x, y))
+            .map_err(|_| Error::LatLngDomain)?;
+        Ok(LatLng {
+            lat: latDeg * M_PI_180,
+            lng: lngDeg * M_PI_180,
+        })
+    }
+
+    /**
+     * Reprojects spherical coordinates to the CRS.
+     *
+     * @param g The spherical coordinates in radians.
+     * @return The coordinate in the CRS, or LatLngDomain if it cannot be
+     *         reprojected.
+     */
+    pub fn from_lat_lng(&self, g: &LatLng) -> Result<(f64, f64), Error> {
+        self.fromWgs84
+            .convert((g.lng * M_180_PI, g.lat * M_180_PI))
+            .map_err(|_| Error::LatLngDomain)
+    }
+
+    /**
+     * Reprojects a sequence of spherical coordinates, such as a cell
+     * boundary, to the CRS.
+     *
+     * @param verts The spherical coordinates in radians.
+     * @return The coordinates in the CRS.
+     */
+    pub fn from_lat_lngs(&self, verts: &[LatLng]) -> Result<Vec<(f64, f64)>, Error> {
+        verts.iter().map(|g| self.from_lat_lng(g)).collect()
+    }
+
+    /**
+     * Reprojects a sequence of coordinates in the CRS, such as a polygon
+     * ring, to spherical coordinates.
+     *
+     * @param coords The coordinates in the CRS.
+     * @return The spherical coordinates in radians.
+     */
+    pub fn to_lat_lngs(&self, coords: &[(f64, f64)]) -> Result<Vec<LatLng>, Error> {
+        coords.iter().map(|&(x, y)| self.to_lat_lng(x, y)).collect()
+    }
+}
+
+/**
+ * Encodes a coordinate in another CRS to the H3 index of the containing cell
+ * at the specified resolution.
+ *
+ * @param transform The transformations for the CRS of the coordinate.
+ * @param x The first coordinate in the CRS.
+ * @param y The second coordinate in the CRS.
+ * @param res The desired H3 resolution for the encoding.
+ * @return The encoded H3Index.
+ */
+pub fn latLngToCellCrs(
+    transform: &CrsTransform,
+    x: f64,
+    y: f64,
+    res: i32,
+) -> Result<H3Index, Error> {
+    let g = transform.to_lat_lng(x, y)?;
+    latLngToCell(&g, res)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::h3_index::{cellToBoundary, cellToLatLng};
+
+    use super::*;
+
+    /** Web Mercator, whose coordinates have a closed form to check against. */
+    const WEB_MERCATOR: &str = "EPSG:3857";
+
+    fn assertClose(a: (f64, f64), b: (f64, f64), tol: f64, msg: &str) {
+        assert!(
+            (a.0 - b.0).abs() < tol && (a.1 - b.1).abs() < tol,
+            "{}: {:?} vs {:?}",
+            msg,
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn webMercatorKnownPoints() {
+        let transform = CrsTransform::new(WEB_MERCATOR).unwrap();
+        let cases = [
+            (0.0, 0.0, (0.0, 0.0)),
+            (0.0, 180.0, (20037508.342789244, 0.0)),
+            (45.0, 0.0, (0.0, 5621521.486192066)),
+            (37.7749, -122.4194, (-13627665.271218073, 4547675.354340557)),
+        ];
+        for (lat, lng, xy) in cases {
+            let g = LatLng::from_degrees(lat, lng);
+            let projected = transform.from_lat_lng(&g).unwrap();
+            assertClose(projected, xy, 1e-3, "projects to the known coordinate");
+
+            let back = transform.to_lat_lng(xy.0, xy.1).unwrap();
+            assertClose(
+                (back.lat, back.lng),
+                (g.lat, g.lng),
+                1e-9,
+                "reprojects to the original coordinate",
+            );
+        }
+    }
+
+    #[test]
+    fn geographicIsIdentity() {
+        let transform = CrsTransform::new(WGS84).unwrap();
+        let g = transform.to_lat_lng(-122.4194, 37.7749).unwrap();
+        assertClose(
+            (g.lat, g.lng),
+            (37.7749 * M_PI_180, -122.4194 * M_PI_180),
+            1e-9,
+            "x is longitude and y is latitude",
+        );
+    }
+
+    #[test]
+    fn boundaryRoundTrip() {
+        let transform = CrsTransform::new(WEB_MERCATOR).unwrap();
+        let boundary = cellToBoundary(0x85283473fffffff).unwrap().to_vec();
+        let coords = transform.from_lat_lngs(&boundary).unwrap();
+        assert_eq!(coords.len(), boundary.len(), "one coordinate per vertex");
+        let back = transform.to_lat_lngs(&coords).unwrap();
+        for (a, b) in back.iter().zip(&boundary) {
+            assertClose((a.lat, a.lng), (b.lat, b.lng), 1e-9, "round trip");
+        }
+    }
+
+    #[test]
+    fn latLngToCellCrsMatchesLatLngToCell() {
+        let transform = CrsTransform::new(WEB_MERCATOR).unwrap();
+        let center = cellToLatLng(0x85283473fffffff).unwrap();
+        let (x, y) = transform.from_lat_lng(&center).unwrap();
+        assert_eq!(
+            latLngToCellCrs(&transform, x, y, 5),
+            Ok(0x85283473fffffff),
+            "projected cell center indexes to the cell"
+        );
+        for res in [0, 9, 15] {
+            assert_eq!(
+                latLngToCellCrs(&transform, x, y, res),
+                latLngToCell(&center, res),
+                "same cell as the geographic coordinate"
+            );
+        }
+        assert_eq!(
+            latLngToCellCrs(&transform, x, y, 16),
+            Err(Error::ResDomain),
+            "resolution is checked"
+        );
+    }
+
+    #[test]
+    fn invalidCrs() {
+        assert!(
+            CrsTransform::new("EPSG:not-a-code").is_err(),
+            "unknown CRS is rejected"
+        );
+    }
+}
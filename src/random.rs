@@ -0,0 +1,178 @@
+// Uniformly random valid cells, for load testing and randomized benchmarks.
+// Every cell at the resolution is equally likely, so unlike sampling points
+// on the sphere, small cells near the pentagons are as common as large ones.
+
+use rand::{Rng, RngExt};
+
+use crate::base_cells::_isBaseCellPentagon;
+use crate::constants::{MAX_H3_RES, NUM_BASE_CELLS};
+use crate::coord_ijk::Direction;
+use crate::error::Error;
+use crate::h3_index::{H3Index, H3_SET_INDEX_DIGIT, setH3Index};
+use crate::H3_NULL;
+
+/** The number of descendants of a hexagon res levels down. */
+fn _hexagonDescendants(res: i32) -> u64 {
+    7u64.pow(res as u32)
+}
+
+/**
+ * The number of descendants of a pentagon res levels down: the center child
+ * is a pentagon, and it has five hexagon children.
+ */
+fn _pentagonDescendants(res: i32) -> u64 {
+    1 + 5 * (_hexagonDescendants(res) - 1) / 6
+}
+
+/** The number of descendants of a base cell at the resolution. */
+fn _baseCellDescendants(baseCell: i32, res: i32) -> u64 {
+    if _isBaseCellPentagon(baseCell) {
+        return _pentagonDescendants(res);
+    }
+    _hexagonDescendants(res)
+}
+
+/**
+ * Finds a descendant of a base cell by its position among all of the base
+ * cell's descendants at the resolution, in index order.
+ */
+fn _baseCellDescendant(baseCell: i32, res: i32, mut pos: u64) -> H3Index {
+    let mut h: H3Index = H3_NULL;
+    setH3Index(&mut h, res, baseCell, 0);
+    let mut pentagon = _isBaseCellPentagon(baseCell);
+    for r in 1..=res {
+        let below = _hexagonDescendants(res - r);
+        let digit = if pentagon {
+            // the center child is a pentagon, and the K axis child is deleted
+            let center = _pentagonDescendants(res - r);
+            if pos < center {
+                0
+            } else {
+                pos -= center;
+                pentagon = false;
+                let digit = Direction::JAxesDigit as u64 + pos / below;
+                pos %= below;
+                digit
+            }
+        } else {
+            let digit = pos / below;
+            pos %= below;
+            digit
+        };
+        H3_SET_INDEX_DIGIT(&mut h, r, digit as i32);
+    }
+    h
+}
+
+/**
+ * Returns a uniformly random valid cell at the resolution.
+ *
+ * @param res The resolution.
+ * @param rng The random number generator.
+ * @return The cell, or ResDomain if the resolution is invalid.
+ */
+pub fn random_cell<R: Rng + ?Sized>(res: i32, rng: &mut R) -> Result<H3Index, Error> {
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let mut pos = rng.random_range(0..2 + 120 * _hexagonDescendants(res));
+    for baseCell in 0..NUM_BASE_CELLS {
+        let count = _baseCellDescendants(baseCell, res);
+        if pos < count {
+            return Ok(_baseCellDescendant(baseCell, res, pos));
+        }
+        pos -= count;
+    }
+    unreachable!("position is less than the number of cells");
+}
+
+/**
+ * Returns a uniformly random valid cell at the resolution within a base
+ * cell.
+ *
+ * @param baseCell The base cell number, from 0 to 121.
+ * @param res The resolution.
+ * @param rng The random number generator.
+ * @return The cell, Domain if the base cell is invalid, or ResDomain if the
+ *         resolution is invalid.
+ */
+pub fn random_cell_in<R: Rng + ?Sized>(
+    baseCell: i32,
+    res: i32,
+    rng: &mut R,
+) -> Result<H3Index, Error> {
+    if !(0..NUM_BASE_CELLS).contains(&baseCell) {
+        return Err(Error::Domain);
+    }
+    if !(0..=MAX_H3_RES).contains(&res) {
+        return Err(Error::ResDomain);
+    }
+    let pos = rng.random_range(0.._baseCellDescendants(baseCell, res));
+    Ok(_baseCellDescendant(baseCell, res, pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use crate::h3_index::{cellToChildren, getBaseCellNumber, getResolution, isPentagon, isValidCell};
+    use crate::base_cells::baseCellNumberToCell;
+
+    use super::*;
+
+    #[test]
+    fn descendantsInOrder() {
+        // positions enumerate the children in index order
+        for baseCell in [4, 20] {
+            let parent = baseCellNumberToCell(baseCell).unwrap();
+            let children = cellToChildren(parent, 3).unwrap();
+            assert_eq!(children.len() as u64, _baseCellDescendants(baseCell, 3));
+            for (pos, &child) in children.iter().enumerate() {
+                assert_eq!(
+                    _baseCellDescendant(baseCell, 3, pos as u64),
+                    child,
+                    "position {}",
+                    pos
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn randomCells() {
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        for res in 0..=MAX_H3_RES {
+            for _ in 0..200 {
+                let h = random_cell(res, &mut rng).unwrap();
+                assert!(isValidCell(h), "valid cell");
+                assert_eq!(getResolution(h), res, "resolution");
+            }
+        }
+
+        // at res 1 each of the 842 cells is about equally likely
+        let mut counts: BTreeMap<H3Index, u32> = BTreeMap::new();
+        for _ in 0..84_200 {
+            *counts.entry(random_cell(1, &mut rng).unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 842, "every cell is generated");
+        assert!(counts.values().all(|&n| (40..=170).contains(&n)), "uniform");
+        let pentagons: u32 = counts
+            .iter()
+            .filter(|(h, _)| isPentagon(**h))
+            .map(|(_, n)| n)
+            .sum();
+        assert!((600..1800).contains(&pentagons), "pentagons in proportion");
+
+        for _ in 0..100 {
+            let h = random_cell_in(4, 9, &mut rng).unwrap();
+            assert!(isValidCell(h), "valid cell in a pentagon base cell");
+            assert_eq!(getBaseCellNumber(h), 4, "base cell");
+        }
+        assert_eq!(random_cell(16, &mut rng), Err(Error::ResDomain));
+        assert_eq!(random_cell_in(122, 5, &mut rng), Err(Error::Domain));
+        assert_eq!(random_cell_in(0, -1, &mut rng), Err(Error::ResDomain));
+    }
+}
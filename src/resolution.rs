@@ -0,0 +1,152 @@
+use enum_primitive::FromPrimitive;
+
+use crate::constants::MAX_H3_RES;
+use crate::error::Error;
+use crate::h3_index::isResolutionClassIII;
+
+enum_from_primitive! {
+    /**
+     * An H3 resolution, from 0 (coarsest) to 15 (finest).
+     *
+     * Functions taking a Resolution can't be given an out of range value, so
+     * unlike their i32 counterparts they never fail with ResDomain. Convert
+     * from an i32 with TryFrom.
+     */
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+    pub enum Resolution {
+        /** resolution 0 */
+        Res0 = 0,
+        /** resolution 1 */
+        Res1 = 1,
+        /** resolution 2 */
+        Res2 = 2,
+        /** resolution 3 */
+        Res3 = 3,
+        /** resolution 4 */
+        Res4 = 4,
+        /** resolution 5 */
+        Res5 = 5,
+        /** resolution 6 */
+        Res6 = 6,
+        /** resolution 7 */
+        Res7 = 7,
+        /** resolution 8 */
+        Res8 = 8,
+        /** resolution 9 */
+        Res9 = 9,
+        /** resolution 10 */
+        Res10 = 10,
+        /** resolution 11 */
+        Res11 = 11,
+        /** resolution 12 */
+        Res12 = 12,
+        /** resolution 13 */
+        Res13 = 13,
+        /** resolution 14 */
+        Res14 = 14,
+        /** resolution 15 */
+        Res15 = 15,
+    }
+}
+
+impl Resolution {
+    /** All resolutions, from coarsest to finest. */
+    pub const ALL: [Resolution; (MAX_H3_RES + 1) as usize] = [
+        Resolution::Res0,
+        Resolution::Res1,
+        Resolution::Res2,
+        Resolution::Res3,
+        Resolution::Res4,
+        Resolution::Res5,
+        Resolution::Res6,
+        Resolution::Res7,
+        Resolution::Res8,
+        Resolution::Res9,
+        Resolution::Res10,
+        Resolution::Res11,
+        Resolution::Res12,
+        Resolution::Res13,
+        Resolution::Res14,
+        Resolution::Res15,
+    ];
+
+    /**
+     * Returns the resolution RES, checked when compiling, for pipelines where
+     * the resolution is fixed: `Resolution::new_const::<16>()` does not
+     * compile, rather than failing with ResDomain.
+     */
+    pub const fn new_const<const RES: u8>() -> Resolution {
+        const { assert!(RES as i32 <= MAX_H3_RES, "resolution out of range") };
+        Resolution::ALL[RES as usize]
+    }
+
+    /** Returns whether the resolution has Class III orientation. */
+    pub fn is_class_iii(self) -> bool {
+        isResolutionClassIII(self as i32)
+    }
+
+    /** Returns the next finer resolution, or None at resolution 15. */
+    pub fn finer(self) -> Option<Resolution> {
+        Resolution::from_i32(self as i32 + 1)
+    }
+
+    /** Returns the next coarser resolution, or None at resolution 0. */
+    pub fn coarser(self) -> Option<Resolution> {
+        Resolution::from_i32(self as i32 - 1)
+    }
+}
+
+impl From<Resolution> for i32 {
+    fn from(res: Resolution) -> i32 {
+        res as i32
+    }
+}
+
+impl TryFrom<i32> for Resolution {
+    type Error = Error;
+
+    fn try_from(res: i32) -> Result<Resolution, Error> {
+        Resolution::from_i32(res).ok_or(Error::ResDomain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newConst() {
+        const RES: Resolution = Resolution::new_const::<7>();
+        assert_eq!(RES, Resolution::Res7);
+        assert_eq!(Resolution::new_const::<0>(), Resolution::Res0);
+        assert_eq!(Resolution::new_const::<15>(), Resolution::Res15);
+    }
+
+    #[test]
+    fn tryFromI32() {
+        for res in 0..=MAX_H3_RES {
+            let r = Resolution::try_from(res).unwrap();
+            assert_eq!(i32::from(r), res, "resolution round trips");
+            assert_eq!(Resolution::ALL[res as usize], r, "ALL is in order");
+            assert_eq!(
+                r.is_class_iii(),
+                res % 2 == 1,
+                "odd resolutions are Class III"
+            );
+        }
+        assert_eq!(Resolution::try_from(-1), Err(Error::ResDomain));
+        assert_eq!(Resolution::try_from(MAX_H3_RES + 1), Err(Error::ResDomain));
+    }
+
+    #[test]
+    fn finerCoarser() {
+        assert_eq!(Resolution::Res0.coarser(), None, "nothing coarser than 0");
+        assert_eq!(Resolution::Res15.finer(), None, "nothing finer than 15");
+        assert_eq!(Resolution::Res4.finer(), Some(Resolution::Res5));
+        assert_eq!(Resolution::Res4.coarser(), Some(Resolution::Res3));
+        assert!(
+            Resolution::Res3 < Resolution::Res4,
+            "ordered coarse to fine"
+        );
+    }
+}
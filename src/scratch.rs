@@ -0,0 +1,97 @@
+// Thread-local scratch space for algorithms that need temporary storage
+// besides their output, such as the visited set of the safe gridDisk
+// algorithm and of the polygon flood fill.
+//
+// Child expansion (cellToChildren, uncompactCells) does not use it: children
+// stream out of IterCellsChildren straight into the output, which is sized up
+// front, so there is nothing temporary to reuse. Callers that want to reuse
+// the output as well can use cellToChildrenInto and uncompactCellsInto.
+
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+
+use crate::h3_index::H3Index;
+
+/** Set of cells used for scratch space. Without std there is no HashSet, so
+ * an ordered set stands in. */
+#[cfg(feature = "std")]
+pub type ScratchSet = std::collections::HashSet<H3Index, BuildCellHasher>;
+#[cfg(not(feature = "std"))]
+pub type ScratchSet = alloc::collections::BTreeSet<H3Index>;
+
+/**
+ * Hashes cells with a single multiply. Scratch sets only hold cells computed
+ * by the library, so the DoS resistance of the default hasher isn't needed,
+ * and it is a large part of the cost of the safe gridDisk algorithm.
+ */
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CellHasher {
+    hash: u64,
+}
+
+#[cfg(feature = "std")]
+impl core::hash::Hasher for CellHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, h: u64) {
+        // the high bits of the product depend on all of the input bits, so
+        // fold them into the low bits, which pick the bucket
+        let x = (self.hash ^ h).wrapping_mul(0x9e3779b97f4a7c15);
+        self.hash = x ^ (x >> 32);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(feature = "std")]
+pub type BuildCellHasher = core::hash::BuildHasherDefault<CellHasher>;
+
+/** Scratch buffers larger than this many entries are freed after use, so a
+ * single huge request does not pin its memory to the thread for good. */
+#[cfg(feature = "std")]
+const MAX_RETAINED_LEN: usize = 1 << 20;
+
+#[cfg(feature = "std")]
+thread_local! {
+    /** Visited set used by the polygon flood fill and the safe gridDisk
+     * algorithm. */
+    static CELL_SCRATCH: RefCell<ScratchSet> = RefCell::new(ScratchSet::default());
+}
+
+/**
+ * Runs a function with an empty scratch set of cells, reusing the allocation
+ * from previous calls on this thread.
+ *
+ * @param f The function to run with the set.
+ * @return The result of f.
+ */
+#[cfg(feature = "std")]
+pub fn withCellScratch<T>(f: impl FnOnce(&mut ScratchSet) -> T) -> T {
+    CELL_SCRATCH.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut set) => {
+            set.clear();
+            let result = f(&mut set);
+            if set.capacity() > MAX_RETAINED_LEN {
+                *set = ScratchSet::default();
+            } else {
+                set.clear();
+            }
+            result
+        }
+        // already in use further up the stack, fall back to a new set
+        Err(_) => f(&mut ScratchSet::default()),
+    })
+}
+
+/** Without thread locals, every call gets a new set. */
+#[cfg(not(feature = "std"))]
+pub fn withCellScratch<T>(f: impl FnOnce(&mut ScratchSet) -> T) -> T {
+    f(&mut ScratchSet::default())
+}
@@ -0,0 +1,216 @@
+use core::fmt;
+
+use ::serde::de::{self, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cell_index::CellIndex;
+use crate::h3_index::{h3ToString, stringToH3, H3Index};
+use crate::lat_lng::{CellBoundary, LatLng, MAX_CELL_BNDRY_VERTS};
+use crate::prelude::*;
+
+/**
+ * Serializes as the hexadecimal string in human readable formats such as
+ * JSON, and as the u64 elsewhere, and deserializes the same way, so it also
+ * works with formats that are not self-describing. Use the cell_hex and
+ * cell_u64 modules with `#[serde(with = ...)]` to pick a representation
+ * explicitly.
+ */
+impl Serialize for CellIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.serialize_str(&self.to_string());
+        }
+        serializer.serialize_u64(self.index())
+    }
+}
+
+impl<'de> Deserialize<'de> for CellIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CellIndex, D::Error> {
+        let h = if deserializer.is_human_readable() {
+            deserializer.deserialize_str(IndexVisitor)?
+        } else {
+            deserializer.deserialize_u64(IndexVisitor)?
+        };
+        CellIndex::new(h).map_err(de::Error::custom)
+    }
+}
+
+/** Accepts an H3 index as either a u64 or a hexadecimal string. */
+struct IndexVisitor;
+
+impl<'de> Visitor<'de> for IndexVisitor {
+    type Value = H3Index;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an H3 index as a u64 or hexadecimal string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<H3Index, E> {
+        Ok(v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<H3Index, E> {
+        stringToH3(v).map_err(E::custom)
+    }
+}
+
+/**
+ * Serializes only the vertices in use, as a sequence of LatLng.
+ */
+impl Serialize for CellBoundary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.verts[..self.numVerts].serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CellBoundary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CellBoundary, D::Error> {
+        let verts = Vec::<LatLng>::deserialize(deserializer)?;
+        if verts.len() > MAX_CELL_BNDRY_VERTS {
+            return Err(de::Error::invalid_length(
+                verts.len(),
+                &"at most MAX_CELL_BNDRY_VERTS vertices",
+            ));
+        }
+        let mut cb = CellBoundary::new();
+        cb.verts[..verts.len()].copy_from_slice(&verts);
+        cb.numVerts = verts.len();
+        Ok(cb)
+    }
+}
+
+/**
+ * Serializes an H3Index field as its hexadecimal string in every format, with
+ * `#[serde(with = "h3_rs::serde::hex")]`.
+ */
+pub mod hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(h: &H3Index, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&h3ToString(*h))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<H3Index, D::Error> {
+        deserializer.deserialize_str(IndexVisitor)
+    }
+}
+
+/**
+ * Serializes a CellIndex field as its hexadecimal string in every format,
+ * with `#[serde(with = "h3_rs::serde::cell_hex")]`.
+ */
+pub mod cell_hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cell: &CellIndex, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&cell.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CellIndex, D::Error> {
+        let h = deserializer.deserialize_str(IndexVisitor)?;
+        CellIndex::new(h).map_err(de::Error::custom)
+    }
+}
+
+/**
+ * Serializes a CellIndex field as a u64 in every format, with
+ * `#[serde(with = "h3_rs::serde::cell_u64")]`.
+ */
+pub mod cell_u64 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cell: &CellIndex, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(cell.index())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CellIndex, D::Error> {
+        let h = deserializer.deserialize_u64(IndexVisitor)?;
+        CellIndex::new(h).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::de::value::{Error as ValueError, StrDeserializer, U64Deserializer};
+    use ::serde::de::IntoDeserializer;
+
+    use super::*;
+
+    /** A deserializer for a format that is not self-describing, which only
+     * produces the type it is asked for. */
+    #[derive(Clone, Copy)]
+    struct Strict {
+        readable: bool,
+        h: H3Index,
+    }
+
+    impl<'de> Deserializer<'de> for Strict {
+        type Error = ValueError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ValueError> {
+            Err(de::Error::custom("deserialize_any is not supported"))
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+            visitor.visit_str(&h3ToString(self.h))
+        }
+
+        fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+            visitor.visit_u64(self.h)
+        }
+
+        fn is_human_readable(&self) -> bool {
+            self.readable
+        }
+
+        ::serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u128 f32 f64 char string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    #[test]
+    fn withoutDeserializeAny() {
+        let expected = CellIndex::new(0x85283473fffffff).unwrap();
+        for readable in [true, false] {
+            let d = Strict {
+                readable,
+                h: expected.index(),
+            };
+            assert_eq!(CellIndex::deserialize(d), Ok(expected), "cell index");
+            assert_eq!(cell_hex::deserialize(d), Ok(expected), "cell_hex");
+            assert_eq!(cell_u64::deserialize(d), Ok(expected), "cell_u64");
+            assert_eq!(hex::deserialize(d), Ok(expected.index()), "hex");
+        }
+    }
+
+    #[test]
+    fn cellIndexFromStrOrU64() {
+        let expected = CellIndex::new(0x85283473fffffff).unwrap();
+
+        let d: StrDeserializer<ValueError> = "85283473fffffff".into_deserializer();
+        assert_eq!(CellIndex::deserialize(d), Ok(expected), "parses hex string");
+
+        let d: U64Deserializer<ValueError> = 0x85283473fffffffu64.into_deserializer();
+        assert_eq!(CellIndex::deserialize(d), Ok(expected), "parses u64");
+
+        let d: U64Deserializer<ValueError> = 0u64.into_deserializer();
+        assert!(CellIndex::deserialize(d).is_err(), "rejects invalid cell");
+
+        let d: StrDeserializer<ValueError> = "not a cell".into_deserializer();
+        assert!(CellIndex::deserialize(d).is_err(), "rejects garbage");
+    }
+
+    #[test]
+    fn hexField() {
+        let d: StrDeserializer<ValueError> = "8928308280fffff".into_deserializer();
+        assert_eq!(hex::deserialize(d), Ok(0x8928308280fffff), "parses hex");
+
+        let d: U64Deserializer<ValueError> = 0x8928308280fffffu64.into_deserializer();
+        assert_eq!(
+            cell_u64::deserialize(d).map(|c| c.index()),
+            Ok(0x8928308280fffff)
+        );
+    }
+}
@@ -0,0 +1,129 @@
+// sqlx column types for cells, so they can be bound and fetched without casts
+// in every query. A CellIndex is stored as BIGINT, which holds every cell
+// since the high bit of an index is always 0. CellHex stores a cell as TEXT
+// in its canonical hexadecimal form. Both work with any sqlx database that
+// supports i64 or strings.
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+use crate::cell_index::CellIndex;
+
+impl<DB: Database> Type<DB> for CellIndex
+where
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for CellIndex
+where
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        i64::from(*self).encode_by_ref(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for CellIndex
+where
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<CellIndex, BoxDynError> {
+        Ok(CellIndex::try_from(i64::decode(value)?)?)
+    }
+}
+
+/**
+ * A cell stored as TEXT, in the lowercase hexadecimal form that Display
+ * writes and FromStr reads.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellHex(pub CellIndex);
+
+impl<DB: Database> Type<DB> for CellHex
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for CellHex
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.0.to_string().encode_by_ref(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for CellHex
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<CellHex, BoxDynError> {
+        Ok(CellHex(<&str as Decode<DB>>::decode(value)?.parse()?))
+    }
+}
+
+impl From<CellIndex> for CellHex {
+    fn from(cell: CellIndex) -> CellHex {
+        CellHex(cell)
+    }
+}
+
+impl From<CellHex> for CellIndex {
+    fn from(hex: CellHex) -> CellIndex {
+        hex.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Connection, SqliteConnection};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn bigintAndText() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        let cell = CellIndex::new(0x8f2830828052d25).unwrap();
+
+        let (stored, kind): (CellIndex, String) = sqlx::query_as("SELECT ?1, typeof(?1)")
+            .bind(cell)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!((stored, kind.as_str()), (cell, "integer"), "as BIGINT");
+
+        let (stored, text): (CellHex, String) = sqlx::query_as("SELECT ?1, ?1")
+            .bind(CellHex(cell))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(stored, CellHex(cell), "as TEXT");
+        assert_eq!(text, "8f2830828052d25", "hexadecimal");
+
+        let invalid: Result<CellIndex, _> = sqlx::query_scalar("SELECT -1")
+            .fetch_one(&mut conn)
+            .await;
+        assert!(invalid.is_err(), "negative BIGINT");
+        let invalid: Result<CellHex, _> = sqlx::query_scalar("SELECT 'not a cell'")
+            .fetch_one(&mut conn)
+            .await;
+        assert!(invalid.is_err(), "invalid TEXT");
+    }
+}
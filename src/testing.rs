@@ -0,0 +1,230 @@
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+use ::proptest::prelude::*;
+
+use crate::base_cells::_isBaseCellPentagon;
+use crate::cell_index::CellIndex;
+use crate::constants::*;
+use crate::coord_ijk::Direction;
+use crate::directed_edge::{directedEdgeToCells, originToDirectedEdges};
+use crate::h3_index::{H3Index, H3_SET_INDEX_DIGIT, setH3Index};
+use crate::lat_lng::LatLng;
+use crate::resolution::Resolution;
+use crate::H3_NULL;
+
+/**
+ * Checks that a resolution range strategy would generate anything.
+ *
+ * Clamping an out of bounds range would quietly test fewer resolutions than
+ * asked for, and an empty range only fails once proptest first samples it,
+ * so both are rejected up front.
+ */
+fn _checkResRange(res_range: &RangeInclusive<i32>) {
+    assert!(
+        *res_range.start() >= 0 && *res_range.end() <= MAX_H3_RES,
+        "resolution range {:?} is not within 0..={}",
+        res_range,
+        MAX_H3_RES
+    );
+    assert!(
+        !res_range.is_empty(),
+        "resolution range {:?} is empty",
+        res_range
+    );
+}
+
+/**
+ * Generates valid H3 cells with a resolution in the given range.
+ *
+ * Base cells and index digits are chosen uniformly, so pentagons are much
+ * more common than in cells sampled by area, which is usually what is wanted
+ * when looking for edge cases.
+ *
+ * @param res_range The resolutions to generate, a non-empty range within
+ *                  [0, 15]; any other range panics.
+ * @return A strategy producing valid cells.
+ */
+pub fn any_valid_cell(res_range: RangeInclusive<i32>) -> impl Strategy<Value = H3Index> {
+    _checkResRange(&res_range);
+    (
+        res_range,
+        0..NUM_BASE_CELLS,
+        prop::collection::vec(0..7i32, MAX_H3_RES as usize),
+    )
+        .prop_map(|(res, baseCell, digits)| {
+            let mut h: H3Index = H3_NULL;
+            setH3Index(&mut h, res, baseCell, 0);
+
+            let mut leading = _isBaseCellPentagon(baseCell);
+            for r in 1..=res {
+                let mut digit = digits[(r - 1) as usize];
+                // pentagons have no subsequence in the deleted direction
+                if leading && digit == Direction::PENTAGON_SKIPPED_DIGIT as i32 {
+                    digit = Direction::JAxesDigit as i32;
+                }
+                if digit != 0 {
+                    leading = false;
+                }
+                H3_SET_INDEX_DIGIT(&mut h, r, digit);
+            }
+            h
+        })
+}
+
+/**
+ * Generates spherical coordinates distributed uniformly over the sphere.
+ *
+ * @return A strategy producing coordinates in radians.
+ */
+pub fn any_latlng() -> impl Strategy<Value = LatLng> {
+    (-1.0..=1.0f64, -PI..PI).prop_map(|(z, lng): (f64, f64)| LatLng { lat: z.asin(), lng })
+}
+
+/**
+ * Generates resolutions in the given range.
+ *
+ * @param res_range The resolutions to generate, a non-empty range within
+ *                  [0, 15]; any other range panics.
+ * @return A strategy producing resolutions.
+ */
+pub fn any_resolution(res_range: RangeInclusive<i32>) -> impl Strategy<Value = Resolution> {
+    _checkResRange(&res_range);
+    res_range.prop_map(|res| Resolution::try_from(res).unwrap())
+}
+
+/**
+ * Generates valid directed edges from cells with a resolution in the given
+ * range.
+ *
+ * @param res_range The resolutions of the origin cells, a non-empty range
+ *                  within [0, 15]; any other range panics.
+ * @return A strategy producing valid directed edges.
+ */
+pub fn any_directed_edge(res_range: RangeInclusive<i32>) -> impl Strategy<Value = H3Index> {
+    (any_valid_cell(res_range), 0..6usize).prop_map(|(origin, i)| {
+        let edges = originToDirectedEdges(origin);
+        // pentagons have one fewer neighbor, so skip past the missing edge
+        (0..6)
+            .map(|j| edges[(i + j) % 6])
+            .find(|e| *e != H3_NULL)
+            .unwrap()
+    })
+}
+
+/**
+ * Generates pairs of neighboring cells at any resolution.
+ *
+ * @return A strategy producing (origin, neighbor) pairs.
+ */
+pub fn neighbor_pairs() -> impl Strategy<Value = (H3Index, H3Index)> {
+    any_directed_edge(0..=MAX_H3_RES).prop_map(|edge| directedEdgeToCells(edge).unwrap())
+}
+
+impl Arbitrary for CellIndex {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<CellIndex>;
+
+    fn arbitrary_with(_args: ()) -> BoxedStrategy<CellIndex> {
+        any_valid_cell(0..=MAX_H3_RES)
+            .prop_map(|h| CellIndex::new(h).unwrap())
+            .boxed()
+    }
+}
+
+impl Arbitrary for Resolution {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Resolution>;
+
+    fn arbitrary_with(_args: ()) -> BoxedStrategy<Resolution> {
+        any_resolution(0..=MAX_H3_RES).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directed_edge::{cellsToDirectedEdge, isValidDirectedEdge};
+    use crate::h3_index::{cellToLatLng, getResolution, isValidCell, latLngToCell};
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "is not within")]
+    fn anyValidCellOutOfBounds() {
+        let _ = any_valid_cell(0..=16);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn anyValidCellEmpty() {
+        #[allow(
+            clippy::reversed_empty_ranges,
+            reason = "testing the empty range check"
+        )]
+        let _ = any_valid_cell(5..=3);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not within")]
+    fn anyResolutionOutOfBounds() {
+        let _ = any_resolution(-1..=3);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn anyResolutionEmpty() {
+        #[allow(
+            clippy::reversed_empty_ranges,
+            reason = "testing the empty range check"
+        )]
+        let _ = any_resolution(3..=2);
+    }
+
+    proptest! {
+        #[test]
+        fn anyValidCellIsValid(h in any_valid_cell(0..=MAX_H3_RES)) {
+            prop_assert!(isValidCell(h));
+        }
+
+        #[test]
+        fn anyValidCellInRange(h in any_valid_cell(3..=5)) {
+            prop_assert!((3..=5).contains(&getResolution(h)));
+        }
+
+        #[test]
+        fn cellCenterRoundTrip(h in any_valid_cell(0..=MAX_H3_RES)) {
+            let center = cellToLatLng(h).unwrap();
+            prop_assert_eq!(latLngToCell(&center, getResolution(h)).unwrap(), h);
+        }
+
+        #[test]
+        fn anyLatLngIndexes(g in any_latlng(), res in 0..=MAX_H3_RES) {
+            prop_assert!(isValidCell(latLngToCell(&g, res).unwrap()));
+        }
+
+        #[test]
+        fn arbitraryCellIndexParses(cell in any::<CellIndex>()) {
+            prop_assert_eq!(cell.to_string().parse::<CellIndex>(), Ok(cell));
+        }
+
+        #[test]
+        fn arbitraryResolutionIndexes(g in any_latlng(), res in any::<Resolution>()) {
+            prop_assert_eq!(CellIndex::from_latlng(&g, res).unwrap().resolution(), res);
+        }
+
+        #[test]
+        fn anyResolutionInRange(res in any_resolution(3..=5)) {
+            prop_assert!((3..=5).contains(&i32::from(res)));
+        }
+
+        #[test]
+        fn anyDirectedEdgeIsValid(edge in any_directed_edge(0..=MAX_H3_RES)) {
+            prop_assert!(isValidDirectedEdge(edge));
+        }
+
+        #[test]
+        fn neighborPairsAreNeighbors((origin, neighbor) in neighbor_pairs()) {
+            prop_assert!(cellsToDirectedEdge(origin, neighbor).is_ok());
+        }
+    }
+}
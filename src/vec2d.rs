@@ -1,4 +1,6 @@
-use num::Float;
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
 
 #[derive(Copy, Clone)]
 pub struct Vec2d {
@@ -12,5 +14,44 @@ pub struct Vec2d {
  * @return The magnitude of the vector.
  */
 pub fn _v2dMag(v: &Vec2d) -> f64 {
-    return (v.x * v.x + v.y * v.y).sqrt();
+    (v.x * v.x + v.y * v.y).sqrt()
+}
+
+/**
+ * Finds the intersection between two lines. Assumes that the lines intersect
+ * and that the intersection is not at an endpoint of either line.
+ * @param p0 The first endpoint of the first line.
+ * @param p1 The second endpoint of the first line.
+ * @param p2 The first endpoint of the second line.
+ * @param p3 The second endpoint of the second line.
+ * @return The intersection point.
+ */
+pub fn _v2dIntersect(p0: &Vec2d, p1: &Vec2d, p2: &Vec2d, p3: &Vec2d) -> Vec2d {
+    let s1 = Vec2d {
+        x: p1.x - p0.x,
+        y: p1.y - p0.y,
+    };
+    let s2 = Vec2d {
+        x: p3.x - p2.x,
+        y: p3.y - p2.y,
+    };
+
+    // single precision, as in the reference implementation
+    let t: f32 =
+        ((s2.x * (p0.y - p2.y) - s2.y * (p0.x - p2.x)) / (-s2.x * s1.y + s1.x * s2.y)) as f32;
+
+    Vec2d {
+        x: p0.x + (t as f64 * s1.x),
+        y: p0.y + (t as f64 * s1.y),
+    }
+}
+
+/**
+ * Whether two 2D vectors are almost equal, within some threshold
+ * @param v1 First vector to compare
+ * @param v2 Second vector to compare
+ * @return Whether the vectors are almost equal
+ */
+pub fn _v2dAlmostEquals(v1: &Vec2d, v2: &Vec2d) -> bool {
+    ((v1.x - v2.x) as f32).abs() < f32::EPSILON && ((v1.y - v2.y) as f32).abs() < f32::EPSILON
 }
@@ -1,6 +1,11 @@
+#[cfg(not(any(feature = "std", test)))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
 use crate::lat_lng::LatLng;
 
-#[derive(Copy, Clone)]
+/** 3D floating point structure */
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Vec3d {
     pub x: f64,
     pub y: f64,
@@ -8,11 +13,29 @@ pub struct Vec3d {
 }
 
 fn _square(x: f64) -> f64 {
-    return x * x;
+    x * x
 }
 
 pub fn _pointSquareDist(v1: Vec3d, v2: Vec3d) -> f64 {
-    return _square(v1.x - v2.x) + _square(v1.y - v2.y) + _square(v1.z - v2.z);
+    _square(v1.x - v2.x) + _square(v1.y - v2.y) + _square(v1.z - v2.z)
+}
+
+/**
+ * Calculates the dot product of two 3D vectors.
+ */
+pub fn _vec3dDot(v1: &Vec3d, v2: &Vec3d) -> f64 {
+    v1.x * v2.x + v1.y * v2.y + v1.z * v2.z
+}
+
+/**
+ * Calculates the cross product of two 3D vectors.
+ */
+pub fn _vec3dCross(v1: &Vec3d, v2: &Vec3d) -> Vec3d {
+    Vec3d {
+        x: v1.y * v2.z - v1.z * v2.y,
+        y: v1.z * v2.x - v1.x * v2.z,
+        z: v1.x * v2.y - v1.y * v2.x,
+    }
 }
 
 /**
@@ -28,3 +51,17 @@ pub fn _geoToVec3d(geo: &LatLng, v: &mut Vec3d) {
     v.x = geo.lng.cos() * r;
     v.y = geo.lng.sin() * r;
 }
+
+/**
+ * Calculate the latitude and longitude of the point on the unit sphere in the
+ * direction of a 3D coordinate. The vector does not need to be normalized.
+ *
+ * @param v The 3D coordinate of the point.
+ * @return The latitude and longitude of the point.
+ */
+pub fn _vec3dToGeo(v: &Vec3d) -> LatLng {
+    LatLng {
+        lat: v.z.atan2((v.x * v.x + v.y * v.y).sqrt()),
+        lng: v.y.atan2(v.x),
+    }
+}
@@ -0,0 +1,617 @@
+use crate::{
+    algos::{directionForNeighbor, h3NeighborRotations},
+    base_cells::{
+        _baseCellToCCWrot60, _isBaseCellPentagon, _isBaseCellPolarPentagon, baseCellData,
+    },
+    constants::{H3_CELL_MODE, H3_VERTEX_MODE, NUM_HEX_VERTS, NUM_PENTAGONS, NUM_PENT_VERTS},
+    coord_ijk::Direction,
+    error::Error,
+    face_ijk::{FaceIJK, _faceIjkPentToCellBoundary, _faceIjkToCellBoundary},
+    h3_index::{
+        H3Index, _h3LeadingNonZeroDigit, _h3ToFaceIjk, isPentagon, isValidCell, H3_GET_BASE_CELL,
+        H3_GET_INDEX_DIGIT, H3_GET_MODE, H3_GET_RESERVED_BITS, H3_GET_RESOLUTION, H3_SET_MODE,
+        H3_SET_RESERVED_BITS,
+    },
+    lat_lng::{CellBoundary, LatLng},
+    H3_NULL,
+};
+
+/** Invalid vertex number */
+pub const INVALID_VERTEX_NUM: i32 = -1;
+
+/** Offset of the first direction in PentagonDirectionFaces.faces */
+const DIRECTION_INDEX_OFFSET: usize = 2;
+
+/** @struct PentagonDirectionFaces
+ *  @brief The faces in each axial direction of a given pentagon base cell
+ */
+struct PentagonDirectionFaces {
+    /** base cell number */
+    baseCell: i32,
+    /** face numbers for each axial direction, in order, starting with J */
+    faces: [i32; NUM_PENT_VERTS as usize],
+}
+
+/** @brief Table of direction-to-face mapping for each pentagon
+ *
+ * Note that faces are in directional order, starting at J_AXES_DIGIT.
+ */
+const pentagonDirectionFaces: [PentagonDirectionFaces; NUM_PENTAGONS as usize] = [
+    PentagonDirectionFaces {
+        baseCell: 4,
+        faces: [4, 0, 2, 1, 3],
+    },
+    PentagonDirectionFaces {
+        baseCell: 14,
+        faces: [6, 11, 2, 7, 1],
+    },
+    PentagonDirectionFaces {
+        baseCell: 24,
+        faces: [5, 10, 1, 6, 0],
+    },
+    PentagonDirectionFaces {
+        baseCell: 38,
+        faces: [7, 12, 3, 8, 2],
+    },
+    PentagonDirectionFaces {
+        baseCell: 49,
+        faces: [9, 14, 0, 5, 4],
+    },
+    PentagonDirectionFaces {
+        baseCell: 58,
+        faces: [8, 13, 4, 9, 3],
+    },
+    PentagonDirectionFaces {
+        baseCell: 63,
+        faces: [11, 6, 15, 10, 16],
+    },
+    PentagonDirectionFaces {
+        baseCell: 72,
+        faces: [12, 7, 16, 11, 17],
+    },
+    PentagonDirectionFaces {
+        baseCell: 83,
+        faces: [10, 5, 19, 14, 15],
+    },
+    PentagonDirectionFaces {
+        baseCell: 97,
+        faces: [13, 8, 17, 12, 18],
+    },
+    PentagonDirectionFaces {
+        baseCell: 107,
+        faces: [14, 9, 18, 13, 19],
+    },
+    PentagonDirectionFaces {
+        baseCell: 117,
+        faces: [15, 19, 17, 18, 16],
+    },
+];
+
+/**
+ * Get the number of CCW rotations of the cell's vertex numbers
+ * compared to the directional layout of its neighbors.
+ * @return Number of CCW rotations for the cell
+ */
+fn vertexRotations(cell: H3Index) -> Result<i32, Error> {
+    // Get the face and other info for the origin
+    let fijk: FaceIJK = _h3ToFaceIjk(cell)?;
+    let baseCell = H3_GET_BASE_CELL(cell);
+    let cellLeadingDigit = _h3LeadingNonZeroDigit(cell);
+
+    // get the base cell face
+    let baseFijk: FaceIJK = baseCellData[baseCell as usize].homeFijk;
+
+    let mut ccwRot60 = _baseCellToCCWrot60(baseCell, fijk.face);
+
+    if _isBaseCellPentagon(baseCell) {
+        // Find the appropriate direction-to-face mapping
+        let dirFaces = pentagonDirectionFaces
+            .iter()
+            .find(|dirFaces| dirFaces.baseCell == baseCell)
+            .ok_or(Error::Failed)?;
+        let ikFace = dirFaces.faces[Direction::IKAxesDigit as usize - DIRECTION_INDEX_OFFSET];
+        let jkFace = dirFaces.faces[Direction::JKAxesDigit as usize - DIRECTION_INDEX_OFFSET];
+
+        // additional CCW rotation for polar neighbors or IK neighbors
+        if fijk.face != baseFijk.face && (_isBaseCellPolarPentagon(baseCell) || fijk.face == ikFace)
+        {
+            ccwRot60 = (ccwRot60 + 1) % 6;
+        }
+
+        // Check whether the cell crosses a deleted pentagon subsequence
+        if cellLeadingDigit == Direction::JKAxesDigit && fijk.face == ikFace {
+            // Vertex is on the IK axis, so rotate CW
+            ccwRot60 = (ccwRot60 + 5) % 6;
+        } else if cellLeadingDigit == Direction::IKAxesDigit && fijk.face == jkFace {
+            // Vertex is on the JK axis, so rotate CCW
+            ccwRot60 = (ccwRot60 + 1) % 6;
+        }
+    }
+    Ok(ccwRot60)
+}
+
+/** @brief Hexagon direction to vertex number relationships (same face).
+ *         Note that we don't use direction 0 (center).
+ */
+const directionToVertexNumHex: [i32; Direction::NUM_DIGITS as usize] =
+    [Direction::InvalidDigit as i32, 3, 1, 2, 5, 4, 0];
+
+/** @brief Pentagon direction to vertex number relationships (same face).
+ *         Note that we don't use directions 0 (center) or 1 (deleted K axis).
+ */
+const directionToVertexNumPent: [i32; Direction::NUM_DIGITS as usize] = [
+    Direction::InvalidDigit as i32,
+    Direction::InvalidDigit as i32,
+    1,
+    2,
+    4,
+    3,
+    0,
+];
+
+/** @brief Vertex number to hexagon direction relationships (same face).
+ */
+const vertexNumToDirectionHex: [Direction; NUM_HEX_VERTS as usize] = [
+    Direction::IJAxesDigit,
+    Direction::JAxesDigit,
+    Direction::JKAxesDigit,
+    Direction::KAxesDigit,
+    Direction::IKAxesDigit,
+    Direction::IAxesDigit,
+];
+
+/** @brief Vertex number to pentagon direction relationships (same face).
+ */
+const vertexNumToDirectionPent: [Direction; NUM_PENT_VERTS as usize] = [
+    Direction::IJAxesDigit,
+    Direction::JAxesDigit,
+    Direction::JKAxesDigit,
+    Direction::IKAxesDigit,
+    Direction::IAxesDigit,
+];
+
+/**
+ * Get the first vertex number for a given direction. The neighbor in this
+ * direction is located between this vertex number and the next number in
+ * sequence.
+ * @returns The number for the first topological vertex, or INVALID_VERTEX_NUM
+ *          if the direction is not valid for this cell
+ */
+pub fn vertexNumForDirection(origin: H3Index, direction: Direction) -> i32 {
+    let isPent = isPentagon(origin);
+    // Check for invalid directions
+    if direction == Direction::CenterDigit
+        || direction >= Direction::InvalidDigit
+        || (isPent && direction == Direction::KAxesDigit)
+    {
+        return INVALID_VERTEX_NUM;
+    }
+
+    // Determine the vertex rotations for this cell
+    let rotations = match vertexRotations(origin) {
+        Ok(rotations) => rotations,
+        Err(_) => return INVALID_VERTEX_NUM,
+    };
+
+    // Find the appropriate vertex, rotating CCW if necessary
+    if isPent {
+        (directionToVertexNumPent[direction as usize] + NUM_PENT_VERTS - rotations) % NUM_PENT_VERTS
+    } else {
+        (directionToVertexNumHex[direction as usize] + NUM_HEX_VERTS - rotations) % NUM_HEX_VERTS
+    }
+}
+
+/**
+ * Get the direction for a given vertex number. This returns the direction for
+ * the neighbor between the given vertex number and the next number in
+ * sequence.
+ * @returns The direction for this vertex, or INVALID_DIGIT if the vertex
+ * number is invalid.
+ */
+pub fn directionForVertexNum(origin: H3Index, vertexNum: i32) -> Direction {
+    let isPent = isPentagon(origin);
+    // Check for invalid vertexes
+    let numVerts = if isPent {
+        NUM_PENT_VERTS
+    } else {
+        NUM_HEX_VERTS
+    };
+    if vertexNum < 0 || vertexNum > numVerts - 1 {
+        return Direction::InvalidDigit;
+    }
+
+    // Determine the vertex rotations for this cell
+    let rotations = match vertexRotations(origin) {
+        Ok(rotations) => rotations,
+        Err(_) => return Direction::InvalidDigit,
+    };
+
+    // Find the appropriate direction, rotating CW if necessary
+    if isPent {
+        return vertexNumToDirectionPent[((vertexNum + rotations) % NUM_PENT_VERTS) as usize];
+    }
+    vertexNumToDirectionHex[((vertexNum + rotations) % NUM_HEX_VERTS) as usize]
+}
+
+/** @brief Directions in CCW order */
+const DIRECTIONS: [Direction; NUM_HEX_VERTS as usize] = [
+    Direction::JAxesDigit,
+    Direction::JKAxesDigit,
+    Direction::KAxesDigit,
+    Direction::IKAxesDigit,
+    Direction::IAxesDigit,
+    Direction::IJAxesDigit,
+];
+
+/** @brief Reverse direction from neighbor in each direction,
+ *         given as an index into DIRECTIONS to facilitate rotation
+ */
+const revNeighborDirectionsHex: [i32; Direction::NUM_DIGITS as usize] =
+    [Direction::InvalidDigit as i32, 5, 3, 4, 1, 0, 2];
+
+/**
+ * Get a single vertex for a given cell, as an H3 index, or
+ * an error if the vertex is invalid
+ * @param cell    Cell to get the vertex for
+ * @param vertexNum Number (index) of the vertex to calculate
+ * @return The vertex index
+ */
+pub fn cellToVertex(cell: H3Index, vertexNum: i32) -> Result<H3Index, Error> {
+    let cellIsPentagon = isPentagon(cell);
+    let cellNumVerts = if cellIsPentagon {
+        NUM_PENT_VERTS
+    } else {
+        NUM_HEX_VERTS
+    };
+    let res = H3_GET_RESOLUTION(cell);
+
+    // Check for invalid vertexes
+    if vertexNum < 0 || vertexNum > cellNumVerts - 1 {
+        return Err(Error::Domain);
+    }
+
+    // Default the owner and vertex number to the input cell
+    let mut owner: H3Index = cell;
+    let mut ownerVertexNum = vertexNum;
+
+    // Determine the owner, looking at the three cells that share the vertex.
+    // By convention, the owner is the cell with the lowest numerical index.
+
+    // If the cell is the center child of its parent, it will always have
+    // the lowest index of any neighbor, so we can skip determining the owner
+    if res == 0 || H3_GET_INDEX_DIGIT(cell, res) != Direction::CenterDigit {
+        // Get the left neighbor of the vertex, with its rotations
+        let left = directionForVertexNum(cell, vertexNum);
+        if left == Direction::InvalidDigit {
+            return Err(Error::Failed); // LCOV_EXCL_LINE
+        }
+        let mut lRotations = 0;
+        let leftNeighbor = h3NeighborRotations(cell, left, &mut lRotations)?;
+        // Set to owner if lowest index
+        if leftNeighbor < owner {
+            owner = leftNeighbor;
+        }
+
+        // As above, skip the right neighbor if the left is known lowest
+        if res == 0 || H3_GET_INDEX_DIGIT(leftNeighbor, res) != Direction::CenterDigit {
+            // Get the right neighbor of the vertex, with its rotations
+            // Note that vertex - 1 is the right side, as vertex numbers are
+            // CCW
+            let right = directionForVertexNum(cell, (vertexNum - 1 + cellNumVerts) % cellNumVerts);
+            if right == Direction::InvalidDigit {
+                return Err(Error::Failed); // LCOV_EXCL_LINE
+            }
+            let mut rRotations = 0;
+            let rightNeighbor = h3NeighborRotations(cell, right, &mut rRotations)?;
+            // Set to owner if lowest index
+            if rightNeighbor < owner {
+                owner = rightNeighbor;
+                let dir = if isPentagon(owner) {
+                    directionForNeighbor(owner, cell)
+                } else {
+                    DIRECTIONS[((revNeighborDirectionsHex[right as usize] + rRotations)
+                        % NUM_HEX_VERTS) as usize]
+                };
+                ownerVertexNum = vertexNumForDirection(owner, dir);
+            }
+        }
+
+        // Determine the vertex number for the left neighbor
+        if owner == leftNeighbor {
+            let ownerIsPentagon = isPentagon(owner);
+            let dir = if ownerIsPentagon {
+                directionForNeighbor(owner, cell)
+            } else {
+                DIRECTIONS[((revNeighborDirectionsHex[left as usize] + lRotations) % NUM_HEX_VERTS)
+                    as usize]
+            };
+
+            // For the left neighbor, we need the second vertex of the
+            // edge, which may involve looping around the vertex nums
+            ownerVertexNum = vertexNumForDirection(owner, dir) + 1;
+            if ownerVertexNum == NUM_HEX_VERTS
+                || (ownerIsPentagon && ownerVertexNum == NUM_PENT_VERTS)
+            {
+                ownerVertexNum = 0;
+            }
+        }
+    }
+
+    // Create the vertex index
+    let mut vertex: H3Index = owner;
+    H3_SET_MODE(&mut vertex, H3_VERTEX_MODE);
+    H3_SET_RESERVED_BITS(&mut vertex, ownerVertexNum);
+    Ok(vertex)
+}
+
+/**
+ * Get all vertexes for the given cell
+ * @param cell      Cell to get the vertexes for
+ * @return The vertex indexes; for a pentagon the final slot is H3_NULL
+ */
+pub fn cellToVertexes(cell: H3Index) -> Result<[H3Index; NUM_HEX_VERTS as usize], Error> {
+    let mut vertexes: [H3Index; NUM_HEX_VERTS as usize] = [H3_NULL; NUM_HEX_VERTS as usize];
+    // Get all vertexes. If the cell is a pentagon, will fill the final slot
+    // with H3_NULL.
+    for i in 0..NUM_HEX_VERTS {
+        if i == 5 && isPentagon(cell) {
+            vertexes[i as usize] = H3_NULL;
+        } else {
+            vertexes[i as usize] = cellToVertex(cell, i)?;
+        }
+    }
+    Ok(vertexes)
+}
+
+/**
+ * Get the geocoordinates of an H3 vertex
+ * @param vertex H3 index describing a vertex
+ * @return The geographic coordinates of the vertex
+ */
+pub fn vertexToLatLng(vertex: H3Index) -> Result<LatLng, Error> {
+    // Get the vertex number and owner from the vertex
+    let vertexNum = H3_GET_RESERVED_BITS(vertex);
+    let mut owner: H3Index = vertex;
+    H3_SET_MODE(&mut owner, H3_CELL_MODE);
+    H3_SET_RESERVED_BITS(&mut owner, 0);
+
+    // Get the single vertex from the boundary
+    let mut gb = CellBoundary::new();
+    let fijk: FaceIJK = _h3ToFaceIjk(owner)?;
+    let res = H3_GET_RESOLUTION(owner);
+
+    if isPentagon(owner) {
+        _faceIjkPentToCellBoundary(&fijk, res, vertexNum, 1, &mut gb);
+    } else {
+        _faceIjkToCellBoundary(&fijk, res, vertexNum, 1, &mut gb);
+    }
+
+    // Copy from boundary to output coord
+    Ok(gb.verts[0])
+}
+
+/**
+ * Whether the input is a valid H3 vertex
+ * @param  vertex H3 index possibly describing a vertex
+ * @return        Whether the input is valid
+ */
+pub fn isValidVertex(vertex: H3Index) -> bool {
+    if H3_GET_MODE(vertex) != H3_VERTEX_MODE {
+        return false;
+    }
+
+    let vertexNum = H3_GET_RESERVED_BITS(vertex);
+    let mut owner: H3Index = vertex;
+    H3_SET_MODE(&mut owner, H3_CELL_MODE);
+    H3_SET_RESERVED_BITS(&mut owner, 0);
+
+    if !isValidCell(owner) {
+        return false;
+    }
+
+    // The easiest way to ensure that the owner + vertex number is valid,
+    // and that the vertex is canonical, is to recreate and compare.
+    match cellToVertex(owner, vertexNum) {
+        Ok(canonical) => vertex == canonical,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algos::gridDiskDistances,
+        base_cells::getRes0Cells,
+        h3_index::{cellToBoundary, cellToChildren, latLngToCell},
+        lat_lng::geoAlmostEqualThreshold,
+    };
+
+    use super::*;
+
+    #[test]
+    fn pentagonDirectionFacesTable() {
+        // The faces of the neighbors of each res 1 pentagon, in direction
+        // order starting at J
+        for dirFaces in pentagonDirectionFaces.iter() {
+            let mut pentagon: H3Index = 0;
+            crate::h3_index::setH3Index(&mut pentagon, 1, dirFaces.baseCell, 0);
+            assert!(isPentagon(pentagon), "base cell is a pentagon");
+            for dir in 2..7 {
+                let mut rotations = 0;
+                let neighbor = h3NeighborRotations(
+                    pentagon,
+                    num::FromPrimitive::from_i32(dir).unwrap(),
+                    &mut rotations,
+                )
+                .unwrap();
+                assert_eq!(
+                    _h3ToFaceIjk(neighbor).unwrap().face,
+                    dirFaces.faces[dir as usize - DIRECTION_INDEX_OFFSET],
+                    "face matches for direction {}",
+                    dir
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cellToVertex_badVerts() {
+        let origin: H3Index = 0x823d6ffffffffff;
+        assert_eq!(cellToVertex(origin, -1), Err(Error::Domain), "negative");
+        assert_eq!(cellToVertex(origin, 6), Err(Error::Domain), "too high");
+
+        let pentagon: H3Index = 0x823007fffffffff;
+        assert_eq!(
+            cellToVertex(pentagon, 5),
+            Err(Error::Domain),
+            "too high for pentagon"
+        );
+    }
+
+    #[test]
+    fn cellToVertex_sharedVertexes() {
+        // every vertex is shared by exactly three cells (two next to
+        // pentagon deleted regions excepted), and all of them agree on the
+        // index
+        let sf = LatLng {
+            lat: 0.659966917655,
+            lng: -2.1364398519396,
+        };
+        let origin = latLngToCell(&sf, 9).unwrap();
+        let mut counts: std::collections::HashMap<H3Index, i32> = std::collections::HashMap::new();
+        for (cell, _distance) in gridDiskDistances(origin, 1).unwrap() {
+            for v in 0..NUM_HEX_VERTS {
+                let vertex = cellToVertex(cell, v).unwrap();
+                assert!(isValidVertex(vertex), "vertex is valid");
+                *counts.entry(vertex).or_insert(0) += 1;
+            }
+        }
+        for v in 0..NUM_HEX_VERTS {
+            let vertex = cellToVertex(origin, v).unwrap();
+            assert_eq!(counts[&vertex], 3, "origin vertex shared by three cells");
+        }
+        assert_eq!(counts.len(), 24, "disk of 7 hexagons has 24 vertexes");
+    }
+
+    #[test]
+    fn isValidVertex_invalid() {
+        let origin: H3Index = 0x823d6ffffffffff;
+        assert!(!isValidVertex(origin), "cell is not a vertex");
+
+        let vertex = cellToVertex(origin, 0).unwrap();
+        let mut badOwner = vertex;
+        H3_SET_RESERVED_BITS(&mut badOwner, 6);
+        assert!(!isValidVertex(badOwner), "vertex number out of range");
+
+        // a non-canonical owner for a shared vertex is not valid
+        for v in 0..NUM_HEX_VERTS {
+            let canonical = cellToVertex(origin, v).unwrap();
+            let mut owner = canonical;
+            H3_SET_MODE(&mut owner, H3_CELL_MODE);
+            H3_SET_RESERVED_BITS(&mut owner, 0);
+            if owner != origin {
+                let mut nonCanonical = origin;
+                H3_SET_MODE(&mut nonCanonical, H3_VERTEX_MODE);
+                H3_SET_RESERVED_BITS(&mut nonCanonical, v);
+                assert!(!isValidVertex(nonCanonical), "non-canonical owner");
+            }
+        }
+    }
+
+    #[test]
+    fn cellToVertex_everyVertexShared() {
+        for res in 0..3 {
+            let mut counts: std::collections::HashMap<H3Index, i32> =
+                std::collections::HashMap::new();
+            let mut numCells = 0;
+            for res0 in getRes0Cells().unwrap() {
+                for cell in cellToChildren(res0, res).unwrap() {
+                    numCells += 1;
+                    let numVerts = if isPentagon(cell) {
+                        NUM_PENT_VERTS
+                    } else {
+                        NUM_HEX_VERTS
+                    };
+                    for v in 0..numVerts {
+                        let vertex = cellToVertex(cell, v).unwrap();
+                        assert!(isValidVertex(vertex), "vertex of {:x} is valid", cell);
+                        *counts.entry(vertex).or_insert(0) += 1;
+                    }
+                }
+            }
+            assert!(
+                counts.values().all(|count| *count == 3),
+                "every vertex is shared by three cells at res {}",
+                res
+            );
+            // Euler's formula for a sphere where each vertex has degree 3
+            assert_eq!(
+                counts.len(),
+                2 * numCells - 4,
+                "vertex count at res {}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn cellToVertexes_hexagonAndPentagon() {
+        let origin: H3Index = 0x823d6ffffffffff;
+        let vertexes = cellToVertexes(origin).unwrap();
+        for (i, vertex) in vertexes.iter().enumerate() {
+            assert_eq!(
+                *vertex,
+                cellToVertex(origin, i as i32).unwrap(),
+                "matches cellToVertex"
+            );
+        }
+        let mut sorted = vertexes.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 6, "hexagon vertexes are distinct");
+
+        let pentagon: H3Index = 0x823007fffffffff;
+        let vertexes = cellToVertexes(pentagon).unwrap();
+        assert!(
+            vertexes[..5].iter().all(|v| isValidVertex(*v)),
+            "pentagon vertexes"
+        );
+        assert_eq!(vertexes[5], H3_NULL, "last pentagon slot is empty");
+
+        let mut invalid: H3Index = origin;
+        crate::h3_index::H3_SET_BASE_CELL(&mut invalid, 127);
+        assert!(cellToVertexes(invalid).is_err(), "invalid cell fails");
+    }
+
+    #[test]
+    fn vertexToLatLng_matchesBoundary() {
+        // Class II and Class III cells, including pentagons and cells whose
+        // boundaries cross icosahedron edges
+        for res in 0..3 {
+            for res0 in getRes0Cells().unwrap() {
+                for cell in cellToChildren(res0, res).unwrap() {
+                    let boundary = cellToBoundary(cell).unwrap();
+                    let vertexes = cellToVertexes(cell).unwrap();
+                    for vertex in vertexes.iter().filter(|v| **v != H3_NULL) {
+                        let coord = vertexToLatLng(*vertex).unwrap();
+                        let onBoundary = boundary.verts[..boundary.numVerts]
+                            .iter()
+                            .any(|v| geoAlmostEqualThreshold(v, &coord, 1e-9));
+                        assert!(onBoundary, "vertex of {:x} is on its boundary", cell);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn vertexToLatLng_invalid() {
+        let mut invalid: H3Index = cellToVertex(0x823d6ffffffffff, 0).unwrap();
+        crate::h3_index::H3_SET_BASE_CELL(&mut invalid, 127);
+        assert_eq!(
+            vertexToLatLng(invalid),
+            Err(Error::CellInvalid),
+            "invalid owner fails"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::constants::M_180_PI;
+use crate::lat_lng::{geoAlmostEqualThreshold, LatLng};
+use crate::prelude::*;
+
+/** Vertices closer than this, in radians, are the same vertex. The face edge
+ * crossings added to class III boundaries can differ by a few nanoradians
+ * between the two cells sharing them, while the shortest res 15 edges are
+ * still almost 10 times longer than this. */
+const VERTEX_EPSILON_RAD: f64 = 1.0e-8;
+/** Vertices are bucketed on a grid of this many buckets per degree, so
+ * buckets are larger than VERTEX_EPSILON_RAD. */
+const BUCKETS_PER_DEGREE: f64 = 1.0e6;
+
+/**
+ * A graph of directed edges between cell boundary vertices.
+ *
+ * Adjacent cells compute their shared vertices separately, so vertices are
+ * matched to within VERTEX_EPSILON_RAD rather than exactly. Adding an edge
+ * whose reverse is already present removes both, so after adding the
+ * boundaries of a set of cells only the outline of the set remains.
+ */
+#[derive(Debug, Default)]
+pub(crate) struct VertexGraph {
+    /** distinct vertices */
+    verts: Vec<LatLng>,
+    /** vertex ids by bucket */
+    buckets: BTreeMap<(i64, i64), Vec<usize>>,
+    /** directed edges, as (from, to) vertex ids */
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl VertexGraph {
+    pub(crate) fn new() -> VertexGraph {
+        VertexGraph::default()
+    }
+
+    fn _bucket(g: &LatLng) -> (i64, i64) {
+        (
+            (g.lat * M_180_PI * BUCKETS_PER_DEGREE).floor() as i64,
+            (g.lng * M_180_PI * BUCKETS_PER_DEGREE).floor() as i64,
+        )
+    }
+
+    /**
+     * Finds the id of a vertex, adding it if it is new. Neighboring buckets
+     * are searched too, for vertices that round into different buckets.
+     */
+    fn _vertexId(&mut self, g: &LatLng) -> usize {
+        let (lat, lng) = VertexGraph::_bucket(g);
+        for dLat in -1..=1 {
+            for dLng in -1..=1 {
+                if let Some(ids) = self.buckets.get(&(lat + dLat, lng + dLng)) {
+                    for &id in ids {
+                        if geoAlmostEqualThreshold(&self.verts[id], g, VERTEX_EPSILON_RAD) {
+                            return id;
+                        }
+                    }
+                }
+            }
+        }
+        let id = self.verts.len();
+        self.verts.push(*g);
+        self.buckets.entry((lat, lng)).or_default().push(id);
+        id
+    }
+
+    /**
+     * Adds an edge, or removes its reverse if that is already present.
+     *
+     * @param from The origin vertex.
+     * @param to The destination vertex.
+     */
+    pub(crate) fn addEdge(&mut self, from: &LatLng, to: &LatLng) {
+        let from = self._vertexId(from);
+        let to = self._vertexId(to);
+        if from == to {
+            return;
+        }
+        if !self.edges.remove(&(to, from)) {
+            self.edges.insert((from, to));
+        }
+    }
+
+    /**
+     * Adds the edges of a loop of vertices, closing the loop.
+     *
+     * @param verts The vertices of the loop.
+     */
+    pub(crate) fn addLoop(&mut self, verts: &[LatLng]) {
+        for i in 0..verts.len() {
+            self.addEdge(&verts[i], &verts[(i + 1) % verts.len()]);
+        }
+    }
+
+    /**
+     * Removes the remaining edges as closed loops, by following edges from
+     * vertex to vertex until returning to the start.
+     *
+     * @return The loops, without repeated closing vertices.
+     */
+    pub(crate) fn takeLoops(&mut self) -> Vec<Vec<LatLng>> {
+        let mut loops: Vec<Vec<LatLng>> = Vec::new();
+        while let Some((start, mut to)) = self.edges.pop_first() {
+            let mut verts = vec![self.verts[start]];
+            while to != start {
+                verts.push(self.verts[to]);
+                let next = self.edges.range((to, 0)..(to + 1, 0)).next().copied();
+                match next {
+                    Some(edge) => {
+                        self.edges.remove(&edge);
+                        to = edge.1;
+                    }
+                    // dangling edge, only possible with degenerate input
+                    None => break,
+                }
+            }
+            loops.push(verts);
+        }
+        loops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::h3_index::cellToBoundary;
+
+    use super::*;
+
+    #[test]
+    fn sharedEdgesCancel() {
+        let a = cellToBoundary(0x8928308280fffff).unwrap();
+        let mut graph = VertexGraph::new();
+        graph.addLoop(&a.to_vec());
+        graph.addLoop(&a.to_vec().into_iter().rev().collect::<Vec<LatLng>>());
+        assert!(graph.takeLoops().is_empty(), "reversed loop cancels");
+
+        graph.addLoop(&a.to_vec());
+        let loops = graph.takeLoops();
+        assert_eq!(loops.len(), 1, "one loop");
+        assert_eq!(loops[0].len(), 6, "hexagon loop");
+    }
+}
@@ -0,0 +1,151 @@
+// Well-known text (WKT) output, for pasting results into PostGIS, QGIS and
+// other GIS tools. Coordinates are written as longitude then latitude, in
+// degrees, and rings are closed by repeating their first vertex.
+
+use core::fmt::Write;
+
+use crate::constants::M_180_PI;
+use crate::lat_lng::{CellBoundary, LatLng};
+use crate::polygon::{GeoLoop, GeoMultiPolygon, GeoPolygon};
+use crate::prelude::*;
+
+/** Appends a closed ring of vertices, such as `(1 2, 3 4, 1 2)`. */
+fn _writeRing(out: &mut String, verts: &[LatLng]) {
+    out.push('(');
+    for (i, vert) in verts.iter().chain(verts.first()).enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write!(out, "{} {}", vert.lng * M_180_PI, vert.lat * M_180_PI).unwrap();
+    }
+    out.push(')');
+}
+
+/** Appends the rings of a polygon, such as `((...), (...))`. */
+fn _writePolygon(out: &mut String, polygon: &GeoPolygon) {
+    out.push('(');
+    _writeRing(out, &polygon.geoloop.verts);
+    for hole in &polygon.holes {
+        out.push_str(", ");
+        _writeRing(out, &hole.verts);
+    }
+    out.push(')');
+}
+
+impl CellBoundary {
+    /** Returns the boundary as a WKT POLYGON. */
+    pub fn to_wkt(&self) -> String {
+        if self.numVerts == 0 {
+            return "POLYGON EMPTY".to_string();
+        }
+        let mut out = "POLYGON (".to_string();
+        _writeRing(&mut out, &self.verts[..self.numVerts]);
+        out.push(')');
+        out
+    }
+}
+
+impl GeoLoop {
+    /** Returns the loop as a WKT POLYGON without holes. */
+    pub fn to_wkt(&self) -> String {
+        if self.verts.is_empty() {
+            return "POLYGON EMPTY".to_string();
+        }
+        let mut out = "POLYGON (".to_string();
+        _writeRing(&mut out, &self.verts);
+        out.push(')');
+        out
+    }
+}
+
+impl GeoPolygon {
+    /** Returns the polygon and its holes as a WKT POLYGON. */
+    pub fn to_wkt(&self) -> String {
+        if self.geoloop.verts.is_empty() {
+            return "POLYGON EMPTY".to_string();
+        }
+        let mut out = "POLYGON ".to_string();
+        _writePolygon(&mut out, self);
+        out
+    }
+}
+
+impl GeoMultiPolygon {
+    /** Returns the polygons as a WKT MULTIPOLYGON. */
+    pub fn to_wkt(&self) -> String {
+        if self.polygons.is_empty() {
+            return "MULTIPOLYGON EMPTY".to_string();
+        }
+        let mut out = "MULTIPOLYGON (".to_string();
+        for (i, polygon) in self.polygons.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            _writePolygon(&mut out, polygon);
+        }
+        out.push(')');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::{cellsToMultiPolygon, gridDisk};
+    use crate::h3_index::cellToBoundary;
+
+    use super::*;
+
+    fn degVert(lat: f64, lng: f64) -> LatLng {
+        LatLng::from_degrees(lat, lng)
+    }
+
+    #[test]
+    fn geoPolygonToWkt() {
+        let polygon = GeoPolygon {
+            geoloop: GeoLoop {
+                verts: vec![degVert(0.0, 0.0), degVert(0.0, 4.0), degVert(4.0, 0.0)],
+            },
+            holes: vec![GeoLoop {
+                verts: vec![degVert(1.0, 1.0), degVert(2.0, 1.0), degVert(1.0, 2.0)],
+            }],
+        };
+        assert_eq!(
+            polygon.to_wkt(),
+            "POLYGON ((0 0, 4 0, 0 4, 0 0), (1 1, 1 2, 2 1, 1 1))",
+            "lng lat order, closed rings"
+        );
+        assert_eq!(
+            GeoMultiPolygon {
+                polygons: vec![polygon.clone(), polygon]
+            }
+            .to_wkt(),
+            "MULTIPOLYGON (((0 0, 4 0, 0 4, 0 0), (1 1, 1 2, 2 1, 1 1)), \
+             ((0 0, 4 0, 0 4, 0 0), (1 1, 1 2, 2 1, 1 1)))",
+            "multipolygon"
+        );
+        assert_eq!(GeoPolygon::default().to_wkt(), "POLYGON EMPTY");
+        assert_eq!(GeoMultiPolygon::default().to_wkt(), "MULTIPOLYGON EMPTY");
+    }
+
+    #[test]
+    fn cellBoundaryToWkt() {
+        let boundary = cellToBoundary(0x85283473fffffff).unwrap();
+        let wkt = boundary.to_wkt();
+        assert!(wkt.starts_with("POLYGON (("), "polygon");
+        assert_eq!(wkt.matches(',').count(), 6, "six vertices plus closing");
+        assert_eq!(
+            wkt,
+            GeoLoop {
+                verts: boundary.to_vec()
+            }
+            .to_wkt(),
+            "boundary matches the equivalent loop"
+        );
+
+        let disk = cellsToMultiPolygon(&gridDisk(0x85283473fffffff, 1).unwrap()).unwrap();
+        assert!(
+            disk.to_wkt().starts_with("MULTIPOLYGON (((-"),
+            "disk outline is a multipolygon"
+        );
+    }
+}